@@ -9,13 +9,14 @@ use std::time::Duration;
 
 use remotefs_ssh::SshKeyStorage as SshKeyStorageTrait;
 
+use crate::config::bookmarks::SecretRef;
 use crate::filetransfer::{FileTransferParams, FileTransferProtocol};
 use crate::host::{HostError, Localhost};
 use crate::system::bookmarks_client::BookmarksClient;
 use crate::system::config_client::ConfigClient;
-use crate::system::environment;
 use crate::system::sshkey_storage::SshKeyStorage;
 use crate::system::theme_provider::ThemeProvider;
+use crate::system::{environment, secrets};
 use crate::ui::activities::auth::AuthActivity;
 use crate::ui::activities::filetransfer::FileTransferActivity;
 use crate::ui::activities::setup::SetupActivity;
@@ -62,16 +63,20 @@ impl ActivityManager {
         })
     }
 
-    /// Set file transfer params
+    /// Set file transfer params.
+    /// If the password is missing, it's resolved from `secret`, if provided, or prompted
     pub fn set_filetransfer_params(
         &mut self,
         mut params: FileTransferParams,
         password: Option<&str>,
+        secret: Option<&SecretRef>,
     ) -> Result<(), String> {
         // Set password if provided
         if params.password_missing() {
             if let Some(password) = password {
                 params.set_default_secret(password.to_string());
+            } else if let Some(secret) = secret {
+                params.set_default_secret(secrets::resolve(secret)?);
             } else if matches!(
                 params.protocol,
                 FileTransferProtocol::Scp | FileTransferProtocol::Sftp,
@@ -138,7 +143,18 @@ impl ActivityManager {
                 None => Err(format!(
                     r#"Could not resolve bookmark name: "{bookmark_name}" no such bookmark"#
                 )),
-                Some(params) => self.set_filetransfer_params(params, password),
+                Some(params) => {
+                    let settings = bookmarks_client
+                        .get_bookmark_settings(bookmark_name)
+                        .cloned();
+                    let hooks = bookmarks_client.get_bookmark_hooks(bookmark_name).cloned();
+                    let secret = bookmarks_client.get_bookmark_secret(bookmark_name).cloned();
+                    let ctx = self.context.as_mut().unwrap();
+                    ctx.config_mut().set_session_settings(settings);
+                    ctx.set_bookmark_hooks(hooks);
+                    ctx.set_bookmark_name(Some(bookmark_name.to_string()));
+                    self.set_filetransfer_params(params, password, secret.as_ref())
+                }
             }
         } else {
             Err(String::from(
@@ -336,16 +352,21 @@ impl ActivityManager {
                     let bookmarks_file: PathBuf =
                         environment::get_bookmarks_paths(config_dir_path.as_path());
                     // Initialize client
-                    BookmarksClient::new(bookmarks_file.as_path(), config_dir_path.as_path(), 16)
-                        .map(Option::Some)
-                        .map_err(|e| {
-                            format!(
-                                "Could not initialize bookmarks (at \"{}\", \"{}\"): {}",
-                                bookmarks_file.display(),
-                                config_dir_path.display(),
-                                e
-                            )
-                        })
+                    let mut client = BookmarksClient::new(
+                        bookmarks_file.as_path(),
+                        config_dir_path.as_path(),
+                        16,
+                    )
+                    .map_err(|e| {
+                        format!(
+                            "Could not initialize bookmarks (at \"{}\", \"{}\"): {}",
+                            bookmarks_file.display(),
+                            config_dir_path.display(),
+                            e
+                        )
+                    })?;
+                    Self::unlock_bookmarks_client(&mut client)?;
+                    Ok(Some(client))
                 } else {
                     Ok(None)
                 }
@@ -354,6 +375,22 @@ impl ActivityManager {
         }
     }
 
+    /// If bookmarks are protected by a master password, prompt user for it to unlock the client
+    fn unlock_bookmarks_client(client: &mut BookmarksClient) -> Result<(), String> {
+        if !client.is_locked() {
+            return Ok(());
+        }
+        match tty::read_secret_from_tty("Master password: ") {
+            Err(err) => Err(format!("Could not read master password: {err}")),
+            Ok(Some(password)) => client
+                .unlock(password.as_str())
+                .map_err(|e| format!("Could not unlock bookmarks: {e}")),
+            Ok(None) => Err(String::from(
+                "Could not unlock bookmarks: master password not provided",
+            )),
+        }
+    }
+
     /// Initialize configuration client
     fn init_config_client() -> Result<ConfigClient, String> {
         // Get config dir
@@ -9,12 +9,17 @@ use argh::FromArgs;
 
 use crate::activity_manager::NextActivity;
 use crate::filetransfer::FileTransferParams;
+use crate::system::bookmarks_export::ExportFormat;
+use crate::system::bookmarks_import::{ConflictPolicy, ImportFormat};
 use crate::system::logging::LogLevel;
 
 pub enum Task {
     Activity(NextActivity),
+    ExportBookmarks(ExportBookmarksArgs),
+    ImportBookmarks(ImportBookmarksArgs),
     ImportTheme(PathBuf),
     InstallUpdate,
+    MasterPassword(MasterPasswordArgs),
 }
 
 #[derive(FromArgs)]
@@ -66,15 +71,66 @@ pub struct Args {
 #[argh(subcommand)]
 pub enum ArgsSubcommands {
     Config(ConfigArgs),
+    ExportBookmarks(ExportBookmarksArgs),
+    ImportBookmarks(ImportBookmarksArgs),
     LoadTheme(LoadThemeArgs),
+    MasterPassword(MasterPasswordArgs),
     Update(UpdateArgs),
 }
 
+#[derive(FromArgs)]
+/// set the master password protecting the passwords saved in bookmarks, re-encrypting them
+#[argh(subcommand, name = "master-password")]
+pub struct MasterPasswordArgs {
+    /// remove the master password; passwords are encrypted with the key in the key storage again
+    #[argh(switch)]
+    pub disable: bool,
+}
+
 #[derive(FromArgs)]
 /// open termscp configuration
 #[argh(subcommand, name = "config")]
 pub struct ConfigArgs {}
 
+#[derive(FromArgs)]
+/// export bookmarks to a file, to share them with other users
+#[argh(subcommand, name = "export-bookmarks")]
+pub struct ExportBookmarksArgs {
+    #[argh(positional)]
+    /// file to export the bookmarks to
+    pub path: PathBuf,
+    /// file format: toml or json; if not provided, it's guessed from the file extension
+    #[argh(option)]
+    pub format: Option<ExportFormat>,
+    /// export passwords too, encrypted with a passphrase which is prompted
+    #[argh(switch)]
+    pub with_passwords: bool,
+}
+
+#[derive(FromArgs)]
+/// import bookmarks from another client (filezilla, winscp, ssh, rclone) or exported by termscp
+#[argh(subcommand, name = "import-bookmarks")]
+pub struct ImportBookmarksArgs {
+    #[argh(positional)]
+    /// client to import the bookmarks from: filezilla, winscp, ssh, rclone or termscp
+    pub format: ImportFormat,
+    #[argh(positional)]
+    /// file to import; if not provided, the default location of the client is used
+    pub path: Option<PathBuf>,
+    /// list the bookmarks to import, without importing them
+    #[argh(switch)]
+    pub dry_run: bool,
+    /// what to do when a bookmark with the same name already exists: skip, overwrite or rename; default skip
+    #[argh(option, default = "ConflictPolicy::Skip")]
+    pub on_conflict: ConflictPolicy,
+    /// import the passwords of a termscp export, prompting for its passphrase
+    #[argh(switch)]
+    pub with_passwords: bool,
+    /// import the hooks and the password commands of a termscp export, which run commands when connecting; check them with --dry-run first
+    #[argh(switch)]
+    pub with_commands: bool,
+}
+
 #[derive(FromArgs)]
 /// import the specified theme
 #[argh(subcommand, name = "update")]
@@ -104,6 +160,13 @@ impl RunOpts {
         }
     }
 
+    pub fn master_password(args: MasterPasswordArgs) -> Self {
+        Self {
+            task: Task::MasterPassword(args),
+            ..Default::default()
+        }
+    }
+
     pub fn update() -> Self {
         Self {
             task: Task::InstallUpdate,
@@ -111,6 +174,20 @@ impl RunOpts {
         }
     }
 
+    pub fn export_bookmarks(args: ExportBookmarksArgs) -> Self {
+        Self {
+            task: Task::ExportBookmarks(args),
+            ..Default::default()
+        }
+    }
+
+    pub fn import_bookmarks(args: ImportBookmarksArgs) -> Self {
+        Self {
+            task: Task::ImportBookmarks(args),
+            ..Default::default()
+        }
+    }
+
     pub fn import_theme(theme: PathBuf) -> Self {
         Self {
             task: Task::ImportTheme(theme),
@@ -3,12 +3,14 @@
 //! `bookmarks` is the module which provides data types and de/serializer for bookmarks
 
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use serde::de::Error as DeError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::config::params::FileSortingConfig;
 use crate::filetransfer::params::{
     AwsS3Params, GenericProtocolParams, ProtocolParams, SmbParams as TransferSmbParams,
     WebDAVProtocolParams,
@@ -19,10 +21,33 @@ use crate::filetransfer::{FileTransferParams, FileTransferProtocol};
 /// It contains both `Bookmark`
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct UserHosts {
+    /// Set if the secrets are encrypted with a key derived from the master password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub master_password: Option<MasterPassword>,
     pub bookmarks: HashMap<String, Bookmark>,
     pub recents: HashMap<String, Bookmark>,
 }
 
+/// MasterPassword contains the parameters to derive the bookmarks key from the master password.
+/// The master password itself is never stored
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct MasterPassword {
+    /// Salt used to derive the key
+    pub salt: String,
+    /// Known value encrypted with the derived key, used to check the master password
+    pub check: String,
+}
+
+/// ExportedBookmarks contains the bookmarks exported to be shared with other users.
+/// Secrets are either stripped or encrypted with the export passphrase
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct ExportedBookmarks {
+    /// Salt used to derive the key encrypting the secrets from the passphrase; set only if secrets are exported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    pub bookmarks: HashMap<String, Bookmark>,
+}
+
 /// Bookmark describes a single bookmark entry in the user hosts storage
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
 pub struct Bookmark {
@@ -37,19 +62,108 @@ pub struct Bookmark {
     pub port: Option<u16>,
     /// Username for generic parameters
     pub username: Option<String>,
-    /// Password is optional; base64, aes-128 encrypted password
-    pub password: Option<String>,
+    /// Password is optional; either stored in the bookmark or resolved from a secret backend
+    pub password: Option<BookmarkPassword>,
     /// Remote folder to connect to (serde rename for legacy reasons)
     #[serde(rename = "directory")]
     pub remote_path: Option<PathBuf>,
     /// local folder to open at startup
     pub local_path: Option<PathBuf>,
+    /// Free-form tags used to filter bookmarks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Settings overriding the user interface configuration while connected to this bookmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<BookmarkSettings>,
+    /// Commands run around the connection to this bookmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<BookmarkHooks>,
+    /// Local paths synchronized with the remote by the file watcher when connected to this bookmark
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watch: Vec<WatchedPath>,
     /// S3 params; optional. When used other fields are empty for sure
     pub s3: Option<S3Params>,
     /// SMB params; optional. Extra params required for SMB protocol
     pub smb: Option<SmbParams>,
 }
 
+/// Password of a bookmark
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum BookmarkPassword {
+    /// Password stored in the bookmark; base64, aes-128 encrypted password
+    Inline(String),
+    /// Password resolved from a secret backend when connecting
+    Secret(SecretRef),
+}
+
+/// Reference to a secret kept out of the bookmarks, resolved when connecting to the bookmark
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretRef {
+    /// Output of a command run on localhost
+    Cmd(String),
+    /// Value of an environment variable
+    Env(String),
+    /// Secret stored with `pass` at the provided path
+    Pass(String),
+    /// Secret stored with `gopass` at the provided path
+    Gopass(String),
+}
+
+/// Settings of a bookmark which override the keys of `UserInterfaceConfig` for its sessions.
+/// Keys which are not set fallback to the user configuration
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Default)]
+pub struct BookmarkSettings {
+    pub text_editor: Option<PathBuf>,
+    pub show_hidden_files: Option<bool>,
+    pub prompt_on_file_replace: Option<bool>,
+    pub group_dirs: Option<String>,
+    pub file_fmt: Option<String>,
+    pub remote_file_fmt: Option<String>,
+    pub file_sorting: Option<FileSortingConfig>,
+    pub remote_file_sorting: Option<FileSortingConfig>,
+}
+
+/// Hooks of a bookmark, which are commands run around the connection to the bookmark
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Default)]
+pub struct BookmarkHooks {
+    /// Run before connecting; only local hooks can run, since the remote is not connected yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_connect: Vec<Hook>,
+    /// Run after the connection has been established
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after_connect: Vec<Hook>,
+    /// Run when disconnecting; remote hooks run before the connection is closed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_disconnect: Vec<Hook>,
+}
+
+/// A command run by a bookmark hook
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct Hook {
+    pub command: String,
+    /// Run the command on the remote host, instead of localhost; Default false
+    #[serde(default)]
+    pub remote: bool,
+    /// Abort the connection if the command fails or exits with a non-zero code; Default false
+    #[serde(default)]
+    pub abort_on_failure: bool,
+}
+
+/// A local path synchronized with a remote path by the file watcher
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct WatchedPath {
+    pub local: PathBuf,
+    pub remote: PathBuf,
+    /// Patterns of the files whose changes are not synchronized
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Upload the files changed while termscp was closed, when the watch is restored; Default false
+    #[serde(default)]
+    pub reconcile: bool,
+}
+
 /// Connection parameters for Aws s3 protocol
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Default)]
 pub struct S3Params {
@@ -72,6 +186,35 @@ pub struct SmbParams {
 
 // -- impls
 
+impl BookmarkPassword {
+    /// Get the password stored in the bookmark, if any
+    pub fn inline(&self) -> Option<&str> {
+        match self {
+            Self::Inline(password) => Some(password.as_str()),
+            Self::Secret(_) => None,
+        }
+    }
+
+    /// Get the reference to the secret backend, if any
+    pub fn secret(&self) -> Option<&SecretRef> {
+        match self {
+            Self::Inline(_) => None,
+            Self::Secret(secret) => Some(secret),
+        }
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cmd(cmd) => write!(f, "command \"{cmd}\""),
+            Self::Env(name) => write!(f, "environment variable \"{name}\""),
+            Self::Pass(path) => write!(f, "pass \"{path}\""),
+            Self::Gopass(path) => write!(f, "gopass \"{path}\""),
+        }
+    }
+}
+
 impl From<FileTransferParams> for Bookmark {
     fn from(params: FileTransferParams) -> Self {
         let protocol = params.protocol;
@@ -84,9 +227,13 @@ impl From<FileTransferParams> for Bookmark {
                 address: Some(params.address),
                 port: Some(params.port),
                 username: params.username,
-                password: params.password,
+                password: params.password.map(BookmarkPassword::Inline),
                 remote_path,
                 local_path,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: None,
             },
@@ -98,6 +245,10 @@ impl From<FileTransferParams> for Bookmark {
                 password: None,
                 remote_path,
                 local_path,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: Some(S3Params::from(params)),
                 smb: None,
             },
@@ -110,9 +261,13 @@ impl From<FileTransferParams> for Bookmark {
                 #[cfg(windows)]
                 port: None,
                 username: params.username,
-                password: params.password,
+                password: params.password.map(BookmarkPassword::Inline),
                 remote_path,
                 local_path,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
             },
             ProtocolParams::WebDAV(parms) => Self {
@@ -120,9 +275,13 @@ impl From<FileTransferParams> for Bookmark {
                 address: Some(parms.uri),
                 port: None,
                 username: Some(parms.username),
-                password: Some(parms.password),
+                password: Some(BookmarkPassword::Inline(parms.password)),
                 remote_path,
                 local_path,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: None,
             },
@@ -132,6 +291,12 @@ impl From<FileTransferParams> for Bookmark {
 
 impl From<Bookmark> for FileTransferParams {
     fn from(bookmark: Bookmark) -> Self {
+        // NOTE: secrets referenced by the bookmark are resolved when connecting
+        let password = bookmark
+            .password
+            .as_ref()
+            .and_then(BookmarkPassword::inline)
+            .map(str::to_string);
         // Create generic or others based on protocol
         match bookmark.protocol {
             FileTransferProtocol::AwsS3 => {
@@ -146,7 +311,7 @@ impl From<Bookmark> for FileTransferParams {
                     .address(bookmark.address.unwrap_or_default())
                     .port(bookmark.port.unwrap_or(22))
                     .username(bookmark.username)
-                    .password(bookmark.password);
+                    .password(password);
                 Self::new(bookmark.protocol, ProtocolParams::Generic(params))
             }
             #[cfg(unix)]
@@ -157,7 +322,7 @@ impl From<Bookmark> for FileTransferParams {
                 )
                 .port(bookmark.port.unwrap_or(445))
                 .username(bookmark.username)
-                .password(bookmark.password)
+                .password(password)
                 .workgroup(bookmark.smb.and_then(|x| x.workgroup));
 
                 Self::new(bookmark.protocol, ProtocolParams::Smb(params))
@@ -169,7 +334,7 @@ impl From<Bookmark> for FileTransferParams {
                     bookmark.smb.clone().map(|x| x.share).unwrap_or_default(),
                 )
                 .username(bookmark.username)
-                .password(bookmark.password);
+                .password(password);
 
                 Self::new(bookmark.protocol, ProtocolParams::Smb(params))
             }
@@ -178,7 +343,7 @@ impl From<Bookmark> for FileTransferParams {
                 ProtocolParams::WebDAV(WebDAVProtocolParams {
                     uri: bookmark.address.unwrap_or_default(),
                     username: bookmark.username.unwrap_or_default(),
-                    password: bookmark.password.unwrap_or_default(),
+                    password: password.unwrap_or_default(),
                 }),
             ),
         }
@@ -273,9 +438,13 @@ mod tests {
             port: Some(22),
             protocol: FileTransferProtocol::Sftp,
             username: Some(String::from("root")),
-            password: Some(String::from("password")),
+            password: Some(BookmarkPassword::Inline(String::from("password"))),
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: None,
         };
@@ -284,9 +453,13 @@ mod tests {
             port: Some(22),
             protocol: FileTransferProtocol::Scp,
             username: Some(String::from("admin")),
-            password: Some(String::from("password")),
+            password: Some(BookmarkPassword::Inline(String::from("password"))),
             remote_path: Some(PathBuf::from("/home")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: None,
         };
@@ -294,14 +467,24 @@ mod tests {
         bookmarks.insert(String::from("test"), bookmark);
         let mut recents: HashMap<String, Bookmark> = HashMap::with_capacity(1);
         recents.insert(String::from("ISO20201218T181432"), recent);
-        let hosts: UserHosts = UserHosts { bookmarks, recents };
+        let hosts: UserHosts = UserHosts {
+            master_password: None,
+            bookmarks,
+            recents,
+        };
         // Verify
         let bookmark: &Bookmark = hosts.bookmarks.get(&String::from("test")).unwrap();
         assert_eq!(bookmark.address.as_deref().unwrap(), "192.168.1.1");
         assert_eq!(bookmark.port.unwrap(), 22);
         assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
         assert_eq!(bookmark.username.as_deref().unwrap(), "root");
-        assert_eq!(bookmark.password.as_deref().unwrap(), "password");
+        assert_eq!(
+            bookmark
+                .password
+                .as_ref()
+                .and_then(BookmarkPassword::inline),
+            Some("password")
+        );
         assert_eq!(
             bookmark.remote_path.as_deref().unwrap(),
             std::path::Path::new("/tmp")
@@ -318,7 +501,13 @@ mod tests {
         assert_eq!(bookmark.port.unwrap(), 22);
         assert_eq!(bookmark.protocol, FileTransferProtocol::Scp);
         assert_eq!(bookmark.username.as_deref().unwrap(), "admin");
-        assert_eq!(bookmark.password.as_deref().unwrap(), "password");
+        assert_eq!(
+            bookmark
+                .password
+                .as_ref()
+                .and_then(BookmarkPassword::inline),
+            Some("password")
+        );
         assert_eq!(
             bookmark.remote_path.as_deref().unwrap(),
             std::path::Path::new("/home")
@@ -345,7 +534,13 @@ mod tests {
         assert_eq!(bookmark.address.as_deref().unwrap(), "127.0.0.1");
         assert_eq!(bookmark.port.unwrap(), 10222);
         assert_eq!(bookmark.username.as_deref().unwrap(), "root");
-        assert_eq!(bookmark.password.as_deref().unwrap(), "omar");
+        assert_eq!(
+            bookmark
+                .password
+                .as_ref()
+                .and_then(BookmarkPassword::inline),
+            Some("omar")
+        );
         assert_eq!(
             bookmark.remote_path.as_deref().unwrap(),
             std::path::Path::new("/home")
@@ -387,9 +582,13 @@ mod tests {
             port: Some(22),
             protocol: FileTransferProtocol::Sftp,
             username: Some(String::from("root")),
-            password: Some(String::from("password")),
+            password: Some(BookmarkPassword::Inline(String::from("password"))),
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: None,
         };
@@ -417,9 +616,13 @@ mod tests {
             port: None,
             protocol: FileTransferProtocol::WebDAV,
             username: Some(String::from("root")),
-            password: Some(String::from("password")),
+            password: Some(BookmarkPassword::Inline(String::from("password"))),
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: None,
         };
@@ -449,6 +652,10 @@ mod tests {
             password: None,
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: Some(S3Params {
                 bucket: String::from("veeso"),
                 region: Some(String::from("eu-west-1")),
@@ -488,9 +695,13 @@ mod tests {
             address: Some("localhost".to_string()),
             port: Some(445),
             username: Some("foo".to_string()),
-            password: Some("bar".to_string()),
+            password: Some(BookmarkPassword::Inline("bar".to_string())),
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: Some(SmbParams {
                 share: "test".to_string(),
@@ -528,6 +739,10 @@ mod tests {
             password: None,
             remote_path: Some(PathBuf::from("/tmp")),
             local_path: Some(PathBuf::from("/usr")),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            watch: Vec::new(),
             s3: None,
             smb: Some(SmbParams {
                 share: "test".to_string(),
@@ -0,0 +1,313 @@
+//! ## CustomActions
+//!
+//! `custom_actions` is the module which provides the user defined actions executed on the selected files
+
+// locals
+use std::str::FromStr;
+
+// ext
+use remotefs::File;
+use thiserror::Error;
+use wildmatch::WildMatch;
+
+use super::keybindings::{KeyBinding, KeyBindingError};
+use super::params::CustomActionConfig;
+
+/// Placeholders replaced with the attributes of each selected file
+const PLACEHOLDERS: [&str; 3] = ["{name}", "{path}", "{dir}"];
+
+/// Describes an error while parsing a custom action
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum CustomActionError {
+    #[error("empty command")]
+    EmptyCommand,
+    #[error("{0}")]
+    InvalidKey(KeyBindingError),
+    #[error("invalid scope \"{0}\"")]
+    InvalidScope(String),
+}
+
+/// Host where a custom action can be executed
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CustomActionScope {
+    Local,
+    Remote,
+    Both,
+}
+
+impl FromStr for CustomActionScope {
+    type Err = CustomActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "remote" => Ok(Self::Remote),
+            "both" => Ok(Self::Both),
+            _ => Err(CustomActionError::InvalidScope(s.to_string())),
+        }
+    }
+}
+
+/// A command defined by the user, executed on the selected files
+#[derive(Debug, Clone)]
+pub struct CustomAction {
+    pub name: String,
+    pub command: String,
+    /// Key which runs the action from the actions menu
+    pub key: Option<KeyBinding>,
+    pub scope: CustomActionScope,
+    /// Pattern the names of the selected files must match
+    pub pattern: Option<String>,
+    /// Ask for confirmation before running the action
+    pub confirm: bool,
+}
+
+impl CustomAction {
+    /// Instantiate a custom action from its configuration
+    pub fn new(config: &CustomActionConfig) -> Result<Self, CustomActionError> {
+        if config.command.trim().is_empty() {
+            return Err(CustomActionError::EmptyCommand);
+        }
+        Ok(Self {
+            name: config.name.clone(),
+            command: config.command.clone(),
+            key: config
+                .key
+                .as_deref()
+                .map(KeyBinding::from_str)
+                .transpose()
+                .map_err(CustomActionError::InvalidKey)?,
+            scope: config
+                .scope
+                .as_deref()
+                .map(CustomActionScope::from_str)
+                .transpose()?
+                .unwrap_or(CustomActionScope::Both),
+            pattern: config.pattern.clone().filter(|x| !x.is_empty()),
+            confirm: config.confirm.unwrap_or(false),
+        })
+    }
+
+    /// Returns whether the command is executed once for each selected file
+    pub fn has_file_placeholders(&self) -> bool {
+        PLACEHOLDERS.iter().any(|x| self.command.contains(x))
+    }
+
+    /// Returns whether the action can be executed on the remote host (or on localhost) with `files` selected
+    pub fn applies_to(&self, remote: bool, files: &[&File]) -> bool {
+        let scope = match self.scope {
+            CustomActionScope::Local => !remote,
+            CustomActionScope::Remote => remote,
+            CustomActionScope::Both => true,
+        };
+        if !scope {
+            return false;
+        }
+        match (&self.pattern, files.is_empty()) {
+            (Some(_), true) => false,
+            (Some(pattern), false) => {
+                let pattern = WildMatch::new(pattern);
+                files.iter().all(|x| pattern.matches(x.name().as_str()))
+            }
+            (None, true) => !self.has_file_placeholders(),
+            (None, false) => true,
+        }
+    }
+
+    /// Get the commands to execute for `files`: if the command contains placeholders, a command is returned
+    /// for each file, with the placeholders replaced with its attributes, quoted with `quote`
+    pub fn commands<Q>(&self, files: &[&File], quote: Q) -> Vec<String>
+    where
+        Q: Fn(&str) -> String,
+    {
+        if !self.has_file_placeholders() {
+            return vec![self.command.clone()];
+        }
+        files
+            .iter()
+            .map(|file| {
+                let dir = file
+                    .path()
+                    .parent()
+                    .map(|x| x.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let values = [
+                    quote(&file.name()),
+                    quote(&file.path().to_string_lossy()),
+                    quote(&dir),
+                ];
+                Self::fill_placeholders(&self.command, &values)
+            })
+            .collect()
+    }
+
+    /// Replace the placeholders in `command` with `values`, in the same order as `PLACEHOLDERS`.
+    /// The command is scanned once, so the values are never scanned for placeholders themselves
+    fn fill_placeholders(command: &str, values: &[String; 3]) -> String {
+        let mut filled = String::with_capacity(command.len());
+        let mut rest = command;
+        while !rest.is_empty() {
+            match PLACEHOLDERS
+                .iter()
+                .zip(values.iter())
+                .find(|(placeholder, _)| rest.starts_with(*placeholder))
+            {
+                Some((placeholder, value)) => {
+                    filled.push_str(value);
+                    rest = &rest[placeholder.len()..];
+                }
+                None => {
+                    let ch = rest.chars().next().unwrap();
+                    filled.push(ch);
+                    rest = &rest[ch.len_utf8()..];
+                }
+            }
+        }
+        filled
+    }
+}
+
+/// Instantiate the custom actions from their configuration.
+/// Invalid actions are reported and ignored
+pub fn parse_custom_actions(config: &[CustomActionConfig]) -> Vec<CustomAction> {
+    config
+        .iter()
+        .filter_map(|x| match CustomAction::new(x) {
+            Ok(action) => Some(action),
+            Err(err) => {
+                warn!("Ignoring custom action {}: {}", x.name, err);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+    use tuirealm::event::{Key, KeyModifiers};
+
+    use super::*;
+    use crate::utils::string::shell_quote;
+    use crate::utils::test_helpers::make_fsentry;
+
+    fn config(command: &str, scope: Option<&str>, pattern: Option<&str>) -> CustomActionConfig {
+        CustomActionConfig {
+            name: String::from("test"),
+            command: command.to_string(),
+            key: None,
+            scope: scope.map(str::to_string),
+            pattern: pattern.map(str::to_string),
+            confirm: None,
+        }
+    }
+
+    #[test]
+    fn should_parse_custom_action() {
+        let mut cfg = config(
+            "systemctl restart {name}",
+            Some("remote"),
+            Some("*.service"),
+        );
+        cfg.key = Some(String::from("r"));
+        cfg.confirm = Some(true);
+        let action = CustomAction::new(&cfg).unwrap();
+        assert_eq!(action.name.as_str(), "test");
+        assert_eq!(action.command.as_str(), "systemctl restart {name}");
+        assert_eq!(
+            action.key,
+            Some(KeyBinding::new(Key::Char('r'), KeyModifiers::NONE))
+        );
+        assert_eq!(action.scope, CustomActionScope::Remote);
+        assert_eq!(action.pattern.as_deref(), Some("*.service"));
+        assert!(action.confirm);
+        // defaults
+        let action = CustomAction::new(&config("code {path}", None, None)).unwrap();
+        assert_eq!(action.key, None);
+        assert_eq!(action.scope, CustomActionScope::Both);
+        assert_eq!(action.pattern, None);
+        assert!(!action.confirm);
+    }
+
+    #[test]
+    fn should_not_parse_invalid_custom_action() {
+        assert_eq!(
+            CustomAction::new(&config(" ", None, None)).unwrap_err(),
+            CustomActionError::EmptyCommand
+        );
+        assert_eq!(
+            CustomAction::new(&config("ls", Some("everywhere"), None)).unwrap_err(),
+            CustomActionError::InvalidScope(String::from("everywhere"))
+        );
+        let mut cfg = config("ls", None, None);
+        cfg.key = Some(String::from("ctrl+"));
+        assert!(matches!(
+            CustomAction::new(&cfg).unwrap_err(),
+            CustomActionError::InvalidKey(_)
+        ));
+        assert_eq!(
+            parse_custom_actions(&[config("ls", None, None), cfg])
+                .into_iter()
+                .map(|x| x.command)
+                .collect::<Vec<String>>(),
+            vec![String::from("ls")]
+        );
+    }
+
+    #[test]
+    fn should_tell_whether_custom_action_applies() {
+        let service = make_fsentry("/etc/systemd/system/nginx.service", false);
+        let conf = make_fsentry("/etc/nginx/nginx.conf", false);
+        let action = CustomAction::new(&config(
+            "systemctl restart {name}",
+            Some("remote"),
+            Some("*.service"),
+        ))
+        .unwrap();
+        assert!(action.applies_to(true, &[&service]));
+        assert!(!action.applies_to(false, &[&service]));
+        assert!(!action.applies_to(true, &[&service, &conf]));
+        assert!(!action.applies_to(true, &[]));
+        let action = CustomAction::new(&config("code {path}", Some("local"), None)).unwrap();
+        assert!(action.applies_to(false, &[&conf]));
+        assert!(!action.applies_to(true, &[&conf]));
+        assert!(!action.applies_to(false, &[]));
+        let action = CustomAction::new(&config("df -h", None, None)).unwrap();
+        assert!(action.applies_to(false, &[]));
+        assert!(action.applies_to(true, &[&conf]));
+    }
+
+    #[test]
+    fn should_get_custom_action_commands() {
+        let service = make_fsentry("/etc/systemd/system/nginx.service", false);
+        let conf = make_fsentry("/etc/nginx/my site.conf", false);
+        let action = CustomAction::new(&config("cp {path} {dir}/{name}.bak", None, None)).unwrap();
+        assert!(action.has_file_placeholders());
+        assert_eq!(
+            action.commands(&[&service, &conf], shell_quote),
+            vec![
+                String::from(
+                    "cp '/etc/systemd/system/nginx.service' '/etc/systemd/system'/'nginx.service'.bak"
+                ),
+                String::from("cp '/etc/nginx/my site.conf' '/etc/nginx'/'my site.conf'.bak"),
+            ]
+        );
+        let action = CustomAction::new(&config("df -h", None, None)).unwrap();
+        assert!(!action.has_file_placeholders());
+        assert_eq!(
+            action.commands(&[&service, &conf], shell_quote),
+            vec![String::from("df -h")]
+        );
+    }
+
+    #[test]
+    fn should_not_replace_placeholders_in_file_attributes() {
+        let file = make_fsentry("/tmp/a;rm -rf ~/{path}", false);
+        let action = CustomAction::new(&config("cat {name} {dir}", None, None)).unwrap();
+        assert_eq!(
+            action.commands(&[&file], shell_quote),
+            vec![String::from("cat '{path}' '/tmp/a;rm -rf ~'")]
+        );
+    }
+}
@@ -0,0 +1,821 @@
+//! ## KeyBindings
+//!
+//! `keybindings` is the module which provides the user defined key bindings
+
+// locals
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+// ext
+use thiserror::Error;
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+
+/// Describes an error while parsing key bindings
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum KeyBindingError {
+    #[error("invalid key \"{0}\"")]
+    InvalidKey(String),
+    #[error("unknown action \"{0}\"")]
+    UnknownAction(String),
+}
+
+/// Activity where a key action can be performed
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum KeyScope {
+    Auth,
+    Explorer,
+}
+
+/// Describes an action which can be bound to keys
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyAction {
+    // -- auth
+    AuthDeleteBookmark,
+    AuthFilterBookmarks,
+    AuthHelp,
+    AuthImportBookmarks,
+    AuthQuickConnect,
+    AuthReleaseNotes,
+    AuthSaveBookmark,
+    AuthSetup,
+    // -- explorer
+    Chmod,
+    CommandLine,
+    ContextMenu,
+    Copy,
+    CustomActions,
+    Delete,
+    Exec,
+    FileInfo,
+    Find,
+    Follow,
+    GoTo,
+    GoToParentDirectory,
+    GoToPreviousDirectory,
+    Help,
+    LogPanel,
+    Mark,
+    Mkdir,
+    NewFile,
+    Open,
+    OpenTextFile,
+    OpenWith,
+    PauseSync,
+    Quit,
+    Reload,
+    Rename,
+    ReverseSorting,
+    SaveAs,
+    SelectAll,
+    Shell,
+    SortByNextColumn,
+    Sorting,
+    Symlink,
+    SyncBrowsing,
+    SyncQueue,
+    TableView,
+    ToggleHiddenFiles,
+    Transfer,
+    Undo,
+    Watch,
+    WatchedPaths,
+}
+
+impl KeyAction {
+    /// All the actions, in the order they're displayed to the user
+    pub const ALL: [KeyAction; 48] = [
+        Self::AuthHelp,
+        Self::AuthSetup,
+        Self::AuthSaveBookmark,
+        Self::AuthDeleteBookmark,
+        Self::AuthFilterBookmarks,
+        Self::AuthQuickConnect,
+        Self::AuthImportBookmarks,
+        Self::AuthReleaseNotes,
+        Self::Help,
+        Self::Quit,
+        Self::Transfer,
+        Self::GoToPreviousDirectory,
+        Self::ToggleHiddenFiles,
+        Self::Sorting,
+        Self::Copy,
+        Self::Mkdir,
+        Self::Delete,
+        Self::Find,
+        Self::GoTo,
+        Self::FileInfo,
+        Self::Symlink,
+        Self::Reload,
+        Self::Mark,
+        Self::NewFile,
+        Self::OpenTextFile,
+        Self::LogPanel,
+        Self::Rename,
+        Self::SaveAs,
+        Self::Watch,
+        Self::GoToParentDirectory,
+        Self::Open,
+        Self::OpenWith,
+        Self::Exec,
+        Self::CustomActions,
+        Self::SyncBrowsing,
+        Self::Chmod,
+        Self::SelectAll,
+        Self::SortByNextColumn,
+        Self::ReverseSorting,
+        Self::WatchedPaths,
+        Self::SyncQueue,
+        Self::PauseSync,
+        Self::Follow,
+        Self::Shell,
+        Self::TableView,
+        Self::CommandLine,
+        Self::ContextMenu,
+        Self::Undo,
+    ];
+
+    /// Name of the action in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AuthDeleteBookmark => "auth_delete_bookmark",
+            Self::AuthFilterBookmarks => "auth_filter_bookmarks",
+            Self::AuthHelp => "auth_help",
+            Self::AuthImportBookmarks => "auth_import_bookmarks",
+            Self::AuthQuickConnect => "auth_quick_connect",
+            Self::AuthReleaseNotes => "auth_release_notes",
+            Self::AuthSaveBookmark => "auth_save_bookmark",
+            Self::AuthSetup => "auth_setup",
+            Self::Chmod => "chmod",
+            Self::CommandLine => "command_line",
+            Self::ContextMenu => "context_menu",
+            Self::Copy => "copy",
+            Self::CustomActions => "custom_actions",
+            Self::Delete => "delete",
+            Self::Exec => "exec",
+            Self::FileInfo => "file_info",
+            Self::Find => "find",
+            Self::Follow => "follow",
+            Self::GoTo => "go_to",
+            Self::GoToParentDirectory => "go_to_parent_directory",
+            Self::GoToPreviousDirectory => "go_to_previous_directory",
+            Self::Help => "help",
+            Self::LogPanel => "log_panel",
+            Self::Mark => "mark",
+            Self::Mkdir => "mkdir",
+            Self::NewFile => "new_file",
+            Self::Open => "open",
+            Self::OpenTextFile => "open_text_file",
+            Self::OpenWith => "open_with",
+            Self::PauseSync => "pause_sync",
+            Self::Quit => "quit",
+            Self::Reload => "reload",
+            Self::Rename => "rename",
+            Self::ReverseSorting => "reverse_sorting",
+            Self::SaveAs => "save_as",
+            Self::SelectAll => "select_all",
+            Self::Shell => "shell",
+            Self::SortByNextColumn => "sort_by_next_column",
+            Self::Sorting => "sorting",
+            Self::Symlink => "symlink",
+            Self::SyncBrowsing => "sync_browsing",
+            Self::SyncQueue => "sync_queue",
+            Self::TableView => "table_view",
+            Self::ToggleHiddenFiles => "toggle_hidden_files",
+            Self::Transfer => "transfer",
+            Self::Undo => "undo",
+            Self::Watch => "watch",
+            Self::WatchedPaths => "watched_paths",
+        }
+    }
+
+    /// Description of the action, as displayed in the keybindings popup
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::AuthDeleteBookmark => "Delete selected bookmark",
+            Self::AuthFilterBookmarks => "Filter bookmarks by tag",
+            Self::AuthHelp | Self::Help => "Show help",
+            Self::AuthImportBookmarks => "Import bookmarks from another client",
+            Self::AuthQuickConnect => "Search bookmarks and recents to connect",
+            Self::AuthReleaseNotes => "Show release notes",
+            Self::AuthSaveBookmark => "Save bookmark",
+            Self::AuthSetup => "Enter setup",
+            Self::Chmod => "Change file permissions",
+            Self::CommandLine => "Open command line",
+            Self::ContextMenu => "Show actions for the selected files",
+            Self::Copy => "Copy",
+            Self::CustomActions => "Show custom actions",
+            Self::Delete => "Delete selected file",
+            Self::Exec => "Execute shell command",
+            Self::FileInfo => "Show info about selected file",
+            Self::Find => "Search files",
+            Self::Follow => "Follow remote file (tail -f)",
+            Self::GoTo => "Go to path",
+            Self::GoToParentDirectory => "Go to parent directory",
+            Self::GoToPreviousDirectory => "Go to previous directory",
+            Self::LogPanel => "Toggle log panel",
+            Self::Mark => "Select file",
+            Self::Mkdir => "Make directory",
+            Self::NewFile => "Create new file",
+            Self::Open => "Open file with default application for file type",
+            Self::OpenTextFile => "Open text file with preferred editor",
+            Self::OpenWith => "Open file with specified application",
+            Self::PauseSync => "Pause/resume sync of watched paths",
+            Self::Quit => "Quit termscp",
+            Self::Reload => "Reload directory content",
+            Self::Rename => "Rename file",
+            Self::ReverseSorting => "Reverse sorting order",
+            Self::SaveAs => "Save file as",
+            Self::SelectAll => "Select all files",
+            Self::Shell => "Open remote shell (SCP/SFTP)",
+            Self::SortByNextColumn => "Sort files by next column",
+            Self::Sorting => "Change file sorting mode",
+            Self::Symlink => "Create symlink pointing to the current selected entry",
+            Self::SyncBrowsing => "Toggle synchronized browsing",
+            Self::SyncQueue => "Show changes waiting to be synched",
+            Self::TableView => "Toggle table view",
+            Self::ToggleHiddenFiles => "Toggle hidden files",
+            Self::Transfer => "Upload/Download file",
+            Self::Undo => "Undo the latest delete, rename or move",
+            Self::Watch => "Watch/unwatch file changes",
+            Self::WatchedPaths => "Show watched paths",
+        }
+    }
+
+    /// Activity where the action can be performed
+    pub fn scope(&self) -> KeyScope {
+        match self {
+            Self::AuthDeleteBookmark
+            | Self::AuthFilterBookmarks
+            | Self::AuthHelp
+            | Self::AuthImportBookmarks
+            | Self::AuthQuickConnect
+            | Self::AuthReleaseNotes
+            | Self::AuthSaveBookmark
+            | Self::AuthSetup => KeyScope::Auth,
+            _ => KeyScope::Explorer,
+        }
+    }
+
+    /// Keys bound to the action if not configured by the user
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Self::AuthDeleteBookmark => &["del", "e"],
+            Self::AuthFilterBookmarks => &["/"],
+            Self::AuthHelp => &["f1", "ctrl+h"],
+            Self::AuthImportBookmarks => &["ctrl+o"],
+            Self::AuthQuickConnect => &["ctrl+f"],
+            Self::AuthReleaseNotes => &["ctrl+r"],
+            Self::AuthSaveBookmark => &["ctrl+s"],
+            Self::AuthSetup => &["ctrl+c"],
+            Self::Chmod => &["z"],
+            Self::CommandLine => &[":"],
+            Self::ContextMenu => &["ctrl+o"],
+            Self::Copy => &["c", "f5"],
+            Self::CustomActions => &["ctrl+u"],
+            Self::Delete => &["e", "del", "f8"],
+            Self::Exec => &["x"],
+            Self::FileInfo => &["i"],
+            Self::Find => &["f"],
+            Self::Follow => &["ctrl+f"],
+            Self::GoTo => &["g"],
+            Self::GoToParentDirectory => &["u"],
+            Self::GoToPreviousDirectory => &["backspace"],
+            Self::Help => &["h", "f1"],
+            Self::LogPanel => &["p"],
+            Self::Mark => &["m"],
+            Self::Mkdir => &["d", "f7"],
+            Self::NewFile => &["n"],
+            Self::Open => &["v", "f3"],
+            Self::OpenTextFile => &["o", "f4"],
+            Self::OpenWith => &["w"],
+            Self::PauseSync => &["ctrl+p"],
+            Self::Quit => &["q", "f10"],
+            Self::Reload => &["l"],
+            Self::Rename => &["r", "f6"],
+            Self::ReverseSorting => &["ctrl+r"],
+            Self::SaveAs => &["s", "f2"],
+            Self::SelectAll => &["ctrl+a"],
+            Self::Shell => &["ctrl+x"],
+            Self::SortByNextColumn => &["ctrl+b"],
+            Self::Sorting => &["b"],
+            Self::Symlink => &["k"],
+            Self::SyncBrowsing => &["y"],
+            Self::SyncQueue => &["ctrl+y"],
+            Self::TableView => &["ctrl+v"],
+            Self::ToggleHiddenFiles => &["a"],
+            Self::Transfer => &["space"],
+            Self::Undo => &["ctrl+z"],
+            Self::Watch => &["t"],
+            Self::WatchedPaths => &["ctrl+t"],
+        }
+    }
+
+    /// Keys bound to the action if not configured by the user
+    pub fn default_bindings(&self) -> Vec<KeyBinding> {
+        self.default_keys()
+            .iter()
+            .map(|x| KeyBinding::from_str(x).unwrap())
+            .collect()
+    }
+}
+
+impl FromStr for KeyAction {
+    type Err = KeyBindingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.name() == s)
+            .ok_or_else(|| KeyBindingError::UnknownAction(s.to_string()))
+    }
+}
+
+/// A key chord, such as `ctrl+a`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct KeyBinding {
+    code: Key,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: Key, modifiers: KeyModifiers) -> Self {
+        Self {
+            code,
+            modifiers: Self::normalize_modifiers(code, modifiers),
+        }
+    }
+
+    /// Returns whether `ev` is this key chord
+    pub fn matches(&self, ev: &KeyEvent) -> bool {
+        self.code == ev.code && self.modifiers == Self::normalize_modifiers(ev.code, ev.modifiers)
+    }
+
+    /// Get the key event, as sent by the terminal, for this key chord
+    pub fn key_event(&self) -> KeyEvent {
+        let modifiers = match self.code {
+            Key::Char(ch) if ch.is_uppercase() => self.modifiers | KeyModifiers::SHIFT,
+            Key::BackTab => self.modifiers | KeyModifiers::SHIFT,
+            _ => self.modifiers,
+        };
+        KeyEvent::new(self.code, modifiers)
+    }
+
+    /// Get the key chord as displayed to the user (e.g. `CTRL+A`)
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            Key::Char(' ') => "SPACE".to_string(),
+            Key::Char(ch) if ch.is_uppercase() => format!("SHIFT+{ch}"),
+            Key::Char(ch) => ch.to_uppercase().to_string(),
+            _ => self.to_string().rsplit('+').next().unwrap().to_uppercase(),
+        };
+        format!(
+            "{}{key}",
+            Self::modifiers_prefix(self.modifiers).to_uppercase()
+        )
+    }
+
+    /// Shift is implied by the character case and by backtab, so it's ignored for them
+    fn normalize_modifiers(code: Key, modifiers: KeyModifiers) -> KeyModifiers {
+        match code {
+            Key::Char(_) | Key::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        }
+    }
+
+    fn modifiers_prefix(modifiers: KeyModifiers) -> String {
+        let mut prefix = String::new();
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("ctrl+");
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("alt+");
+        }
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            prefix.push_str("shift+");
+        }
+        prefix
+    }
+
+    fn parse_key(s: &str) -> Option<Key> {
+        let mut chars = s.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Some(Key::Char(ch));
+        }
+        let key = match s.to_lowercase().as_str() {
+            "backspace" => Key::Backspace,
+            "backtab" => Key::BackTab,
+            "del" | "delete" => Key::Delete,
+            "down" => Key::Down,
+            "end" => Key::End,
+            "enter" => Key::Enter,
+            "esc" => Key::Esc,
+            "home" => Key::Home,
+            "ins" | "insert" => Key::Insert,
+            "left" => Key::Left,
+            "pagedown" => Key::PageDown,
+            "pageup" => Key::PageUp,
+            "right" => Key::Right,
+            "space" => Key::Char(' '),
+            "tab" => Key::Tab,
+            "up" => Key::Up,
+            key => match key.strip_prefix('f').map(u8::from_str) {
+                Some(Ok(n)) if (1..=12).contains(&n) => Key::Function(n),
+                _ => return None,
+            },
+        };
+        Some(key)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = KeyBindingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || KeyBindingError::InvalidKey(s.to_string());
+        let s = s.trim();
+        // NOTE: '+' may be the key itself (e.g. `ctrl++`)
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (Some(modifiers), "+"),
+            None if s == "+" => (None, "+"),
+            None => match s.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, s),
+            },
+        };
+        let mut code = Self::parse_key(key).ok_or_else(err)?;
+        let mut mods = KeyModifiers::NONE;
+        for modifier in modifiers.into_iter().flat_map(|x| x.split('+')) {
+            mods |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(err()),
+            };
+        }
+        // Shift + char is the uppercase char
+        if mods.contains(KeyModifiers::SHIFT) {
+            match code {
+                Key::Char(ch) if ch.is_alphabetic() => {
+                    code = Key::Char(ch.to_uppercase().next().unwrap_or(ch))
+                }
+                Key::Tab => code = Key::BackTab,
+                _ => {}
+            }
+        }
+        Ok(Self::new(code, mods))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self.code {
+            Key::Backspace => "backspace".to_string(),
+            Key::BackTab => "backtab".to_string(),
+            Key::Char(' ') => "space".to_string(),
+            Key::Char(ch) => ch.to_string(),
+            Key::Delete => "del".to_string(),
+            Key::Down => "down".to_string(),
+            Key::End => "end".to_string(),
+            Key::Enter => "enter".to_string(),
+            Key::Esc => "esc".to_string(),
+            Key::Function(n) => format!("f{n}"),
+            Key::Home => "home".to_string(),
+            Key::Insert => "ins".to_string(),
+            Key::Left => "left".to_string(),
+            Key::PageDown => "pagedown".to_string(),
+            Key::PageUp => "pageup".to_string(),
+            Key::Right => "right".to_string(),
+            Key::Tab => "tab".to_string(),
+            Key::Up => "up".to_string(),
+            key => format!("{key:?}").to_lowercase(),
+        };
+        write!(f, "{}{key}", Self::modifiers_prefix(self.modifiers))
+    }
+}
+
+/// Parse a comma separated list of key chords (e.g. `e, del, f8`)
+pub fn parse_key_bindings(s: &str) -> Result<Vec<KeyBinding>, KeyBindingError> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(KeyBinding::from_str)
+        .collect()
+}
+
+/// Key chords bound to each action
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct KeyBindings {
+    bindings: Vec<(KeyAction, Vec<KeyBinding>)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::ALL
+                .into_iter()
+                .map(|x| (x, x.default_bindings()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Instantiate key bindings from the `keybindings` table of the configuration.
+    /// Unknown actions and actions with invalid keys are reported and left to their defaults.
+    /// Keys configured for several actions of the same scope are reported and bound to the last action only
+    pub fn new(config: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut bindings = Self::default();
+        let mut configured: Vec<KeyAction> = Vec::new();
+        for (action, keys) in config.iter() {
+            let action = match KeyAction::from_str(action) {
+                Ok(action) => action,
+                Err(err) => {
+                    warn!("Ignoring key binding: {}", err);
+                    continue;
+                }
+            };
+            match keys
+                .iter()
+                .map(|x| KeyBinding::from_str(x))
+                .collect::<Result<Vec<KeyBinding>, KeyBindingError>>()
+            {
+                Ok(keys) => {
+                    for (other, key) in bindings.clashes(&configured, action, &keys) {
+                        warn!(
+                            "Key {} is bound to both {} and {}: unbinding it from {}",
+                            key.label(),
+                            other.name(),
+                            action.name(),
+                            other.name()
+                        );
+                    }
+                    bindings.set(action, keys);
+                    configured.push(action);
+                }
+                Err(err) => warn!("Ignoring key binding for {}: {}", action.name(), err),
+            }
+        }
+        bindings
+    }
+
+    /// Get the `keys` which are already bound to one of the `configured` actions of the scope of `action`
+    fn clashes(
+        &self,
+        configured: &[KeyAction],
+        action: KeyAction,
+        keys: &[KeyBinding],
+    ) -> Vec<(KeyAction, KeyBinding)> {
+        configured
+            .iter()
+            .filter(|x| **x != action && x.scope() == action.scope())
+            .flat_map(|x| {
+                self.keys(*x)
+                    .iter()
+                    .filter(|key| keys.contains(key))
+                    .map(move |key| (*x, *key))
+            })
+            .collect()
+    }
+
+    /// Bind `keys` to `action`. The keys are unbound from the other actions of the same scope
+    pub fn set(&mut self, action: KeyAction, keys: Vec<KeyBinding>) {
+        for (other, other_keys) in self.bindings.iter_mut() {
+            if *other == action {
+                *other_keys = keys.clone();
+            } else if other.scope() == action.scope() {
+                other_keys.retain(|x| !keys.contains(x));
+            }
+        }
+    }
+
+    /// Get the keys bound to `action`
+    pub fn keys(&self, action: KeyAction) -> &[KeyBinding] {
+        self.bindings
+            .iter()
+            .find(|(x, _)| *x == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the action of `scope` bound to key event `ev`
+    pub fn action(&self, scope: KeyScope, ev: &KeyEvent) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.scope() == scope)
+            .find(|(_, keys)| keys.iter().any(|x| x.matches(ev)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Iterate over the actions of `scope` and their keys
+    pub fn iter(&self, scope: KeyScope) -> impl Iterator<Item = (KeyAction, &[KeyBinding])> {
+        self.bindings
+            .iter()
+            .filter(move |(action, _)| action.scope() == scope)
+            .map(|(action, keys)| (*action, keys.as_slice()))
+    }
+
+    /// Get the keys bound to `action` as displayed to the user (e.g. `<E|DEL|F8>`)
+    pub fn label(&self, action: KeyAction) -> String {
+        let keys: Vec<String> = self.keys(action).iter().map(|x| x.label()).collect();
+        match keys.is_empty() {
+            true => String::from("<NONE>"),
+            false => format!("<{}>", keys.join("|")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_key_binding() {
+        assert_eq!(
+            KeyBinding::from_str("e").unwrap(),
+            KeyBinding::new(Key::Char('e'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::from_str("ctrl+a").unwrap(),
+            KeyBinding::new(Key::Char('a'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyBinding::from_str("CTRL+ALT+Del").unwrap(),
+            KeyBinding::new(Key::Delete, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            KeyBinding::from_str("shift+a").unwrap(),
+            KeyBinding::new(Key::Char('A'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::from_str("shift+tab").unwrap(),
+            KeyBinding::new(Key::BackTab, KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::from_str("F8").unwrap(),
+            KeyBinding::new(Key::Function(8), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::from_str("space").unwrap(),
+            KeyBinding::new(Key::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::from_str("ctrl++").unwrap(),
+            KeyBinding::new(Key::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyBinding::from_str("+").unwrap(),
+            KeyBinding::new(Key::Char('+'), KeyModifiers::NONE)
+        );
+        assert!(KeyBinding::from_str("").is_err());
+        assert!(KeyBinding::from_str("f13").is_err());
+        assert!(KeyBinding::from_str("hyper+a").is_err());
+        assert!(KeyBinding::from_str("ctrl+foo").is_err());
+    }
+
+    #[test]
+    fn should_display_key_binding() {
+        for key in [
+            "e",
+            "ctrl+a",
+            "ctrl+alt+del",
+            "A",
+            "backtab",
+            "f8",
+            "space",
+            "ctrl++",
+        ] {
+            assert_eq!(KeyBinding::from_str(key).unwrap().to_string(), key);
+        }
+        assert_eq!(KeyBinding::from_str("e").unwrap().label(), "E");
+        assert_eq!(KeyBinding::from_str("E").unwrap().label(), "SHIFT+E");
+        assert_eq!(KeyBinding::from_str("ctrl+a").unwrap().label(), "CTRL+A");
+        assert_eq!(KeyBinding::from_str("del").unwrap().label(), "DEL");
+        assert_eq!(KeyBinding::from_str("space").unwrap().label(), "SPACE");
+    }
+
+    #[test]
+    fn should_match_key_event() {
+        let key = KeyBinding::from_str("A").unwrap();
+        assert!(key.matches(&KeyEvent::new(Key::Char('A'), KeyModifiers::SHIFT)));
+        assert!(!key.matches(&KeyEvent::new(Key::Char('a'), KeyModifiers::NONE)));
+        assert_eq!(
+            key.key_event(),
+            KeyEvent::new(Key::Char('A'), KeyModifiers::SHIFT)
+        );
+        let key = KeyBinding::from_str("ctrl+t").unwrap();
+        assert!(key.matches(&KeyEvent::new(Key::Char('t'), KeyModifiers::CONTROL)));
+        assert!(!key.matches(&KeyEvent::new(Key::Char('t'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn should_parse_key_bindings_list() {
+        assert_eq!(
+            parse_key_bindings("e, del,f8").unwrap(),
+            vec![
+                KeyBinding::new(Key::Char('e'), KeyModifiers::NONE),
+                KeyBinding::new(Key::Delete, KeyModifiers::NONE),
+                KeyBinding::new(Key::Function(8), KeyModifiers::NONE),
+            ]
+        );
+        assert!(parse_key_bindings("").unwrap().is_empty());
+        assert_eq!(
+            parse_key_bindings("e, foo").unwrap_err(),
+            KeyBindingError::InvalidKey(String::from("foo"))
+        );
+    }
+
+    #[test]
+    fn should_get_default_key_bindings() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.label(KeyAction::Delete), "<E|DEL|F8>");
+        assert_eq!(
+            bindings.action(
+                KeyScope::Explorer,
+                &KeyEvent::new(Key::Char('r'), KeyModifiers::CONTROL)
+            ),
+            Some(KeyAction::ReverseSorting)
+        );
+        assert_eq!(
+            bindings.action(
+                KeyScope::Auth,
+                &KeyEvent::new(Key::Char('r'), KeyModifiers::CONTROL)
+            ),
+            Some(KeyAction::AuthReleaseNotes)
+        );
+        assert_eq!(
+            bindings.action(
+                KeyScope::Explorer,
+                &KeyEvent::new(Key::Char('j'), KeyModifiers::NONE)
+            ),
+            None
+        );
+        // Every action has a unique name and default keys don't clash
+        for action in KeyAction::ALL {
+            assert_eq!(KeyAction::from_str(action.name()).unwrap(), action);
+            for key in bindings.keys(action) {
+                assert_eq!(
+                    bindings.action(action.scope(), &key.key_event()),
+                    Some(action)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_configure_key_bindings() {
+        let mut config = BTreeMap::new();
+        config.insert(
+            String::from("copy"),
+            vec![String::from("e"), String::from("ctrl+c")],
+        );
+        config.insert(String::from("symlink"), vec![]);
+        config.insert(String::from("rename"), vec![String::from("foo")]);
+        config.insert(String::from("fly"), vec![String::from("j")]);
+        let bindings = KeyBindings::new(&config);
+        assert_eq!(bindings.label(KeyAction::Copy), "<E|CTRL+C>");
+        // 'e' has been unbound from delete
+        assert_eq!(bindings.label(KeyAction::Delete), "<DEL|F8>");
+        assert_eq!(bindings.label(KeyAction::Symlink), "<NONE>");
+        assert_eq!(bindings.label(KeyAction::Rename), "<R|F6>");
+        // Other scopes are not affected
+        assert_eq!(bindings.label(KeyAction::AuthSetup), "<CTRL+C>");
+        assert_eq!(bindings.label(KeyAction::AuthDeleteBookmark), "<DEL|E>");
+        assert_eq!(
+            bindings.action(
+                KeyScope::Explorer,
+                &KeyEvent::new(Key::Char('e'), KeyModifiers::NONE)
+            ),
+            Some(KeyAction::Copy)
+        );
+    }
+
+    #[test]
+    fn should_find_clashing_key_bindings() {
+        let mut config = BTreeMap::new();
+        config.insert(String::from("copy"), vec![String::from("x")]);
+        let bindings = KeyBindings::new(&config);
+        let x = KeyBinding::new(Key::Char('x'), KeyModifiers::NONE);
+        let y = KeyBinding::new(Key::Char('y'), KeyModifiers::NONE);
+        // clashes with the other configured actions of the scope only
+        assert_eq!(
+            bindings.clashes(&[KeyAction::Copy], KeyAction::Rename, &[x, y]),
+            vec![(KeyAction::Copy, x)]
+        );
+        assert!(bindings
+            .clashes(&[KeyAction::Copy], KeyAction::Copy, &[x])
+            .is_empty());
+        assert!(bindings
+            .clashes(&[KeyAction::Copy], KeyAction::AuthSetup, &[x])
+            .is_empty());
+        assert!(bindings.clashes(&[], KeyAction::Rename, &[x]).is_empty());
+        // the key is bound to the last action only
+        config.insert(String::from("rename"), vec![String::from("x")]);
+        let bindings = KeyBindings::new(&config);
+        assert_eq!(bindings.label(KeyAction::Copy), "<NONE>");
+        assert_eq!(bindings.label(KeyAction::Rename), "<X>");
+    }
+}
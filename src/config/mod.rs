@@ -5,6 +5,8 @@
 // export
 
 pub mod bookmarks;
+pub mod custom_actions;
+pub mod keybindings;
 pub mod params;
 pub mod serialization;
 pub mod themes;
@@ -3,7 +3,7 @@
 //! `config` is the module which provides access to termscp configuration
 
 // Locals
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 // Ext
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::filetransfer::FileTransferProtocol;
 
 pub const DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD: u64 = 536870912; // 512MB
+pub const DEFAULT_REMOTE_WATCH_INTERVAL: u64 = 10; // seconds
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 /// UserConfig contains all the configurations for the user,
@@ -19,6 +20,12 @@ pub const DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD: u64 = 536870912; // 512MB
 pub struct UserConfig {
     pub user_interface: UserInterfaceConfig,
     pub remote: RemoteConfig,
+    /// Keys bound to each action; actions not listed here use their default keys
+    #[serde(default)]
+    pub keybindings: BTreeMap<String, Vec<String>>, // @! Since 0.14.0
+    /// Commands defined by the user, executed on the selected files from the actions menu
+    #[serde(default)]
+    pub custom_actions: Vec<CustomActionConfig>, // @! Since 0.14.0
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -35,6 +42,44 @@ pub struct UserInterfaceConfig {
     pub remote_file_fmt: Option<String>,     // @! Since 0.5.0
     pub notifications: Option<bool>,         // @! Since 0.7.0; Default true
     pub notification_threshold: Option<u64>, // @! Since 0.7.0; Default 512MB
+    pub mouse_support: Option<bool>,         // @! Since 0.14.0; Default true
+    pub vim_mode: Option<bool>,              // @! Since 0.14.0; Default false
+    pub remote_watch_interval: Option<u64>,  // @! Since 0.14.0; Default 10 seconds
+    pub local_trash: Option<bool>,           // @! Since 0.14.0; Default true
+    pub remote_trash: Option<bool>,          // @! Since 0.14.0; Default false
+    /// file sorting. Refers to local host
+    pub file_sorting: Option<FileSortingConfig>, // @! Since 0.14.0
+    pub remote_file_sorting: Option<FileSortingConfig>, // @! Since 0.14.0
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+/// Describes how files are sorted in a file explorer
+pub struct FileSortingConfig {
+    /// Sorting criteria (e.g. `by_name`); Default by name
+    pub by: Option<String>,
+    /// Reverse sorting order; Default false
+    pub reverse: Option<bool>,
+    /// Compare numbers in names by their value (e.g. `file2` before `file10`); Default true
+    pub natural: Option<bool>,
+    /// Compare names case-sensitively; Default false
+    pub case_sensitive: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+/// Describes a command defined by the user, executed on the selected files
+pub struct CustomActionConfig {
+    /// Name displayed in the actions menu
+    pub name: String,
+    /// Command to execute; `{name}`, `{path}` and `{dir}` are replaced with the attributes of each selected file
+    pub command: String,
+    /// Key which runs the action from the actions menu
+    pub key: Option<String>,
+    /// Where the action can be executed: `local`, `remote` or `both`; Default both
+    pub scope: Option<String>,
+    /// Pattern the names of the selected files must match (e.g. `*.service`)
+    pub pattern: Option<String>,
+    /// Ask for confirmation before running the action; Default false
+    pub confirm: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -83,6 +128,13 @@ impl Default for UserInterfaceConfig {
             remote_file_fmt: None,
             notifications: Some(true),
             notification_threshold: Some(DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD),
+            mouse_support: Some(true),
+            vim_mode: Some(false),
+            remote_watch_interval: Some(DEFAULT_REMOTE_WATCH_INTERVAL),
+            local_trash: Some(true),
+            remote_trash: Some(false),
+            file_sorting: None,
+            remote_file_sorting: None,
         }
     }
 }
@@ -118,6 +170,13 @@ mod tests {
             remote_file_fmt: Some(String::from("{USER}")),
             notifications: Some(true),
             notification_threshold: Some(DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD),
+            mouse_support: Some(true),
+            vim_mode: Some(false),
+            remote_watch_interval: Some(DEFAULT_REMOTE_WATCH_INTERVAL),
+            local_trash: Some(true),
+            remote_trash: Some(false),
+            file_sorting: None,
+            remote_file_sorting: None,
         };
         assert_eq!(ui.default_protocol, String::from("SFTP"));
         assert_eq!(ui.text_editor, PathBuf::from("nano"));
@@ -126,9 +185,17 @@ mod tests {
         assert_eq!(ui.prompt_on_file_replace, Some(true));
         assert_eq!(ui.group_dirs, Some(String::from("first")));
         assert_eq!(ui.file_fmt, Some(String::from("{NAME}")));
+        let mut keybindings = BTreeMap::new();
+        keybindings.insert(String::from("delete"), vec![String::from("ctrl+d")]);
         let cfg: UserConfig = UserConfig {
             user_interface: ui,
             remote,
+            keybindings,
+            custom_actions: vec![CustomActionConfig {
+                name: String::from("Restart service"),
+                command: String::from("systemctl restart {name}"),
+                ..Default::default()
+            }],
         };
         assert_eq!(
             *cfg.remote
@@ -157,5 +224,18 @@ mod tests {
             cfg.user_interface.notification_threshold,
             Some(DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD)
         );
+        assert_eq!(cfg.user_interface.mouse_support, Some(true));
+        assert_eq!(cfg.user_interface.vim_mode, Some(false));
+        assert_eq!(cfg.user_interface.local_trash, Some(true));
+        assert_eq!(cfg.user_interface.remote_trash, Some(false));
+        assert_eq!(cfg.custom_actions.len(), 1);
+        assert_eq!(
+            cfg.user_interface.remote_watch_interval,
+            Some(DEFAULT_REMOTE_WATCH_INTERVAL)
+        );
+        assert_eq!(
+            cfg.keybindings.get("delete").unwrap(),
+            &vec![String::from("ctrl+d")]
+        );
     }
 }
@@ -115,8 +115,10 @@ mod tests {
     use tuirealm::tui::style::Color;
 
     use super::*;
-    use crate::config::bookmarks::{Bookmark, S3Params, SmbParams, UserHosts};
-    use crate::config::params::UserConfig;
+    use crate::config::bookmarks::{
+        Bookmark, BookmarkPassword, Hook, S3Params, SecretRef, SmbParams, UserHosts, WatchedPath,
+    };
+    use crate::config::params::{CustomActionConfig, FileSortingConfig, UserConfig};
     use crate::config::themes::Theme;
     use crate::filetransfer::FileTransferProtocol;
     use crate::utils::test_helpers::create_file_ioers;
@@ -171,6 +173,11 @@ mod tests {
         assert_eq!(cfg.user_interface.prompt_on_file_replace.unwrap(), false);
         assert_eq!(cfg.user_interface.notifications.unwrap(), false);
         assert_eq!(cfg.user_interface.notification_threshold.unwrap(), 1024);
+        assert_eq!(cfg.user_interface.mouse_support, Some(false));
+        assert_eq!(cfg.user_interface.vim_mode, Some(true));
+        assert_eq!(cfg.user_interface.remote_watch_interval, Some(30));
+        assert_eq!(cfg.user_interface.local_trash, Some(false));
+        assert_eq!(cfg.user_interface.remote_trash, Some(true));
         assert_eq!(cfg.user_interface.group_dirs, Some(String::from("last")));
         // Remote
         assert_eq!(
@@ -185,6 +192,24 @@ mod tests {
             cfg.user_interface.remote_file_fmt,
             Some(String::from("{NAME} {USER}")),
         );
+        assert_eq!(
+            cfg.user_interface.file_sorting,
+            Some(FileSortingConfig {
+                by: Some(String::from("by_size")),
+                reverse: Some(true),
+                natural: None,
+                case_sensitive: None,
+            })
+        );
+        assert_eq!(
+            cfg.user_interface.remote_file_sorting,
+            Some(FileSortingConfig {
+                by: Some(String::from("by_extension")),
+                reverse: None,
+                natural: Some(false),
+                case_sensitive: Some(true),
+            })
+        );
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -201,6 +226,31 @@ mod tests {
             PathBuf::from("/home/omar/.ssh/beaglebone.key")
         );
         assert!(cfg.remote.ssh_keys.get(&String::from("1.1.1.1")).is_none());
+        // Verify keybindings
+        assert_eq!(
+            cfg.keybindings.get("delete").unwrap(),
+            &vec![String::from("ctrl+d"), String::from("f8")]
+        );
+        assert!(cfg.keybindings.get("copy").unwrap().is_empty());
+        // Verify custom actions
+        assert_eq!(
+            cfg.custom_actions,
+            vec![
+                CustomActionConfig {
+                    name: String::from("Restart service"),
+                    command: String::from("systemctl restart {name}"),
+                    key: Some(String::from("r")),
+                    scope: Some(String::from("remote")),
+                    pattern: Some(String::from("*.service")),
+                    confirm: Some(true),
+                },
+                CustomActionConfig {
+                    name: String::from("Disk usage"),
+                    command: String::from("df -h"),
+                    ..Default::default()
+                },
+            ]
+        );
     }
 
     #[test]
@@ -224,7 +274,16 @@ mod tests {
         assert!(cfg.user_interface.remote_file_fmt.is_none());
         assert!(cfg.user_interface.notifications.is_none());
         assert!(cfg.user_interface.notification_threshold.is_none());
+        assert!(cfg.user_interface.mouse_support.is_none());
+        assert!(cfg.user_interface.vim_mode.is_none());
+        assert!(cfg.user_interface.remote_watch_interval.is_none());
+        assert!(cfg.user_interface.local_trash.is_none());
+        assert!(cfg.user_interface.remote_trash.is_none());
+        assert!(cfg.user_interface.file_sorting.is_none());
+        assert!(cfg.user_interface.remote_file_sorting.is_none());
         assert!(cfg.remote.ssh_config.is_none());
+        assert!(cfg.keybindings.is_empty());
+        assert!(cfg.custom_actions.is_empty());
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -256,6 +315,11 @@ mod tests {
     fn test_config_serialization_params_serialize() {
         let mut cfg: UserConfig = UserConfig::default();
         let toml_file: tempfile::NamedTempFile = tempfile::NamedTempFile::new().ok().unwrap();
+        cfg.custom_actions.push(CustomActionConfig {
+            name: String::from("Disk usage"),
+            command: String::from("df -h"),
+            ..Default::default()
+        });
         // Insert key
         cfg.remote.ssh_keys.insert(
             String::from("192.168.1.31"),
@@ -267,7 +331,8 @@ mod tests {
         // Reload configuration and check if it's ok
         toml_file.as_file().sync_all().unwrap();
         toml_file.as_file().rewind().unwrap();
-        assert!(deserialize::<UserConfig>(Box::new(toml_file)).is_ok());
+        let reloaded = deserialize::<UserConfig>(Box::new(toml_file)).unwrap();
+        assert_eq!(reloaded.custom_actions, cfg.custom_actions);
     }
 
     #[test]
@@ -302,6 +367,20 @@ mod tests {
         remote_file_fmt = "{NAME} {USER}"
         notifications = false
         notification_threshold = 1024
+        mouse_support = false
+        vim_mode = true
+        remote_watch_interval = 30
+        local_trash = false
+        remote_trash = true
+
+        [user_interface.file_sorting]
+        by = "by_size"
+        reverse = true
+
+        [user_interface.remote_file_sorting]
+        by = "by_extension"
+        natural = false
+        case_sensitive = true
 
         [remote]
         ssh_config = "/home/omar/.ssh/config"
@@ -309,6 +388,22 @@ mod tests {
         [remote.ssh_keys]
         "192.168.1.31" = "/home/omar/.ssh/raspberry.key"
         "192.168.1.32" = "/home/omar/.ssh/beaglebone.key"
+
+        [keybindings]
+        delete = ["ctrl+d", "f8"]
+        copy = []
+
+        [[custom_actions]]
+        name = "Restart service"
+        command = "systemctl restart {name}"
+        key = "r"
+        scope = "remote"
+        pattern = "*.service"
+        confirm = true
+
+        [[custom_actions]]
+        name = "Disk usage"
+        command = "df -h"
         "#;
         tmpfile.write_all(file_content.as_bytes()).unwrap();
         tmpfile
@@ -366,19 +461,45 @@ mod tests {
         assert_eq!(host.username.as_deref().unwrap(), "root");
         assert_eq!(host.password, None);
         // Verify bookmarks
-        assert_eq!(hosts.bookmarks.len(), 5);
+        assert_eq!(hosts.bookmarks.len(), 8);
         let host: &Bookmark = hosts.bookmarks.get("raspberrypi2").unwrap();
         assert_eq!(host.address.as_deref().unwrap(), "192.168.1.31");
         assert_eq!(host.port.unwrap(), 22);
         assert_eq!(host.protocol, FileTransferProtocol::Sftp);
         assert_eq!(host.username.as_deref().unwrap(), "root");
-        assert_eq!(host.password.as_deref().unwrap(), "mypassword");
+        assert_eq!(
+            host.password,
+            Some(BookmarkPassword::Inline(String::from("mypassword")))
+        );
+        assert!(host.tags.is_empty());
+        // Grouped bookmark with tags
+        let host: &Bookmark = hosts.bookmarks.get("prod/eu/db01").unwrap();
+        assert_eq!(host.address.as_deref().unwrap(), "10.0.1.1");
+        assert_eq!(host.tags, vec![String::from("db"), String::from("eu")]);
+        // Passwords resolved from secret backends
+        let host: &Bookmark = hosts.bookmarks.get("prod/eu/db02").unwrap();
+        assert_eq!(
+            host.password,
+            Some(BookmarkPassword::Secret(SecretRef::Cmd(String::from(
+                "pass show prod/db02"
+            ))))
+        );
+        let host: &Bookmark = hosts.bookmarks.get("prod/eu/db03").unwrap();
+        assert_eq!(
+            host.password,
+            Some(BookmarkPassword::Secret(SecretRef::Env(String::from(
+                "PROD_PASS"
+            ))))
+        );
         let host: &Bookmark = hosts.bookmarks.get("msi-estrem").unwrap();
         assert_eq!(host.address.as_deref().unwrap(), "192.168.1.30");
         assert_eq!(host.port.unwrap(), 22);
         assert_eq!(host.protocol, FileTransferProtocol::Sftp);
         assert_eq!(host.username.as_deref().unwrap(), "cvisintin");
-        assert_eq!(host.password.as_deref().unwrap(), "mysecret");
+        assert_eq!(
+            host.password,
+            Some(BookmarkPassword::Inline(String::from("mysecret")))
+        );
         assert_eq!(
             host.remote_path.as_deref().unwrap(),
             std::path::Path::new("/tmp")
@@ -404,6 +525,15 @@ mod tests {
         assert_eq!(s3.access_key.as_deref().unwrap(), "pippo");
         assert_eq!(s3.secret_access_key.as_deref().unwrap(), "pluto");
         assert_eq!(s3.new_path_style.unwrap(), true);
+        let settings = host.settings.as_ref().unwrap();
+        assert_eq!(settings.show_hidden_files, Some(true));
+        assert_eq!(
+            settings.remote_file_sorting.as_ref().unwrap().by.as_deref(),
+            Some("by_size")
+        );
+        assert!(settings.file_sorting.is_none());
+        assert!(hosts.bookmarks.get("smb").unwrap().settings.is_none());
+        assert!(host.hooks.is_none());
 
         // smb
         let host = hosts.bookmarks.get("smb").unwrap();
@@ -412,7 +542,34 @@ mod tests {
         #[cfg(unix)]
         assert_eq!(host.username.as_deref().unwrap(), "test");
         #[cfg(unix)]
-        assert_eq!(host.password.as_deref().unwrap(), "test");
+        assert_eq!(
+            host.password,
+            Some(BookmarkPassword::Inline(String::from("test")))
+        );
+
+        let hooks = host.hooks.as_ref().unwrap();
+        assert_eq!(
+            hooks.on_connect,
+            vec![Hook {
+                command: String::from("wg-quick up office"),
+                remote: false,
+                abort_on_failure: true,
+            }]
+        );
+        assert_eq!(hooks.after_connect.len(), 1);
+        assert!(hooks.after_connect[0].remote);
+        assert!(!hooks.after_connect[0].abort_on_failure);
+        assert!(hooks.on_disconnect.is_empty());
+        assert_eq!(
+            host.watch,
+            vec![WatchedPath {
+                local: PathBuf::from("/home/omar/develop/site"),
+                remote: PathBuf::from("/var/www/site"),
+                ignore: vec![String::from("*.swp"), String::from("node_modules/")],
+                reconcile: true,
+            }]
+        );
+        assert!(hosts.bookmarks.get("my-bucket").unwrap().watch.is_empty());
 
         let smb = host.smb.as_ref().unwrap();
         assert_eq!(smb.share.as_str(), "temp");
@@ -443,6 +600,10 @@ mod tests {
                 password: None,
                 remote_path: None,
                 local_path: None,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: None,
             },
@@ -454,9 +615,13 @@ mod tests {
                 port: Some(4022),
                 protocol: FileTransferProtocol::Sftp,
                 username: Some(String::from("cvisintin")),
-                password: Some(String::from("password")),
+                password: Some(BookmarkPassword::Inline(String::from("password"))),
                 remote_path: Some(PathBuf::from("/tmp")),
                 local_path: Some(PathBuf::from("/usr")),
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: None,
             },
@@ -471,6 +636,10 @@ mod tests {
                 password: None,
                 remote_path: None,
                 local_path: None,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: Some(S3Params {
                     bucket: "veeso".to_string(),
                     region: Some("eu-west-1".to_string()),
@@ -497,6 +666,10 @@ mod tests {
                 password: None,
                 remote_path: None,
                 local_path: None,
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: smb_params,
             },
@@ -509,16 +682,24 @@ mod tests {
                 port: Some(3022),
                 protocol: FileTransferProtocol::Scp,
                 username: Some(String::from("omar")),
-                password: Some(String::from("aaa")),
+                password: Some(BookmarkPassword::Inline(String::from("aaa"))),
                 remote_path: Some(PathBuf::from("/tmp")),
                 local_path: Some(PathBuf::from("/usr")),
+                tags: Vec::new(),
+                settings: None,
+                hooks: None,
+                watch: Vec::new(),
                 s3: None,
                 smb: None,
             },
         );
         let tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
         // Serialize
-        let hosts: UserHosts = UserHosts { bookmarks, recents };
+        let hosts: UserHosts = UserHosts {
+            master_password: None,
+            bookmarks,
+            recents,
+        };
         assert!(serialize(&hosts, Box::new(tmpfile)).is_ok());
     }
 
@@ -556,6 +737,9 @@ mod tests {
         raspberrypi2 = { address = "192.168.1.31", port = 22, protocol = "SFTP", username = "root", password = "mypassword" }
         msi-estrem = { address = "192.168.1.30", port = 22, protocol = "SFTP", username = "cvisintin", password = "mysecret", directory = "/tmp", local_path = "/usr" }
         aws-server-prod1 = { address = "51.23.67.12", port = 21, protocol = "FTPS", username = "aws001" }
+        "prod/eu/db01" = { address = "10.0.1.1", port = 22, protocol = "SFTP", username = "root", tags = ["db", "eu"] }
+        "prod/eu/db02" = { address = "10.0.1.2", port = 22, protocol = "SFTP", username = "root", password = { cmd = "pass show prod/db02" } }
+        "prod/eu/db03" = { address = "10.0.1.3", port = 22, protocol = "SFTP", username = "root", password = { env = "PROD_PASS" } }
         
         [bookmarks.my-bucket]
         protocol = "S3"
@@ -569,6 +753,12 @@ mod tests {
         secret_access_key = "pluto"
         new_path_style = true
 
+        [bookmarks.my-bucket.settings]
+        show_hidden_files = true
+
+        [bookmarks.my-bucket.settings.remote_file_sorting]
+        by = "by_size"
+
         [bookmarks.smb]
         protocol = "SMB"
         address = "localhost"
@@ -580,6 +770,20 @@ mod tests {
         share = "temp"
         workgroup = "test"
 
+        [[bookmarks.smb.hooks.on_connect]]
+        command = "wg-quick up office"
+        abort_on_failure = true
+
+        [[bookmarks.smb.hooks.after_connect]]
+        command = "./notify.sh"
+        remote = true
+
+        [[bookmarks.smb.watch]]
+        local = "/home/omar/develop/site"
+        remote = "/var/www/site"
+        ignore = ["*.swp", "node_modules/"]
+        reconcile = true
+
         [recents]
         ISO20201215T094000Z = { address = "172.16.104.10", port = 22, protocol = "SCP", username = "root" }
         "#;
@@ -46,6 +46,30 @@ impl FileExplorerBuilder {
         self
     }
 
+    /// Enable REVERSE_SORTING option
+    pub fn with_reverse_sorting(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.opts.set(ExplorerOpts::REVERSE_SORTING, val);
+        }
+        self
+    }
+
+    /// Enable NATURAL_SORTING option
+    pub fn with_natural_sorting(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.opts.set(ExplorerOpts::NATURAL_SORTING, val);
+        }
+        self
+    }
+
+    /// Enable CASE_SENSITIVE_SORTING option
+    pub fn with_case_sensitive_sorting(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.opts.set(ExplorerOpts::CASE_SENSITIVE_SORTING, val);
+        }
+        self
+    }
+
     /// Enable DIRS_FIRST option
     pub fn with_group_dirs(&mut self, group_dirs: Option<GroupDirs>) -> &mut FileExplorerBuilder {
         if let Some(e) = self.explorer.as_mut() {
@@ -67,11 +91,22 @@ impl FileExplorerBuilder {
     pub fn with_formatter(&mut self, fmt_str: Option<&str>) -> &mut FileExplorerBuilder {
         if let Some(e) = self.explorer.as_mut() {
             if let Some(fmt_str) = fmt_str {
+                let content_sniffing = e.fmt.content_sniffing;
                 e.fmt = Formatter::new(fmt_str);
+                e.fmt.content_sniffing = content_sniffing;
             }
         }
         self
     }
+
+    /// Allow formatter to read files content (e.g. to detect mime type).
+    /// Must be enabled only for explorers on the local file system
+    pub fn with_content_sniffing(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.fmt.content_sniffing = val;
+        }
+        self
+    }
 }
 
 #[cfg(test)]
@@ -89,6 +124,7 @@ mod tests {
         assert_eq!(explorer.file_sorting, FileSorting::Name); // Default
         assert_eq!(explorer.group_dirs, None);
         assert_eq!(explorer.stack_size, 16);
+        assert!(!explorer.fmt.content_sniffing);
     }
 
     #[test]
@@ -97,13 +133,21 @@ mod tests {
             .with_file_sorting(FileSorting::ModifyTime)
             .with_group_dirs(Some(GroupDirs::First))
             .with_hidden_files(true)
+            .with_reverse_sorting(true)
+            .with_natural_sorting(true)
+            .with_case_sensitive_sorting(true)
             .with_stack_size(24)
+            .with_content_sniffing(true)
             .with_formatter(Some("{NAME}"))
             .build();
         // Verify
         assert!(explorer.opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES));
+        assert!(explorer.reverse_sorting());
+        assert!(explorer.natural_sorting());
+        assert!(explorer.case_sensitive_sorting());
         assert_eq!(explorer.file_sorting, FileSorting::ModifyTime); // Default
         assert_eq!(explorer.group_dirs, Some(GroupDirs::First));
         assert_eq!(explorer.stack_size, 24);
+        assert!(explorer.fmt.content_sniffing);
     }
 }
@@ -3,8 +3,12 @@
 //! `formatter` is the module which provides formatting utilities for `FileExplorer`
 
 // Locals
-use std::path::PathBuf;
-use std::time::UNIX_EPOCH;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs::File as StdFile;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // Ext
 use bytesize::ByteSize;
@@ -14,24 +18,36 @@ use unicode_width::UnicodeWidthStr;
 #[cfg(unix)]
 use users::{get_group_by_gid, get_user_by_uid};
 
-use crate::utils::fmt::{fmt_path_elide, fmt_pex, fmt_time};
+use super::FileSorting;
+use crate::utils::fmt::{fmt_path_elide, fmt_pex, fmt_reltime, fmt_time};
 use crate::utils::path::diff_paths;
 use crate::utils::string::secure_substring;
 // Types
 // FmtCallback: Formatter, fsentry: &File, cur_str, prefix, length, extra
 type FmtCallback = fn(&Formatter, &File, &str, &str, Option<&usize>, Option<&String>) -> String;
+/// Key used to cache expensive values for a `File`: path, size and modification time
+type FileCacheKey = (PathBuf, u64, Option<SystemTime>);
 
 // Keys
 const FMT_KEY_ATIME: &str = "ATIME";
 const FMT_KEY_CTIME: &str = "CTIME";
+const FMT_KEY_EXT: &str = "EXT";
+const FMT_KEY_GID: &str = "GID";
 const FMT_KEY_GROUP: &str = "GROUP";
+const FMT_KEY_ICON: &str = "ICON";
+const FMT_KEY_MIME: &str = "MIME";
 const FMT_KEY_MTIME: &str = "MTIME";
 const FMT_KEY_NAME: &str = "NAME";
+const FMT_KEY_OCTAL: &str = "OCTAL";
 const FMT_KEY_PATH: &str = "PATH";
 const FMT_KEY_PEX: &str = "PEX";
+const FMT_KEY_RELTIME: &str = "RELTIME";
 const FMT_KEY_SIZE: &str = "SIZE";
 const FMT_KEY_SYMLINK: &str = "SYMLINK";
+const FMT_KEY_UID: &str = "UID";
 const FMT_KEY_USER: &str = "USER";
+// Amount of bytes read to sniff file content
+const MIME_SNIFF_SIZE: u64 = 8192;
 // Default
 const FMT_DEFAULT_STX: &str = "{NAME} {PEX} {USER} {SIZE} {MTIME}";
 /**
@@ -115,19 +131,97 @@ impl CallChainBlock {
     }
 }
 
+/// A column of the file table; there's a column for each key in the format string
+pub struct FmtColumn {
+    /// The format key (e.g. `NAME`)
+    key: String,
+    /// The function to call to format the cell
+    func: FmtCallback,
+    /// The fmt len, specied for key as `{KEY:LEN}`
+    fmt_len: Option<usize>,
+    /// The extra argument for formatting, specified for key as `{KEY:LEN:EXTRA}`
+    fmt_extra: Option<String>,
+}
+
+impl FmtColumn {
+    /// Get the label to display in the table header
+    pub fn label(&self) -> &'static str {
+        match self.key.as_str() {
+            FMT_KEY_ATIME => "Accessed",
+            FMT_KEY_CTIME => "Created",
+            FMT_KEY_EXT => "Ext",
+            FMT_KEY_GID => "GID",
+            FMT_KEY_GROUP => "Group",
+            FMT_KEY_ICON => "",
+            FMT_KEY_MIME => "Type",
+            FMT_KEY_MTIME => "Modified",
+            FMT_KEY_NAME => "Name",
+            FMT_KEY_OCTAL => "Mode",
+            FMT_KEY_PATH => "Path",
+            FMT_KEY_PEX => "Permissions",
+            FMT_KEY_RELTIME => match self.fmt_extra.as_deref() {
+                Some(FMT_KEY_ATIME) => "Accessed",
+                Some(FMT_KEY_CTIME) => "Created",
+                _ => "Modified",
+            },
+            FMT_KEY_SIZE => "Size",
+            FMT_KEY_SYMLINK => "Link",
+            FMT_KEY_UID => "UID",
+            FMT_KEY_USER => "User",
+            _ => "",
+        }
+    }
+
+    /// Get the file sorting which sorts files by this column, if any
+    pub fn sorting(&self) -> Option<FileSorting> {
+        match self.key.as_str() {
+            FMT_KEY_ATIME => Some(FileSorting::AccessTime),
+            FMT_KEY_CTIME => Some(FileSorting::CreationTime),
+            FMT_KEY_EXT => Some(FileSorting::Extension),
+            FMT_KEY_GID | FMT_KEY_GROUP => Some(FileSorting::Group),
+            FMT_KEY_ICON | FMT_KEY_MIME => Some(FileSorting::Type),
+            FMT_KEY_MTIME => Some(FileSorting::ModifyTime),
+            FMT_KEY_NAME | FMT_KEY_SYMLINK => Some(FileSorting::Name),
+            FMT_KEY_OCTAL | FMT_KEY_PEX => Some(FileSorting::Permissions),
+            FMT_KEY_PATH => Some(FileSorting::Path),
+            FMT_KEY_RELTIME => match self.fmt_extra.as_deref() {
+                Some(FMT_KEY_ATIME) => Some(FileSorting::AccessTime),
+                Some(FMT_KEY_CTIME) => Some(FileSorting::CreationTime),
+                _ => Some(FileSorting::ModifyTime),
+            },
+            FMT_KEY_SIZE => Some(FileSorting::Size),
+            FMT_KEY_UID | FMT_KEY_USER => Some(FileSorting::Owner),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the column content has a variable width.
+    /// These columns are never elided by the formatter, since the table fits them to the available width
+    fn elastic(&self) -> bool {
+        matches!(
+            self.key.as_str(),
+            FMT_KEY_EXT | FMT_KEY_MIME | FMT_KEY_NAME | FMT_KEY_PATH | FMT_KEY_SYMLINK
+        )
+    }
+}
+
 /// Formatter takes care of formatting FsEntries according to the provided keys.
 /// Formatting is performed using the `CallChainBlock`, which composed makes a Call Chain. This method is extremely fast compared to match the format groups
 /// at each fmt call.
 pub struct Formatter {
     call_chain: CallChainBlock,
+    /// Columns for table view
+    columns: Vec<FmtColumn>,
+    /// Whether files can be read from the local file system to sniff their content
+    pub(crate) content_sniffing: bool,
+    /// Cache for mime types, which are computed lazily
+    mime_cache: RefCell<HashMap<FileCacheKey, String>>,
 }
 
 impl Default for Formatter {
     /// Instantiates a Formatter with the default fmt syntax
     fn default() -> Self {
-        Formatter {
-            call_chain: Self::make_callchain(FMT_DEFAULT_STX),
-        }
+        Self::new(FMT_DEFAULT_STX)
     }
 }
 
@@ -136,6 +230,9 @@ impl Formatter {
     pub fn new(fmt_str: &str) -> Self {
         Formatter {
             call_chain: Self::make_callchain(fmt_str),
+            columns: Self::make_columns(fmt_str),
+            content_sniffing: false,
+            mime_cache: RefCell::new(HashMap::new()),
         }
     }
 
@@ -145,6 +242,54 @@ impl Formatter {
         self.call_chain.next(self, fsentry, "")
     }
 
+    /// Get table columns
+    pub fn columns(&self) -> &[FmtColumn] {
+        self.columns.as_slice()
+    }
+
+    /// Format fsentry as a table row; a cell for each column is returned.
+    /// Variable width columns are not elided nor padded
+    pub fn fmt_columns(&self, fsentry: &File) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| match column.elastic() {
+                true => (column.func)(self, fsentry, "", "", Some(&0), column.fmt_extra.as_ref()),
+                false => (column.func)(
+                    self,
+                    fsentry,
+                    "",
+                    "",
+                    column.fmt_len.as_ref(),
+                    column.fmt_extra.as_ref(),
+                )
+                .trim_end()
+                .to_string(),
+            })
+            .collect()
+    }
+
+    /// Get mime type for file. Mime types are cached, since they may be expensive to compute
+    pub fn mime_type(&self, fsentry: &File) -> String {
+        let key = Self::cache_key(fsentry);
+        let cached: Option<String> = self.mime_cache.borrow().get(&key).cloned();
+        match cached {
+            Some(mime) => mime,
+            None => {
+                let mime = self.file_mime(fsentry);
+                self.mime_cache.borrow_mut().insert(key, mime.clone());
+                mime
+            }
+        }
+    }
+
+    /// Drop cached values for files which are not in `files` anymore
+    pub fn retain_cache(&self, files: &[File]) {
+        let keys: HashSet<FileCacheKey> = files.iter().map(Self::cache_key).collect();
+        self.mime_cache
+            .borrow_mut()
+            .retain(|key, _| keys.contains(key));
+    }
+
     // Fmt methods
 
     /// Format last access time
@@ -201,6 +346,43 @@ impl Formatter {
         )
     }
 
+    /// Format file extension
+    fn fmt_ext(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        let ext: String = match fsentry.is_dir() {
+            true => String::new(),
+            false => fsentry.extension().unwrap_or_default(),
+        };
+        let ext_len: usize = *fmt_len.unwrap_or(&6);
+        format!("{cur_str}{prefix}{}", Self::elide_str(&ext, ext_len))
+    }
+
+    /// Format owner group id
+    fn fmt_gid(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        // the owner is unknown for some protocols
+        let gid: String = match fsentry.metadata().gid {
+            Some(gid) => gid.to_string(),
+            None => String::from("-"),
+        };
+        format!(
+            "{cur_str}{prefix}{gid:>width$}",
+            width = fmt_len.unwrap_or(&6)
+        )
+    }
+
     /// Format owner group
     fn fmt_group(
         &self,
@@ -234,6 +416,40 @@ impl Formatter {
         )
     }
 
+    /// Format file icon (nerd-font glyph)
+    fn fmt_icon(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        format!(
+            "{}{}{:width$}",
+            cur_str,
+            prefix,
+            Self::file_icon(fsentry),
+            width = fmt_len.unwrap_or(&2)
+        )
+    }
+
+    /// Format mime type.
+    /// If content sniffing is enabled, the mime type is detected from the file content,
+    /// otherwise it is guessed from the file extension.
+    fn fmt_mime(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        let mime: String = self.mime_type(fsentry);
+        let mime_len: usize = *fmt_len.unwrap_or(&24);
+        format!("{cur_str}{prefix}{}", Self::elide_str(&mime, mime_len))
+    }
+
     /// Format last change time
     fn fmt_mtime(
         &self,
@@ -276,6 +492,13 @@ impl Formatter {
             None => 24,
         };
         let name = fsentry.name();
+        // Length 0 means the name must not be elided
+        if file_len == 0 {
+            return match fsentry.is_dir() {
+                true => format!("{cur_str}{prefix}{name}/"),
+                false => format!("{cur_str}{prefix}{name}"),
+            };
+        }
         let last_idx: usize = match fsentry.is_dir() {
             // NOTE: For directories is l - 2, since we push '/' to name
             true => file_len - 2,
@@ -292,6 +515,28 @@ impl Formatter {
         format!("{cur_str}{prefix}{name:0file_len$}")
     }
 
+    /// Format file permissions in octal notation
+    fn fmt_octal(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        let octal: String = match fsentry.metadata().mode {
+            None => String::from("????"),
+            Some(mode) => format!("{:04o}", u32::from(mode)),
+        };
+        format!(
+            "{}{}{:width$}",
+            cur_str,
+            prefix,
+            octal,
+            width = fmt_len.unwrap_or(&4)
+        )
+    }
+
     /// Format path
     fn fmt_path(
         &self,
@@ -311,7 +556,7 @@ impl Formatter {
             cur_str,
             prefix,
             match fmt_len {
-                None => p.display().to_string(),
+                None | Some(0) => p.display().to_string(),
                 Some(len) => fmt_path_elide(p.as_path(), *len),
             }
         )
@@ -352,6 +597,35 @@ impl Formatter {
         format!("{cur_str}{prefix}{pex:10}")
     }
 
+    /// Format time relative to now (e.g. "3h ago").
+    /// Extra might be provided to choose the time to format between `ATIME`, `CTIME` and `MTIME` (default)
+    fn fmt_reltime(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        fmt_extra: Option<&String>,
+    ) -> String {
+        let time: Option<SystemTime> = match fmt_extra.map(|x| x.as_str()) {
+            Some(FMT_KEY_ATIME) => fsentry.metadata().accessed,
+            Some(FMT_KEY_CTIME) => fsentry.metadata().created,
+            _ => fsentry.metadata().modified,
+        };
+        // the time is unknown for some protocols
+        let reltime: String = match time {
+            Some(time) => fmt_reltime(time, SystemTime::now()),
+            None => String::from("-"),
+        };
+        format!(
+            "{}{}{:width$}",
+            cur_str,
+            prefix,
+            reltime,
+            width = fmt_len.unwrap_or(&8)
+        )
+    }
+
     /// Format file size
     fn fmt_size(
         &self,
@@ -399,7 +673,9 @@ impl Formatter {
         };
         // Replace `FMT_KEY_NAME` with name
         match fsentry.metadata().symlink.as_deref() {
+            None if file_len == 0 => format!("{cur_str}{prefix}"),
             None => format!("{cur_str}{prefix}                        "),
+            Some(p) if file_len == 0 => format!("{cur_str}{prefix}-> {}", p.display()),
             Some(p) => format!(
                 "{}{}-> {:0width$}",
                 cur_str,
@@ -410,6 +686,26 @@ impl Formatter {
         }
     }
 
+    /// Format owner user id
+    fn fmt_uid(
+        &self,
+        fsentry: &File,
+        cur_str: &str,
+        prefix: &str,
+        fmt_len: Option<&usize>,
+        _fmt_extra: Option<&String>,
+    ) -> String {
+        // the owner is unknown for some protocols
+        let uid: String = match fsentry.metadata().uid {
+            Some(uid) => uid.to_string(),
+            None => String::from("-"),
+        };
+        format!(
+            "{cur_str}{prefix}{uid:>width$}",
+            width = fmt_len.unwrap_or(&6)
+        )
+    }
+
     /// Format owner user
     fn fmt_user(
         &self,
@@ -451,8 +747,153 @@ impl Formatter {
         format!("{cur_str}{prefix}")
     }
 
+    // Helpers
+
+    /// Get mime type for file
+    fn file_mime(&self, fsentry: &File) -> String {
+        if fsentry.is_dir() {
+            return String::from("inode/directory");
+        }
+        if fsentry.is_symlink() {
+            return String::from("inode/symlink");
+        }
+        let guess: Option<String> = mime_guess::from_path(fsentry.path())
+            .first()
+            .map(|x| x.essence_str().to_string());
+        if !self.content_sniffing {
+            return guess.unwrap_or_else(|| String::from("application/octet-stream"));
+        }
+        match Self::sniff_mime(fsentry.path()) {
+            Some(mime) if mime == "text/plain" => {
+                guess.filter(|x| x.starts_with("text/")).unwrap_or(mime)
+            }
+            Some(mime) => mime,
+            None => guess.unwrap_or_else(|| String::from("application/octet-stream")),
+        }
+    }
+
+    /// Detect mime type reading the first bytes of the file
+    fn sniff_mime(path: &Path) -> Option<String> {
+        let mut buffer: Vec<u8> = Vec::with_capacity(MIME_SNIFF_SIZE as usize);
+        StdFile::open(path)
+            .ok()?
+            .take(MIME_SNIFF_SIZE)
+            .read_to_end(&mut buffer)
+            .ok()?;
+        if buffer.is_empty() {
+            return Some(String::from("inode/x-empty"));
+        }
+        if let Some(kind) = infer::get(&buffer) {
+            return Some(kind.mime_type().to_string());
+        }
+        match content_inspector::inspect(&buffer).is_text() {
+            true => Some(String::from("text/plain")),
+            false => Some(String::from("application/octet-stream")),
+        }
+    }
+
+    /// Get nerd-font glyph for file
+    fn file_icon(fsentry: &File) -> char {
+        if fsentry.is_symlink() {
+            return '\u{f0c1}'; // link
+        }
+        if fsentry.is_dir() {
+            return '\u{f07b}'; // folder
+        }
+        let ext: String = fsentry.extension().unwrap_or_default().to_lowercase();
+        match ext.as_str() {
+            "rs" => '\u{e7a8}',
+            "py" | "pyc" => '\u{e73c}',
+            "js" | "mjs" | "cjs" | "ts" | "jsx" | "tsx" => '\u{e74e}',
+            "md" | "markdown" => '\u{e73e}',
+            "html" | "htm" => '\u{f13b}',
+            "css" | "scss" | "sass" => '\u{e749}',
+            "c" | "h" | "cpp" | "cc" | "hpp" | "go" | "java" | "kt" | "rb" | "php" | "lua"
+            | "swift" | "cs" | "json" | "toml" | "yaml" | "yml" | "xml" | "ini" | "conf" => {
+                '\u{f1c9}' // code
+            }
+            "sh" | "bash" | "zsh" | "fish" | "ps1" | "bat" | "cmd" => '\u{f120}',
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tiff" | "heic" => {
+                '\u{f1c5}' // image
+            }
+            "mp3" | "wav" | "flac" | "ogg" | "m4a" | "aac" | "opus" => '\u{f1c7}',
+            "mp4" | "mkv" | "avi" | "mov" | "webm" | "wmv" | "flv" => '\u{f1c8}',
+            "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar" | "deb" | "rpm" => {
+                '\u{f1c6}' // archive
+            }
+            "pdf" => '\u{f1c1}',
+            "doc" | "docx" | "odt" => '\u{f1c2}',
+            "xls" | "xlsx" | "ods" | "csv" => '\u{f1c3}',
+            "ppt" | "pptx" | "odp" => '\u{f1c4}',
+            "db" | "sqlite" | "sql" => '\u{f1c0}',
+            "lock" | "key" | "pem" | "crt" | "gpg" => '\u{f023}',
+            "txt" | "log" => '\u{f15c}',
+            _ => '\u{f15b}', // generic file
+        }
+    }
+
+    /// Elide string if longer than `len` and pad it to `len`.
+    /// If `len` is 0, the string is returned as is
+    fn elide_str(s: &str, len: usize) -> String {
+        match len > 0 && s.width() > len {
+            false => format!("{s:len$}"),
+            true => format!("{}…", secure_substring(s, 0, len.saturating_sub(1))),
+        }
+    }
+
+    fn cache_key(fsentry: &File) -> FileCacheKey {
+        (
+            fsentry.path().to_path_buf(),
+            fsentry.metadata().size,
+            fsentry.metadata().modified,
+        )
+    }
+
     // Static
 
+    /// Get the format function associated to the provided key, if the key is known
+    fn key_callback(key: &str) -> Option<FmtCallback> {
+        let callback: FmtCallback = match key {
+            FMT_KEY_ATIME => Self::fmt_atime,
+            FMT_KEY_CTIME => Self::fmt_ctime,
+            FMT_KEY_EXT => Self::fmt_ext,
+            FMT_KEY_GID => Self::fmt_gid,
+            FMT_KEY_GROUP => Self::fmt_group,
+            FMT_KEY_ICON => Self::fmt_icon,
+            FMT_KEY_MIME => Self::fmt_mime,
+            FMT_KEY_MTIME => Self::fmt_mtime,
+            FMT_KEY_NAME => Self::fmt_name,
+            FMT_KEY_OCTAL => Self::fmt_octal,
+            FMT_KEY_PATH => Self::fmt_path,
+            FMT_KEY_PEX => Self::fmt_pex,
+            FMT_KEY_RELTIME => Self::fmt_reltime,
+            FMT_KEY_SIZE => Self::fmt_size,
+            FMT_KEY_SYMLINK => Self::fmt_symlink,
+            FMT_KEY_UID => Self::fmt_uid,
+            FMT_KEY_USER => Self::fmt_user,
+            _ => return None,
+        };
+        Some(callback)
+    }
+
+    /// Make table columns starting from the fmt str. Unknown keys are ignored
+    fn make_columns(fmt_str: &str) -> Vec<FmtColumn> {
+        FMT_KEY_REGEX
+            .captures_iter(fmt_str)
+            .filter_map(|regex_match| {
+                let attrs = FMT_ATTR_REGEX.captures(&regex_match[1])?;
+                let key: String = attrs.get(1)?.as_str().to_string();
+                let func: FmtCallback = Self::key_callback(key.as_str())?;
+                Some(FmtColumn {
+                    key,
+                    func,
+                    fmt_len: attrs.get(3).and_then(|x| x.as_str().parse::<usize>().ok()),
+                    fmt_extra: attrs.get(5).map(|x| x.as_str().to_string()),
+                })
+            })
+            .collect()
+    }
+
     /// Make a callchain starting from the fmt str
     fn make_callchain(fmt_str: &str) -> CallChainBlock {
         // Init chain block
@@ -472,19 +913,7 @@ impl Formatter {
                 Some(regex_match) => {
                     // Match group 0 (which is name)
                     let callback: FmtCallback = match &regex_match.get(1) {
-                        Some(key) => match key.as_str() {
-                            FMT_KEY_ATIME => Self::fmt_atime,
-                            FMT_KEY_CTIME => Self::fmt_ctime,
-                            FMT_KEY_GROUP => Self::fmt_group,
-                            FMT_KEY_MTIME => Self::fmt_mtime,
-                            FMT_KEY_NAME => Self::fmt_name,
-                            FMT_KEY_PATH => Self::fmt_path,
-                            FMT_KEY_PEX => Self::fmt_pex,
-                            FMT_KEY_SIZE => Self::fmt_size,
-                            FMT_KEY_SYMLINK => Self::fmt_symlink,
-                            FMT_KEY_USER => Self::fmt_user,
-                            _ => Self::fmt_fallback,
-                        },
+                        Some(key) => Self::key_callback(key.as_str()).unwrap_or(Self::fmt_fallback),
                         None => Self::fmt_fallback,
                     };
                     // Match format length: group 3
@@ -524,7 +953,7 @@ impl Formatter {
 mod tests {
 
     use std::path::PathBuf;
-    use std::time::SystemTime;
+    use std::time::{Duration, SystemTime};
 
     use pretty_assertions::assert_eq;
     use remotefs::fs::{File, FileType, Metadata, UnixPex};
@@ -1002,6 +1431,123 @@ mod tests {
         assert_eq!(formatter.fmt(&entry).as_str(), "喵喵喵喵喵喵喵…");
     }
 
+    #[test]
+    fn should_fmt_ext() {
+        let formatter: Formatter = Formatter::new("{EXT}|{EXT:3}");
+        assert_eq!(
+            formatter.fmt(&make_file("/tmp/foo.txt", 0o644)).as_str(),
+            "txt   |txt"
+        );
+        assert_eq!(
+            formatter
+                .fmt(&make_file("/tmp/foo.markdown", 0o644))
+                .as_str(),
+            "markd…|ma…"
+        );
+        assert_eq!(
+            formatter.fmt(&make_file("/tmp/foo", 0o644)).as_str(),
+            "      |   "
+        );
+    }
+
+    #[test]
+    fn should_fmt_octal_and_ids() {
+        let mut entry = make_file("/tmp/foo.txt", 0o755);
+        entry.metadata.uid = Some(1000);
+        entry.metadata.gid = Some(100);
+        let formatter: Formatter = Formatter::new("{OCTAL} {UID} {GID:3}");
+        assert_eq!(formatter.fmt(&entry).as_str(), "0755   1000 100");
+        entry.metadata.mode = None;
+        entry.metadata.uid = None;
+        assert_eq!(formatter.fmt(&entry).as_str(), "????      - 100");
+        entry.metadata.gid = None;
+        assert_eq!(formatter.fmt(&entry).as_str(), "????      -   -");
+    }
+
+    #[test]
+    fn should_fmt_reltime() {
+        let mut entry = make_file("/tmp/foo.txt", 0o644);
+        entry.metadata.modified = Some(SystemTime::now() - Duration::from_secs(7200));
+        entry.metadata.accessed = Some(SystemTime::now() - Duration::from_secs(300));
+        let formatter: Formatter = Formatter::new("{RELTIME}|{RELTIME:0:ATIME}");
+        assert_eq!(formatter.fmt(&entry).as_str(), "2h ago  |5m ago");
+        // unknown time
+        entry.metadata.accessed = None;
+        assert_eq!(formatter.fmt(&entry).as_str(), "2h ago  |-");
+    }
+
+    #[test]
+    fn should_fmt_icon() {
+        let formatter: Formatter = Formatter::new("{ICON}{NAME}");
+        assert_eq!(
+            formatter.fmt(&make_file("/tmp/main.rs", 0o644)).as_str(),
+            "\u{e7a8} main.rs                 "
+        );
+        let mut entry = make_file("/tmp/foo", 0o755);
+        entry.metadata.file_type = FileType::Directory;
+        assert_eq!(
+            formatter.fmt(&entry).as_str(),
+            "\u{f07b} foo/                    "
+        );
+    }
+
+    #[test]
+    fn should_fmt_mime_guessing_from_extension() {
+        let formatter: Formatter = Formatter::new("{MIME}");
+        assert_eq!(
+            formatter.fmt(&make_file("/tmp/foo.png", 0o644)).as_str(),
+            "image/png               "
+        );
+        assert_eq!(
+            formatter.fmt(&make_file("/tmp/foo", 0o644)).as_str(),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn should_fmt_mime_sniffing_content() {
+        use std::io::Write;
+
+        let mut formatter: Formatter = Formatter::new("{MIME:0}");
+        formatter.content_sniffing = true;
+        // png without extension
+        let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+        tmpfile
+            .write_all(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR")
+            .unwrap();
+        let entry = make_file(tmpfile.path().to_str().unwrap(), 0o644);
+        assert_eq!(formatter.fmt(&entry).as_str(), "image/png");
+        // mime is cached
+        assert_eq!(formatter.mime_cache.borrow().len(), 1);
+        tmpfile.as_file().set_len(0).unwrap();
+        assert_eq!(formatter.fmt(&entry).as_str(), "image/png");
+        // cache is dropped for files which are not listed anymore
+        formatter.retain_cache(&[]);
+        assert!(formatter.mime_cache.borrow().is_empty());
+        assert_eq!(formatter.fmt(&entry).as_str(), "inode/x-empty");
+        // text
+        std::fs::write(tmpfile.path(), b"hello world").unwrap();
+        formatter.retain_cache(&[]);
+        assert_eq!(formatter.fmt(&entry).as_str(), "text/plain");
+    }
+
+    fn make_file(path: &str, mode: u32) -> File {
+        File {
+            path: PathBuf::from(path),
+            metadata: Metadata {
+                accessed: None,
+                created: None,
+                modified: None,
+                file_type: FileType::File,
+                size: 8192,
+                symlink: None,
+                uid: None,
+                gid: None,
+                mode: Some(UnixPex::from(mode)),
+            },
+        }
+    }
+
     /// Dummy formatter, just yelds an 'A' at the end of the current string
     fn dummy_fmt(
         _fmt: &Formatter,
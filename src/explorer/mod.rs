@@ -12,15 +12,21 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
 
+pub use formatter::FmtColumn;
 use formatter::Formatter;
 // Ext
 use remotefs::fs::File;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 bitflags! {
     /// ExplorerOpts are bit options which provides different behaviours to `FileExplorer`
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub(crate) struct ExplorerOpts: u32 {
         const SHOW_HIDDEN_FILES = 0b00000001;
+        const REVERSE_SORTING = 0b00000010;
+        const NATURAL_SORTING = 0b00000100;
+        const CASE_SENSITIVE_SORTING = 0b00001000;
     }
 }
 
@@ -30,7 +36,14 @@ pub enum FileSorting {
     Name,
     ModifyTime,
     CreationTime,
+    AccessTime,
     Size,
+    Extension,
+    Type,
+    Owner,
+    Group,
+    Permissions,
+    Path,
 }
 
 /// GroupDirs defines how directories should be grouped in sorting files
@@ -40,6 +53,32 @@ pub enum GroupDirs {
     Last,
 }
 
+/// A chunk of a file name, used as key to sort files by name
+#[derive(Debug, PartialEq, Eq)]
+enum NameChunk {
+    Char(char),
+    /// A sequence of digits without leading zeros; numbers are compared by length first, then by digits
+    Number(usize, String),
+}
+
+impl Ord for NameChunk {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Self::Char(a), Self::Char(b)) => a.cmp(b),
+            (Self::Number(a_len, a), Self::Number(b_len, b)) => (a_len, a).cmp(&(b_len, b)),
+            // Numbers are sorted as any digit
+            (Self::Char(c), Self::Number(..)) => c.cmp(&'0'),
+            (Self::Number(..), Self::Char(c)) => '0'.cmp(c),
+        }
+    }
+}
+
+impl PartialOrd for NameChunk {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// File explorer states
 pub struct FileExplorer {
     pub wrkdir: PathBuf,                      // Current directory
@@ -87,6 +126,7 @@ impl FileExplorer {
     /// This method will also sort entries based on current options
     /// Once all sorting have been performed, index is moved to first valid entry.
     pub fn set_files(&mut self, files: Vec<File>) {
+        self.fmt.retain_cache(&files);
         self.files = files;
         // Sort
         self.sort();
@@ -153,6 +193,16 @@ impl FileExplorer {
         self.fmt.fmt(entry)
     }
 
+    /// Format a file entry as a table row; a cell for each column is returned
+    pub fn fmt_file_columns(&self, entry: &File) -> Vec<String> {
+        self.fmt.fmt_columns(entry)
+    }
+
+    /// Get the table columns, derived from the format string
+    pub fn columns(&self) -> &[FmtColumn] {
+        self.fmt.columns()
+    }
+
     // Sorting
 
     /// Choose sorting method; then sort files
@@ -169,6 +219,57 @@ impl FileExplorer {
         self.file_sorting
     }
 
+    /// Sort files by the column next to the one files are currently sorted by.
+    /// Once the last column is reached, the first column is chosen
+    pub fn sort_by_next_column(&mut self) {
+        let mut sortings: Vec<FileSorting> = Vec::new();
+        for sorting in self.columns().iter().filter_map(|x| x.sorting()) {
+            if !sortings.contains(&sorting) {
+                sortings.push(sorting);
+            }
+        }
+        let next = match sortings.iter().position(|x| *x == self.file_sorting) {
+            Some(i) => sortings.get(i + 1).or_else(|| sortings.first()),
+            None => sortings.first(),
+        };
+        if let Some(sorting) = next.copied() {
+            self.sort_by(sorting);
+        }
+    }
+
+    /// Returns whether sorting order is reversed
+    pub fn reverse_sorting(&self) -> bool {
+        self.opts.intersects(ExplorerOpts::REVERSE_SORTING)
+    }
+
+    /// Reverse sorting order; then sort files
+    pub fn toggle_reverse_sorting(&mut self) {
+        self.opts.toggle(ExplorerOpts::REVERSE_SORTING);
+        self.sort();
+    }
+
+    /// Returns whether names are sorted in natural order (e.g. `file2` comes before `file10`)
+    pub fn natural_sorting(&self) -> bool {
+        self.opts.intersects(ExplorerOpts::NATURAL_SORTING)
+    }
+
+    /// Enable/disable natural sorting; then sort files
+    pub fn toggle_natural_sorting(&mut self) {
+        self.opts.toggle(ExplorerOpts::NATURAL_SORTING);
+        self.sort();
+    }
+
+    /// Returns whether names are sorted case-sensitively
+    pub fn case_sensitive_sorting(&self) -> bool {
+        self.opts.intersects(ExplorerOpts::CASE_SENSITIVE_SORTING)
+    }
+
+    /// Enable/disable case-sensitive sorting; then sort files
+    pub fn toggle_case_sensitive_sorting(&mut self) {
+        self.opts.toggle(ExplorerOpts::CASE_SENSITIVE_SORTING);
+        self.sort();
+    }
+
     /// Choose group dirs method; then sort files
     pub fn group_dirs_by(&mut self, group_dirs: Option<GroupDirs>) {
         // If method HAS ACTUALLY CHANGED, sort (performance!)
@@ -185,7 +286,18 @@ impl FileExplorer {
             FileSorting::Name => self.sort_files_by_name(),
             FileSorting::CreationTime => self.sort_files_by_creation_time(),
             FileSorting::ModifyTime => self.sort_files_by_mtime(),
+            FileSorting::AccessTime => self.sort_files_by_access_time(),
             FileSorting::Size => self.sort_files_by_size(),
+            FileSorting::Extension => self.sort_files_by_extension(),
+            FileSorting::Type => self.sort_files_by_type(),
+            FileSorting::Owner => self.sort_files_by_owner(),
+            FileSorting::Group => self.sort_files_by_group(),
+            FileSorting::Permissions => self.sort_files_by_permissions(),
+            FileSorting::Path => self.sort_files_by_path(),
+        }
+        // Reverse order if necessary (NOTE: directories are grouped anyway)
+        if self.reverse_sorting() {
+            self.files.reverse();
         }
         // Directories first (NOTE: MUST COME AFTER OTHER SORTING)
         // Group directories if necessary
@@ -197,9 +309,12 @@ impl FileExplorer {
         }
     }
 
-    /// Sort explorer files by their name. All names are converted to lowercase
+    /// Sort explorer files by their name.
+    /// Unless sorting is case-sensitive, names are converted to lowercase and accents are ignored
     fn sort_files_by_name(&mut self) {
-        self.files.sort_by_key(|x: &File| x.name().to_lowercase());
+        let opts = self.opts;
+        self.files
+            .sort_by_cached_key(|x: &File| Self::name_sort_key(x.name().as_str(), opts));
     }
 
     /// Sort files by mtime; the newest comes first
@@ -214,12 +329,96 @@ impl FileExplorer {
             .sort_by_key(|b: &File| Reverse(b.metadata().created));
     }
 
+    /// Sort files by access time; the newest comes first
+    fn sort_files_by_access_time(&mut self) {
+        self.files
+            .sort_by_key(|b: &File| Reverse(b.metadata().accessed));
+    }
+
     /// Sort files by size
     fn sort_files_by_size(&mut self) {
         self.files
             .sort_by_key(|b: &File| Reverse(b.metadata().size));
     }
 
+    /// Sort files by extension, then by name
+    fn sort_files_by_extension(&mut self) {
+        let opts = self.opts;
+        self.files.sort_by_cached_key(|x: &File| {
+            (
+                x.extension().unwrap_or_default().to_lowercase(),
+                Self::name_sort_key(x.name().as_str(), opts),
+            )
+        });
+    }
+
+    /// Sort files by mime type, then by name
+    fn sort_files_by_type(&mut self) {
+        let opts = self.opts;
+        let fmt = &self.fmt;
+        self.files.sort_by_cached_key(|x: &File| {
+            (
+                fmt.mime_type(x),
+                Self::name_sort_key(x.name().as_str(), opts),
+            )
+        });
+    }
+
+    /// Sort files by owner user id
+    fn sort_files_by_owner(&mut self) {
+        self.files.sort_by_key(|x: &File| x.metadata().uid);
+    }
+
+    /// Sort files by owner group id
+    fn sort_files_by_group(&mut self) {
+        self.files.sort_by_key(|x: &File| x.metadata().gid);
+    }
+
+    /// Sort files by permissions
+    fn sort_files_by_permissions(&mut self) {
+        self.files
+            .sort_by_key(|x: &File| x.metadata().mode.map(u32::from));
+    }
+
+    /// Sort files by their full path
+    fn sort_files_by_path(&mut self) {
+        let opts = self.opts;
+        self.files.sort_by_cached_key(|x: &File| {
+            Self::name_sort_key(x.path().to_string_lossy().as_ref(), opts)
+        });
+    }
+
+    /// Make the key to sort `name` with, based on sorting options
+    fn name_sort_key(name: &str, opts: ExplorerOpts) -> Vec<NameChunk> {
+        let name: String = match opts.intersects(ExplorerOpts::CASE_SENSITIVE_SORTING) {
+            true => name.to_string(),
+            false => name
+                .nfd()
+                .filter(|x| !is_combining_mark(*x))
+                .collect::<String>()
+                .to_lowercase(),
+        };
+        if !opts.intersects(ExplorerOpts::NATURAL_SORTING) {
+            return name.chars().map(NameChunk::Char).collect();
+        }
+        // Split name into chars and numbers
+        let mut chunks: Vec<NameChunk> = Vec::with_capacity(name.len());
+        let mut chars = name.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !c.is_ascii_digit() {
+                chunks.push(NameChunk::Char(c));
+                continue;
+            }
+            let mut number = String::from(c);
+            while let Some(digit) = chars.next_if(|x| x.is_ascii_digit()) {
+                number.push(digit);
+            }
+            let digits = number.trim_start_matches('0').to_string();
+            chunks.push(NameChunk::Number(digits.len(), digits));
+        }
+        chunks
+    }
+
     /// Sort files; directories come first
     fn sort_files_directories_first(&mut self) {
         self.files.sort_by_key(|x: &File| !x.is_dir());
@@ -246,10 +445,17 @@ impl FileExplorer {
 impl ToString for FileSorting {
     fn to_string(&self) -> String {
         String::from(match self {
+            FileSorting::AccessTime => "by_access_time",
             FileSorting::CreationTime => "by_creation_time",
+            FileSorting::Extension => "by_extension",
+            FileSorting::Group => "by_group",
             FileSorting::ModifyTime => "by_mtime",
             FileSorting::Name => "by_name",
+            FileSorting::Owner => "by_owner",
+            FileSorting::Path => "by_path",
+            FileSorting::Permissions => "by_permissions",
             FileSorting::Size => "by_size",
+            FileSorting::Type => "by_type",
         })
     }
 }
@@ -258,10 +464,17 @@ impl FromStr for FileSorting {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_str() {
+            "by_access_time" => Ok(FileSorting::AccessTime),
             "by_creation_time" => Ok(FileSorting::CreationTime),
+            "by_extension" => Ok(FileSorting::Extension),
+            "by_group" => Ok(FileSorting::Group),
             "by_mtime" => Ok(FileSorting::ModifyTime),
             "by_name" => Ok(FileSorting::Name),
+            "by_owner" => Ok(FileSorting::Owner),
+            "by_path" => Ok(FileSorting::Path),
+            "by_permissions" => Ok(FileSorting::Permissions),
             "by_size" => Ok(FileSorting::Size),
+            "by_type" => Ok(FileSorting::Type),
             _ => Err(()),
         }
     }
@@ -297,6 +510,7 @@ mod tests {
     use remotefs::fs::{File, FileType, Metadata, UnixPex};
 
     use super::*;
+    use crate::explorer::builder::FileExplorerBuilder;
     use crate::utils::fmt::fmt_time;
 
     #[test]
@@ -497,6 +711,166 @@ mod tests {
         assert_eq!(explorer.files.get(7).unwrap().name(), "README.md");
     }
 
+    #[test]
+    fn test_fs_explorer_sort_by_name_natural() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("file10.txt", false),
+            make_fs_entry("file2.txt", false),
+            make_fs_entry("file1.txt", false),
+            make_fs_entry("file.txt", false),
+            make_fs_entry("file002b.txt", false),
+        ]);
+        // Lexicographic
+        assert_eq!(explorer.files[0].name(), "file.txt");
+        assert_eq!(explorer.files[1].name(), "file002b.txt");
+        assert_eq!(explorer.files[2].name(), "file1.txt");
+        assert_eq!(explorer.files[3].name(), "file10.txt");
+        assert_eq!(explorer.files[4].name(), "file2.txt");
+        // Natural
+        explorer.toggle_natural_sorting();
+        assert!(explorer.natural_sorting());
+        assert_eq!(explorer.files[0].name(), "file.txt");
+        assert_eq!(explorer.files[1].name(), "file1.txt");
+        assert_eq!(explorer.files[2].name(), "file2.txt");
+        assert_eq!(explorer.files[3].name(), "file002b.txt");
+        assert_eq!(explorer.files[4].name(), "file10.txt");
+    }
+
+    #[test]
+    fn test_fs_explorer_sort_by_name_case_sensitive() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("b.txt", false),
+            make_fs_entry("École.txt", false),
+            make_fs_entry("C.txt", false),
+            make_fs_entry("a.txt", false),
+        ]);
+        // Case-insensitive; accents are ignored
+        assert_eq!(explorer.files[0].name(), "a.txt");
+        assert_eq!(explorer.files[1].name(), "b.txt");
+        assert_eq!(explorer.files[2].name(), "C.txt");
+        assert_eq!(explorer.files[3].name(), "École.txt");
+        // Case-sensitive
+        explorer.toggle_case_sensitive_sorting();
+        assert!(explorer.case_sensitive_sorting());
+        assert_eq!(explorer.files[0].name(), "C.txt");
+        assert_eq!(explorer.files[1].name(), "a.txt");
+        assert_eq!(explorer.files[2].name(), "b.txt");
+        assert_eq!(explorer.files[3].name(), "École.txt");
+    }
+
+    #[test]
+    fn test_fs_explorer_sort_by_extension() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("README.md", false),
+            make_fs_entry("Cargo.toml", false),
+            make_fs_entry("LICENSE", false),
+            make_fs_entry("CHANGELOG.md", false),
+        ]);
+        explorer.sort_by(FileSorting::Extension);
+        assert_eq!(explorer.files[0].name(), "LICENSE");
+        assert_eq!(explorer.files[1].name(), "CHANGELOG.md");
+        assert_eq!(explorer.files[2].name(), "README.md");
+        assert_eq!(explorer.files[3].name(), "Cargo.toml");
+    }
+
+    #[test]
+    fn test_fs_explorer_sort_by_type() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("notes.txt", false),
+            make_fs_entry("image.png", false),
+            make_fs_entry("src", true),
+        ]);
+        explorer.sort_by(FileSorting::Type);
+        // image/png < inode/directory < text/plain
+        assert_eq!(explorer.files[0].name(), "image.png");
+        assert_eq!(explorer.files[1].name(), "src");
+        assert_eq!(explorer.files[2].name(), "notes.txt");
+    }
+
+    #[test]
+    fn test_fs_explorer_reverse_sorting() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry_with_size("README.md", false, 1024),
+            make_fs_entry_with_size("src", true, 4096),
+            make_fs_entry_with_size("CONTRIBUTING.md", false, 256),
+        ]);
+        explorer.sort_by(FileSorting::Size);
+        assert!(!explorer.reverse_sorting());
+        explorer.toggle_reverse_sorting();
+        assert!(explorer.reverse_sorting());
+        assert_eq!(explorer.files[0].name(), "CONTRIBUTING.md");
+        assert_eq!(explorer.files[1].name(), "README.md");
+        assert_eq!(explorer.files[2].name(), "src");
+        // Directories are still grouped
+        explorer.group_dirs_by(Some(GroupDirs::First));
+        assert_eq!(explorer.files[0].name(), "src");
+        assert_eq!(explorer.files[1].name(), "CONTRIBUTING.md");
+        // Sorting is kept reversed when files change
+        explorer.set_files(vec![
+            make_fs_entry("a.txt", false),
+            make_fs_entry("b.txt", false),
+        ]);
+        explorer.sort_by(FileSorting::Name);
+        assert_eq!(explorer.files[0].name(), "b.txt");
+        explorer.toggle_reverse_sorting();
+        assert_eq!(explorer.files[0].name(), "a.txt");
+    }
+
+    #[test]
+    fn test_fs_explorer_columns() {
+        let mut explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_formatter(Some("{NAME:8} {SIZE} {FOO} {RELTIME:8:CTIME}"))
+            .build();
+        let columns: Vec<(&str, Option<FileSorting>)> = explorer
+            .columns()
+            .iter()
+            .map(|x| (x.label(), x.sorting()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("Name", Some(FileSorting::Name)),
+                ("Size", Some(FileSorting::Size)),
+                ("Created", Some(FileSorting::CreationTime)),
+            ]
+        );
+        let entry = make_fs_entry_with_size("a_very_long_file_name.txt", false, 1024);
+        explorer.set_files(vec![entry.clone()]);
+        // Name is not elided in table view
+        assert_eq!(
+            explorer.fmt_file_columns(&entry),
+            vec![
+                String::from("a_very_long_file_name.txt"),
+                String::from("1.0 KB"),
+                String::from("now")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fs_explorer_sort_by_next_column() {
+        let mut explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_formatter(Some("{NAME} {SIZE} {USER} {UID} {FOO}"))
+            .build();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::Name);
+        explorer.sort_by_next_column();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::Size);
+        explorer.sort_by_next_column();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::Owner);
+        // USER and UID have the same sorting
+        explorer.sort_by_next_column();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::Name);
+        // Sorting without a column
+        explorer.sort_by(FileSorting::ModifyTime);
+        explorer.sort_by_next_column();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::Name);
+    }
+
     #[test]
     fn test_fs_explorer_fmt() {
         let explorer: FileExplorer = FileExplorer::default();
@@ -541,6 +915,11 @@ mod tests {
         assert_eq!(FileSorting::ModifyTime.to_string(), "by_mtime");
         assert_eq!(FileSorting::Name.to_string(), "by_name");
         assert_eq!(FileSorting::Size.to_string(), "by_size");
+        assert_eq!(FileSorting::Extension.to_string(), "by_extension");
+        assert_eq!(
+            FileSorting::from_str("by_permissions").ok().unwrap(),
+            FileSorting::Permissions
+        );
         assert_eq!(
             FileSorting::from_str("by_creation_time").ok().unwrap(),
             FileSorting::CreationTime
@@ -4,7 +4,6 @@
 
 use std::path::PathBuf;
 
-use remotefs::RemoteFs;
 use remotefs_aws_s3::AwsS3Fs;
 use remotefs_ftp::FtpFs;
 #[cfg(smb_unix)]
@@ -19,7 +18,7 @@ use super::params::WebDAVProtocolParams;
 use super::params::{AwsS3Params, GenericProtocolParams};
 #[cfg(smb)]
 use super::params::{AwsS3Params, GenericProtocolParams, SmbParams};
-use super::{FileTransferProtocol, ProtocolParams};
+use super::{FileTransferProtocol, ProtocolParams, RemoteClient};
 use crate::system::config_client::ConfigClient;
 use crate::system::sshkey_storage::SshKeyStorage;
 use crate::utils::ssh as ssh_utils;
@@ -35,7 +34,7 @@ impl Builder {
         protocol: FileTransferProtocol,
         params: ProtocolParams,
         config_client: &ConfigClient,
-    ) -> Box<dyn RemoteFs> {
+    ) -> Box<dyn RemoteClient> {
         match (protocol, params) {
             (FileTransferProtocol::AwsS3, ProtocolParams::AwsS3(params)) => {
                 Box::new(Self::aws_s3_client(params))
@@ -0,0 +1,126 @@
+//! ## Client
+//!
+//! `client` is the module which exposes the capabilities of the remotefs clients which are not part of `RemoteFs`
+
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+use remotefs::fs::RemoteResult;
+use remotefs::{RemoteError, RemoteErrorType, RemoteFs};
+use remotefs_aws_s3::AwsS3Fs;
+use remotefs_ftp::FtpFs;
+#[cfg(smb)]
+use remotefs_smb::SmbFs;
+use remotefs_ssh::{ScpFs, SftpFs};
+use remotefs_webdav::WebDAVFs;
+use ssh2::{Channel, Session};
+
+use crate::utils::string::shell_quote;
+
+/// A remotefs client
+pub trait RemoteClient: RemoteFs {
+    /// Get the SSH session the client is connected through, if any
+    fn ssh_session(&mut self) -> Option<Session> {
+        None
+    }
+
+    /// Execute `cmd` in the working directory, as `RemoteFs::exec`.
+    /// Returns the exit code, the stdout and the stderr of the command; stderr is empty if the protocol
+    /// doesn't report it separately
+    fn exec_output(&mut self, cmd: &str) -> RemoteResult<(u32, String, String)> {
+        self.exec(cmd)
+            .map(|(rc, stdout)| (rc, stdout, String::new()))
+    }
+}
+
+impl RemoteClient for AwsS3Fs {}
+
+impl RemoteClient for FtpFs {}
+
+impl RemoteClient for ScpFs {
+    fn ssh_session(&mut self) -> Option<Session> {
+        self.session().cloned()
+    }
+
+    fn exec_output(&mut self, cmd: &str) -> RemoteResult<(u32, String, String)> {
+        let wrkdir = self.pwd()?;
+        match self.ssh_session() {
+            Some(session) => ssh_exec(&session, wrkdir.as_path(), cmd),
+            None => Err(RemoteError::new(RemoteErrorType::NotConnected)),
+        }
+    }
+}
+
+impl RemoteClient for SftpFs {
+    fn ssh_session(&mut self) -> Option<Session> {
+        self.session().cloned()
+    }
+
+    fn exec_output(&mut self, cmd: &str) -> RemoteResult<(u32, String, String)> {
+        let wrkdir = self.pwd()?;
+        match self.ssh_session() {
+            Some(session) => ssh_exec(&session, wrkdir.as_path(), cmd),
+            None => Err(RemoteError::new(RemoteErrorType::NotConnected)),
+        }
+    }
+}
+
+#[cfg(smb)]
+impl RemoteClient for SmbFs {}
+
+impl RemoteClient for WebDAVFs {}
+
+/// Execute `cmd` in `wrkdir` on a new channel of `session`, collecting stdout and stderr separately
+fn ssh_exec(session: &Session, wrkdir: &Path, cmd: &str) -> RemoteResult<(u32, String, String)> {
+    let protocol_error = |err: &dyn std::fmt::Display| {
+        RemoteError::new_ex(RemoteErrorType::ProtocolError, err.to_string())
+    };
+    let mut channel = session.channel_session().map_err(|e| protocol_error(&e))?;
+    channel
+        .exec(format!("cd {}; {}", shell_quote(&wrkdir.to_string_lossy()), cmd).as_str())
+        .map_err(|e| protocol_error(&e))?;
+    // stdout and stderr must be read interleaved, otherwise the command may hang filling the stderr window.
+    // The same session is used by the client, so blocking mode must be restored afterwards
+    session.set_blocking(false);
+    let output = read_channel_output(&mut channel);
+    session.set_blocking(true);
+    let (stdout, stderr) = output.map_err(|e| protocol_error(&e))?;
+    channel.wait_close().map_err(|e| protocol_error(&e))?;
+    let rc = channel.exit_status().map_err(|e| protocol_error(&e))?;
+    Ok((
+        rc as u32,
+        String::from_utf8_lossy(&stdout).to_string(),
+        String::from_utf8_lossy(&stderr).to_string(),
+    ))
+}
+
+/// Read stdout and stderr of the non-blocking `channel`, until the remote end sends EOF
+fn read_channel_output(channel: &mut Channel) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    loop {
+        // check EOF before reading, so the data received along with it is read too
+        let eof = channel.eof();
+        let read = read_available(channel, &mut stdout)?
+            + read_available(&mut channel.stderr(), &mut stderr)?;
+        match (read, eof) {
+            (0, true) => return Ok((stdout, stderr)),
+            (0, false) => std::thread::sleep(Duration::from_millis(1)),
+            _ => {}
+        }
+    }
+}
+
+/// Append the data available on the non-blocking `reader` to `buffer`. Returns the amount of bytes read
+fn read_available<R: Read>(reader: &mut R, buffer: &mut Vec<u8>) -> io::Result<usize> {
+    let mut chunk = [0; 8192];
+    match reader.read(&mut chunk) {
+        Ok(bytes) => {
+            buffer.extend_from_slice(&chunk[..bytes]);
+            Ok(bytes)
+        }
+        Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(0),
+        Err(err) => Err(err),
+    }
+}
@@ -3,10 +3,12 @@
 //! `filetransfer` is the module which provides the file transfer protocols and remotefs builders
 
 mod builder;
+mod client;
 pub mod params;
 
 // -- export types
 pub use builder::Builder;
+pub use client::RemoteClient;
 pub use params::{FileTransferParams, ProtocolParams};
 
 /// This enum defines the different transfer protocol available in termscp
@@ -40,6 +40,10 @@ pub enum HostErrorType {
     ExecutionFailed,
     #[error("Could not delete file")]
     DeleteFailed,
+    #[error("Could not move file to trash")]
+    TrashFailed,
+    #[error("Could not restore file from trash")]
+    RestoreFailed,
 }
 
 /// HostError is a wrapper for the error type and the exact io error
@@ -281,6 +285,104 @@ impl Localhost {
         }
     }
 
+    /// Move file entry to the trash of the system
+    pub fn trash(&mut self, entry: &File) -> Result<(), HostError> {
+        debug!("Moving {} to trash", entry.path().display());
+        if !entry.path().exists() {
+            error!("File doesn't exist");
+            return Err(HostError::new(
+                HostErrorType::NoSuchFileOrDirectory,
+                None,
+                entry.path(),
+            ));
+        }
+        match trash::delete(entry.path()) {
+            Ok(_) => {
+                // Update dir
+                self.files = self.scan_dir(self.wrkdir.as_path())?;
+                info!("Moved {} to trash", entry.path().display());
+                Ok(())
+            }
+            Err(err) => {
+                error!("Could not move file to trash: {}", err);
+                Err(HostError::new(
+                    HostErrorType::TrashFailed,
+                    Some(std::io::Error::other(err.to_string())),
+                    entry.path(),
+                ))
+            }
+        }
+    }
+
+    /// Restore the entry, which was located at `path`, from the trash of the system.
+    /// If `path` has been trashed several times, the latest entry is restored
+    pub fn restore_from_trash(&mut self, path: &Path) -> Result<(), HostError> {
+        debug!("Restoring {} from trash", path.display());
+        if path.exists() {
+            error!("File already exists");
+            return Err(HostError::new(HostErrorType::FileAlreadyExists, None, path));
+        }
+        Self::restore_trash_item(path).map_err(|err| {
+            error!("Could not restore file from trash: {}", err);
+            HostError::new(
+                HostErrorType::RestoreFailed,
+                Some(std::io::Error::other(err)),
+                path,
+            )
+        })?;
+        // Update dir
+        self.files = self.scan_dir(self.wrkdir.as_path())?;
+        info!("Restored {} from trash", path.display());
+        Ok(())
+    }
+
+    /// Returns whether the entries moved to the trash can be restored on this platform
+    pub fn can_restore_from_trash() -> bool {
+        cfg!(any(
+            target_os = "windows",
+            all(
+                unix,
+                not(target_os = "macos"),
+                not(target_os = "ios"),
+                not(target_os = "android")
+            )
+        ))
+    }
+
+    #[cfg(any(
+        target_os = "windows",
+        all(
+            unix,
+            not(target_os = "macos"),
+            not(target_os = "ios"),
+            not(target_os = "android")
+        )
+    ))]
+    fn restore_trash_item(path: &Path) -> Result<(), String> {
+        let item = trash::os_limited::list()
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .filter(|x| x.original_path() == path)
+            .max_by_key(|x| x.time_deleted)
+            .ok_or_else(|| String::from("file not found in trash"))?;
+        trash::os_limited::restore_all([item]).map_err(|err| err.to_string())
+    }
+
+    #[cfg(not(any(
+        target_os = "windows",
+        all(
+            unix,
+            not(target_os = "macos"),
+            not(target_os = "ios"),
+            not(target_os = "android")
+        )
+    )))]
+    fn restore_trash_item(_path: &Path) -> Result<(), String> {
+        Err(String::from(
+            "restoring files from trash is not supported on this platform",
+        ))
+    }
+
     /// Rename file or directory to new name
     pub fn rename(&mut self, entry: &File, dst_path: &Path) -> Result<(), HostError> {
         match std::fs::rename(entry.path(), dst_path) {
@@ -427,21 +529,30 @@ impl Localhost {
         Ok(())
     }
 
-    /// Execute a command on localhost
-    pub fn exec(&self, cmd: &str) -> Result<String, HostError> {
-        // Make command
-        let args: Vec<&str> = cmd.split(' ').collect();
-        let cmd: &str = args.first().unwrap();
-        let argv: &[&str] = &args[1..];
-        info!("Executing command: {} {:?}", cmd, argv);
-        match std::process::Command::new(cmd).args(argv).output() {
-            Ok(output) => match std::str::from_utf8(&output.stdout) {
-                Ok(s) => {
-                    info!("Command output: {}", s);
-                    Ok(s.to_string())
-                }
-                Err(_) => Ok(String::new()),
-            },
+    /// Execute a command on localhost through the shell (`sh` on unix, `cmd` on windows), in the working directory.
+    /// Returns the exit code of the command (1 if it was terminated by a signal), its stdout and its stderr
+    pub fn exec_shell(&self, cmd: &str) -> Result<(u32, String, String), HostError> {
+        info!("Executing command through shell: {}", cmd);
+        #[cfg(unix)]
+        let mut command = {
+            let mut command = std::process::Command::new("sh");
+            command.arg("-c").arg(cmd);
+            command
+        };
+        #[cfg(windows)]
+        let mut command = {
+            let mut command = std::process::Command::new("cmd");
+            command.arg("/C").arg(cmd);
+            command
+        };
+        match command.current_dir(self.wrkdir.as_path()).output() {
+            Ok(output) => {
+                let rc = output.status.code().unwrap_or(1) as u32;
+                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                info!("Command output (exitcode: {}): {}", rc, stdout);
+                Ok((rc, stdout, stderr))
+            }
             Err(err) => {
                 error!("Failed to run command: {}", err);
                 Err(HostError::new(
@@ -890,6 +1001,65 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_host_localhost_trash() {
+        // The trash test runs in a child process, whose trash is in a temp directory instead of the trash of the user;
+        // the environment of the test binary can't be changed, since tests run in parallel
+        let data_home: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "host::tests::test_host_localhost_trash_in_data_home",
+                "--ignored",
+                "--test-threads=1",
+            ])
+            .env("XDG_DATA_HOME", data_home.path())
+            .env("TERMSCP_TEST_TRASH", "1")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+        // The trash in the temp directory has been used
+        assert!(data_home.path().join("Trash/info").exists());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[ignore = "run by test_host_localhost_trash"]
+    fn test_host_localhost_trash_in_data_home() {
+        // Never use the trash of the user
+        if std::env::var_os("TERMSCP_TEST_TRASH").is_none() {
+            return;
+        }
+        let data_home = PathBuf::from(std::env::var_os("XDG_DATA_HOME").unwrap());
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        // Create sample file
+        let path = tmpdir.path().join("foo.txt");
+        assert!(StdFile::create(path.as_path()).is_ok());
+        let mut host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        let files: Vec<File> = host.list_dir();
+        assert_eq!(files.len(), 1); // There should be 1 file now
+                                    // Trash file
+        assert!(host.trash(files.first().unwrap()).is_ok());
+        assert_eq!(host.list_dir().len(), 0);
+        assert!(!path.exists());
+        assert!(data_home.join("Trash/files/foo.txt").exists());
+        // Restore file
+        assert!(host.restore_from_trash(path.as_path()).is_ok());
+        assert_eq!(host.list_dir().len(), 1);
+        assert!(path.exists());
+        // Can't restore an existing file
+        assert!(host.restore_from_trash(path.as_path()).is_err());
+        // Trash unexisting file
+        assert!(host
+            .trash(&make_fsentry(PathBuf::from("/aaaaaaa"), false))
+            .is_err());
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_host_localhost_rename() {
@@ -1081,7 +1251,23 @@ mod tests {
         let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
         let host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
         // Execute
-        assert!(host.exec("echo 5").ok().unwrap().as_str().contains("5"));
+        let (rc, output, _) = host.exec_shell("echo 5").ok().unwrap();
+        assert_eq!(rc, 0);
+        assert!(output.as_str().contains("5"));
+        assert_eq!(host.exec_shell("exit 1").ok().unwrap().0, 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_host_exec_shell() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        assert!(make_file_at(tmpdir.path(), "my file.txt").is_ok());
+        assert_eq!(
+            host.exec_shell("ls 'my file.txt' && echo oops >&2 && exit 3")
+                .unwrap(),
+            (3, String::from("my file.txt\n"), String::from("oops\n"))
+        );
     }
 
     #[test]
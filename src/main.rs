@@ -32,7 +32,10 @@ mod utils;
 
 // namespaces
 use activity_manager::{ActivityManager, NextActivity};
-use cli_opts::{Args, ArgsSubcommands, BookmarkParams, HostParams, Remote, RunOpts, Task};
+use cli_opts::{
+    Args, ArgsSubcommands, BookmarkParams, ExportBookmarksArgs, HostParams, ImportBookmarksArgs,
+    MasterPasswordArgs, Remote, RunOpts, Task,
+};
 use filetransfer::FileTransferParams;
 use system::logging::{self, LogLevel};
 
@@ -65,7 +68,10 @@ fn main() {
 fn parse_args(args: Args) -> Result<RunOpts, String> {
     let run_opts = match args.nested {
         Some(ArgsSubcommands::Update(_)) => RunOpts::update(),
+        Some(ArgsSubcommands::ExportBookmarks(args)) => RunOpts::export_bookmarks(args),
+        Some(ArgsSubcommands::ImportBookmarks(args)) => RunOpts::import_bookmarks(args),
         Some(ArgsSubcommands::LoadTheme(args)) => RunOpts::import_theme(args.theme),
+        Some(ArgsSubcommands::MasterPassword(args)) => RunOpts::master_password(args),
         Some(ArgsSubcommands::Config(_)) => RunOpts::config(),
         None => {
             let mut run_opts: RunOpts = RunOpts::default();
@@ -137,12 +143,41 @@ fn parse_remote_address(remote: &str) -> Result<FileTransferParams, String> {
 /// Run task and return rc
 fn run(run_opts: RunOpts) -> i32 {
     match run_opts.task {
+        Task::ExportBookmarks(args) => run_export_bookmarks(args),
+        Task::ImportBookmarks(args) => run_import_bookmarks(args),
         Task::ImportTheme(theme) => run_import_theme(&theme),
         Task::InstallUpdate => run_install_update(),
+        Task::MasterPassword(args) => run_master_password(args),
         Task::Activity(activity) => run_activity(activity, run_opts.ticks, run_opts.remote),
     }
 }
 
+fn run_export_bookmarks(args: ExportBookmarksArgs) -> i32 {
+    match support::export_bookmarks(args) {
+        Ok(msg) => {
+            println!("{msg}");
+            0
+        }
+        Err(err) => {
+            eprintln!("Could not export bookmarks: {err}");
+            1
+        }
+    }
+}
+
+fn run_import_bookmarks(args: ImportBookmarksArgs) -> i32 {
+    match support::import_bookmarks(args) {
+        Ok(msg) => {
+            println!("{msg}");
+            0
+        }
+        Err(err) => {
+            eprintln!("Could not import bookmarks: {err}");
+            1
+        }
+    }
+}
+
 fn run_import_theme(theme: &Path) -> i32 {
     match support::import_theme(theme) {
         Ok(_) => {
@@ -156,6 +191,19 @@ fn run_import_theme(theme: &Path) -> i32 {
     }
 }
 
+fn run_master_password(args: MasterPasswordArgs) -> i32 {
+    match support::master_password(args) {
+        Ok(msg) => {
+            println!("{msg}");
+            0
+        }
+        Err(err) => {
+            eprintln!("Could not set master password: {err}");
+            1
+        }
+    }
+}
+
 fn run_install_update() -> i32 {
     match support::install_update() {
         Ok(msg) => {
@@ -187,7 +235,7 @@ fn run_activity(activity: NextActivity, ticks: Duration, remote: Remote) -> i32
             }
         }
         Remote::Host(HostParams { params, password }) => {
-            if let Err(err) = manager.set_filetransfer_params(params, password.as_deref()) {
+            if let Err(err) = manager.set_filetransfer_params(params, password.as_deref(), None) {
                 eprintln!("{err}");
                 return 1;
             }
@@ -6,11 +6,16 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::cli_opts::{ExportBookmarksArgs, ImportBookmarksArgs, MasterPasswordArgs};
 use crate::system::auto_update::{Update, UpdateStatus};
+use crate::system::bookmarks_client::BookmarksClient;
+use crate::system::bookmarks_export::{self, ExportFormat};
+use crate::system::bookmarks_import::{self, ImportFormat, ImportPlan};
 use crate::system::config_client::ConfigClient;
 use crate::system::environment;
 use crate::system::notifications::Notification;
 use crate::system::theme_provider::ThemeProvider;
+use crate::utils::tty;
 
 /// Import theme at provided path into termscp
 pub fn import_theme(p: &Path) -> Result<(), String> {
@@ -31,6 +36,102 @@ pub fn import_theme(p: &Path) -> Result<(), String> {
         .map_err(|e| format!("Could not import theme: {e}"))
 }
 
+/// Import the bookmarks of another client into termscp.
+/// With `dry_run`, the bookmarks to import are only listed
+pub fn import_bookmarks(args: ImportBookmarksArgs) -> Result<String, String> {
+    let path: PathBuf = match args.path {
+        Some(path) => path,
+        None => args.format.default_path().ok_or_else(|| {
+            format!(
+                "could not find the {} configuration; please, provide its path",
+                args.format
+            )
+        })?,
+    };
+    let passphrase = match (args.with_passwords, args.format) {
+        (false, _) => None,
+        (true, ImportFormat::Termscp) => Some(read_secret("Passphrase: ")?),
+        (true, format) => {
+            return Err(format!(
+                "passwords can't be imported from {format}; passwords can be imported only from termscp"
+            ))
+        }
+    };
+    let sites = bookmarks_import::read_sites(args.format, path.as_path(), passphrase.as_deref())
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut client: BookmarksClient = get_bookmarks_client()?;
+    let plan = ImportPlan::new(sites, client.iter_bookmarks(), args.on_conflict)
+        .with_commands(args.with_commands);
+    print!("{plan}");
+    let dropped = plan.dropped_commands();
+    if dropped > 0 {
+        println!("{dropped} commands are not imported; check them and use --with-commands to import them");
+    }
+    let (imported, skipped) = (plan.imported(), plan.skipped());
+    if args.dry_run {
+        return Ok(format!(
+            "{imported} bookmarks would be imported; {skipped} skipped"
+        ));
+    }
+    plan.apply(&mut client);
+    client
+        .write_bookmarks()
+        .map_err(|e| format!("could not write bookmarks: {e}"))?;
+    Ok(format!(
+        "{imported} bookmarks have been imported; {skipped} skipped"
+    ))
+}
+
+/// Export bookmarks to a file. With `with_passwords` secrets are encrypted with a passphrase read from tty,
+/// otherwise they're stripped
+pub fn export_bookmarks(args: ExportBookmarksArgs) -> Result<String, String> {
+    let client: BookmarksClient = get_bookmarks_client()?;
+    let passphrase = match args.with_passwords {
+        true => Some(read_new_secret("Passphrase: ", "Confirm passphrase: ")?),
+        false => None,
+    };
+    let exported = bookmarks_export::export_bookmarks(&client, passphrase.as_deref())
+        .map_err(|e| format!("could not export bookmarks: {e}"))?;
+    let format = args
+        .format
+        .unwrap_or_else(|| ExportFormat::from_path(args.path.as_path()));
+    bookmarks_export::write_export(&exported, args.path.as_path(), format)
+        .map_err(|e| format!("{}: {e}", args.path.display()))?;
+    Ok(format!(
+        "{} bookmarks have been exported to {} ({})",
+        exported.bookmarks.len(),
+        args.path.display(),
+        match exported.has_secrets() {
+            true => "passwords encrypted with passphrase",
+            false => "passwords stripped",
+        }
+    ))
+}
+
+/// Set or remove the master password protecting the secrets saved in bookmarks.
+/// Saved secrets are re-encrypted with the new key
+pub fn master_password(args: MasterPasswordArgs) -> Result<String, String> {
+    let mut client: BookmarksClient = get_bookmarks_client()?;
+    let password = match args.disable {
+        true if !client.has_master_password() => {
+            return Err(String::from("master password is not set"))
+        }
+        true => None,
+        false => Some(read_new_secret(
+            "New master password: ",
+            "Confirm master password: ",
+        )?),
+    };
+    client
+        .set_master_password(password.as_deref())
+        .map_err(|e| e.to_string())?;
+    client.write_bookmarks().map_err(|e| e.to_string())?;
+    Ok(match args.disable {
+        true => String::from("Master password has been removed; saved passwords are now encrypted with the key in the key storage"),
+        false => String::from("Master password has been set; you'll be prompted for it when starting termscp"),
+    })
+}
+
 /// Install latest version of termscp if an update is available
 pub fn install_update() -> Result<String, String> {
     match Update::default()
@@ -73,6 +174,43 @@ fn get_config_dir() -> Result<PathBuf, String> {
     }
 }
 
+/// Read a secret (e.g. a passphrase) from tty, prompting with `prompt`; the secret can't be empty
+fn read_secret(prompt: &str) -> Result<String, String> {
+    let name = prompt.trim_end_matches(": ").to_lowercase();
+    match tty::read_secret_from_tty(prompt) {
+        Ok(Some(secret)) => Ok(secret),
+        Ok(None) => Err(format!("{name} can't be empty")),
+        Err(err) => Err(format!("could not read {name}: {err}")),
+    }
+}
+
+/// Read a new secret from tty, asking to type it twice
+fn read_new_secret(prompt: &str, confirm_prompt: &str) -> Result<String, String> {
+    let secret = read_secret(prompt)?;
+    if read_secret(confirm_prompt)? != secret {
+        return Err(format!(
+            "{}s don't match",
+            prompt.trim_end_matches(": ").to_lowercase()
+        ));
+    }
+    Ok(secret)
+}
+
+/// Get bookmarks client; if bookmarks are protected by a master password, it is prompted to unlock them
+fn get_bookmarks_client() -> Result<BookmarksClient, String> {
+    let cfg_dir: PathBuf = get_config_dir()?;
+    let bookmarks_file: PathBuf = environment::get_bookmarks_paths(cfg_dir.as_path());
+    let mut client = BookmarksClient::new(bookmarks_file.as_path(), cfg_dir.as_path(), 16)
+        .map_err(|e| format!("could not initialize bookmarks: {e}"))?;
+    if client.is_locked() {
+        let password = read_secret("Master password: ")?;
+        client
+            .unlock(password.as_str())
+            .map_err(|e| format!("could not unlock bookmarks: {e}"))?;
+    }
+    Ok(client)
+}
+
 /// Get configuration client
 fn get_config_client() -> Option<ConfigClient> {
     match get_config_dir() {
@@ -15,7 +15,10 @@ use super::keys::keyringstorage::KeyringStorage;
 use super::keys::{KeyStorage, KeyStorageError};
 // Local
 use crate::config::{
-    bookmarks::{Bookmark, UserHosts},
+    bookmarks::{
+        Bookmark, BookmarkHooks, BookmarkPassword, BookmarkSettings, MasterPassword, SecretRef,
+        UserHosts, WatchedPath,
+    },
     serialization::{deserialize, serialize, SerializerError, SerializerErrorKind},
 };
 use crate::filetransfer::FileTransferParams;
@@ -23,10 +26,14 @@ use crate::utils::crypto;
 use crate::utils::fmt::fmt_time;
 use crate::utils::random::random_alphanumeric_with_len;
 
+/// Value encrypted with the key derived from the master password, to check the master password
+const MASTER_PASSWORD_CHECK: &str = "termscp";
+
 /// BookmarksClient provides a layer between the host system and the bookmarks module
 pub struct BookmarksClient {
     hosts: UserHosts,
     bookmarks_file: PathBuf,
+    storage_path: PathBuf,
     key: String,
     recents_size: usize,
 }
@@ -40,75 +47,12 @@ impl BookmarksClient {
         storage_path: &Path,
         recents_size: usize,
     ) -> Result<BookmarksClient, SerializerError> {
-        // Create default hosts
-        let default_hosts: UserHosts = UserHosts::default();
         debug!("Setting up bookmarks client...");
-        // Make a key storage (with-keyring)
-        #[cfg(feature = "with-keyring")]
-        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
-            debug!("Setting up KeyStorage");
-            let username: String = whoami::username();
-            let storage: KeyringStorage = KeyringStorage::new(username.as_str());
-            // Check if keyring storage is supported
-            #[cfg(not(test))]
-            let app_name: &str = "termscp";
-            #[cfg(test)] // NOTE: when running test, add -test
-            let app_name: &str = "termscp-test";
-            match storage.is_supported() {
-                true => {
-                    debug!("Using KeyringStorage");
-                    (Box::new(storage), app_name)
-                }
-                false => {
-                    warn!("KeyringStorage is not supported; using FileStorage");
-                    (Box::new(FileStorage::new(storage_path)), "bookmarks")
-                }
-            }
-        };
-        // Make a key storage (wno-keyring)
-        #[cfg(not(feature = "with-keyring"))]
-        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
-            #[cfg(not(test))]
-            let app_name: &str = "bookmarks";
-            #[cfg(test)] // NOTE: when running test, add -test
-            let app_name: &str = "bookmarks-test";
-            debug!("Using FileStorage");
-            (Box::new(FileStorage::new(storage_path)), app_name)
-        };
-        // Load key
-        let key: String = match key_storage.get_key(service_id) {
-            Ok(k) => {
-                debug!("Key loaded with success");
-                k
-            }
-            Err(e) => match e {
-                KeyStorageError::NoSuchKey => {
-                    // If no such key, generate key and set it into the storage
-                    let key: String = Self::generate_key();
-                    debug!("Key doesn't exist yet or could not be loaded; generated a new key");
-                    if let Err(e) = key_storage.set_key(service_id, key.as_str()) {
-                        error!("Failed to set new key into storage: {}", e);
-                        return Err(SerializerError::new_ex(
-                            SerializerErrorKind::Io,
-                            format!("Could not write key to storage: {e}"),
-                        ));
-                    }
-                    // Return key
-                    key
-                }
-                _ => {
-                    error!("Failed to get key from storage: {}", e);
-                    return Err(SerializerError::new_ex(
-                        SerializerErrorKind::Io,
-                        format!("Could not get key from storage: {e}"),
-                    ));
-                }
-            },
-        };
         let mut client: BookmarksClient = BookmarksClient {
-            hosts: default_hosts,
+            hosts: UserHosts::default(),
             bookmarks_file: PathBuf::from(bookmarks_file),
-            key,
+            storage_path: PathBuf::from(storage_path),
+            key: String::new(),
             recents_size,
         };
         // If bookmark file doesn't exist, initialize it
@@ -125,11 +69,80 @@ impl BookmarksClient {
                 return Err(err);
             }
         }
+        // Load key; if secrets are protected by the master password, client is locked until `unlock` is called
+        match client.hosts.master_password.is_some() {
+            true => info!("Bookmarks are protected by a master password; client is locked"),
+            false => client.key = Self::load_key(storage_path)?,
+        }
         info!("Bookmarks client initialized");
-        // Load key
         Ok(client)
     }
 
+    /// Returns whether the secrets are protected by a master password
+    pub fn has_master_password(&self) -> bool {
+        self.hosts.master_password.is_some()
+    }
+
+    /// Returns whether the client is locked, since the master password has not been provided yet.
+    /// While locked, secrets can't be decrypted
+    pub fn is_locked(&self) -> bool {
+        self.key.is_empty()
+    }
+
+    /// Unlock the client deriving the key from the master password.
+    /// Returns error if the master password is wrong
+    pub fn unlock(&mut self, password: &str) -> Result<(), SerializerError> {
+        let master_password = match self.hosts.master_password.as_ref() {
+            Some(master_password) => master_password,
+            None => return Ok(()),
+        };
+        let key = Self::derive_key(password, master_password.salt.as_str())?;
+        match crypto::aes128_b64_decrypt(key.as_str(), master_password.check.as_str()) {
+            Ok(check) if check == MASTER_PASSWORD_CHECK => {
+                info!("Bookmarks client unlocked");
+                self.key = key;
+                Ok(())
+            }
+            _ => {
+                error!("Failed to unlock bookmarks client: wrong master password");
+                Err(SerializerError::new_ex(
+                    SerializerErrorKind::Generic,
+                    String::from("wrong master password"),
+                ))
+            }
+        }
+    }
+
+    /// Set the master password the secrets key is derived from, or remove it if `None`, in which case
+    /// the key is stored into the key storage again.
+    /// The secrets of the bookmarks are re-encrypted with the new key.
+    /// The client must be unlocked
+    pub fn set_master_password(&mut self, password: Option<&str>) -> Result<(), SerializerError> {
+        if self.is_locked() {
+            return Err(SerializerError::new_ex(
+                SerializerErrorKind::Generic,
+                String::from("bookmarks are locked"),
+            ));
+        }
+        let (key, master_password) = match password {
+            Some(password) => {
+                let salt = random_alphanumeric_with_len(32);
+                let key = Self::derive_key(password, salt.as_str())?;
+                let check = crypto::aes128_b64_crypt(key.as_str(), MASTER_PASSWORD_CHECK);
+                (key, Some(MasterPassword { salt, check }))
+            }
+            None => (Self::load_key(self.storage_path.as_path())?, None),
+        };
+        self.reencrypt_secrets(key.as_str())?;
+        info!(
+            "Bookmarks secrets re-encrypted; master password set: {}",
+            master_password.is_some()
+        );
+        self.key = key;
+        self.hosts.master_password = master_password;
+        Ok(())
+    }
+
     /// Iterate over bookmarks keys
     pub fn iter_bookmarks(&self) -> impl Iterator<Item = &String> + '_ {
         Box::new(self.hosts.bookmarks.keys())
@@ -140,7 +153,7 @@ impl BookmarksClient {
         debug!("Getting bookmark {}", key);
         let mut entry: Bookmark = self.hosts.bookmarks.get(key).cloned()?;
         // Decrypt password first
-        if let Some(pwd) = entry.password.as_mut() {
+        if let Some(BookmarkPassword::Inline(pwd)) = entry.password.as_mut() {
             match self.decrypt_str(pwd.as_str()) {
                 Ok(decrypted_pwd) => {
                     *pwd = decrypted_pwd;
@@ -208,9 +221,122 @@ impl BookmarksClient {
                 s3.secret_access_key = None;
             }
         }
+        // Keep tags, settings, hooks and watched paths of the replaced bookmark, since they're not part of the params
+        if let Some(old) = self.hosts.bookmarks.get(&name) {
+            // Keep the reference to the secret backend too, unless a password has been provided
+            if host.password.is_none() && matches!(old.password, Some(BookmarkPassword::Secret(_)))
+            {
+                host.password = old.password.clone();
+            }
+            host.tags = old.tags.clone();
+            host.settings = old.settings.clone();
+            host.hooks = old.hooks.clone();
+            host.watch = old.watch.clone();
+        }
         self.hosts.bookmarks.insert(name, host);
     }
 
+    /// Get the reference to the secret backend the password of the bookmark associated to key is resolved from
+    pub fn get_bookmark_secret(&self, key: &str) -> Option<&SecretRef> {
+        self.hosts
+            .bookmarks
+            .get(key)
+            .and_then(|x| x.password.as_ref())
+            .and_then(BookmarkPassword::secret)
+    }
+
+    /// Set the reference to the secret backend the password of the bookmark associated to key is resolved from.
+    /// If `None`, the reference is removed
+    pub fn set_bookmark_secret(&mut self, key: &str, secret: Option<SecretRef>) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            match secret {
+                Some(secret) => bookmark.password = Some(BookmarkPassword::Secret(secret)),
+                None if matches!(bookmark.password, Some(BookmarkPassword::Secret(_))) => {
+                    bookmark.password = None
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Get tags of the bookmark associated to key
+    pub fn get_bookmark_tags(&self, key: &str) -> Option<&[String]> {
+        self.hosts.bookmarks.get(key).map(|x| x.tags.as_slice())
+    }
+
+    /// Set tags of the bookmark associated to key.
+    /// Tags are trimmed and deduplicated; empty tags are discarded
+    pub fn set_bookmark_tags(&mut self, key: &str, tags: Vec<String>) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            let mut tags: Vec<String> = tags
+                .iter()
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+                .collect();
+            tags.sort();
+            tags.dedup();
+            debug!("Set tags {:?} for bookmark {}", tags, key);
+            bookmark.tags = tags;
+        }
+    }
+
+    /// Get the settings of the bookmark associated to key, which override the configuration for its sessions
+    pub fn get_bookmark_settings(&self, key: &str) -> Option<&BookmarkSettings> {
+        self.hosts.bookmarks.get(key)?.settings.as_ref()
+    }
+
+    /// Set the settings of the bookmark associated to key
+    pub fn set_bookmark_settings(&mut self, key: &str, settings: Option<BookmarkSettings>) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            debug!("Set settings {:?} for bookmark {}", settings, key);
+            bookmark.settings = settings;
+        }
+    }
+
+    /// Get the hooks of the bookmark associated to key
+    pub fn get_bookmark_hooks(&self, key: &str) -> Option<&BookmarkHooks> {
+        self.hosts.bookmarks.get(key)?.hooks.as_ref()
+    }
+
+    /// Set the hooks of the bookmark associated to key
+    pub fn set_bookmark_hooks(&mut self, key: &str, hooks: Option<BookmarkHooks>) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            debug!("Set hooks {:?} for bookmark {}", hooks, key);
+            bookmark.hooks = hooks;
+        }
+    }
+
+    /// Get the paths synchronized by the file watcher when connected to the bookmark associated to key
+    pub fn get_bookmark_watched_paths(&self, key: &str) -> Option<&[WatchedPath]> {
+        self.hosts.bookmarks.get(key).map(|x| x.watch.as_slice())
+    }
+
+    /// Add a path synchronized by the file watcher to the bookmark associated to key.
+    /// If the local path is already watched, the watched path is replaced
+    pub fn add_bookmark_watched_path(&mut self, key: &str, watched: WatchedPath) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            debug!(
+                "Added watched path {} to bookmark {}",
+                watched.local.display(),
+                key
+            );
+            bookmark.watch.retain(|x| x.local != watched.local);
+            bookmark.watch.push(watched);
+        }
+    }
+
+    /// Remove the path synchronized by the file watcher at `local` from the bookmark associated to key
+    pub fn del_bookmark_watched_path(&mut self, key: &str, local: &Path) {
+        if let Some(bookmark) = self.hosts.bookmarks.get_mut(key) {
+            debug!(
+                "Removed watched path {} from bookmark {}",
+                local.display(),
+                key
+            );
+            bookmark.watch.retain(|x| x.local != local);
+        }
+    }
+
     /// Delete entry from bookmarks
     pub fn del_bookmark(&mut self, name: &str) {
         let _ = self.hosts.bookmarks.remove(name);
@@ -239,13 +365,16 @@ impl BookmarksClient {
             s3.access_key = None;
             s3.secret_access_key = None;
         }
-        // Check if duplicated
-        for (key, value) in &self.hosts.recents {
-            if *value == host {
-                debug!("Discarding recent since duplicated ({})", key);
-                // Don't save duplicates
-                return;
-            }
+        // Check if duplicated; if so, move it to the top of the recents
+        let duplicated: Option<String> = self
+            .hosts
+            .recents
+            .iter()
+            .find(|(_, value)| **value == host)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = duplicated {
+            debug!("Recent is duplicated ({}); updating its time", key);
+            self.hosts.recents.remove(&key);
         }
         // If hosts size is bigger than self.recents_size; pop last
         if self.hosts.recents.len() >= self.recents_size {
@@ -271,6 +400,28 @@ impl BookmarksClient {
         self.hosts.recents.insert(name, host);
     }
 
+    /// Get the key of the most recent connection made with the bookmark associated to key.
+    /// Recents keys are timestamps, so they can be compared to sort bookmarks by last use
+    pub fn bookmark_last_used(&self, key: &str) -> Option<&str> {
+        let mut bookmark: Bookmark = self.hosts.bookmarks.get(key).cloned()?;
+        // Recents have no secrets, tags, settings, hooks and watched paths
+        bookmark.password = None;
+        bookmark.tags = Vec::new();
+        bookmark.settings = None;
+        bookmark.hooks = None;
+        bookmark.watch = Vec::new();
+        if let Some(s3) = bookmark.s3.as_mut() {
+            s3.access_key = None;
+            s3.secret_access_key = None;
+        }
+        self.hosts
+            .recents
+            .iter()
+            .filter(|(_, recent)| **recent == bookmark)
+            .map(|(key, _)| key.as_str())
+            .max()
+    }
+
     /// Delete entry from recents
     pub fn del_recent(&mut self, name: &str) {
         let _ = self.hosts.recents.remove(name);
@@ -326,6 +477,109 @@ impl BookmarksClient {
         }
     }
 
+    /// Load the secrets key from the key storage; if the key doesn't exist yet, it is generated and stored
+    fn load_key(storage_path: &Path) -> Result<String, SerializerError> {
+        // Make a key storage (with-keyring)
+        #[cfg(feature = "with-keyring")]
+        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
+            debug!("Setting up KeyStorage");
+            let username: String = whoami::username();
+            let storage: KeyringStorage = KeyringStorage::new(username.as_str());
+            // Check if keyring storage is supported
+            #[cfg(not(test))]
+            let app_name: &str = "termscp";
+            #[cfg(test)] // NOTE: when running test, add -test
+            let app_name: &str = "termscp-test";
+            match storage.is_supported() {
+                true => {
+                    debug!("Using KeyringStorage");
+                    (Box::new(storage), app_name)
+                }
+                false => {
+                    warn!("KeyringStorage is not supported; using FileStorage");
+                    (Box::new(FileStorage::new(storage_path)), "bookmarks")
+                }
+            }
+        };
+        // Make a key storage (wno-keyring)
+        #[cfg(not(feature = "with-keyring"))]
+        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
+            #[cfg(not(test))]
+            let app_name: &str = "bookmarks";
+            #[cfg(test)] // NOTE: when running test, add -test
+            let app_name: &str = "bookmarks-test";
+            debug!("Using FileStorage");
+            (Box::new(FileStorage::new(storage_path)), app_name)
+        };
+        // Load key
+        match key_storage.get_key(service_id) {
+            Ok(k) => {
+                debug!("Key loaded with success");
+                Ok(k)
+            }
+            Err(e) => match e {
+                KeyStorageError::NoSuchKey => {
+                    // If no such key, generate key and set it into the storage
+                    let key: String = Self::generate_key();
+                    debug!("Key doesn't exist yet or could not be loaded; generated a new key");
+                    if let Err(e) = key_storage.set_key(service_id, key.as_str()) {
+                        error!("Failed to set new key into storage: {}", e);
+                        return Err(SerializerError::new_ex(
+                            SerializerErrorKind::Io,
+                            format!("Could not write key to storage: {e}"),
+                        ));
+                    }
+                    // Return key
+                    Ok(key)
+                }
+                _ => {
+                    error!("Failed to get key from storage: {}", e);
+                    Err(SerializerError::new_ex(
+                        SerializerErrorKind::Io,
+                        format!("Could not get key from storage: {e}"),
+                    ))
+                }
+            },
+        }
+    }
+
+    /// Derive the secrets key from the master password
+    fn derive_key(password: &str, salt: &str) -> Result<String, SerializerError> {
+        crypto::derive_key(password, salt).map_err(|e| {
+            SerializerError::new_ex(
+                SerializerErrorKind::Generic,
+                format!("Could not derive key from master password: {e}"),
+            )
+        })
+    }
+
+    /// Re-encrypt the secrets of the bookmarks with `key`.
+    /// Bookmarks are left untouched if any secret can't be decrypted
+    fn reencrypt_secrets(&mut self, key: &str) -> Result<(), SerializerError> {
+        let mut bookmarks = self.hosts.bookmarks.clone();
+        for (name, bookmark) in bookmarks.iter_mut() {
+            let mut secrets: Vec<&mut String> = match bookmark.password.as_mut() {
+                Some(BookmarkPassword::Inline(pwd)) => vec![pwd],
+                _ => Vec::new(),
+            };
+            if let Some(s3) = bookmark.s3.as_mut() {
+                secrets.extend(s3.access_key.iter_mut());
+                secrets.extend(s3.secret_access_key.iter_mut());
+            }
+            for secret in secrets {
+                let plain = self.decrypt_str(secret.as_str()).map_err(|e| {
+                    SerializerError::new_ex(
+                        SerializerErrorKind::Syntax,
+                        format!("Could not decrypt secrets of bookmark {name}: {e}"),
+                    )
+                })?;
+                *secret = crypto::aes128_b64_crypt(key, plain.as_str());
+            }
+        }
+        self.hosts.bookmarks = bookmarks;
+        Ok(())
+    }
+
     /// Generate a new AES key
     fn generate_key() -> String {
         // Generate 256 bytes (2048 bits) key
@@ -336,8 +590,8 @@ impl BookmarksClient {
     fn make_bookmark(&self, params: FileTransferParams) -> Bookmark {
         let mut bookmark: Bookmark = Bookmark::from(params);
         // Encrypt password
-        if let Some(pwd) = bookmark.password {
-            bookmark.password = Some(self.encrypt_str(pwd.as_str()));
+        if let Some(BookmarkPassword::Inline(pwd)) = bookmark.password.as_mut() {
+            *pwd = self.encrypt_str(pwd.as_str());
         }
         // Encrypt aws s3 params
         if let Some(s3) = bookmark.s3.as_mut() {
@@ -379,6 +633,7 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::config::bookmarks::Hook;
     use crate::filetransfer::params::{AwsS3Params, GenericProtocolParams};
     use crate::filetransfer::{FileTransferProtocol, ProtocolParams};
 
@@ -447,6 +702,109 @@ mod tests {
         assert_eq!(bookmark.4, None);
     }
 
+    #[test]
+    fn should_protect_secrets_with_master_password() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        assert!(!client.has_master_password());
+        assert!(!client.is_locked());
+        client.add_bookmark(
+            "raspberry",
+            make_generic_ftparams(
+                FileTransferProtocol::Sftp,
+                "192.168.1.31",
+                22,
+                "pi",
+                Some("mypassword"),
+            ),
+            true,
+        );
+        let storage_key: String = client.key.clone();
+        // Set master password; secrets are re-encrypted
+        assert!(client.set_master_password(Some("master")).is_ok());
+        assert!(client.has_master_password());
+        assert_ne!(client.key, storage_key);
+        assert_eq!(
+            ftparams_to_tup(client.get_bookmark("raspberry").unwrap()).4,
+            Some(String::from("mypassword"))
+        );
+        assert!(client.write_bookmarks().is_ok());
+        // Re-initialize client; it is locked until unlocked with the master password
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        assert!(client.has_master_password());
+        assert!(client.is_locked());
+        assert!(client.set_master_password(None).is_err());
+        assert!(client.unlock("wrong").is_err());
+        assert!(client.is_locked());
+        assert!(client.unlock("master").is_ok());
+        assert!(!client.is_locked());
+        assert_eq!(
+            ftparams_to_tup(client.get_bookmark("raspberry").unwrap()).4,
+            Some(String::from("mypassword"))
+        );
+        // Remove master password; the key of the storage is used again
+        assert!(client.set_master_password(None).is_ok());
+        assert!(!client.has_master_password());
+        assert_eq!(client.key, storage_key);
+        assert!(client.write_bookmarks().is_ok());
+        let client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        assert!(!client.is_locked());
+        assert_eq!(
+            ftparams_to_tup(client.get_bookmark("raspberry").unwrap()).4,
+            Some(String::from("mypassword"))
+        );
+    }
+
+    #[test]
+    fn should_keep_bookmark_secret_ref() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        let params =
+            make_generic_ftparams(FileTransferProtocol::Sftp, "10.0.1.2", 22, "root", None);
+        client.add_bookmark("prod/eu/db02", params.clone(), true);
+        assert!(client.get_bookmark_secret("prod/eu/db02").is_none());
+        let secret = SecretRef::Cmd(String::from("pass show prod/db02"));
+        client
+            .hosts
+            .bookmarks
+            .get_mut("prod/eu/db02")
+            .unwrap()
+            .password = Some(BookmarkPassword::Secret(secret.clone()));
+        assert_eq!(client.get_bookmark_secret("prod/eu/db02"), Some(&secret));
+        // Secret is resolved when connecting
+        assert!(
+            ftparams_to_tup(client.get_bookmark("prod/eu/db02").unwrap())
+                .4
+                .is_none()
+        );
+        // Reference is kept if the bookmark is saved again without password
+        client.add_bookmark("prod/eu/db02", params, true);
+        assert_eq!(client.get_bookmark_secret("prod/eu/db02"), Some(&secret));
+        // A password replaces the reference
+        client.add_bookmark(
+            "prod/eu/db02",
+            make_generic_ftparams(
+                FileTransferProtocol::Sftp,
+                "10.0.1.2",
+                22,
+                "root",
+                Some("mypassword"),
+            ),
+            true,
+        );
+        assert!(client.get_bookmark_secret("prod/eu/db02").is_none());
+        assert_eq!(
+            ftparams_to_tup(client.get_bookmark("prod/eu/db02").unwrap()).4,
+            Some(String::from("mypassword"))
+        );
+    }
+
     #[test]
     fn should_make_s3_bookmark_with_secrets() {
         let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
@@ -550,6 +908,115 @@ mod tests {
         assert_eq!(bookmark.2, FileTransferProtocol::Sftp);
         assert_eq!(bookmark.3, String::from("pi"));
         assert_eq!(*bookmark.4.as_ref().unwrap(), String::from("mypassword"));
+        // Tags
+        assert!(client.get_bookmark_tags("raspberry").unwrap().is_empty());
+        client.set_bookmark_tags(
+            "raspberry",
+            vec![
+                String::from("pi "),
+                String::from(""),
+                String::from("home"),
+                String::from("pi"),
+            ],
+        );
+        assert_eq!(
+            client.get_bookmark_tags("raspberry").unwrap(),
+            &[String::from("home"), String::from("pi")]
+        );
+        assert!(client.get_bookmark_tags("unknown").is_none());
+        // Tags are kept when the bookmark is replaced
+        client.add_bookmark(
+            "raspberry",
+            make_generic_ftparams(
+                FileTransferProtocol::Sftp,
+                "192.168.1.32",
+                22,
+                "pi",
+                Some("mypassword"),
+            ),
+            true,
+        );
+        assert_eq!(client.get_bookmark_tags("raspberry").unwrap().len(), 2);
+        // Settings
+        assert!(client.get_bookmark_settings("raspberry").is_none());
+        let settings = BookmarkSettings {
+            show_hidden_files: Some(true),
+            ..Default::default()
+        };
+        client.set_bookmark_settings("raspberry", Some(settings.clone()));
+        assert_eq!(client.get_bookmark_settings("raspberry"), Some(&settings));
+        // Hooks
+        assert!(client.get_bookmark_hooks("raspberry").is_none());
+        let hooks = BookmarkHooks {
+            on_connect: vec![Hook {
+                command: String::from("wg-quick up home"),
+                remote: false,
+                abort_on_failure: true,
+            }],
+            ..Default::default()
+        };
+        client.set_bookmark_hooks("raspberry", Some(hooks.clone()));
+        assert_eq!(client.get_bookmark_hooks("raspberry"), Some(&hooks));
+        // Watched paths
+        assert!(client
+            .get_bookmark_watched_paths("raspberry")
+            .unwrap()
+            .is_empty());
+        let watched = WatchedPath {
+            local: PathBuf::from("/home/pi/site"),
+            remote: PathBuf::from("/var/www/site"),
+            ignore: vec![String::from("*.swp")],
+            reconcile: false,
+        };
+        client.add_bookmark_watched_path("raspberry", watched.clone());
+        client.add_bookmark_watched_path(
+            "raspberry",
+            WatchedPath {
+                reconcile: true,
+                ..watched.clone()
+            },
+        );
+        client.add_bookmark_watched_path(
+            "raspberry",
+            WatchedPath {
+                local: PathBuf::from("/home/pi/docs"),
+                ..watched.clone()
+            },
+        );
+        let watched = WatchedPath {
+            reconcile: true,
+            ..watched
+        };
+        assert_eq!(
+            client
+                .get_bookmark_watched_paths("raspberry")
+                .unwrap()
+                .len(),
+            2
+        );
+        client.del_bookmark_watched_path("raspberry", Path::new("/home/pi/docs"));
+        assert_eq!(
+            client.get_bookmark_watched_paths("raspberry").unwrap(),
+            std::slice::from_ref(&watched)
+        );
+        // Settings are kept when the bookmark is replaced
+        client.add_bookmark(
+            "raspberry",
+            make_generic_ftparams(
+                FileTransferProtocol::Sftp,
+                "192.168.1.31",
+                22,
+                "pi",
+                Some("mypassword"),
+            ),
+            true,
+        );
+        assert_eq!(client.get_bookmark_settings("raspberry"), Some(&settings));
+        assert_eq!(client.get_bookmark_hooks("raspberry"), Some(&hooks));
+        assert_eq!(
+            client.get_bookmark_watched_paths("raspberry").unwrap(),
+            &[watched]
+        );
         // Write bookmarks
         assert!(client.write_bookmarks().is_ok());
         // Delete bookmark
@@ -646,6 +1113,43 @@ mod tests {
         assert!(client.write_bookmarks().is_ok());
     }
 
+    #[test]
+    fn should_get_bookmark_last_used() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        let params = make_generic_ftparams(
+            FileTransferProtocol::Sftp,
+            "192.168.1.31",
+            22,
+            "pi",
+            Some("mypassword"),
+        );
+        client.add_bookmark("raspberry", params.clone(), true);
+        client.set_bookmark_tags("raspberry", vec![String::from("home")]);
+        assert!(client.bookmark_last_used("raspberry").is_none());
+        client.add_recent(make_generic_ftparams(
+            FileTransferProtocol::Sftp,
+            "192.168.1.32",
+            22,
+            "pi",
+            None,
+        ));
+        assert!(client.bookmark_last_used("raspberry").is_none());
+        client.add_recent(params);
+        let recent = client
+            .iter_recents()
+            .find(|x| ftparams_to_tup(client.get_recent(x).unwrap()).0.as_str() == "192.168.1.31")
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            client.bookmark_last_used("raspberry"),
+            Some(recent.as_str())
+        );
+        assert!(client.bookmark_last_used("unknown").is_none());
+    }
+
     #[test]
 
     fn test_system_bookmarks_dup_recent() {
@@ -0,0 +1,392 @@
+//! ## Bookmarks export
+//!
+//! `bookmarks_export` is the module which exports the bookmarks to a portable file, to share them with other users.
+//! Since the bookmarks key is local to the machine, secrets are either stripped or encrypted with a key derived from
+//! a passphrase.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::bookmarks_client::BookmarksClient;
+use crate::config::bookmarks::{Bookmark, BookmarkPassword, ExportedBookmarks};
+use crate::utils::crypto;
+use crate::utils::random::random_alphanumeric_with_len;
+
+pub type ExportResult<T> = Result<T, ExportError>;
+
+/// Describes an error returned while exporting or reading exported bookmarks
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("I/O error: {0}")]
+    Io(std::io::Error),
+    #[error("could not derive key from passphrase: {0}")]
+    KeyDerivation(argon2::Error),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error("syntax error: {0}")]
+    Syntax(String),
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Format of the exported bookmarks file
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Toml,
+}
+
+impl ExportFormat {
+    /// Guess the format from the extension of `path`; TOML is used if the extension is not `json`
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "toml" => Ok(Self::Toml),
+            _ => Err(format!(
+                "unknown format \"{s}\"; expected one of: toml, json"
+            )),
+        }
+    }
+}
+
+/// Export the bookmarks of `client`.
+/// If `passphrase` is provided, secrets are encrypted with a key derived from it, otherwise they're stripped
+pub fn export_bookmarks(
+    client: &BookmarksClient,
+    passphrase: Option<&str>,
+) -> ExportResult<ExportedBookmarks> {
+    let salt = passphrase.map(|_| random_alphanumeric_with_len(32));
+    let key = match (passphrase, salt.as_deref()) {
+        (Some(passphrase), Some(salt)) => {
+            Some(crypto::derive_key(passphrase, salt).map_err(ExportError::KeyDerivation)?)
+        }
+        _ => None,
+    };
+    let bookmarks = client
+        .iter_bookmarks()
+        .filter_map(|name| {
+            // NOTE: the bookmark returned by the client has its secrets decrypted
+            let mut bookmark = Bookmark::from(client.get_bookmark(name)?);
+            bookmark.tags = client.get_bookmark_tags(name).unwrap_or_default().to_vec();
+            bookmark.settings = client.get_bookmark_settings(name).cloned();
+            bookmark.hooks = client.get_bookmark_hooks(name).cloned();
+            match key.as_deref() {
+                Some(key) => {
+                    map_secrets(&mut bookmark, |x| Some(crypto::aes256_hmac_crypt(key, x)))
+                }
+                None => map_secrets(&mut bookmark, |_| None),
+            }
+            // NOTE: references to secret backends are not secrets, so they're exported
+            if let Some(secret) = client.get_bookmark_secret(name) {
+                bookmark.password = Some(BookmarkPassword::Secret(secret.clone()));
+            }
+            Some((name.clone(), bookmark))
+        })
+        .collect();
+    Ok(ExportedBookmarks { salt, bookmarks })
+}
+
+/// Write exported bookmarks to `path`
+pub fn write_export(
+    exported: &ExportedBookmarks,
+    path: &Path,
+    format: ExportFormat,
+) -> ExportResult<()> {
+    let data = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(exported)
+            .map_err(|e| ExportError::Serialization(e.to_string()))?,
+        ExportFormat::Toml => {
+            toml::to_string(exported).map_err(|e| ExportError::Serialization(e.to_string()))?
+        }
+    };
+    let mut file = File::create(path)?;
+    file.write_all(data.as_bytes())?;
+    Ok(())
+}
+
+/// Read exported bookmarks from the content of an export file, either TOML or JSON
+pub fn read_export(content: &str) -> ExportResult<ExportedBookmarks> {
+    if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|e| ExportError::Syntax(e.to_string()))
+    } else {
+        toml::from_str(content).map_err(|e| ExportError::Syntax(e.to_string()))
+    }
+}
+
+impl ExportedBookmarks {
+    /// Returns whether the secrets have been exported
+    pub fn has_secrets(&self) -> bool {
+        self.salt.is_some()
+    }
+
+    /// Decrypt the secrets with `passphrase`.
+    /// Returns `WrongPassphrase` if a secret can't be authenticated; in this case the secrets are left untouched
+    pub fn decrypt_secrets(&mut self, passphrase: &str) -> ExportResult<()> {
+        let salt = match self.salt.as_deref() {
+            Some(salt) => salt,
+            None => return Ok(()),
+        };
+        let key = crypto::derive_key(passphrase, salt).map_err(ExportError::KeyDerivation)?;
+        let mut bookmarks = self.bookmarks.clone();
+        let mut authenticated = true;
+        for (name, bookmark) in bookmarks.iter_mut() {
+            map_secrets(bookmark, |x| match crypto::aes256_hmac_decrypt(&key, x) {
+                Ok(secret) => Some(secret),
+                Err(err) => {
+                    error!("Failed to decrypt secret for bookmark {}: {}", name, err);
+                    authenticated = false;
+                    None
+                }
+            });
+        }
+        if !authenticated {
+            return Err(ExportError::WrongPassphrase);
+        }
+        self.salt = None;
+        self.bookmarks = bookmarks;
+        Ok(())
+    }
+
+    /// Remove the secrets
+    pub fn strip_secrets(&mut self) {
+        self.salt = None;
+        for bookmark in self.bookmarks.values_mut() {
+            map_secrets(bookmark, |_| None);
+        }
+    }
+}
+
+/// Replace each secret of `bookmark` with the result of `f`
+fn map_secrets<F>(bookmark: &mut Bookmark, mut f: F)
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    // NOTE: references to secret backends are not secrets, so they're kept; `cmd` references are
+    // imported only on demand, since they run commands (see `ImportPlan::with_commands`)
+    if let Some(BookmarkPassword::Inline(pwd)) = bookmark.password.as_ref() {
+        bookmark.password = f(pwd.as_str()).map(BookmarkPassword::Inline);
+    }
+    if let Some(s3) = bookmark.s3.as_mut() {
+        s3.access_key = s3.access_key.as_deref().and_then(&mut f);
+        s3.secret_access_key = s3.secret_access_key.as_deref().and_then(&mut f);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::config::bookmarks::SecretRef;
+    use crate::filetransfer::params::{AwsS3Params, GenericProtocolParams};
+    use crate::filetransfer::{FileTransferParams, FileTransferProtocol, ProtocolParams};
+
+    fn bookmarks_client(dir: &TempDir) -> BookmarksClient {
+        let mut client =
+            BookmarksClient::new(&dir.path().join("bookmarks.toml"), dir.path(), 16).unwrap();
+        client.add_bookmark(
+            "raspberry",
+            FileTransferParams::new(
+                FileTransferProtocol::Sftp,
+                ProtocolParams::Generic(
+                    GenericProtocolParams::default()
+                        .address("192.168.1.31")
+                        .username(Some("pi"))
+                        .password(Some("raspberry")),
+                ),
+            ),
+            true,
+        );
+        client.set_bookmark_tags("raspberry", vec![String::from("home")]);
+        client.add_bookmark(
+            "bucket",
+            FileTransferParams::new(
+                FileTransferProtocol::AwsS3,
+                ProtocolParams::AwsS3(
+                    AwsS3Params::new("bucket", Some("eu-west-1"), None)
+                        .access_key(Some("AKIA"))
+                        .secret_access_key(Some("secret")),
+                ),
+            ),
+            true,
+        );
+        client
+    }
+
+    #[test]
+    fn should_export_bookmarks_without_secrets() {
+        let dir = TempDir::new().unwrap();
+        let exported = export_bookmarks(&bookmarks_client(&dir), None).unwrap();
+        assert!(!exported.has_secrets());
+        assert_eq!(exported.bookmarks.len(), 2);
+        let bookmark = exported.bookmarks.get("raspberry").unwrap();
+        assert_eq!(bookmark.address.as_deref(), Some("192.168.1.31"));
+        assert_eq!(bookmark.username.as_deref(), Some("pi"));
+        assert!(bookmark.password.is_none());
+        assert_eq!(bookmark.tags, vec![String::from("home")]);
+        let s3 = exported
+            .bookmarks
+            .get("bucket")
+            .unwrap()
+            .s3
+            .as_ref()
+            .unwrap();
+        assert!(s3.access_key.is_none());
+        assert!(s3.secret_access_key.is_none());
+    }
+
+    #[test]
+    fn should_export_bookmarks_with_encrypted_secrets() {
+        let dir = TempDir::new().unwrap();
+        let mut exported = export_bookmarks(&bookmarks_client(&dir), Some("passphrase")).unwrap();
+        assert!(exported.has_secrets());
+        let password = exported
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .password
+            .clone()
+            .unwrap();
+        assert_ne!(
+            password,
+            BookmarkPassword::Inline(String::from("raspberry"))
+        );
+        // salt is random
+        assert_ne!(
+            exported.salt,
+            export_bookmarks(&bookmarks_client(&dir), Some("passphrase"))
+                .unwrap()
+                .salt
+        );
+        assert!(matches!(
+            exported.decrypt_secrets("wrong"),
+            Err(ExportError::WrongPassphrase)
+        ));
+        // secrets are untouched after a failure
+        assert!(exported.has_secrets());
+        assert_eq!(
+            exported.bookmarks.get("raspberry").unwrap().password,
+            Some(password)
+        );
+        assert!(exported.decrypt_secrets("passphrase").is_ok());
+        assert!(!exported.has_secrets());
+        assert_eq!(
+            exported
+                .bookmarks
+                .get("raspberry")
+                .unwrap()
+                .password
+                .as_ref()
+                .and_then(BookmarkPassword::inline),
+            Some("raspberry")
+        );
+        let s3 = exported
+            .bookmarks
+            .get("bucket")
+            .unwrap()
+            .s3
+            .as_ref()
+            .unwrap();
+        assert_eq!(s3.access_key.as_deref(), Some("AKIA"));
+        assert_eq!(s3.secret_access_key.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn should_export_secret_refs() {
+        let dir = TempDir::new().unwrap();
+        let mut client = bookmarks_client(&dir);
+        let secret = SecretRef::Cmd(String::from("pass show home/raspberry"));
+        client.add_bookmark(
+            "raspberry",
+            FileTransferParams::new(
+                FileTransferProtocol::Sftp,
+                ProtocolParams::Generic(
+                    GenericProtocolParams::default()
+                        .address("192.168.1.31")
+                        .username(Some("pi")),
+                ),
+            ),
+            true,
+        );
+        client.set_bookmark_secret("raspberry", Some(secret.clone()));
+        for passphrase in [None, Some("passphrase")] {
+            let exported = export_bookmarks(&client, passphrase).unwrap();
+            assert_eq!(
+                exported.bookmarks.get("raspberry").unwrap().password,
+                Some(BookmarkPassword::Secret(secret.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn should_strip_secrets() {
+        let dir = TempDir::new().unwrap();
+        let mut exported = export_bookmarks(&bookmarks_client(&dir), Some("passphrase")).unwrap();
+        exported.strip_secrets();
+        assert!(!exported.has_secrets());
+        assert!(exported
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .password
+            .is_none());
+    }
+
+    #[test]
+    fn should_write_and_read_export() {
+        let dir = TempDir::new().unwrap();
+        let exported = export_bookmarks(&bookmarks_client(&dir), Some("passphrase")).unwrap();
+        for (file, format) in [
+            ("bookmarks.toml", ExportFormat::Toml),
+            ("bookmarks.json", ExportFormat::Json),
+        ] {
+            let path = dir.path().join(file);
+            assert_eq!(ExportFormat::from_path(&path), format);
+            assert!(write_export(&exported, &path, format).is_ok());
+            let content = std::fs::read_to_string(&path).unwrap();
+            let read = read_export(&content).unwrap();
+            assert_eq!(read.salt, exported.salt);
+            assert_eq!(read.bookmarks, exported.bookmarks);
+        }
+    }
+
+    #[test]
+    fn should_fail_reading_bad_export() {
+        assert!(read_export("{ \"bookmarks\": [] }").is_err());
+        assert!(read_export("bookmarks = 1").is_err());
+    }
+
+    #[test]
+    fn should_parse_export_format() {
+        assert_eq!(ExportFormat::from_str("JSON").unwrap(), ExportFormat::Json);
+        assert_eq!(ExportFormat::from_str("toml").unwrap(), ExportFormat::Toml);
+        assert!(ExportFormat::from_str("yaml").is_err());
+        assert_eq!(
+            ExportFormat::from_path(Path::new("bookmarks")),
+            ExportFormat::Toml
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! ## FileZilla
+//!
+//! Import the sites of the FileZilla site manager (`sitemanager.xml`).
+//! Site manager folders become bookmark groups.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use super::{ImportError, ImportResult, Sites};
+use crate::filetransfer::params::GenericProtocolParams;
+use crate::filetransfer::{FileTransferParams, FileTransferProtocol, ProtocolParams};
+
+pub fn parse(xml: &str) -> ImportResult<Sites> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut sites = Sites::default();
+    // Folders containing the current element; the folder name is the text before its first child
+    let mut folders: Vec<String> = Vec::new();
+    let mut reading_folder_name = false;
+    let mut server: Option<HashMap<String, String>> = None;
+    let mut field: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                reading_folder_name = false;
+                match e.name().as_ref() {
+                    b"Folder" => {
+                        folders.push(String::new());
+                        reading_folder_name = true;
+                    }
+                    b"Server" => server = Some(HashMap::new()),
+                    name if server.is_some() => {
+                        field = Some(String::from_utf8_lossy(name).to_string())
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Text(e)) => {
+                let text = e
+                    .unescape()
+                    .map_err(|e| ImportError::Syntax(e.to_string()))?;
+                if reading_folder_name {
+                    if let Some(folder) = folders.last_mut() {
+                        *folder = text.trim().to_string();
+                    }
+                } else if let (Some(server), Some(field)) = (server.as_mut(), field.as_ref()) {
+                    server.insert(field.clone(), text.to_string());
+                }
+            }
+            Ok(Event::End(e)) => {
+                reading_folder_name = false;
+                match e.name().as_ref() {
+                    b"Folder" => {
+                        folders.pop();
+                    }
+                    b"Server" => {
+                        if let Some(server) = server.take() {
+                            add_server(&mut sites, &folders, server);
+                        }
+                    }
+                    _ => field = None,
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(ImportError::Syntax(e.to_string())),
+            Ok(_) => {}
+        }
+    }
+    Ok(sites)
+}
+
+fn add_server(sites: &mut Sites, folders: &[String], server: HashMap<String, String>) {
+    let address = server.get("Host").map(|x| x.trim()).unwrap_or_default();
+    let name = server
+        .get("Name")
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .unwrap_or(address);
+    let name = folders
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(name))
+        .collect::<Vec<&str>>()
+        .join("/");
+    // FileZilla protocols: 0 FTP, 1 SFTP, 3 FTPS (implicit), 4 FTPES (explicit), 6 FTP (insecure)
+    let protocol = match server.get("Protocol").map(|x| x.trim()).unwrap_or("0") {
+        "0" | "6" => FileTransferProtocol::Ftp(false),
+        "1" => FileTransferProtocol::Sftp,
+        "3" | "4" => FileTransferProtocol::Ftp(true),
+        protocol => {
+            return sites.unsupported(&name, format!("unsupported protocol ({protocol})"));
+        }
+    };
+    if address.is_empty() {
+        return sites.unsupported(&name, "the site has no host");
+    }
+    let default_port = match protocol {
+        FileTransferProtocol::Sftp => 22,
+        _ => 21,
+    };
+    let port = server
+        .get("Port")
+        .and_then(|x| x.trim().parse::<u16>().ok())
+        .unwrap_or(default_port);
+    let username = server
+        .get("User")
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty());
+    let params = FileTransferParams::new(
+        protocol,
+        ProtocolParams::Generic(
+            GenericProtocolParams::default()
+                .address(address)
+                .port(port)
+                .username(username),
+        ),
+    )
+    .remote_path(server.get("RemoteDir").and_then(|x| parse_remote_dir(x)))
+    .local_path(
+        server
+            .get("LocalDir")
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty()),
+    );
+    sites.add(&name, params);
+}
+
+/// Parse the FileZilla remote directory, which is serialized as the server type, the prefix length
+/// and the length of each segment followed by the segment (e.g. `1 0 4 home 2 pi` is `/home/pi`).
+/// Only unix paths are supported.
+fn parse_remote_dir(dir: &str) -> Option<PathBuf> {
+    let (kind, rest) = dir.trim().split_once(' ')?;
+    let (prefix, mut rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    if kind != "1" || prefix != "0" {
+        return None;
+    }
+    let mut path = PathBuf::from("/");
+    while !rest.is_empty() {
+        let (len, segment) = rest.split_once(' ')?;
+        let len = len.parse::<usize>().ok()?;
+        let end = segment
+            .char_indices()
+            .nth(len)
+            .map(|(i, _)| i)
+            .unwrap_or(segment.len());
+        path.push(&segment[..end]);
+        rest = segment[end..].trim_start();
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const SITE_MANAGER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>
+<FileZilla3 version="3.66.1" platform="*nix">
+    <Servers>
+        <Server>
+            <Host>192.168.1.31</Host>
+            <Port>22</Port>
+            <Protocol>1</Protocol>
+            <Type>0</Type>
+            <User>pi</User>
+            <Pass encoding="base64">cGFzc3dvcmQ=</Pass>
+            <Logontype>1</Logontype>
+            <Name>raspberry</Name>
+            <RemoteDir>1 0 4 home 2 pi</RemoteDir>
+            <LocalDir>/home/omar</LocalDir>
+        </Server>
+        <Folder expanded="1">
+            prod &amp; test
+            <Folder>eu
+                <Server>
+                    <Host>ftp.example.com</Host>
+                    <Port>990</Port>
+                    <Protocol>3</Protocol>
+                    <User>omar</User>
+                    <Name>web 01</Name>
+                </Server>
+            </Folder>
+            <Server>
+                <Host>s3.amazonaws.com</Host>
+                <Protocol>5</Protocol>
+                <Name>bucket</Name>
+            </Server>
+        </Folder>
+    </Servers>
+</FileZilla3>
+"#;
+
+    #[test]
+    fn should_parse_site_manager() {
+        let sites = parse(SITE_MANAGER).unwrap();
+        assert_eq!(sites.sites.len(), 2);
+        let site = &sites.sites[0];
+        assert_eq!(site.name, "raspberry");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Sftp);
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "192.168.1.31");
+        assert_eq!(params.port, 22);
+        assert_eq!(params.username.as_deref(), Some("pi"));
+        assert!(params.password.is_none());
+        assert_eq!(
+            site.params.remote_path.as_deref(),
+            Some(Path::new("/home/pi"))
+        );
+        assert_eq!(
+            site.params.local_path.as_deref(),
+            Some(Path::new("/home/omar"))
+        );
+        let site = &sites.sites[1];
+        assert_eq!(site.name, "prod & test/eu/web 01");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Ftp(true));
+        assert_eq!(site.params.params.generic_params().unwrap().port, 990);
+        assert!(site.params.remote_path.is_none());
+        assert_eq!(sites.unsupported.len(), 1);
+        assert_eq!(sites.unsupported[0].name, "prod & test/bucket");
+    }
+
+    #[test]
+    fn should_fail_parsing_bad_xml() {
+        assert!(parse("<FileZilla3><Servers></FileZilla3>").is_err());
+    }
+
+    #[test]
+    fn should_parse_remote_dir() {
+        assert_eq!(
+            parse_remote_dir("1 0 4 home 2 pi").as_deref(),
+            Some(Path::new("/home/pi"))
+        );
+        assert_eq!(
+            parse_remote_dir("1 0 8 My files").as_deref(),
+            Some(Path::new("/My files"))
+        );
+        assert_eq!(parse_remote_dir("1 0").as_deref(), Some(Path::new("/")));
+        assert!(parse_remote_dir("8 3 C: 5 Users").is_none());
+        assert!(parse_remote_dir("").is_none());
+    }
+}
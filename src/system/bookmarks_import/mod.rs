@@ -0,0 +1,645 @@
+//! ## Bookmarks import
+//!
+//! `bookmarks_import` is the module which imports the sites saved by other clients (FileZilla, WinSCP, ssh, rclone)
+//! and the bookmarks exported by termscp.
+//! Secrets of other clients are never imported, since they're stored obscured or encrypted.
+
+mod filezilla;
+mod rclone;
+mod ssh;
+mod termscp;
+mod winscp;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::bookmarks_client::BookmarksClient;
+use crate::config::bookmarks::{BookmarkHooks, BookmarkSettings, SecretRef};
+use crate::filetransfer::{FileTransferParams, ProtocolParams};
+
+pub type ImportResult<T> = Result<T, ImportError>;
+
+/// Describes an error returned while reading the sites of another client
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("could not read file: {0}")]
+    Io(std::io::Error),
+    #[error("syntax error: {0}")]
+    Syntax(String),
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// The client the bookmarks are imported from
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ImportFormat {
+    FileZilla,
+    Rclone,
+    SshConfig,
+    Termscp,
+    WinScp,
+}
+
+impl ImportFormat {
+    pub const ALL: [ImportFormat; 5] = [
+        ImportFormat::FileZilla,
+        ImportFormat::WinScp,
+        ImportFormat::SshConfig,
+        ImportFormat::Rclone,
+        ImportFormat::Termscp,
+    ];
+
+    /// Get the path where the client stores its sites by default, if any
+    pub fn default_path(&self) -> Option<PathBuf> {
+        match self {
+            Self::FileZilla if cfg!(windows) => {
+                dirs::config_dir().map(|x| x.join("FileZilla").join("sitemanager.xml"))
+            }
+            Self::FileZilla => {
+                dirs::home_dir().map(|x| x.join(".config/filezilla/sitemanager.xml"))
+            }
+            Self::Rclone if cfg!(windows) => {
+                dirs::config_dir().map(|x| x.join("rclone").join("rclone.conf"))
+            }
+            Self::Rclone => dirs::home_dir().map(|x| x.join(".config/rclone/rclone.conf")),
+            Self::SshConfig => dirs::home_dir().map(|x| x.join(".ssh").join("config")),
+            Self::Termscp => None,
+            Self::WinScp => dirs::config_dir().map(|x| x.join("WinSCP.ini")),
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::FileZilla => "filezilla",
+            Self::Rclone => "rclone",
+            Self::SshConfig => "ssh",
+            Self::Termscp => "termscp",
+            Self::WinScp => "winscp",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "filezilla" => Ok(Self::FileZilla),
+            "rclone" => Ok(Self::Rclone),
+            "ssh" => Ok(Self::SshConfig),
+            "termscp" => Ok(Self::Termscp),
+            "winscp" => Ok(Self::WinScp),
+            _ => Err(format!(
+                "unknown format \"{s}\"; expected one of: filezilla, winscp, ssh, rclone, termscp"
+            )),
+        }
+    }
+}
+
+/// What to do when an imported site has the same name of an existing bookmark
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ConflictPolicy {
+    /// Keep the existing bookmark
+    #[default]
+    Skip,
+    /// Replace the existing bookmark
+    Overwrite,
+    /// Import the site with a new name (e.g. `raspberry (2)`)
+    Rename,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            _ => Err(format!(
+                "unknown conflict policy \"{s}\"; expected one of: skip, overwrite, rename"
+            )),
+        }
+    }
+}
+
+/// A site read from another client
+#[derive(Debug, Clone)]
+pub struct ImportedSite {
+    pub name: String,
+    pub params: FileTransferParams,
+    pub tags: Vec<String>,
+    pub settings: Option<BookmarkSettings>,
+    pub hooks: Option<BookmarkHooks>,
+    /// Reference to the secret backend of the password
+    pub secret: Option<SecretRef>,
+}
+
+impl ImportedSite {
+    /// Describe the commands the site would run on localhost or on the remote when it's used
+    /// (i.e. its hooks and the command its password is read from)
+    pub fn commands(&self) -> Vec<String> {
+        let mut commands = Vec::new();
+        if let Some(SecretRef::Cmd(cmd)) = self.secret.as_ref() {
+            commands.push(format!("password command (local): {cmd}"));
+        }
+        if let Some(hooks) = self.hooks.as_ref() {
+            for (event, hooks) in [
+                ("on_connect", &hooks.on_connect),
+                ("after_connect", &hooks.after_connect),
+                ("on_disconnect", &hooks.on_disconnect),
+            ] {
+                commands.extend(hooks.iter().map(|hook| {
+                    let host = match hook.remote {
+                        true => "remote",
+                        false => "local",
+                    };
+                    format!("{event} hook ({host}): {}", hook.command)
+                }));
+            }
+        }
+        commands
+    }
+}
+
+/// A site read from another client which can't be converted into a bookmark
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnsupportedSite {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Sites read from another client
+#[derive(Debug, Default)]
+pub struct Sites {
+    pub sites: Vec<ImportedSite>,
+    pub unsupported: Vec<UnsupportedSite>,
+}
+
+impl Sites {
+    fn add(&mut self, name: &str, params: FileTransferParams) {
+        self.add_bookmark(name, params, Vec::new(), None, None, None);
+    }
+
+    /// Add a site with the tags, the settings, the hooks and the secret reference of a termscp bookmark
+    fn add_bookmark(
+        &mut self,
+        name: &str,
+        params: FileTransferParams,
+        tags: Vec<String>,
+        settings: Option<BookmarkSettings>,
+        hooks: Option<BookmarkHooks>,
+        secret: Option<SecretRef>,
+    ) {
+        match normalize_name(name) {
+            name if name.is_empty() => self.unsupported(&name, "the site has no name"),
+            name => self.sites.push(ImportedSite {
+                name,
+                params,
+                tags,
+                settings,
+                hooks,
+                secret,
+            }),
+        }
+    }
+
+    fn unsupported(&mut self, name: &str, reason: impl ToString) {
+        self.unsupported.push(UnsupportedSite {
+            name: normalize_name(name),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// What the import does with a site
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ImportAction {
+    Add,
+    Overwrite,
+    /// Add with another name, since the name is already taken
+    Rename(String),
+    /// Skip, since the name is already taken
+    Skip,
+}
+
+/// The bookmarks to import and what to do with each of them
+#[derive(Debug)]
+pub struct ImportPlan {
+    pub entries: Vec<(ImportedSite, ImportAction)>,
+    pub unsupported: Vec<UnsupportedSite>,
+    /// Whether the commands of the sites are imported; they're dropped by default,
+    /// since whoever wrote the file could run anything on this host
+    with_commands: bool,
+}
+
+impl ImportPlan {
+    /// Plan the import of `sites`, given the names of the existing bookmarks and the conflict policy.
+    /// Sites with the same name in the same file are handled as conflicts too.
+    pub fn new<'a, I>(sites: Sites, bookmarks: I, policy: ConflictPolicy) -> Self
+    where
+        I: Iterator<Item = &'a String>,
+    {
+        let mut taken: Vec<String> = bookmarks.cloned().collect();
+        let entries = sites
+            .sites
+            .into_iter()
+            .map(|site| {
+                let action = match (taken.contains(&site.name), policy) {
+                    (false, _) => ImportAction::Add,
+                    (true, ConflictPolicy::Skip) => ImportAction::Skip,
+                    (true, ConflictPolicy::Overwrite) => ImportAction::Overwrite,
+                    (true, ConflictPolicy::Rename) => {
+                        ImportAction::Rename(free_name(&site.name, &taken))
+                    }
+                };
+                match &action {
+                    ImportAction::Add => taken.push(site.name.clone()),
+                    ImportAction::Rename(name) => taken.push(name.clone()),
+                    ImportAction::Overwrite | ImportAction::Skip => {}
+                }
+                (site, action)
+            })
+            .collect();
+        Self {
+            entries,
+            unsupported: sites.unsupported,
+            with_commands: false,
+        }
+    }
+
+    /// Import the commands of the sites too (see `ImportedSite::commands`)
+    pub fn with_commands(mut self, with_commands: bool) -> Self {
+        self.with_commands = with_commands;
+        self
+    }
+
+    /// Get the amount of commands of the imported bookmarks which are dropped
+    pub fn dropped_commands(&self) -> usize {
+        match self.with_commands {
+            true => 0,
+            false => self
+                .entries
+                .iter()
+                .filter(|(_, action)| *action != ImportAction::Skip)
+                .map(|(site, _)| site.commands().len())
+                .sum(),
+        }
+    }
+
+    /// Get the amount of bookmarks which are added or replaced by the import
+    pub fn imported(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, action)| *action != ImportAction::Skip)
+            .count()
+    }
+
+    /// Get the amount of sites which are not imported
+    pub fn skipped(&self) -> usize {
+        self.entries.len() - self.imported() + self.unsupported.len()
+    }
+
+    /// Add the planned bookmarks to the bookmarks client. Bookmarks are not written to file.
+    /// The commands of the sites are dropped, unless the plan is `with_commands`
+    pub fn apply(self, client: &mut BookmarksClient) {
+        for (site, action) in self.entries {
+            let name = match action {
+                ImportAction::Add | ImportAction::Overwrite => site.name,
+                ImportAction::Rename(name) => name,
+                ImportAction::Skip => continue,
+            };
+            // NOTE: only termscp exports may contain secrets
+            client.add_bookmark(&name, site.params, true);
+            if !site.tags.is_empty() {
+                client.set_bookmark_tags(&name, site.tags);
+            }
+            if site.settings.is_some() {
+                client.set_bookmark_settings(&name, site.settings);
+            }
+            if site.hooks.is_some() && self.with_commands {
+                client.set_bookmark_hooks(&name, site.hooks);
+            }
+            let secret = match site.secret {
+                Some(SecretRef::Cmd(_)) if !self.with_commands => None,
+                secret => secret,
+            };
+            if secret.is_some() {
+                client.set_bookmark_secret(&name, secret);
+            }
+        }
+    }
+}
+
+impl fmt::Display for ImportPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (site, action) in self.entries.iter() {
+            let address = fmt_params(&site.params);
+            match action {
+                ImportAction::Add => writeln!(f, "add        {} ({address})", site.name)?,
+                ImportAction::Overwrite => writeln!(f, "overwrite  {} ({address})", site.name)?,
+                ImportAction::Rename(name) => {
+                    writeln!(f, "rename     {} -> {name} ({address})", site.name)?
+                }
+                ImportAction::Skip => writeln!(f, "skip       {} (already exists)", site.name)?,
+            }
+            if *action == ImportAction::Skip {
+                continue;
+            }
+            for command in site.commands() {
+                match self.with_commands {
+                    true => writeln!(f, "             {command}")?,
+                    false => writeln!(f, "             {command} (not imported)")?,
+                }
+            }
+        }
+        for site in self.unsupported.iter() {
+            writeln!(f, "skip       {} ({})", site.name, site.reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the sites saved by another client from `path`.
+/// The secrets of a termscp export are imported only if `passphrase` is provided
+pub fn read_sites(
+    format: ImportFormat,
+    path: &Path,
+    passphrase: Option<&str>,
+) -> ImportResult<Sites> {
+    debug!("Reading {} sites from {}", format, path.display());
+    let content = std::fs::read_to_string(path)?;
+    match format {
+        ImportFormat::FileZilla => filezilla::parse(&content),
+        ImportFormat::Rclone => rclone::parse(&content),
+        ImportFormat::SshConfig => ssh::parse(path, &content),
+        ImportFormat::Termscp => termscp::parse(&content, passphrase),
+        ImportFormat::WinScp => winscp::parse(&content),
+    }
+}
+
+/// Get the first name in the form `name (n)` which is not taken
+fn free_name(name: &str, taken: &[String]) -> String {
+    (2..)
+        .map(|n| format!("{name} ({n})"))
+        .find(|x| !taken.contains(x))
+        .unwrap()
+}
+
+/// Trim the groups of the name and remove the empty ones
+fn normalize_name(name: &str) -> String {
+    name.split('/')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+fn fmt_params(params: &FileTransferParams) -> String {
+    let protocol = params.protocol.to_string().to_lowercase();
+    match &params.params {
+        ProtocolParams::Generic(params) => match &params.username {
+            Some(username) => format!("{protocol}://{username}@{}:{}", params.address, params.port),
+            None => format!("{protocol}://{}:{}", params.address, params.port),
+        },
+        ProtocolParams::WebDAV(params) => params.uri.clone(),
+        ProtocolParams::AwsS3(params) => format!("{protocol}://{}", params.bucket_name),
+        ProtocolParams::Smb(params) => format!("{protocol}://{}/{}", params.address, params.share),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::config::bookmarks::Hook;
+    use crate::filetransfer::params::GenericProtocolParams;
+    use crate::filetransfer::FileTransferProtocol;
+
+    fn site(name: &str) -> ImportedSite {
+        ImportedSite {
+            name: name.to_string(),
+            params: FileTransferParams::new(
+                FileTransferProtocol::Sftp,
+                ProtocolParams::Generic(
+                    GenericProtocolParams::default()
+                        .address("192.168.1.31")
+                        .username(Some("pi")),
+                ),
+            ),
+            tags: Vec::new(),
+            settings: None,
+            hooks: None,
+            secret: None,
+        }
+    }
+
+    fn sites() -> Sites {
+        Sites {
+            sites: vec![site("raspberry"), site("nas"), site("nas")],
+            unsupported: vec![UnsupportedSite {
+                name: String::from("bucket"),
+                reason: String::from("unsupported protocol"),
+            }],
+        }
+    }
+
+    #[test]
+    fn should_parse_format_and_policy() {
+        assert_eq!(
+            ImportFormat::from_str("FileZilla").unwrap(),
+            ImportFormat::FileZilla
+        );
+        assert_eq!(
+            ImportFormat::from_str("ssh").unwrap(),
+            ImportFormat::SshConfig
+        );
+        assert!(ImportFormat::from_str("putty").is_err());
+        for format in ImportFormat::ALL {
+            assert_eq!(ImportFormat::from_str(&format.to_string()).unwrap(), format);
+        }
+        assert_eq!(
+            ConflictPolicy::from_str("rename").unwrap(),
+            ConflictPolicy::Rename
+        );
+        assert!(ConflictPolicy::from_str("merge").is_err());
+    }
+
+    #[test]
+    fn should_plan_import_skipping_conflicts() {
+        let bookmarks = [String::from("raspberry")];
+        let plan = ImportPlan::new(sites(), bookmarks.iter(), ConflictPolicy::Skip);
+        let actions: Vec<ImportAction> = plan.entries.iter().map(|(_, x)| x.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![ImportAction::Skip, ImportAction::Add, ImportAction::Skip]
+        );
+        assert_eq!(plan.imported(), 1);
+        assert_eq!(plan.skipped(), 3);
+    }
+
+    #[test]
+    fn should_plan_import_overwriting_conflicts() {
+        let bookmarks = [String::from("raspberry")];
+        let plan = ImportPlan::new(sites(), bookmarks.iter(), ConflictPolicy::Overwrite);
+        let actions: Vec<ImportAction> = plan.entries.iter().map(|(_, x)| x.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ImportAction::Overwrite,
+                ImportAction::Add,
+                ImportAction::Overwrite
+            ]
+        );
+        assert_eq!(plan.imported(), 3);
+    }
+
+    #[test]
+    fn should_plan_import_renaming_conflicts() {
+        let bookmarks = [String::from("raspberry"), String::from("raspberry (2)")];
+        let plan = ImportPlan::new(sites(), bookmarks.iter(), ConflictPolicy::Rename);
+        let actions: Vec<ImportAction> = plan.entries.iter().map(|(_, x)| x.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ImportAction::Rename(String::from("raspberry (3)")),
+                ImportAction::Add,
+                ImportAction::Rename(String::from("nas (2)"))
+            ]
+        );
+        assert_eq!(plan.imported(), 3);
+        assert_eq!(plan.skipped(), 1);
+    }
+
+    #[test]
+    fn should_format_plan() {
+        let bookmarks = [String::from("raspberry")];
+        let plan = ImportPlan::new(sites(), bookmarks.iter(), ConflictPolicy::Rename);
+        assert_eq!(
+            plan.to_string(),
+            r#"rename     raspberry -> raspberry (2) (sftp://pi@192.168.1.31:22)
+add        nas (sftp://pi@192.168.1.31:22)
+rename     nas -> nas (2) (sftp://pi@192.168.1.31:22)
+skip       bucket (unsupported protocol)
+"#
+        );
+    }
+
+    fn site_with_commands(name: &str) -> ImportedSite {
+        let mut site = site(name);
+        site.secret = Some(SecretRef::Cmd(String::from("op read op://office/pi")));
+        site.hooks = Some(BookmarkHooks {
+            on_connect: vec![Hook {
+                command: String::from("wg-quick up office"),
+                remote: false,
+                abort_on_failure: true,
+            }],
+            after_connect: vec![Hook {
+                command: String::from("./notify.sh connected"),
+                remote: true,
+                abort_on_failure: false,
+            }],
+            on_disconnect: Vec::new(),
+        });
+        site
+    }
+
+    #[test]
+    fn should_list_commands_in_plan() {
+        let sites = Sites {
+            sites: vec![
+                site_with_commands("office"),
+                site_with_commands("raspberry"),
+            ],
+            unsupported: Vec::new(),
+        };
+        let bookmarks = [String::from("raspberry")];
+        let plan = ImportPlan::new(sites, bookmarks.iter(), ConflictPolicy::Skip);
+        assert_eq!(plan.dropped_commands(), 3);
+        assert_eq!(
+            plan.to_string(),
+            r#"add        office (sftp://pi@192.168.1.31:22)
+             password command (local): op read op://office/pi (not imported)
+             on_connect hook (local): wg-quick up office (not imported)
+             after_connect hook (remote): ./notify.sh connected (not imported)
+skip       raspberry (already exists)
+"#
+        );
+        let plan = plan.with_commands(true);
+        assert_eq!(plan.dropped_commands(), 0);
+        assert_eq!(
+            plan.to_string(),
+            r#"add        office (sftp://pi@192.168.1.31:22)
+             password command (local): op read op://office/pi
+             on_connect hook (local): wg-quick up office
+             after_connect hook (remote): ./notify.sh connected
+skip       raspberry (already exists)
+"#
+        );
+    }
+
+    #[test]
+    fn should_import_commands_only_when_enabled() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let cfg_path = tmp_dir.path().join("bookmarks.toml");
+        let mut client = BookmarksClient::new(&cfg_path, tmp_dir.path(), 16).unwrap();
+        let sites = || Sites {
+            sites: vec![site_with_commands("office")],
+            unsupported: Vec::new(),
+        };
+        ImportPlan::new(sites(), std::iter::empty(), ConflictPolicy::Overwrite).apply(&mut client);
+        assert!(client.get_bookmark_hooks("office").is_none());
+        assert!(client.get_bookmark_secret("office").is_none());
+        ImportPlan::new(sites(), std::iter::empty(), ConflictPolicy::Overwrite)
+            .with_commands(true)
+            .apply(&mut client);
+        assert_eq!(
+            client.get_bookmark_hooks("office"),
+            site_with_commands("office").hooks.as_ref()
+        );
+        assert_eq!(
+            client.get_bookmark_secret("office"),
+            site_with_commands("office").secret.as_ref()
+        );
+    }
+
+    #[test]
+    fn should_apply_plan() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let cfg_path = tmp_dir.path().join("bookmarks.toml");
+        let mut client = BookmarksClient::new(&cfg_path, tmp_dir.path(), 16).unwrap();
+        client.add_bookmark("raspberry", site("raspberry").params, false);
+        let bookmarks: Vec<String> = client.iter_bookmarks().cloned().collect();
+        let plan = ImportPlan::new(sites(), bookmarks.iter(), ConflictPolicy::Rename);
+        plan.apply(&mut client);
+        let mut bookmarks: Vec<&String> = client.iter_bookmarks().collect();
+        bookmarks.sort();
+        assert_eq!(
+            bookmarks,
+            vec!["nas", "nas (2)", "raspberry", "raspberry (2)"]
+        );
+    }
+
+    #[test]
+    fn should_normalize_name() {
+        assert_eq!(
+            normalize_name(" servers // raspberry "),
+            "servers/raspberry"
+        );
+    }
+}
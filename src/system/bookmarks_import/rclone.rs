@@ -0,0 +1,138 @@
+//! ## Rclone
+//!
+//! Import the remotes of rclone (`rclone.conf`).
+//! Only sftp, ftp and webdav remotes can be imported, since the other ones don't describe a single host
+//! (e.g. s3 remotes don't have a bucket and smb remotes don't have a share).
+
+use ini::Ini;
+
+use super::{ImportError, ImportResult, Sites};
+use crate::filetransfer::params::{GenericProtocolParams, WebDAVProtocolParams};
+use crate::filetransfer::{FileTransferParams, FileTransferProtocol, ProtocolParams};
+
+pub fn parse(content: &str) -> ImportResult<Sites> {
+    let ini =
+        Ini::load_from_str_noescape(content).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let mut sites = Sites::default();
+    for (name, props) in ini.iter() {
+        let name = match name {
+            Some(name) => name,
+            None => continue,
+        };
+        let get = |key: &str| props.get(key).map(str::trim).filter(|x| !x.is_empty());
+        let kind = get("type").unwrap_or_default();
+        let params = match kind {
+            "sftp" | "ftp" => {
+                let address = match get("host") {
+                    Some(address) => address,
+                    None => {
+                        sites.unsupported(name, "the remote has no host");
+                        continue;
+                    }
+                };
+                let (protocol, default_port) = match kind {
+                    "sftp" => (FileTransferProtocol::Sftp, 22),
+                    _ => {
+                        let secure =
+                            get("tls") == Some("true") || get("explicit_tls") == Some("true");
+                        (FileTransferProtocol::Ftp(secure), 21)
+                    }
+                };
+                let port = get("port")
+                    .and_then(|x| x.parse::<u16>().ok())
+                    .unwrap_or(default_port);
+                FileTransferParams::new(
+                    protocol,
+                    ProtocolParams::Generic(
+                        GenericProtocolParams::default()
+                            .address(address)
+                            .port(port)
+                            .username(get("user")),
+                    ),
+                )
+            }
+            "webdav" => match get("url") {
+                Some(uri) => FileTransferParams::new(
+                    FileTransferProtocol::WebDAV,
+                    ProtocolParams::WebDAV(WebDAVProtocolParams {
+                        uri: uri.to_string(),
+                        username: get("user").unwrap_or_default().to_string(),
+                        password: String::default(),
+                    }),
+                ),
+                None => {
+                    sites.unsupported(name, "the remote has no url");
+                    continue;
+                }
+            },
+            kind => {
+                sites.unsupported(name, format!("unsupported remote type ({kind})"));
+                continue;
+            }
+        };
+        sites.add(name, params);
+    }
+    Ok(sites)
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const RCLONE_CONF: &str = r#"[raspberry]
+type = sftp
+host = 192.168.1.31
+user = pi
+pass = kB4pqEcUtDGKzhgGgGtgAq2u0lA
+
+[ftp]
+type = ftp
+host = ftp.example.com
+port = 2121
+explicit_tls = true
+
+[nextcloud]
+type = webdav
+url = https://cloud.example.com/remote.php/dav/files/omar
+vendor = nextcloud
+user = omar
+
+[bucket]
+type = s3
+provider = AWS
+region = eu-west-1
+"#;
+
+    #[test]
+    fn should_parse_rclone_conf() {
+        let sites = parse(RCLONE_CONF).unwrap();
+        assert_eq!(sites.sites.len(), 3);
+        let site = &sites.sites[0];
+        assert_eq!(site.name, "raspberry");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Sftp);
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "192.168.1.31");
+        assert_eq!(params.port, 22);
+        assert_eq!(params.username.as_deref(), Some("pi"));
+        assert!(params.password.is_none());
+        let site = &sites.sites[1];
+        assert_eq!(site.params.protocol, FileTransferProtocol::Ftp(true));
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.port, 2121);
+        assert!(params.username.is_none());
+        let site = &sites.sites[2];
+        assert_eq!(site.params.protocol, FileTransferProtocol::WebDAV);
+        let params = site.params.params.webdav_params().unwrap();
+        assert_eq!(
+            params.uri,
+            "https://cloud.example.com/remote.php/dav/files/omar"
+        );
+        assert_eq!(params.username, "omar");
+        assert_eq!(sites.unsupported.len(), 1);
+        assert_eq!(sites.unsupported[0].name, "bucket");
+        assert_eq!(sites.unsupported[0].reason, "unsupported remote type (s3)");
+    }
+}
@@ -0,0 +1,110 @@
+//! ## Ssh
+//!
+//! Import the `Host` blocks of the ssh configuration (e.g. `~/.ssh/config`) as sftp bookmarks.
+//! Patterns (e.g. `Host *.example.com`) are not imported, since they don't describe a single host.
+
+use std::path::Path;
+
+use super::{ImportError, ImportResult, Sites};
+use crate::filetransfer::params::GenericProtocolParams;
+use crate::filetransfer::{FileTransferParams, FileTransferProtocol, ProtocolParams};
+use crate::utils::ssh::parse_ssh2_config;
+
+pub fn parse(path: &Path, content: &str) -> ImportResult<Sites> {
+    let config = parse_ssh2_config(path.to_string_lossy().as_ref()).map_err(ImportError::Syntax)?;
+    let mut sites = Sites::default();
+    for alias in aliases(content) {
+        let params = config.query(&alias);
+        let address = params.host_name.unwrap_or_else(|| alias.clone());
+        sites.add(
+            &alias,
+            FileTransferParams::new(
+                FileTransferProtocol::Sftp,
+                ProtocolParams::Generic(
+                    GenericProtocolParams::default()
+                        .address(address)
+                        .port(params.port.unwrap_or(22))
+                        .username(params.user),
+                ),
+            ),
+        );
+    }
+    Ok(sites)
+}
+
+/// Get the host aliases declared by the `Host` blocks, skipping patterns and negations
+fn aliases(content: &str) -> Vec<String> {
+    let mut aliases: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let mut tokens = line.split_whitespace();
+        if !tokens
+            .next()
+            .is_some_and(|x| x.eq_ignore_ascii_case("host"))
+        {
+            continue;
+        }
+        for host in tokens.map(|x| x.trim_matches('"')) {
+            if !host.contains(['*', '?', '!']) && !aliases.iter().any(|x| x == host) {
+                aliases.push(host.to_string());
+            }
+        }
+    }
+    aliases
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::utils::test_helpers;
+
+    const SSH_CONFIG: &str = r#"
+Host *
+    ServerAliveInterval 60
+
+Host raspberry pi
+    HostName 192.168.1.31
+    User pi
+
+host nas
+    Port 2222
+
+Host *.example.com !bastion.example.com
+    User omar
+"#;
+
+    #[test]
+    fn should_parse_ssh_config() {
+        let file = test_helpers::create_sample_file_with_content(SSH_CONFIG);
+        let sites = parse(file.path(), SSH_CONFIG).unwrap();
+        assert_eq!(sites.sites.len(), 3);
+        let site = &sites.sites[0];
+        assert_eq!(site.name, "raspberry");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Sftp);
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "192.168.1.31");
+        assert_eq!(params.port, 22);
+        assert_eq!(params.username.as_deref(), Some("pi"));
+        assert_eq!(sites.sites[1].name, "pi");
+        let site = &sites.sites[2];
+        assert_eq!(site.name, "nas");
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "nas");
+        assert_eq!(params.port, 2222);
+        assert!(params.username.is_none());
+    }
+
+    #[test]
+    fn should_get_aliases() {
+        assert_eq!(
+            aliases(SSH_CONFIG),
+            vec![
+                String::from("raspberry"),
+                String::from("pi"),
+                String::from("nas")
+            ]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! ## Termscp
+//!
+//! Import the bookmarks exported by termscp with `export-bookmarks`, either as TOML or JSON.
+//! Secrets are imported only if the passphrase of the export is provided.
+
+use super::{ImportError, ImportResult, Sites};
+use crate::config::bookmarks::BookmarkPassword;
+use crate::filetransfer::FileTransferParams;
+use crate::system::bookmarks_export::{self, ExportError};
+
+pub fn parse(content: &str, passphrase: Option<&str>) -> ImportResult<Sites> {
+    let mut exported = bookmarks_export::read_export(content).map_err(|e| match e {
+        ExportError::Io(err) => ImportError::Io(err),
+        err => ImportError::Syntax(err.to_string()),
+    })?;
+    match passphrase {
+        Some(passphrase) => exported
+            .decrypt_secrets(passphrase)
+            .map_err(|_| ImportError::WrongPassphrase)?,
+        None => exported.strip_secrets(),
+    }
+    let mut bookmarks: Vec<_> = exported.bookmarks.into_iter().collect();
+    bookmarks.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut sites = Sites::default();
+    for (name, bookmark) in bookmarks {
+        let tags = bookmark.tags.clone();
+        let settings = bookmark.settings.clone();
+        let hooks = bookmark.hooks.clone();
+        let secret = bookmark
+            .password
+            .as_ref()
+            .and_then(BookmarkPassword::secret)
+            .cloned();
+        sites.add_bookmark(
+            &name,
+            FileTransferParams::from(bookmark),
+            tags,
+            settings,
+            hooks,
+            secret,
+        );
+    }
+    Ok(sites)
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::config::bookmarks::SecretRef;
+    use crate::utils::crypto;
+
+    fn export() -> String {
+        let key = crypto::derive_key("passphrase", "saltsaltsaltsalt").unwrap();
+        format!(
+            r#"salt = "saltsaltsaltsalt"
+
+[bookmarks.raspberry]
+protocol = "SFTP"
+address = "192.168.1.31"
+port = 22
+username = "pi"
+password = "{}"
+tags = ["home"]
+
+[bookmarks.raspberry.settings]
+show_hidden_files = true
+
+[bookmarks."prod/db01"]
+protocol = "SCP"
+address = "10.0.1.1"
+port = 22
+password = {{ pass = "prod/db01" }}
+"#,
+            crypto::aes256_hmac_crypt(&key, "raspberry"),
+        )
+    }
+
+    #[test]
+    fn should_parse_export_without_secrets() {
+        let sites = parse(&export(), None).unwrap();
+        assert_eq!(sites.sites.len(), 2);
+        let site = &sites.sites[0];
+        assert_eq!(site.name, "prod/db01");
+        assert!(site.tags.is_empty());
+        assert_eq!(
+            site.secret,
+            Some(SecretRef::Pass(String::from("prod/db01")))
+        );
+        let site = &sites.sites[1];
+        assert_eq!(site.name, "raspberry");
+        assert_eq!(site.tags, vec![String::from("home")]);
+        assert_eq!(
+            site.settings.as_ref().unwrap().show_hidden_files,
+            Some(true)
+        );
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "192.168.1.31");
+        assert_eq!(params.username.as_deref(), Some("pi"));
+        assert!(params.password.is_none());
+    }
+
+    #[test]
+    fn should_parse_export_with_secrets() {
+        let sites = parse(&export(), Some("passphrase")).unwrap();
+        let params = sites.sites[1].params.params.generic_params().unwrap();
+        assert_eq!(params.password.as_deref(), Some("raspberry"));
+        assert!(matches!(
+            parse(&export(), Some("wrong")),
+            Err(ImportError::WrongPassphrase)
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! ## WinSCP
+//!
+//! Import the sessions of WinSCP, stored in `WinSCP.ini` as `[Sessions\<name>]` sections.
+//! Session folders (e.g. `prod/web01`) become bookmark groups.
+
+use ini::Ini;
+
+use super::{ImportError, ImportResult, Sites};
+use crate::filetransfer::params::{GenericProtocolParams, WebDAVProtocolParams};
+use crate::filetransfer::{FileTransferParams, FileTransferProtocol, ProtocolParams};
+
+const SESSIONS: &str = "Sessions\\";
+const DEFAULT_SETTINGS: &str = "Default Settings";
+
+pub fn parse(content: &str) -> ImportResult<Sites> {
+    let ini =
+        Ini::load_from_str_noescape(content).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let mut sites = Sites::default();
+    for (section, props) in ini.iter() {
+        let name = match section.and_then(|x| x.strip_prefix(SESSIONS)) {
+            Some(name) => decode(name),
+            None => continue,
+        };
+        if name == DEFAULT_SETTINGS {
+            continue;
+        }
+        let get = |key: &str| props.get(key).map(decode).filter(|x| !x.is_empty());
+        let address = match get("HostName") {
+            Some(address) => address,
+            None => {
+                sites.unsupported(&name, "the session has no host");
+                continue;
+            }
+        };
+        let port = get("PortNumber").and_then(|x| x.parse::<u16>().ok());
+        let username = get("UserName");
+        let secure = get("Ftps").map(|x| x != "0").unwrap_or(false);
+        // WinSCP protocols: 0 SCP, 1 SFTP (with SCP fallback), 2 SFTP, 5 FTP, 6 WebDAV, 7 S3
+        let protocol = get("FSProtocol").unwrap_or_else(|| String::from("2"));
+        let params = match protocol.as_str() {
+            "0" | "1" | "2" => {
+                let protocol = match protocol.as_str() {
+                    "0" => FileTransferProtocol::Scp,
+                    _ => FileTransferProtocol::Sftp,
+                };
+                generic(protocol, address, port.unwrap_or(22), username)
+            }
+            "5" => generic(
+                FileTransferProtocol::Ftp(secure),
+                address,
+                port.unwrap_or(21),
+                username,
+            ),
+            "6" => {
+                let scheme = if secure { "https" } else { "http" };
+                let uri = match port {
+                    Some(port) => format!("{scheme}://{address}:{port}"),
+                    None => format!("{scheme}://{address}"),
+                };
+                FileTransferParams::new(
+                    FileTransferProtocol::WebDAV,
+                    ProtocolParams::WebDAV(WebDAVProtocolParams {
+                        uri,
+                        username: username.unwrap_or_default(),
+                        password: String::default(),
+                    }),
+                )
+            }
+            protocol => {
+                sites.unsupported(&name, format!("unsupported protocol ({protocol})"));
+                continue;
+            }
+        };
+        let params = params
+            .remote_path(get("RemoteDirectory"))
+            .local_path(get("LocalDirectory"));
+        sites.add(&name, params);
+    }
+    Ok(sites)
+}
+
+fn generic(
+    protocol: FileTransferProtocol,
+    address: String,
+    port: u16,
+    username: Option<String>,
+) -> FileTransferParams {
+    FileTransferParams::new(
+        protocol,
+        ProtocolParams::Generic(
+            GenericProtocolParams::default()
+                .address(address)
+                .port(port)
+                .username(username),
+        ),
+    )
+}
+
+/// Decode the percent-encoded session names and values (e.g. `My%20server`)
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = match bytes[i] {
+            b'%' => s
+                .get(i + 1..i + 3)
+                .and_then(|x| u8::from_str_radix(x, 16).ok()),
+            _ => None,
+        };
+        match byte {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const WINSCP_INI: &str = r#"[Configuration\Interface]
+RandomSeedFile=%APPDATA%\winscp.rnd
+
+[Sessions\Default%20Settings]
+HostName=default.example.com
+
+[Sessions\raspberry]
+HostName=192.168.1.31
+UserName=pi
+Password=A35C7552BF4DA0B1
+RemoteDirectory=/home/pi
+LocalDirectory=C:%5CUsers%5Comar
+
+[Sessions\prod/web%2001]
+HostName=ftp.example.com
+PortNumber=2121
+UserName=omar
+FSProtocol=5
+Ftps=3
+
+[Sessions\nextcloud]
+HostName=cloud.example.com
+UserName=omar
+FSProtocol=6
+Ftps=1
+
+[Sessions\bucket]
+HostName=s3.amazonaws.com
+FSProtocol=7
+
+[Sessions\empty]
+UserName=omar
+"#;
+
+    #[test]
+    fn should_parse_winscp_ini() {
+        let sites = parse(WINSCP_INI).unwrap();
+        assert_eq!(sites.sites.len(), 3);
+        let site = &sites.sites[0];
+        assert_eq!(site.name, "raspberry");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Sftp);
+        let params = site.params.params.generic_params().unwrap();
+        assert_eq!(params.address, "192.168.1.31");
+        assert_eq!(params.port, 22);
+        assert_eq!(params.username.as_deref(), Some("pi"));
+        assert!(params.password.is_none());
+        assert_eq!(
+            site.params.remote_path.as_deref(),
+            Some(Path::new("/home/pi"))
+        );
+        assert_eq!(
+            site.params.local_path.as_deref(),
+            Some(Path::new("C:\\Users\\omar"))
+        );
+        let site = &sites.sites[1];
+        assert_eq!(site.name, "prod/web 01");
+        assert_eq!(site.params.protocol, FileTransferProtocol::Ftp(true));
+        assert_eq!(site.params.params.generic_params().unwrap().port, 2121);
+        let site = &sites.sites[2];
+        assert_eq!(site.params.protocol, FileTransferProtocol::WebDAV);
+        let params = site.params.params.webdav_params().unwrap();
+        assert_eq!(params.uri, "https://cloud.example.com");
+        assert_eq!(params.username, "omar");
+        assert_eq!(
+            sites
+                .unsupported
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["bucket", "empty"]
+        );
+    }
+
+    #[test]
+    fn should_decode() {
+        assert_eq!(decode("My%20server"), "My server");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+    }
+}
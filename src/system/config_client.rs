@@ -10,7 +10,13 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
 
-use crate::config::params::{UserConfig, DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD};
+use crate::config::bookmarks::BookmarkSettings;
+use crate::config::custom_actions::{self, CustomAction};
+use crate::config::keybindings::{KeyAction, KeyBinding, KeyBindings};
+use crate::config::params::{
+    FileSortingConfig, UserConfig, DEFAULT_NOTIFICATION_TRANSFER_THRESHOLD,
+    DEFAULT_REMOTE_WATCH_INTERVAL,
+};
 use crate::config::serialization::{deserialize, serialize, SerializerError, SerializerErrorKind};
 use crate::explorer::GroupDirs;
 use crate::filetransfer::FileTransferProtocol;
@@ -20,10 +26,11 @@ pub type SshHost = (String, String, PathBuf); // 0: host, 1: username, 2: RSA ke
 
 /// ConfigClient provides a high level API to communicate with the termscp configuration
 pub struct ConfigClient {
-    config: UserConfig,   // Configuration loaded
-    config_path: PathBuf, // Configuration TOML Path
-    ssh_key_dir: PathBuf, // SSH Key storage directory
-    degraded: bool,       // Indicates the `ConfigClient` is working in degraded mode
+    config: UserConfig,                         // Configuration loaded
+    config_path: PathBuf,                       // Configuration TOML Path
+    ssh_key_dir: PathBuf,                       // SSH Key storage directory
+    degraded: bool, // Indicates the `ConfigClient` is working in degraded mode
+    session_settings: Option<BookmarkSettings>, // Settings overriding the configuration for the current session
 }
 
 impl ConfigClient {
@@ -42,6 +49,7 @@ impl ConfigClient {
             config_path: PathBuf::from(config_path),
             ssh_key_dir: PathBuf::from(ssh_key_dir),
             degraded: false,
+            session_settings: None,
         };
         // If ssh key directory doesn't exist, create it
         if !ssh_key_dir.exists() {
@@ -85,14 +93,30 @@ impl ConfigClient {
             config_path: PathBuf::default(),
             ssh_key_dir: PathBuf::default(),
             degraded: true,
+            session_settings: None,
         }
     }
 
+    // Session settings
+
+    /// Set the settings which override the configuration for the current session (e.g. the settings of the bookmark in use).
+    /// The overridden keys are returned by the getters, but they are never written to the configuration
+    pub fn set_session_settings(&mut self, settings: Option<BookmarkSettings>) {
+        self.session_settings = settings;
+    }
+
+    /// Get the settings overriding the configuration for the current session
+    pub fn get_session_settings(&self) -> Option<&BookmarkSettings> {
+        self.session_settings.as_ref()
+    }
+
     // Text editor
 
     /// Get text editor from configuration
     pub fn get_text_editor(&self) -> PathBuf {
-        self.config.user_interface.text_editor.clone()
+        self.get_session_settings()
+            .and_then(|x| x.text_editor.clone())
+            .unwrap_or_else(|| self.config.user_interface.text_editor.clone())
     }
 
     /// Set text editor path
@@ -117,7 +141,9 @@ impl ConfigClient {
 
     /// Get value of `show_hidden_files`
     pub fn get_show_hidden_files(&self) -> bool {
-        self.config.user_interface.show_hidden_files
+        self.get_session_settings()
+            .and_then(|x| x.show_hidden_files)
+            .unwrap_or(self.config.user_interface.show_hidden_files)
     }
 
     /// Set new value for `show_hidden_files`
@@ -137,9 +163,9 @@ impl ConfigClient {
 
     /// Get value of `prompt_on_file_replace`
     pub fn get_prompt_on_file_replace(&self) -> bool {
-        self.config
-            .user_interface
-            .prompt_on_file_replace
+        self.get_session_settings()
+            .and_then(|x| x.prompt_on_file_replace)
+            .or(self.config.user_interface.prompt_on_file_replace)
             .unwrap_or(true)
     }
 
@@ -151,7 +177,11 @@ impl ConfigClient {
     /// Get GroupDirs value from configuration (will be converted from string)
     pub fn get_group_dirs(&self) -> Option<GroupDirs> {
         // Convert string to `GroupDirs`
-        match &self.config.user_interface.group_dirs {
+        let group_dirs = self
+            .get_session_settings()
+            .and_then(|x| x.group_dirs.as_ref())
+            .or(self.config.user_interface.group_dirs.as_ref());
+        match group_dirs {
             None => None,
             Some(val) => match GroupDirs::from_str(val.as_str()) {
                 Ok(val) => Some(val),
@@ -168,7 +198,9 @@ impl ConfigClient {
 
     /// Get current file fmt for local host
     pub fn get_local_file_fmt(&self) -> Option<String> {
-        self.config.user_interface.file_fmt.clone()
+        self.get_session_settings()
+            .and_then(|x| x.file_fmt.clone())
+            .or_else(|| self.config.user_interface.file_fmt.clone())
     }
 
     /// Set file fmt parameter for local host
@@ -181,7 +213,9 @@ impl ConfigClient {
 
     /// Get current file fmt for remote host
     pub fn get_remote_file_fmt(&self) -> Option<String> {
-        self.config.user_interface.remote_file_fmt.clone()
+        self.get_session_settings()
+            .and_then(|x| x.remote_file_fmt.clone())
+            .or_else(|| self.config.user_interface.remote_file_fmt.clone())
     }
 
     /// Set file fmt parameter for remote host
@@ -192,6 +226,52 @@ impl ConfigClient {
         };
     }
 
+    /// Get file sorting for local host
+    pub fn get_local_file_sorting(&self) -> FileSortingConfig {
+        self.get_session_settings()
+            .and_then(|x| x.file_sorting.clone())
+            .or_else(|| self.config.user_interface.file_sorting.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set file sorting for local host
+    pub fn set_local_file_sorting(&mut self, sorting: FileSortingConfig) {
+        self.config.user_interface.file_sorting = Some(sorting);
+    }
+
+    /// Get file sorting for remote host
+    pub fn get_remote_file_sorting(&self) -> FileSortingConfig {
+        self.get_session_settings()
+            .and_then(|x| x.remote_file_sorting.clone())
+            .or_else(|| self.config.user_interface.remote_file_sorting.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set file sorting for remote host
+    pub fn set_remote_file_sorting(&mut self, sorting: FileSortingConfig) {
+        self.config.user_interface.remote_file_sorting = Some(sorting);
+    }
+
+    /// Save the file sorting of the local and remote explorers to the configuration.
+    /// The sorting overridden by the session settings is not saved, since it belongs to the session
+    pub fn save_file_sorting(
+        &mut self,
+        local: FileSortingConfig,
+        remote: FileSortingConfig,
+    ) -> Result<(), SerializerError> {
+        let (local_overridden, remote_overridden) = self
+            .get_session_settings()
+            .map(|x| (x.file_sorting.is_some(), x.remote_file_sorting.is_some()))
+            .unwrap_or_default();
+        if !local_overridden {
+            self.set_local_file_sorting(local);
+        }
+        if !remote_overridden {
+            self.set_remote_file_sorting(remote);
+        }
+        self.write_config()
+    }
+
     /// Get value of `notifications`
     pub fn get_notifications(&self) -> bool {
         self.config.user_interface.notifications.unwrap_or(true)
@@ -215,6 +295,84 @@ impl ConfigClient {
         self.config.user_interface.notification_threshold = Some(value);
     }
 
+    /// Get value of `mouse_support`
+    pub fn get_mouse_support(&self) -> bool {
+        self.config.user_interface.mouse_support.unwrap_or(true)
+    }
+
+    /// Set new value for `mouse_support`
+    pub fn set_mouse_support(&mut self, value: bool) {
+        self.config.user_interface.mouse_support = Some(value);
+    }
+
+    /// Get value of `vim_mode`
+    pub fn get_vim_mode(&self) -> bool {
+        self.config.user_interface.vim_mode.unwrap_or(false)
+    }
+
+    /// Set new value for `vim_mode`
+    pub fn set_vim_mode(&mut self, value: bool) {
+        self.config.user_interface.vim_mode = Some(value);
+    }
+
+    /// Get value of `remote_watch_interval`, in seconds
+    pub fn get_remote_watch_interval(&self) -> u64 {
+        self.config
+            .user_interface
+            .remote_watch_interval
+            .unwrap_or(DEFAULT_REMOTE_WATCH_INTERVAL)
+    }
+
+    /// Set new value for `remote_watch_interval`, in seconds
+    pub fn set_remote_watch_interval(&mut self, value: u64) {
+        self.config.user_interface.remote_watch_interval = Some(value);
+    }
+
+    /// Get value of `local_trash`
+    pub fn get_local_trash(&self) -> bool {
+        self.config.user_interface.local_trash.unwrap_or(true)
+    }
+
+    /// Set new value for `local_trash`
+    pub fn set_local_trash(&mut self, value: bool) {
+        self.config.user_interface.local_trash = Some(value);
+    }
+
+    /// Get value of `remote_trash`
+    pub fn get_remote_trash(&self) -> bool {
+        self.config.user_interface.remote_trash.unwrap_or(false)
+    }
+
+    /// Set new value for `remote_trash`
+    pub fn set_remote_trash(&mut self, value: bool) {
+        self.config.user_interface.remote_trash = Some(value);
+    }
+
+    /// Get key bindings
+    pub fn get_keybindings(&self) -> KeyBindings {
+        KeyBindings::new(&self.config.keybindings)
+    }
+
+    /// Get the custom actions defined by the user
+    pub fn get_custom_actions(&self) -> Vec<CustomAction> {
+        custom_actions::parse_custom_actions(&self.config.custom_actions)
+    }
+
+    /// Bind `keys` to `action`. If `keys` are the default ones, the action is removed from the configuration
+    pub fn set_keybinding(&mut self, action: KeyAction, keys: &[KeyBinding]) {
+        match keys == action.default_bindings().as_slice() {
+            true => {
+                self.config.keybindings.remove(action.name());
+            }
+            false => {
+                self.config.keybindings.insert(
+                    action.name().to_string(),
+                    keys.iter().map(|x| x.to_string()).collect(),
+                );
+            }
+        }
+    }
+
     // Remote params
 
     /// Get ssh config path
@@ -419,7 +577,7 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
-    use crate::config::params::UserConfig;
+    use crate::config::params::{CustomActionConfig, UserConfig};
     use crate::utils::random::random_alphanumeric_with_len;
 
     #[test]
@@ -613,6 +771,128 @@ mod tests {
         assert_eq!(client.get_remote_file_fmt(), None);
     }
 
+    #[test]
+    fn test_system_config_file_sorting() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_local_file_sorting(),
+            FileSortingConfig::default()
+        );
+        assert_eq!(
+            client.get_remote_file_sorting(),
+            FileSortingConfig::default()
+        );
+        let sorting = FileSortingConfig {
+            by: Some(String::from("by_size")),
+            reverse: Some(true),
+            natural: Some(false),
+            case_sensitive: None,
+        };
+        client.set_local_file_sorting(sorting.clone());
+        assert_eq!(client.get_local_file_sorting(), sorting);
+        assert_eq!(
+            client.get_remote_file_sorting(),
+            FileSortingConfig::default()
+        );
+        client.set_remote_file_sorting(sorting.clone());
+        assert_eq!(client.get_remote_file_sorting(), sorting);
+        // Sorting is persisted
+        assert!(client.write_config().is_ok());
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_local_file_sorting(), sorting);
+        assert_eq!(client.get_remote_file_sorting(), sorting);
+    }
+
+    #[test]
+    fn should_override_config_with_session_settings() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        client.set_show_hidden_files(false);
+        client.set_group_dirs(Some(GroupDirs::First));
+        client.set_local_file_fmt(String::from("{NAME}"));
+        assert!(client.get_session_settings().is_none());
+        let sorting = FileSortingConfig {
+            by: Some(String::from("by_size")),
+            ..Default::default()
+        };
+        client.set_session_settings(Some(BookmarkSettings {
+            text_editor: Some(PathBuf::from("nano")),
+            show_hidden_files: Some(true),
+            prompt_on_file_replace: Some(false),
+            group_dirs: Some(String::from("last")),
+            file_fmt: None,
+            remote_file_fmt: Some(String::from("{NAME} {SIZE}")),
+            file_sorting: None,
+            remote_file_sorting: Some(sorting.clone()),
+        }));
+        assert_eq!(client.get_text_editor(), PathBuf::from("nano"));
+        assert!(client.get_show_hidden_files());
+        assert!(!client.get_prompt_on_file_replace());
+        assert_eq!(client.get_group_dirs(), Some(GroupDirs::Last));
+        // Keys which are not overridden fallback to the configuration
+        assert_eq!(client.get_local_file_fmt().unwrap().as_str(), "{NAME}");
+        assert_eq!(
+            client.get_remote_file_fmt().unwrap().as_str(),
+            "{NAME} {SIZE}"
+        );
+        assert_eq!(
+            client.get_local_file_sorting(),
+            FileSortingConfig::default()
+        );
+        assert_eq!(client.get_remote_file_sorting(), sorting);
+        // Session settings are never written to the configuration
+        assert!(client.write_config().is_ok());
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_show_hidden_files());
+        assert_eq!(client.get_group_dirs(), Some(GroupDirs::First));
+        assert!(client.get_remote_file_fmt().is_none());
+    }
+
+    #[test]
+    fn should_not_save_file_sorting_overridden_by_session_settings() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        let bookmark_sorting = FileSortingConfig {
+            by: Some(String::from("by_size")),
+            ..Default::default()
+        };
+        client.set_session_settings(Some(BookmarkSettings {
+            remote_file_sorting: Some(bookmark_sorting.clone()),
+            ..Default::default()
+        }));
+        let local_sorting = FileSortingConfig {
+            by: Some(String::from("by_mtime")),
+            reverse: Some(true),
+            ..Default::default()
+        };
+        assert!(client
+            .save_file_sorting(local_sorting.clone(), bookmark_sorting)
+            .is_ok());
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_local_file_sorting(), local_sorting);
+        // The remote sorting of the bookmark is not saved as global default
+        assert_eq!(
+            client.get_remote_file_sorting(),
+            FileSortingConfig::default()
+        );
+    }
+
     #[test]
     fn test_system_config_notifications() {
         let tmp_dir: TempDir = TempDir::new().ok().unwrap();
@@ -644,6 +924,115 @@ mod tests {
         assert_eq!(client.get_notification_threshold(), 64);
     }
 
+    #[test]
+    fn test_system_config_mouse_support() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_mouse_support()); // Null ?
+        client.set_mouse_support(false);
+        assert!(!client.get_mouse_support());
+        client.set_mouse_support(true);
+        assert!(client.get_mouse_support());
+    }
+
+    #[test]
+    fn test_system_config_vim_mode() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_vim_mode()); // Null ?
+        client.set_vim_mode(true);
+        assert!(client.get_vim_mode());
+        client.set_vim_mode(false);
+        assert!(!client.get_vim_mode());
+    }
+
+    #[test]
+    fn test_system_config_remote_watch_interval() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_remote_watch_interval(),
+            DEFAULT_REMOTE_WATCH_INTERVAL
+        );
+        client.set_remote_watch_interval(60);
+        assert_eq!(client.get_remote_watch_interval(), 60);
+    }
+
+    #[test]
+    fn test_system_config_trash() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_local_trash()); // Null ?
+        assert!(!client.get_remote_trash()); // Null ?
+        client.set_local_trash(false);
+        assert!(!client.get_local_trash());
+        client.set_remote_trash(true);
+        assert!(client.get_remote_trash());
+    }
+
+    #[test]
+    fn test_system_config_keybindings() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_keybindings(), KeyBindings::default());
+        let keys = vec![KeyBinding::from_str("ctrl+d").unwrap()];
+        client.set_keybinding(KeyAction::Delete, &keys);
+        assert_eq!(
+            client.get_keybindings().keys(KeyAction::Delete),
+            keys.as_slice()
+        );
+        assert_eq!(
+            client.config.keybindings.get("delete").unwrap(),
+            &vec![String::from("ctrl+d")]
+        );
+        // Restore defaults
+        client.set_keybinding(KeyAction::Delete, &KeyAction::Delete.default_bindings());
+        assert!(client.config.keybindings.is_empty());
+        assert_eq!(client.get_keybindings(), KeyBindings::default());
+    }
+
+    #[test]
+    fn test_system_config_custom_actions() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_custom_actions().is_empty());
+        client.config.custom_actions = vec![
+            CustomActionConfig {
+                name: String::from("Open in VS Code"),
+                command: String::from("code {path}"),
+                scope: Some(String::from("local")),
+                ..Default::default()
+            },
+            CustomActionConfig {
+                name: String::from("Invalid"),
+                command: String::from("ls"),
+                scope: Some(String::from("nowhere")),
+                ..Default::default()
+            },
+        ];
+        let actions = client.get_custom_actions();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].name.as_str(), "Open in VS Code");
+    }
+
     #[test]
     fn should_get_and_set_ssh_config_dir() {
         let tmp_dir: TempDir = TempDir::new().ok().unwrap();
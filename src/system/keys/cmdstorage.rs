@@ -0,0 +1,112 @@
+//! ## CommandStorage
+//!
+//! `cmdstorage` provides an implementation of the `KeyStorage` trait using the output of a command
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::{KeyStorage, KeyStorageError};
+
+/// Command storage is a read-only implementation of the `KeyStorage` which reads keys from the output of a command.
+/// The storage id is the command to run, which is executed by the shell
+#[derive(Default)]
+pub struct CommandStorage;
+
+impl KeyStorage for CommandStorage {
+    /// Retrieve key running the `storage_id` command
+    fn get_key(&self, storage_id: &str) -> Result<String, KeyStorageError> {
+        run_command(&mut shell_command(storage_id), None)
+    }
+
+    /// Keys can't be written to a command
+    fn set_key(&self, _storage_id: &str, _key: &str) -> Result<(), KeyStorageError> {
+        Err(KeyStorageError::ProviderError)
+    }
+
+    /// is_supported
+    ///
+    /// Returns whether the key storage is supported on the host system
+    fn is_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Make a command which runs `cmd` with the shell of the host system
+#[cfg(windows)]
+fn shell_command(cmd: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(cmd);
+    command
+}
+
+/// Make a command which runs `cmd` with the shell of the host system
+#[cfg(not(windows))]
+fn shell_command(cmd: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(cmd);
+    command
+}
+
+/// Run `command`, writing `input` to its stdin if provided, and return its output without the trailing newline.
+/// Returns error if the command couldn't be executed or exited with a non-zero code
+pub(super) fn run_command(
+    command: &mut Command,
+    input: Option<&str>,
+) -> Result<String, KeyStorageError> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| KeyStorageError::CommandFailed(e.to_string()))?;
+    // Write input; stdin is closed when dropped
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Some(input) = input {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| KeyStorageError::CommandFailed(e.to_string()))?;
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| KeyStorageError::CommandFailed(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = match stderr.trim() {
+            "" => format!("exited with code {}", output.status.code().unwrap_or(1)),
+            stderr => stderr.to_string(),
+        };
+        return Err(KeyStorageError::CommandFailed(reason));
+    }
+    let stdout = String::from_utf8(output.stdout).map_err(|_| KeyStorageError::ProviderError)?;
+    Ok(stdout.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_system_keys_cmdstorage_ok() {
+        let storage = CommandStorage;
+        assert!(storage.is_supported());
+        assert_eq!(
+            storage
+                .get_key("echo Th15-15/My-secret")
+                .ok()
+                .unwrap()
+                .as_str(),
+            "Th15-15/My-secret"
+        );
+    }
+
+    #[test]
+    fn test_system_keys_cmdstorage_err() {
+        let storage = CommandStorage;
+        assert!(storage.get_key("exit 1").is_err());
+        assert!(storage.set_key("echo test", "test").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! ## EnvStorage
+//!
+//! `envstorage` provides an implementation of the `KeyStorage` trait using the environment variables
+
+use std::env;
+
+use super::{KeyStorage, KeyStorageError};
+
+/// Env storage is an implementation of the `KeyStorage` which reads keys from the environment variables.
+/// The storage id is the name of the variable
+#[derive(Default)]
+pub struct EnvStorage;
+
+impl KeyStorage for EnvStorage {
+    /// Retrieve key from the environment variable named `storage_id`
+    fn get_key(&self, storage_id: &str) -> Result<String, KeyStorageError> {
+        env::var(storage_id).map_err(|_| KeyStorageError::NoSuchKey)
+    }
+
+    /// Set the key into the environment of the current process
+    fn set_key(&self, storage_id: &str, key: &str) -> Result<(), KeyStorageError> {
+        env::set_var(storage_id, key);
+        Ok(())
+    }
+
+    /// is_supported
+    ///
+    /// Returns whether the key storage is supported on the host system
+    fn is_supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_system_keys_envstorage() {
+        let storage = EnvStorage;
+        assert!(storage.is_supported());
+        let var: &str = "TERMSCP_TEST_ENVSTORAGE_SECRET";
+        // Secret should not exist
+        assert!(storage.get_key(var).is_err());
+        // Write secret
+        assert!(storage.set_key(var, "Th15-15/My-Супер-Секрет").is_ok());
+        // Get secret
+        assert_eq!(
+            storage.get_key(var).ok().unwrap().as_str(),
+            "Th15-15/My-Супер-Секрет"
+        );
+    }
+}
@@ -3,9 +3,12 @@
 //! `keystorage` provides the trait to manipulate to a KeyStorage
 
 // Storages
+pub mod cmdstorage;
+pub mod envstorage;
 pub mod filestorage;
 #[cfg(feature = "with-keyring")]
 pub mod keyringstorage;
+pub mod passstorage;
 // ext
 #[cfg(feature = "with-keyring")]
 use keyring::Error as KeyringError;
@@ -21,6 +24,8 @@ pub enum KeyStorageError {
     ProviderError,
     #[error("No such key")]
     NoSuchKey,
+    #[error("Command failed: {0}")]
+    CommandFailed(String),
     #[cfg(feature = "with-keyring")]
     #[error("keyring error: {0}")]
     KeyringError(KeyringError),
@@ -71,5 +76,9 @@ mod tests {
             KeyStorageError::NoSuchKey.to_string(),
             String::from("No such key")
         );
+        assert_eq!(
+            KeyStorageError::CommandFailed(String::from("exited with code 1")).to_string(),
+            String::from("Command failed: exited with code 1")
+        );
     }
 }
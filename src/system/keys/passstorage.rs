@@ -0,0 +1,79 @@
+//! ## PassStorage
+//!
+//! `passstorage` provides an implementation of the `KeyStorage` trait using `pass` or a compatible password manager
+
+use std::process::Command;
+
+use super::cmdstorage::run_command;
+use super::{KeyStorage, KeyStorageError};
+
+/// Pass storage is an implementation of the `KeyStorage` which uses the `pass` password manager, or a password manager
+/// with a compatible command line interface (e.g. `gopass`). The storage id is the path of the secret in the store
+pub struct PassStorage {
+    program: String,
+}
+
+impl PassStorage {
+    /// Instantiates a new `PassStorage` using `program` as password manager
+    pub fn new(program: &str) -> Self {
+        PassStorage {
+            program: program.to_string(),
+        }
+    }
+}
+
+impl KeyStorage for PassStorage {
+    /// Retrieve key from the store.
+    /// As for `pass` conventions, the key is the first line of the secret
+    fn get_key(&self, storage_id: &str) -> Result<String, KeyStorageError> {
+        let secret = run_command(
+            Command::new(self.program.as_str())
+                .arg("show")
+                .arg(storage_id),
+            None,
+        )?;
+        secret
+            .lines()
+            .next()
+            .map(str::to_string)
+            .ok_or(KeyStorageError::NoSuchKey)
+    }
+
+    /// Set the key into the store, replacing the existing secret
+    fn set_key(&self, storage_id: &str, key: &str) -> Result<(), KeyStorageError> {
+        run_command(
+            Command::new(self.program.as_str())
+                .arg("insert")
+                .arg("-m")
+                .arg("-f")
+                .arg(storage_id),
+            Some(key),
+        )
+        .map(|_| ())
+    }
+
+    /// is_supported
+    ///
+    /// Returns whether the key storage is supported on the host system
+    fn is_supported(&self) -> bool {
+        Command::new(self.program.as_str())
+            .arg("version")
+            .output()
+            .map(|x| x.status.success())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_system_keys_passstorage_not_supported() {
+        let storage: PassStorage = PassStorage::new("termscp-test-no-such-program");
+        assert!(!storage.is_supported());
+        assert!(storage.get_key("prod/db01").is_err());
+        assert!(storage.set_key("prod/db01", "secret").is_err());
+    }
+}
@@ -5,11 +5,14 @@
 // modules
 pub mod auto_update;
 pub mod bookmarks_client;
+pub mod bookmarks_export;
+pub mod bookmarks_import;
 pub mod config_client;
 pub mod environment;
 mod keys;
 pub mod logging;
 pub mod notifications;
+pub mod secrets;
 pub mod sshkey_storage;
 pub mod theme_provider;
 pub mod watcher;
@@ -0,0 +1,54 @@
+//! ## Secrets
+//!
+//! `secrets` resolves the secrets referenced by bookmarks through the secret backends
+
+use super::keys::cmdstorage::CommandStorage;
+use super::keys::envstorage::EnvStorage;
+use super::keys::passstorage::PassStorage;
+use super::keys::KeyStorage;
+use crate::config::bookmarks::SecretRef;
+
+/// Resolve the secret referenced by `secret` from its backend
+pub fn resolve(secret: &SecretRef) -> Result<String, String> {
+    let (storage, storage_id): (Box<dyn KeyStorage>, &str) = match secret {
+        SecretRef::Cmd(cmd) => (Box::<CommandStorage>::default(), cmd),
+        SecretRef::Env(name) => (Box::<EnvStorage>::default(), name),
+        SecretRef::Pass(path) => (Box::new(PassStorage::new("pass")), path),
+        SecretRef::Gopass(path) => (Box::new(PassStorage::new("gopass")), path),
+    };
+    debug!("Resolving secret from {}", secret);
+    storage
+        .get_key(storage_id)
+        .map_err(|e| format!("Could not resolve secret from {secret}: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_resolve_secrets() {
+        std::env::set_var("TERMSCP_TEST_SECRETS_RESOLVE", "mypassword");
+        assert_eq!(
+            resolve(&SecretRef::Env(String::from(
+                "TERMSCP_TEST_SECRETS_RESOLVE"
+            )))
+            .ok()
+            .unwrap()
+            .as_str(),
+            "mypassword"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            resolve(&SecretRef::Cmd(String::from("echo mypassword")))
+                .ok()
+                .unwrap()
+                .as_str(),
+            "mypassword"
+        );
+        assert!(resolve(&SecretRef::Env(String::from("TERMSCP_TEST_SECRETS_NONE"))).is_err());
+    }
+}
@@ -58,6 +58,17 @@ impl FsChange {
             remote_synched_path,
         ))
     }
+
+    /// Instantiate a new `FsChange::Update` for a file which has been created on the local fs
+    pub fn create(
+        created_path: PathBuf,
+        local_watched_path: &Path,
+        remote_synched_path: &Path,
+    ) -> Self {
+        let mut update = FileUpdate::new(created_path, local_watched_path, remote_synched_path);
+        update.created = true;
+        Self::Update(update)
+    }
 }
 
 /// Describes a file to rename on the remote fs
@@ -67,6 +78,8 @@ pub struct FileToRename {
     source: PathBuf,
     /// new filename
     destination: PathBuf,
+    /// Path to the destination on the local fs
+    local_destination: PathBuf,
 }
 
 impl FileToRename {
@@ -91,6 +104,7 @@ impl FileToRename {
                 local_watched_path,
                 remote_synched_path,
             ),
+            local_destination: destination,
         }
     }
 
@@ -103,6 +117,11 @@ impl FileToRename {
     pub fn destination(&self) -> &Path {
         self.destination.as_path()
     }
+
+    /// Get path to the destination on the local fs
+    pub fn local_destination(&self) -> &Path {
+        self.local_destination.as_path()
+    }
 }
 
 /// Describes a file to remove on remote fs
@@ -139,6 +158,8 @@ pub struct FileUpdate {
     local: PathBuf,
     /// Path to remote file to update
     remote: PathBuf,
+    /// Whether the file has been created on the local fs
+    created: bool,
 }
 
 impl FileUpdate {
@@ -153,6 +174,7 @@ impl FileUpdate {
         Self {
             remote: remote_relative_path(&changed_path, local_watched_path, remote_synched_path),
             local: changed_path,
+            created: false,
         }
     }
 
@@ -165,6 +187,36 @@ impl FileUpdate {
     pub fn remote(&self) -> &Path {
         self.remote.as_path()
     }
+
+    /// Returns whether the file has been created on the local fs, hence it may not exist on the remote yet
+    pub fn created(&self) -> bool {
+        self.created
+    }
+
+    /// Set whether the file has been created on the local fs
+    pub(super) fn with_created(mut self, created: bool) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Get the update of this file once it has been moved by `mov`, which renames the file or one of its ancestors
+    pub(super) fn moved(self, mov: &FileToRename) -> Self {
+        let (local, remote) = match self.remote.strip_prefix(mov.source()) {
+            Ok(relative) if !relative.as_os_str().is_empty() => (
+                mov.local_destination().join(relative),
+                mov.destination().join(relative),
+            ),
+            _ => (
+                mov.local_destination().to_path_buf(),
+                mov.destination().to_path_buf(),
+            ),
+        };
+        Self {
+            local,
+            remote,
+            created: self.created,
+        }
+    }
 }
 
 // -- utils
@@ -231,6 +283,7 @@ mod test {
         if let FsChange::Move(change) = change {
             assert_eq!(change.source(), Path::new("/home/foo/foo.txt"));
             assert_eq!(change.destination(), Path::new("/home/foo/bar.txt"));
+            assert_eq!(change.local_destination(), Path::new("/tmp/bar.txt"));
         } else {
             panic!("not a Move");
         }
@@ -305,6 +358,23 @@ mod test {
         if let FsChange::Update(change) = change {
             assert_eq!(change.local(), Path::new("/tmp/abc/foo.txt"),);
             assert_eq!(change.remote(), Path::new("/home/foo/temp/abc/foo.txt"));
+            assert!(!change.created());
+        } else {
+            panic!("not an update");
+        }
+    }
+
+    #[test]
+    fn should_make_fs_change_create() {
+        let change = FsChange::create(
+            PathBuf::from("/tmp/abc/foo.txt"),
+            Path::new("/tmp"),
+            Path::new("/home/foo/temp"),
+        );
+        if let FsChange::Update(change) = change {
+            assert_eq!(change.local(), Path::new("/tmp/abc/foo.txt"),);
+            assert_eq!(change.remote(), Path::new("/home/foo/temp/abc/foo.txt"));
+            assert!(change.created());
         } else {
             panic!("not an update");
         }
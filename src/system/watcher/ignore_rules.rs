@@ -0,0 +1,127 @@
+//! ## Ignore rules
+//!
+//! this module exposes the rules which tell whether a change to a watched path must not be synced
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Files in the root of the watched directory which contain ignore rules, with the `.gitignore` syntax
+const IGNORE_FILES: &[&str] = &[".gitignore", ".termscpignore"];
+
+/// Patterns which are always ignored
+const BUILTIN_PATTERNS: &[&str] = &[".git/"];
+
+/// Rules which tell whether a change to a file under a watched path must be ignored.
+/// Rules are read from the ignore files in the root of the watched directory, plus the patterns of the watch
+pub struct IgnoreRules {
+    root: PathBuf,
+    matcher: Gitignore,
+    patterns: Vec<String>,
+}
+
+impl IgnoreRules {
+    /// Build the ignore rules for the watched `local` path, with `patterns` in the `.gitignore` syntax
+    pub fn new(local: &Path, patterns: &[String]) -> Self {
+        let root = match local.is_dir() {
+            true => local.to_path_buf(),
+            false => local.parent().unwrap_or(local).to_path_buf(),
+        };
+        let mut builder = GitignoreBuilder::new(root.as_path());
+        for file in IGNORE_FILES {
+            let path = root.join(file);
+            if path.exists() {
+                if let Some(err) = builder.add(path.as_path()) {
+                    warn!("some rules in {} are invalid: {}", path.display(), err);
+                }
+            }
+        }
+        for pattern in BUILTIN_PATTERNS
+            .iter()
+            .copied()
+            .chain(patterns.iter().map(String::as_str))
+        {
+            if let Err(err) = builder.add_line(None, pattern) {
+                warn!("invalid ignore pattern {}: {}", pattern, err);
+            }
+        }
+        let matcher = builder.build().unwrap_or_else(|err| {
+            error!(
+                "failed to build ignore rules for {}: {}",
+                root.display(),
+                err
+            );
+            Gitignore::empty()
+        });
+        Self {
+            root,
+            matcher,
+            patterns: patterns.to_vec(),
+        }
+    }
+
+    /// Returns whether changes to `path` must be ignored
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        // NOTE: the matcher panics with paths outside of the root
+        if !path.starts_with(self.root.as_path()) || path == self.root.as_path() {
+            return false;
+        }
+        self.matcher
+            .matched_path_or_any_parents(path, path.is_dir())
+            .is_ignore()
+    }
+
+    /// Get the patterns of the watch
+    pub fn patterns(&self) -> &[String] {
+        self.patterns.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_ignore_builtin_patterns() {
+        let tempdir = TempDir::new().unwrap();
+        let rules = IgnoreRules::new(tempdir.path(), &[]);
+        assert!(rules.patterns().is_empty());
+        assert!(rules.is_ignored(&tempdir.path().join(".git/objects/ab/cdef")));
+        assert!(!rules.is_ignored(&tempdir.path().join("src/main.rs")));
+        assert!(!rules.is_ignored(Path::new("/outside/.git/HEAD")));
+    }
+
+    #[test]
+    fn should_ignore_patterns() {
+        let tempdir = TempDir::new().unwrap();
+        let patterns = vec![String::from("*.swp"), String::from("node_modules/")];
+        let rules = IgnoreRules::new(tempdir.path(), &patterns);
+        assert_eq!(rules.patterns(), patterns.as_slice());
+        assert!(rules.is_ignored(&tempdir.path().join("src/.main.rs.swp")));
+        assert!(rules.is_ignored(&tempdir.path().join("node_modules/foo/index.js")));
+        assert!(!rules.is_ignored(&tempdir.path().join("src/main.rs")));
+    }
+
+    #[test]
+    fn should_ignore_files_in_ignore_files() {
+        let tempdir = TempDir::new().unwrap();
+        fs::write(
+            tempdir.path().join(".gitignore"),
+            "target/\n*.log\n!keep.log\n",
+        )
+        .unwrap();
+        fs::write(tempdir.path().join(".termscpignore"), "secrets.env\n").unwrap();
+        let rules = IgnoreRules::new(tempdir.path(), &[]);
+        assert!(rules.is_ignored(&tempdir.path().join("target/debug/termscp")));
+        assert!(rules.is_ignored(&tempdir.path().join("logs/debug.log")));
+        assert!(!rules.is_ignored(&tempdir.path().join("logs/keep.log")));
+        assert!(rules.is_ignored(&tempdir.path().join("secrets.env")));
+        assert!(!rules.is_ignored(&tempdir.path().join(".gitignore")));
+    }
+}
@@ -3,17 +3,24 @@
 //! A watcher for file system paths, which reports changes on local fs
 
 mod change;
+mod ignore_rules;
+mod queue;
+mod remote;
 
 // -- export
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
-use std::time::Duration;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Duration, UNIX_EPOCH};
 
 pub use change::FsChange;
+use ignore_rules::IgnoreRules;
 use notify::{
     watcher, DebouncedEvent, Error as WatcherError, RecommendedWatcher, RecursiveMode, Watcher,
 };
+pub use queue::SyncQueue;
+pub use remote::RemoteWatcher;
+use remotefs::fs::Metadata;
 use thiserror::Error;
 
 use crate::utils::path as path_utils;
@@ -40,6 +47,7 @@ impl From<WatcherError> for FsWatcherError {
 /// File system watcher
 pub struct FsWatcher {
     paths: HashMap<PathBuf, PathBuf>,
+    ignore_rules: HashMap<PathBuf, IgnoreRules>,
     receiver: Receiver<DebouncedEvent>,
     watcher: RecommendedWatcher,
 }
@@ -51,40 +59,40 @@ impl FsWatcher {
 
         Ok(Self {
             paths: HashMap::default(),
+            ignore_rules: HashMap::default(),
             receiver,
             watcher: watcher(tx, delay)?,
         })
     }
 
-    /// Poll searching for the first available disk change
-    pub fn poll(&self) -> FsWatcherResult<Option<FsChange>> {
-        match self.receiver.recv_timeout(Duration::from_millis(1)) {
-            Ok(DebouncedEvent::Rename(source, dest)) => Ok(self.build_fs_move(source, dest)),
-            Ok(DebouncedEvent::Remove(p)) => Ok(self.build_fs_remove(p)),
-            Ok(DebouncedEvent::Chmod(p) | DebouncedEvent::Create(p) | DebouncedEvent::Write(p)) => {
-                Ok(self.build_fs_update(p))
-            }
-            Ok(
-                DebouncedEvent::Rescan
-                | DebouncedEvent::NoticeRemove(_)
-                | DebouncedEvent::NoticeWrite(_),
-            ) => Ok(None),
-            Ok(DebouncedEvent::Error(e, _)) => {
-                error!("FsWatcher reported error: {}", e);
-                Err(e.into())
+    /// Poll all the available disk changes, without waiting
+    pub fn poll_all(&self) -> Vec<FsWatcherResult<FsChange>> {
+        let mut changes = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => {
+                    if let Some(change) = self.build_fs_change(event).transpose() {
+                        changes.push(change);
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => panic!("File watcher died"),
             }
-            Err(RecvTimeoutError::Timeout) => Ok(None),
-            Err(RecvTimeoutError::Disconnected) => panic!("File watcher died"),
         }
+        changes
     }
 
-    /// Watch `local` path on localhost
-    pub fn watch(&mut self, local: &Path, remote: &Path) -> FsWatcherResult<()> {
+    /// Watch `local` path on localhost.
+    /// Changes to files matching the `ignore` patterns or the rules in the `.gitignore` and `.termscpignore` files
+    /// of `local` are not reported
+    pub fn watch(&mut self, local: &Path, remote: &Path, ignore: &[String]) -> FsWatcherResult<()> {
         // Start watcher if unwatched
         if !self.watched(local) {
             self.watcher.watch(local, RecursiveMode::Recursive)?;
             // Insert new path to paths
             self.paths.insert(local.to_path_buf(), remote.to_path_buf());
+            self.ignore_rules
+                .insert(local.to_path_buf(), IgnoreRules::new(local, ignore));
             Ok(())
         } else {
             Err(FsWatcherError::PathAlreadyWatched)
@@ -105,6 +113,25 @@ impl FsWatcher {
         Vec::from_iter(self.paths.keys().map(|x| x.as_path()))
     }
 
+    /// Returns the remote path synched with the watched path `path`
+    pub fn remote_path(&self, path: &Path) -> Option<&Path> {
+        self.paths.get(path).map(|x| x.as_path())
+    }
+
+    /// Returns the ignore patterns of the watched path `path`
+    pub fn ignore_patterns(&self, path: &Path) -> Option<&[String]> {
+        self.ignore_rules.get(path).map(|x| x.patterns())
+    }
+
+    /// Returns whether changes to `path` are ignored by the watched path which is its ancestor.
+    /// Paths which are not watched are never ignored
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        match self.find_watched_path(path) {
+            Some((watched_local, _)) => self.ignored(watched_local, path),
+            None => false,
+        }
+    }
+
     /// Unwatch provided path.
     /// When unwatching the path, it searches for the ancestor watched path if any.
     /// Returns the unwatched resolved path
@@ -113,6 +140,7 @@ impl FsWatcher {
         if let Some(watched_path) = watched_path {
             self.watcher.unwatch(watched_path.as_path())?;
             self.paths.remove(watched_path.as_path());
+            self.ignore_rules.remove(watched_path.as_path());
             Ok(watched_path)
         } else {
             Err(FsWatcherError::PathNotWatched)
@@ -128,23 +156,65 @@ impl FsWatcher {
             .map(|(k, v)| (k.as_path(), v.as_path()))
     }
 
-    /// Build `FsChange` from path to local `changed_file`
+    /// Returns whether changes to `path` under the watched path `watched_local` must be ignored
+    fn ignored(&self, watched_local: &Path, path: &Path) -> bool {
+        let ignored = self
+            .ignore_rules
+            .get(watched_local)
+            .map(|x| x.is_ignored(path))
+            .unwrap_or(false);
+        if ignored {
+            debug!("ignoring change to {}", path.display());
+        }
+        ignored
+    }
+
+    /// Build `FsChange` from the event reported by the watcher
+    fn build_fs_change(&self, event: DebouncedEvent) -> FsWatcherResult<Option<FsChange>> {
+        match event {
+            DebouncedEvent::Rename(source, dest) => Ok(self.build_fs_move(source, dest)),
+            DebouncedEvent::Remove(p) => Ok(self.build_fs_remove(p)),
+            DebouncedEvent::Create(p) => Ok(self.build_fs_update(p, true)),
+            DebouncedEvent::Chmod(p) | DebouncedEvent::Write(p) => {
+                Ok(self.build_fs_update(p, false))
+            }
+            DebouncedEvent::Rescan
+            | DebouncedEvent::NoticeRemove(_)
+            | DebouncedEvent::NoticeWrite(_) => Ok(None),
+            DebouncedEvent::Error(e, _) => {
+                error!("FsWatcher reported error: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Build `FsChange` from path to local `changed_file`.
+    /// If only one of `source` and `destination` is ignored, the move is reported as an update of the destination
+    /// or as a removal of the source
     fn build_fs_move(&self, source: PathBuf, destination: PathBuf) -> Option<FsChange> {
-        if let Some((watched_local, watched_remote)) = self.find_watched_path(&source) {
-            Some(FsChange::mov(
+        let (watched_local, watched_remote) = self.find_watched_path(&source)?;
+        match (
+            self.ignored(watched_local, &source),
+            self.ignored(watched_local, &destination),
+        ) {
+            (true, true) => None,
+            (true, false) => Some(FsChange::update(destination, watched_local, watched_remote)),
+            (false, true) => Some(FsChange::remove(source, watched_local, watched_remote)),
+            (false, false) => Some(FsChange::mov(
                 source,
                 destination,
                 watched_local,
                 watched_remote,
-            ))
-        } else {
-            None
+            )),
         }
     }
 
     /// Build `FsChange` from path to local `changed_file`
     fn build_fs_remove(&self, removed_path: PathBuf) -> Option<FsChange> {
         if let Some((watched_local, watched_remote)) = self.find_watched_path(&removed_path) {
+            if self.ignored(watched_local, &removed_path) {
+                return None;
+            }
             Some(FsChange::remove(
                 removed_path,
                 watched_local,
@@ -155,23 +225,45 @@ impl FsWatcher {
         }
     }
 
-    /// Build `FsChange` from path to local `changed_file`
-    fn build_fs_update(&self, changed_file: PathBuf) -> Option<FsChange> {
+    /// Build `FsChange` from path to local `changed_file`; `created` tells whether the file is new
+    fn build_fs_update(&self, changed_file: PathBuf, created: bool) -> Option<FsChange> {
         if let Some((watched_local, watched_remote)) = self.find_watched_path(&changed_file) {
-            Some(FsChange::update(
-                changed_file,
-                watched_local,
-                watched_remote,
-            ))
+            if self.ignored(watched_local, &changed_file) {
+                return None;
+            }
+            Some(match created {
+                true => FsChange::create(changed_file, watched_local, watched_remote),
+                false => FsChange::update(changed_file, watched_local, watched_remote),
+            })
         } else {
             None
         }
     }
 }
 
+/// Returns whether the remote copy described by `remote` is outdated compared to the local file described by `local`.
+/// The file is outdated if sizes differ or if the local file has been modified after the remote one.
+/// Modification times are compared in seconds, since many remote file systems don't store a higher precision
+pub fn is_outdated(local: &Metadata, remote: &Metadata) -> bool {
+    let secs = |m: &Metadata| {
+        m.modified
+            .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+            .map(|x| x.as_secs())
+    };
+    if local.size != remote.size {
+        return true;
+    }
+    match (secs(local), secs(remote)) {
+        (Some(local), Some(remote)) => local > remote,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod test {
 
+    use std::time::SystemTime;
+
     use pretty_assertions::assert_eq;
     use tempfile::TempDir;
 
@@ -190,13 +282,17 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // check if in paths
         assert_eq!(
             watcher.paths.get(tempdir.path()).unwrap(),
             Path::new("/tmp/test")
         );
+        assert_eq!(
+            watcher.remote_path(tempdir.path()).unwrap(),
+            Path::new("/tmp/test")
+        );
         // close tempdir
         assert!(tempdir.close().is_ok());
     }
@@ -206,14 +302,14 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // watch subdir
         let mut subdir = tempdir.path().to_path_buf();
         subdir.push("abc/def");
         // should return already watched
         assert!(watcher
-            .watch(subdir.as_path(), Path::new("/tmp/test/abc/def"))
+            .watch(subdir.as_path(), Path::new("/tmp/test/abc/def"), &[])
             .is_err());
         // close tempdir
         assert!(tempdir.close().is_ok());
@@ -224,7 +320,7 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // unwatch
         assert!(watcher.unwatch(tempdir.path()).is_ok());
@@ -238,7 +334,7 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // unwatch
         let mut subdir = tempdir.path().to_path_buf();
@@ -252,6 +348,110 @@ mod test {
         assert!(tempdir.close().is_ok());
     }
 
+    #[test]
+    fn should_ignore_changes_matching_ignore_rules() {
+        let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
+        let tempdir = TempDir::new().unwrap();
+        assert!(watcher
+            .watch(
+                tempdir.path(),
+                Path::new("/tmp/test"),
+                &[String::from("*.swp")]
+            )
+            .is_ok());
+        assert_eq!(
+            watcher.ignore_patterns(tempdir.path()).unwrap(),
+            &[String::from("*.swp")]
+        );
+        let path = |name: &str| tempdir.path().join(name);
+        assert!(watcher
+            .build_fs_update(path(".main.rs.swp"), false)
+            .is_none());
+        assert!(watcher.build_fs_update(path(".git/index"), false).is_none());
+        assert!(watcher.build_fs_remove(path(".main.rs.swp")).is_none());
+        assert!(watcher.build_fs_update(path("main.rs"), false).is_some());
+        // moves
+        assert!(watcher
+            .build_fs_move(path(".main.rs.swp"), path(".git/main.rs"))
+            .is_none());
+        assert!(matches!(
+            watcher.build_fs_move(path(".main.rs.swp"), path("main.rs")),
+            Some(FsChange::Update(_))
+        ));
+        assert!(matches!(
+            watcher.build_fs_move(path("main.rs"), path(".main.rs.swp")),
+            Some(FsChange::Remove(_))
+        ));
+        assert!(matches!(
+            watcher.build_fs_move(path("main.rs"), path("lib.rs")),
+            Some(FsChange::Move(_))
+        ));
+        // rules are removed on unwatch
+        assert!(watcher.unwatch(tempdir.path()).is_ok());
+        assert!(watcher.ignore_patterns(tempdir.path()).is_none());
+        // close tempdir
+        assert!(tempdir.close().is_ok());
+    }
+
+    #[test]
+    fn should_tell_whether_path_is_ignored() {
+        let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
+        let tempdir = TempDir::new().unwrap();
+        assert!(watcher
+            .watch(
+                tempdir.path(),
+                Path::new("/tmp/test"),
+                &[String::from("*.swp")]
+            )
+            .is_ok());
+        assert!(watcher.is_ignored(&tempdir.path().join("main.rs.swp")));
+        assert!(watcher.is_ignored(&tempdir.path().join(".git/HEAD")));
+        assert!(!watcher.is_ignored(&tempdir.path().join("main.rs")));
+        // Unwatched paths are never ignored
+        assert!(!watcher.is_ignored(Path::new("/unwatched/main.rs.swp")));
+        // close tempdir
+        assert!(tempdir.close().is_ok());
+    }
+
+    #[test]
+    fn should_tell_whether_remote_file_is_outdated() {
+        let now = SystemTime::now();
+        let metadata = |size: u64, modified: Option<SystemTime>| Metadata {
+            size,
+            modified,
+            ..Default::default()
+        };
+        // Same file
+        assert!(!is_outdated(
+            &metadata(64, Some(now)),
+            &metadata(64, Some(now))
+        ));
+        // Size changed
+        assert!(is_outdated(
+            &metadata(128, Some(now)),
+            &metadata(64, Some(now))
+        ));
+        // Modified locally after upload
+        assert!(is_outdated(
+            &metadata(64, Some(now + Duration::from_secs(60))),
+            &metadata(64, Some(now))
+        ));
+        // Remote file is newer
+        assert!(!is_outdated(
+            &metadata(64, Some(now)),
+            &metadata(64, Some(now + Duration::from_secs(60)))
+        ));
+        // Sub-second differences are not considered
+        let secs = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let truncated = UNIX_EPOCH + Duration::from_secs(secs);
+        assert!(!is_outdated(
+            &metadata(64, Some(truncated + Duration::from_millis(500))),
+            &metadata(64, Some(truncated))
+        ));
+        // Unknown modification time
+        assert!(!is_outdated(&metadata(64, None), &metadata(64, Some(now))));
+    }
+
     #[test]
     fn should_return_err_when_unwatching_unwatched_path() {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
@@ -263,7 +463,7 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
         assert_eq!(watcher.watched(tempdir.path()), true);
         let mut subdir = tempdir.path().to_path_buf();
@@ -281,7 +481,7 @@ mod test {
         let tempdir = TempDir::new().unwrap();
         let tempdir_path = PathBuf::from(format!("/private{}", tempdir.path().display()));
         assert!(watcher
-            .watch(tempdir_path.as_path(), Path::new("/tmp/test"))
+            .watch(tempdir_path.as_path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // create file
         let file_path = test_helpers::make_file_at(tempdir_path.as_path(), "test.txt").unwrap();
@@ -289,8 +489,11 @@ mod test {
         std::thread::sleep(Duration::from_millis(500));
         // wait till update
         loop {
-            let fs_change = watcher.poll().unwrap();
-            if let Some(FsChange::Update(_)) = fs_change {
+            let fs_changes = watcher.poll_all();
+            if fs_changes
+                .into_iter()
+                .any(|x| matches!(x.unwrap(), FsChange::Update(_)))
+            {
                 break;
             }
             std::thread::sleep(Duration::from_millis(500));
@@ -307,7 +510,7 @@ mod test {
         let tempdir = TempDir::new().unwrap();
         let tempdir_path = PathBuf::from(format!("/private{}", tempdir.path().display()));
         assert!(watcher
-            .watch(tempdir_path.as_path(), Path::new("/tmp/test"))
+            .watch(tempdir_path.as_path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // create file
         let file_path = test_helpers::make_file_at(tempdir_path.as_path(), "test.txt").unwrap();
@@ -316,8 +519,14 @@ mod test {
         assert!(std::fs::remove_file(file_path.as_path()).is_ok());
         // poll till remove
         loop {
-            let fs_change = watcher.poll().unwrap();
-            if let Some(FsChange::Remove(remove)) = fs_change {
+            let fs_change = watcher
+                .poll_all()
+                .into_iter()
+                .find_map(|x| match x.unwrap() {
+                    FsChange::Remove(remove) => Some(remove),
+                    _ => None,
+                });
+            if let Some(remove) = fs_change {
                 assert_eq!(remove.path(), Path::new("/tmp/test/test.txt"));
                 break;
             }
@@ -335,7 +544,7 @@ mod test {
         let tempdir = TempDir::new().unwrap();
         let tempdir_path = PathBuf::from(format!("/private{}", tempdir.path().display()));
         assert!(watcher
-            .watch(tempdir_path.as_path(), Path::new("/tmp/test"))
+            .watch(tempdir_path.as_path(), Path::new("/tmp/test"), &[])
             .is_ok());
         // create file
         let file_path = test_helpers::make_file_at(tempdir_path.as_path(), "test.txt").unwrap();
@@ -348,8 +557,11 @@ mod test {
         std::thread::sleep(Duration::from_millis(500));
         // wait till rename
         loop {
-            let fs_change = watcher.poll().unwrap();
-            if let Some(FsChange::Move(mov)) = fs_change {
+            let fs_change = watcher.poll_all().into_iter().find_map(|x| match x.unwrap() {
+                FsChange::Move(mov) => Some(mov),
+                _ => None,
+            });
+            if let Some(mov) = fs_change {
                 assert_eq!(mov.source(), Path::new("/tmp/test/test.txt"));
                 assert_eq!(mov.destination(), Path::new("/tmp/test/new.txt"));
                 break;
@@ -369,9 +581,9 @@ mod test {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
         let tempdir = TempDir::new().unwrap();
         assert!(watcher
-            .watch(tempdir.path(), Path::new("/tmp/test"))
+            .watch(tempdir.path(), Path::new("/tmp/test"), &[])
             .is_ok());
-        assert!(watcher.poll().ok().unwrap().is_none());
+        assert!(watcher.poll_all().is_empty());
         // close tempdir
         assert!(tempdir.close().is_ok());
     }
@@ -380,9 +592,11 @@ mod test {
     #[cfg(target_os = "macos")]
     fn should_get_watched_paths() {
         let mut watcher = FsWatcher::init(Duration::from_secs(5)).unwrap();
-        assert!(watcher.watch(Path::new("/tmp"), Path::new("/tmp")).is_ok());
         assert!(watcher
-            .watch(Path::new("/home"), Path::new("/home"))
+            .watch(Path::new("/tmp"), Path::new("/tmp"), &[])
+            .is_ok());
+        assert!(watcher
+            .watch(Path::new("/home"), Path::new("/home"), &[])
             .is_ok());
         let mut watched_paths = watcher.watched_paths();
         watched_paths.sort();
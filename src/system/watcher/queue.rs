@@ -0,0 +1,394 @@
+//! ## Sync queue
+//!
+//! The queue of the changes to sync on the remote file system, which coalesces the changes reported by the watcher
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::change::{FileToRemove, FileToRename, FileUpdate};
+use super::FsChange;
+use crate::utils::path as path_utils;
+
+/// Number of times a change is synched before giving up
+const MAX_ATTEMPTS: usize = 3;
+/// Delay before retrying a change which failed to sync, multiplied by the number of attempts
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Maximum time a change waits to be synched, as a multiple of the coalescing window, even if changes keep coming
+const MAX_LATENCY_WINDOWS: u32 = 5;
+
+/// A change waiting to be synched with the remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingChange {
+    change: FsChange,
+    /// Number of failed attempts
+    attempts: usize,
+    /// The change can't be synched before this instant
+    retry_at: Option<Instant>,
+}
+
+impl PendingChange {
+    fn new(change: FsChange) -> Self {
+        Self {
+            change,
+            attempts: 0,
+            retry_at: None,
+        }
+    }
+
+    /// Get the change to sync
+    pub fn change(&self) -> &FsChange {
+        &self.change
+    }
+
+    /// Get the number of failed attempts to sync the change
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+}
+
+/// Queue of the changes to sync with the remote.
+///
+/// Changes are synched only once no new change has been pushed for the coalescing window, so that a burst of changes
+/// (e.g. a `git checkout`) is reduced to the minimal set of operations: changes to the same path are deduplicated,
+/// files created and removed within the window are never uploaded and new files which have been moved are uploaded
+/// at their destination.
+/// Changes which keep coming don't delay the sync forever though: after `MAX_LATENCY_WINDOWS` windows from the first
+/// change which hasn't been synched, the changes are synched anyway.
+pub struct SyncQueue {
+    changes: VecDeque<PendingChange>,
+    window: Duration,
+    /// When the first change since the queue was last empty has been pushed
+    first_push: Option<Instant>,
+    last_push: Option<Instant>,
+    paused: bool,
+}
+
+impl SyncQueue {
+    /// Instantiate a new `SyncQueue`, coalescing changes pushed within `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            changes: VecDeque::new(),
+            window,
+            first_push: None,
+            last_push: None,
+            paused: false,
+        }
+    }
+
+    /// Push a change reported by the watcher to the queue
+    pub fn push(&mut self, change: FsChange) {
+        let now = Instant::now();
+        self.first_push.get_or_insert(now);
+        self.last_push = Some(now);
+        match change {
+            FsChange::Update(update) => self.push_update(update),
+            FsChange::Remove(remove) => self.push_remove(remove),
+            FsChange::Move(mov) => self.push_move(mov),
+        }
+    }
+
+    /// Take the next change to sync.
+    /// No change is returned while the queue is paused, before the coalescing window has elapsed (unless the changes
+    /// have waited for the maximum latency) or while the first change is waiting to be retried
+    pub fn pop(&mut self) -> Option<PendingChange> {
+        let now = Instant::now();
+        let coalescing = self
+            .last_push
+            .map(|x| now.duration_since(x) < self.window)
+            .unwrap_or(false);
+        let overdue = self
+            .first_push
+            .map(|x| now.duration_since(x) >= self.window * MAX_LATENCY_WINDOWS)
+            .unwrap_or(false);
+        if self.paused || (coalescing && !overdue) {
+            return None;
+        }
+        if self
+            .changes
+            .front()?
+            .retry_at
+            .map(|x| x > now)
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        let pending = self.changes.pop_front();
+        if self.changes.is_empty() {
+            self.first_push = None;
+        }
+        pending
+    }
+
+    /// Put a change which failed to sync back at the head of the queue, to retry it later.
+    /// Returns `false` if the change has failed too many times and has been discarded
+    pub fn retry(&mut self, mut pending: PendingChange) -> bool {
+        pending.attempts += 1;
+        if pending.attempts >= MAX_ATTEMPTS {
+            return false;
+        }
+        pending.retry_at = Some(Instant::now() + RETRY_DELAY * pending.attempts as u32);
+        self.changes.push_front(pending);
+        true
+    }
+
+    /// Pause or resume the sync. Returns whether the sync is now paused
+    pub fn toggle_pause(&mut self) -> bool {
+        self.paused = !self.paused;
+        self.paused
+    }
+
+    /// Returns whether the sync is paused
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Iterate over the pending changes, in the order they'll be synched
+    pub fn iter(&self) -> impl Iterator<Item = &PendingChange> {
+        self.changes.iter()
+    }
+
+    /// Get the amount of pending changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns whether there are no pending changes
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn push_update(&mut self, update: FileUpdate) {
+        let pending_update = self.changes.iter().position(
+            |x| matches!(x.change(), FsChange::Update(u) if u.remote() == update.remote()),
+        );
+        let created = match pending_update.and_then(|x| self.changes.remove(x)) {
+            // keep whether the file is new from the first update
+            Some(PendingChange {
+                change: FsChange::Update(old),
+                ..
+            }) => old.created(),
+            // if the file is going to be removed, it exists on the remote
+            _ if self.changes.iter().any(
+                |x| matches!(x.change(), FsChange::Remove(r) if r.path() == update.remote()),
+            ) =>
+            {
+                false
+            }
+            _ => update.created(),
+        };
+        self.changes.push_back(PendingChange::new(FsChange::Update(
+            update.with_created(created),
+        )));
+    }
+
+    fn push_remove(&mut self, remove: FileToRemove) {
+        let path = remove.path();
+        // the removed file and its children don't need to be uploaded anymore
+        let mut created = false;
+        self.changes.retain(|x| match x.change() {
+            FsChange::Update(u) if path_utils::is_child_of(u.remote(), path) => {
+                created |= u.remote() == path && u.created();
+                false
+            }
+            FsChange::Remove(r) => r.path() != path,
+            _ => true,
+        });
+        // a file created and removed within the window has never been uploaded
+        if !created {
+            self.changes
+                .push_back(PendingChange::new(FsChange::Remove(remove)));
+        }
+    }
+
+    fn push_move(&mut self, mov: FileToRename) {
+        // updates of the moved file (or of its children) must be uploaded at the destination
+        let mut updates = Vec::new();
+        let mut created = false;
+        self.changes.retain(|x| match x.change() {
+            FsChange::Update(u) if path_utils::is_child_of(u.remote(), mov.source()) => {
+                created |= u.remote() == mov.source() && u.created();
+                updates.push(u.clone());
+                false
+            }
+            _ => true,
+        });
+        // a new file doesn't exist on the remote, so there's nothing to rename
+        if !created {
+            self.changes
+                .push_back(PendingChange::new(FsChange::Move(mov.clone())));
+        }
+        for update in updates.into_iter() {
+            self.push_update(update.moved(&mov));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn update(name: &str) -> FsChange {
+        FsChange::update(
+            PathBuf::from(format!("/home/omar/{name}")),
+            Path::new("/home/omar"),
+            Path::new("/srv"),
+        )
+    }
+
+    fn create(name: &str) -> FsChange {
+        FsChange::create(
+            PathBuf::from(format!("/home/omar/{name}")),
+            Path::new("/home/omar"),
+            Path::new("/srv"),
+        )
+    }
+
+    fn remove(name: &str) -> FsChange {
+        FsChange::remove(
+            PathBuf::from(format!("/home/omar/{name}")),
+            Path::new("/home/omar"),
+            Path::new("/srv"),
+        )
+    }
+
+    fn mov(source: &str, destination: &str) -> FsChange {
+        FsChange::mov(
+            PathBuf::from(format!("/home/omar/{source}")),
+            PathBuf::from(format!("/home/omar/{destination}")),
+            Path::new("/home/omar"),
+            Path::new("/srv"),
+        )
+    }
+
+    fn changes(queue: &SyncQueue) -> Vec<FsChange> {
+        queue.iter().map(|x| x.change().clone()).collect()
+    }
+
+    #[test]
+    fn should_deduplicate_updates() {
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(update("a.txt"));
+        queue.push(update("b.txt"));
+        queue.push(update("a.txt"));
+        assert_eq!(changes(&queue), vec![update("b.txt"), update("a.txt")]);
+        // a created file is still new after being written
+        queue.push(create("c.txt"));
+        queue.push(update("c.txt"));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(
+            changes(&queue),
+            vec![update("b.txt"), update("a.txt"), create("c.txt")]
+        );
+    }
+
+    #[test]
+    fn should_collapse_create_and_remove() {
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(create("a.txt"));
+        queue.push(update("b.txt"));
+        queue.push(remove("a.txt"));
+        queue.push(remove("b.txt"));
+        assert_eq!(changes(&queue), vec![remove("b.txt")]);
+        // removing a directory discards the updates of its children
+        queue.push(create("dir"));
+        queue.push(create("dir/a.txt"));
+        queue.push(update("dir/b.txt"));
+        queue.push(remove("dir"));
+        assert_eq!(changes(&queue), vec![remove("b.txt")]);
+        // a file removed and created again exists on the remote
+        queue.push(remove("c.txt"));
+        queue.push(create("c.txt"));
+        queue.push(remove("c.txt"));
+        assert_eq!(changes(&queue), vec![remove("b.txt"), remove("c.txt")]);
+    }
+
+    #[test]
+    fn should_coalesce_moves() {
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        // new file moved: upload at destination
+        queue.push(create("a.txt.tmp"));
+        queue.push(mov("a.txt.tmp", "a.txt"));
+        assert_eq!(changes(&queue), vec![create("a.txt")]);
+        // existing file updated and moved
+        queue.push(update("b.txt"));
+        queue.push(mov("b.txt", "c.txt"));
+        assert_eq!(
+            changes(&queue),
+            vec![create("a.txt"), mov("b.txt", "c.txt"), update("c.txt")]
+        );
+        // children of a moved directory
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(update("dir/d.txt"));
+        queue.push(mov("dir", "renamed"));
+        assert_eq!(
+            changes(&queue),
+            vec![mov("dir", "renamed"), update("renamed/d.txt")]
+        );
+    }
+
+    #[test]
+    fn should_pop_changes_after_window() {
+        let mut queue = SyncQueue::new(Duration::from_secs(60));
+        assert!(queue.is_empty());
+        queue.push(update("a.txt"));
+        assert!(queue.pop().is_none());
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(update("a.txt"));
+        queue.push(update("b.txt"));
+        assert_eq!(queue.pop().unwrap().change(), &update("a.txt"));
+        assert_eq!(queue.pop().unwrap().change(), &update("b.txt"));
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn should_pop_changes_after_max_latency() {
+        let mut queue = SyncQueue::new(Duration::from_secs(1));
+        queue.push(update("a.txt"));
+        queue.push(update("b.txt"));
+        assert!(queue.pop().is_none());
+        // changes keep coming, but the first one has waited too long
+        queue.first_push =
+            Instant::now().checked_sub(Duration::from_secs(MAX_LATENCY_WINDOWS as u64));
+        queue.push(update("c.txt"));
+        assert_eq!(queue.pop().unwrap().change(), &update("a.txt"));
+        assert_eq!(queue.pop().unwrap().change(), &update("b.txt"));
+        assert_eq!(queue.pop().unwrap().change(), &update("c.txt"));
+        // once the queue has been drained, changes are coalesced again
+        queue.push(update("d.txt"));
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn should_pause_sync() {
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(update("a.txt"));
+        assert!(queue.toggle_pause());
+        assert!(queue.paused());
+        assert!(queue.pop().is_none());
+        assert!(!queue.toggle_pause());
+        assert!(queue.pop().is_some());
+    }
+
+    #[test]
+    fn should_retry_failed_changes() {
+        let mut queue = SyncQueue::new(Duration::ZERO);
+        queue.push(update("a.txt"));
+        queue.push(update("b.txt"));
+        let pending = queue.pop().unwrap();
+        assert!(queue.retry(pending));
+        // the failed change is at the head, waiting to be retried
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.iter().next().unwrap().attempts(), 1);
+        assert!(queue.pop().is_none());
+        // give up after too many attempts
+        let mut pending = queue.changes.pop_front().unwrap();
+        pending.attempts = MAX_ATTEMPTS - 1;
+        assert!(!queue.retry(pending));
+        assert_eq!(changes(&queue), vec![update("b.txt")]);
+    }
+}
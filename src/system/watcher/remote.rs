@@ -0,0 +1,235 @@
+//! ## Remote watcher
+//!
+//! A watcher for remote directories, which reports the files to download by comparing the content of the
+//! directories between two polls
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use remotefs::File;
+
+use super::{FsWatcherError, FsWatcherResult};
+
+/// A remote directory watched by the `RemoteWatcher`
+struct RemoteWatchedPath {
+    /// Local directory where files are downloaded
+    local: PathBuf,
+    /// Size and modification time of the files in the directory at the last poll; `None` before the first poll
+    snapshot: Option<HashMap<PathBuf, (u64, Option<SystemTime>)>>,
+    /// Last time the directory has been polled
+    last_poll: Option<Instant>,
+}
+
+/// Remote directories watcher.
+///
+/// The remote file systems can't notify changes, so the directories must be listed every `interval` and passed to
+/// `update`, which returns the files which are new or changed since the previous poll
+pub struct RemoteWatcher {
+    paths: HashMap<PathBuf, RemoteWatchedPath>,
+    interval: Duration,
+}
+
+impl RemoteWatcher {
+    /// Instantiate a new `RemoteWatcher`, polling directories every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            paths: HashMap::default(),
+            interval,
+        }
+    }
+
+    /// Watch the `remote` directory, downloading new and changed files to the `local` directory.
+    /// The files already in the directory are not downloaded
+    pub fn watch(&mut self, remote: &Path, local: &Path) -> FsWatcherResult<()> {
+        if self.watched(remote) {
+            return Err(FsWatcherError::PathAlreadyWatched);
+        }
+        self.paths.insert(
+            remote.to_path_buf(),
+            RemoteWatchedPath {
+                local: local.to_path_buf(),
+                snapshot: None,
+                last_poll: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Unwatch the `remote` directory
+    pub fn unwatch(&mut self, remote: &Path) -> FsWatcherResult<PathBuf> {
+        match self.paths.remove(remote) {
+            Some(_) => Ok(remote.to_path_buf()),
+            None => Err(FsWatcherError::PathNotWatched),
+        }
+    }
+
+    /// Returns whether the `remote` directory is watched
+    pub fn watched(&self, remote: &Path) -> bool {
+        self.paths.contains_key(remote)
+    }
+
+    /// Returns the list of watched remote directories
+    pub fn watched_paths(&self) -> Vec<&Path> {
+        Vec::from_iter(self.paths.keys().map(|x| x.as_path()))
+    }
+
+    /// Returns the local directory paired with the watched `remote` directory
+    pub fn local_path(&self, remote: &Path) -> Option<&Path> {
+        self.paths.get(remote).map(|x| x.local.as_path())
+    }
+
+    /// Returns the watched remote directories which must be polled now
+    pub fn due_paths(&self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        self.paths
+            .iter()
+            .filter(|(_, x)| {
+                x.last_poll
+                    .map(|t| now.duration_since(t) >= self.interval)
+                    .unwrap_or(true)
+            })
+            .map(|(k, _)| k.clone())
+            .collect()
+    }
+
+    /// Update the snapshot of the `remote` directory with its current `entries`.
+    /// Returns the files which have been created or changed (size or modification time) since the previous poll
+    pub fn update(&mut self, remote: &Path, entries: &[File]) -> Vec<File> {
+        let watched = match self.paths.get_mut(remote) {
+            Some(watched) => watched,
+            None => return Vec::new(),
+        };
+        let snapshot: HashMap<PathBuf, (u64, Option<SystemTime>)> = entries
+            .iter()
+            .filter(|x| x.is_file())
+            .map(|x| {
+                (
+                    x.path().to_path_buf(),
+                    (x.metadata().size, x.metadata().modified),
+                )
+            })
+            .collect();
+        let changed = match watched.snapshot.as_ref() {
+            None => Vec::new(),
+            Some(previous) => entries
+                .iter()
+                .filter(|x| x.is_file() && previous.get(x.path()) != snapshot.get(x.path()))
+                .cloned()
+                .collect(),
+        };
+        watched.snapshot = Some(snapshot);
+        watched.last_poll = Some(Instant::now());
+        changed
+    }
+
+    /// Forget the state of `file` in the `remote` directory, so that it's reported again at the next poll
+    /// (e.g. because downloading it failed)
+    pub fn invalidate(&mut self, remote: &Path, file: &Path) {
+        if let Some(snapshot) = self.paths.get_mut(remote).and_then(|x| x.snapshot.as_mut()) {
+            snapshot.remove(file);
+        }
+    }
+
+    /// Postpone the next poll of the `remote` directory to the next interval (e.g. because listing it failed)
+    pub fn postpone(&mut self, remote: &Path) {
+        if let Some(watched) = self.paths.get_mut(remote) {
+            watched.last_poll = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use remotefs::fs::{FileType, Metadata};
+
+    use super::*;
+
+    fn file(name: &str, size: u64, modified: u64) -> File {
+        File {
+            path: PathBuf::from(format!("/var/log/{name}")),
+            metadata: Metadata {
+                size,
+                modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(modified)),
+                file_type: FileType::File,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn names(files: &[File]) -> Vec<String> {
+        files.iter().map(|x| x.name()).collect()
+    }
+
+    #[test]
+    fn should_watch_remote_path() {
+        let mut watcher = RemoteWatcher::new(Duration::from_secs(10));
+        assert!(watcher
+            .watch(Path::new("/var/log"), Path::new("/home/omar/logs"))
+            .is_ok());
+        assert!(watcher.watched(Path::new("/var/log")));
+        assert!(!watcher.watched(Path::new("/var")));
+        assert!(watcher
+            .watch(Path::new("/var/log"), Path::new("/tmp"))
+            .is_err());
+        assert_eq!(watcher.watched_paths(), vec![Path::new("/var/log")]);
+        assert_eq!(
+            watcher.local_path(Path::new("/var/log")).unwrap(),
+            Path::new("/home/omar/logs")
+        );
+        assert_eq!(
+            watcher.unwatch(Path::new("/var/log")).unwrap(),
+            PathBuf::from("/var/log")
+        );
+        assert!(watcher.unwatch(Path::new("/var/log")).is_err());
+        assert!(watcher.watched_paths().is_empty());
+    }
+
+    #[test]
+    fn should_report_changed_files() {
+        let mut watcher = RemoteWatcher::new(Duration::from_secs(10));
+        let remote = Path::new("/var/log");
+        assert!(watcher.watch(remote, Path::new("/home/omar/logs")).is_ok());
+        // first poll takes the snapshot only
+        let entries = vec![file("a.log", 64, 100), file("b.log", 128, 100)];
+        assert!(watcher.update(remote, &entries).is_empty());
+        // nothing changed
+        assert!(watcher.update(remote, &entries).is_empty());
+        // new, grown and touched files
+        let entries = vec![
+            file("a.log", 96, 100),
+            file("b.log", 128, 200),
+            file("c.log", 0, 200),
+        ];
+        assert_eq!(
+            names(&watcher.update(remote, &entries)),
+            vec!["a.log", "b.log", "c.log"]
+        );
+        // removed files and directories are not reported
+        let mut dir = file("archive", 4096, 300);
+        dir.metadata.file_type = FileType::Directory;
+        let entries = vec![file("a.log", 96, 100), dir];
+        assert!(watcher.update(remote, &entries).is_empty());
+        // invalidated files are reported again
+        watcher.invalidate(remote, Path::new("/var/log/a.log"));
+        assert_eq!(names(&watcher.update(remote, &entries)), vec!["a.log"]);
+        // unwatched directory
+        assert!(watcher.update(Path::new("/tmp"), &entries).is_empty());
+    }
+
+    #[test]
+    fn should_get_paths_to_poll() {
+        let mut watcher = RemoteWatcher::new(Duration::from_secs(60));
+        let remote = Path::new("/var/log");
+        assert!(watcher.watch(remote, Path::new("/home/omar/logs")).is_ok());
+        assert_eq!(watcher.due_paths(), vec![PathBuf::from("/var/log")]);
+        watcher.update(remote, &[]);
+        assert!(watcher.due_paths().is_empty());
+        let mut watcher = RemoteWatcher::new(Duration::ZERO);
+        assert!(watcher.watch(remote, Path::new("/home/omar/logs")).is_ok());
+        watcher.postpone(remote);
+        assert_eq!(watcher.due_paths(), vec![PathBuf::from("/var/log")]);
+    }
+}
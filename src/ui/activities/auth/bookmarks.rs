@@ -3,42 +3,151 @@
 //! `auth_activity` is the module which implements the authentication activity
 
 // Locals
+use std::path::PathBuf;
+
+use super::search::{self, Candidate, SearchEntry};
+use super::tree::{self, TreeEntry};
 use super::{AuthActivity, FileTransferParams};
+use crate::config::bookmarks::{BookmarkHooks, BookmarkSettings, SecretRef};
 use crate::filetransfer::params::{
     AwsS3Params, GenericProtocolParams, ProtocolParams, SmbParams, WebDAVProtocolParams,
 };
+use crate::system::bookmarks_import::{self, ConflictPolicy, ImportFormat, ImportPlan};
+use crate::system::secrets;
 
 impl AuthActivity {
+    /// Get the name of the bookmark displayed at index; `None` if the row is a group
+    pub(super) fn bookmark_at(&self, idx: usize) -> Option<&str> {
+        match self.bookmarks_rows.get(idx).map(|x| &x.entry) {
+            Some(TreeEntry::Bookmark(name)) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the row at index is a bookmark group
+    pub(super) fn is_bookmark_group(&self, idx: usize) -> bool {
+        matches!(
+            self.bookmarks_rows.get(idx).map(|x| &x.entry),
+            Some(TreeEntry::Group { .. })
+        )
+    }
+
+    /// Expand or collapse the bookmark group at index, keeping the cursor on it
+    pub(super) fn toggle_bookmark_group(&mut self, idx: usize) {
+        if let Some(TreeEntry::Group { path, .. }) = self.bookmarks_rows.get(idx).map(|x| &x.entry)
+        {
+            let path = path.clone();
+            self.bookmarks_tree.toggle(&path);
+            self.view_bookmarks();
+            self.select_bookmark_row(idx);
+        }
+    }
+
+    /// Filter bookmarks by the tags in `filter`; an empty filter shows all the bookmarks
+    pub(super) fn filter_bookmarks(&mut self, filter: &str) {
+        self.bookmarks_tree.set_filter(filter);
+        self.view_bookmarks();
+    }
+
     /// Delete bookmark
     pub(super) fn del_bookmark(&mut self, idx: usize) {
-        let name = self.bookmarks_list.get(idx).cloned();
+        let name = self.bookmark_at(idx).map(str::to_string);
         if let Some(bookmarks_cli) = self.bookmarks_client_mut() {
             // Iterate over kyes
             if let Some(name) = name {
                 bookmarks_cli.del_bookmark(&name);
                 // Write bookmarks
                 self.write_bookmarks();
+                // Delete element from vec
+                self.bookmarks_list.retain(|b| *b != name);
             }
-            // Delete element from vec
-            self.bookmarks_list.remove(idx);
         }
     }
 
     /// Load selected bookmark (at index) to input fields
     pub(super) fn load_bookmark(&mut self, idx: usize) {
-        if let Some(bookmarks_cli) = self.bookmarks_client() {
-            // Iterate over bookmarks
-            if let Some(key) = self.bookmarks_list.get(idx) {
-                if let Some(bookmark) = bookmarks_cli.get_bookmark(key) {
-                    // Load parameters into components
-                    self.load_bookmark_into_gui(bookmark);
-                }
+        if let Some(key) = self.bookmark_at(idx).map(str::to_string) {
+            self.load_bookmark_by_name(key);
+        }
+    }
+
+    /// Load bookmark associated to key to input fields
+    fn load_bookmark_by_name(&mut self, key: String) {
+        if let Some(bookmark) = self
+            .bookmarks_client()
+            .and_then(|cli| cli.get_bookmark(&key))
+        {
+            self.loaded_bookmark = Some(key);
+            // Load parameters into components
+            self.load_bookmark_into_gui(bookmark);
+        }
+    }
+
+    /// Get the settings of the loaded bookmark, if any
+    pub(super) fn loaded_bookmark_settings(&self) -> Option<BookmarkSettings> {
+        let name = self.loaded_bookmark.as_deref()?;
+        self.bookmarks_client()?
+            .get_bookmark_settings(name)
+            .cloned()
+    }
+
+    /// Get the hooks of the loaded bookmark, if any
+    pub(super) fn loaded_bookmark_hooks(&self) -> Option<BookmarkHooks> {
+        let name = self.loaded_bookmark.as_deref()?;
+        self.bookmarks_client()?.get_bookmark_hooks(name).cloned()
+    }
+
+    /// Get the reference to the secret backend of the loaded bookmark, if any
+    pub(super) fn loaded_bookmark_secret(&self) -> Option<SecretRef> {
+        let name = self.loaded_bookmark.as_deref()?;
+        self.bookmarks_client()?.get_bookmark_secret(name).cloned()
+    }
+
+    /// Resolve `secret` from its backend.
+    /// The terminal is restored while resolving, since `pass`, `gopass` and password commands
+    /// may prompt for a passphrase (e.g. through `pinentry`)
+    pub(super) fn resolve_secret(&mut self, secret: &SecretRef) -> Result<String, String> {
+        // Put input mode back to normal
+        if let Err(err) = self.context_mut().terminal().disable_raw_mode() {
+            error!("Failed to disable raw mode: {}", err);
+        }
+        // Leave alternate mode
+        if let Err(err) = self.context_mut().terminal().leave_alternate_screen() {
+            error!("Could not leave alternate screen: {}", err);
+        }
+        // Lock ports
+        assert!(self.app.lock_ports().is_ok());
+        let result = secrets::resolve(secret);
+        if let Some(ctx) = self.context.as_mut() {
+            // Enter alternate mode
+            if let Err(err) = ctx.terminal().enter_alternate_screen() {
+                error!("Could not enter alternate screen: {}", err);
+            }
+            // Entering alternate screen enables mouse capture
+            let mouse_support = ctx.config().get_mouse_support();
+            if let Err(err) = ctx.set_mouse_capture(mouse_support) {
+                error!("Failed to set mouse capture: {}", err);
             }
+            // Re-enable raw mode
+            if let Err(err) = ctx.terminal().enable_raw_mode() {
+                error!("Failed to enter raw mode: {}", err);
+            }
+            // Clear screens
+            if let Err(err) = ctx.terminal().clear_screen() {
+                error!("Could not clear screen screen: {}", err);
+            }
+            // Unlock ports
+            assert!(self.app.unlock_ports().is_ok());
         }
+        result
     }
 
     /// Save current input fields as a bookmark
-    pub(super) fn save_bookmark(&mut self, name: String, save_password: bool) {
+    pub(super) fn save_bookmark(&mut self, name: String, tags: Vec<String>, save_password: bool) {
+        let name = tree::normalize_name(&name);
+        if name.is_empty() {
+            return;
+        }
         let params = match self.collect_host_params() {
             Ok(p) => p,
             Err(e) => {
@@ -48,15 +157,62 @@ impl AuthActivity {
         };
         if let Some(bookmarks_cli) = self.bookmarks_client_mut() {
             bookmarks_cli.add_bookmark(name.clone(), params, save_password);
+            bookmarks_cli.set_bookmark_tags(&name, tags);
             // Save bookmarks
             self.write_bookmarks();
             // Remove `name` from bookmarks if exists
             self.bookmarks_list.retain(|b| b.as_str() != name.as_str());
-            // Push bookmark to list and sort
-            self.bookmarks_list.push(name);
-            self.sort_bookmarks();
+            // Push bookmark to list and show it
+            self.bookmarks_tree.reveal(&name);
+            self.bookmarks_list.push(name.clone());
+            self.loaded_bookmark = Some(name);
+        }
+    }
+    /// Import the bookmarks of another client from `path`, or from the default location of the client.
+    /// Bookmarks which already exist are kept; passwords and commands of termscp exports are not imported
+    pub(super) fn import_bookmarks(&mut self, format: ImportFormat, path: Option<PathBuf>) {
+        let path = match path.or_else(|| format.default_path()) {
+            Some(path) => path,
+            None => {
+                self.mount_error(format!("Could not find the {format} configuration"));
+                return;
+            }
+        };
+        let sites = match bookmarks_import::read_sites(format, path.as_path(), None) {
+            Ok(sites) => sites,
+            Err(err) => {
+                self.mount_error(format!(
+                    "Could not import bookmarks from {}: {err}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+        let (imported, skipped, dropped) = match self.bookmarks_client_mut() {
+            Some(bookmarks_cli) => {
+                let plan =
+                    ImportPlan::new(sites, bookmarks_cli.iter_bookmarks(), ConflictPolicy::Skip);
+                let report = (plan.imported(), plan.skipped(), plan.dropped_commands());
+                plan.apply(bookmarks_cli);
+                report
+            }
+            None => {
+                self.mount_error("Bookmarks are not supported on this system");
+                return;
+            }
+        };
+        self.write_bookmarks();
+        // Reload bookmarks list
+        self.init_bookmarks_client();
+        let mut report = format!("{imported} bookmarks have been imported; {skipped} skipped");
+        if dropped > 0 {
+            report.push_str(&format!(
+                "; {dropped} commands were not imported (use `termscp import-bookmarks --with-commands`)"
+            ));
         }
+        self.mount_info(report);
     }
+
     /// Delete recent
     pub(super) fn del_recent(&mut self, idx: usize) {
         let name = self.recents_list.get(idx).cloned();
@@ -73,14 +229,57 @@ impl AuthActivity {
 
     /// Load selected recent (at index) to input fields
     pub(super) fn load_recent(&mut self, idx: usize) {
-        if let Some(client) = self.bookmarks_client() {
-            // Iterate over bookmarks
-            if let Some(key) = self.recents_list.get(idx) {
-                if let Some(bookmark) = client.get_recent(key) {
-                    // Load parameters
-                    self.load_bookmark_into_gui(bookmark);
-                }
-            }
+        if let Some(key) = self.recents_list.get(idx).cloned() {
+            self.load_recent_by_key(&key);
+        }
+    }
+
+    /// Load recent associated to key to input fields
+    fn load_recent_by_key(&mut self, key: &str) {
+        if let Some(bookmark) = self.bookmarks_client().and_then(|cli| cli.get_recent(key)) {
+            self.loaded_bookmark = None;
+            // Load parameters
+            self.load_bookmark_into_gui(bookmark);
+        }
+    }
+
+    /// Get the bookmarks and recents matching the quick connect `query`, best match first
+    pub(super) fn search_hosts(&self, query: &str) -> Vec<SearchEntry> {
+        let client = match self.bookmarks_client() {
+            Some(client) => client,
+            None => return Vec::new(),
+        };
+        let bookmarks = self.bookmarks_list.iter().filter_map(|name| {
+            let params = client.get_bookmark(name)?;
+            let mut fields = vec![name.clone(), Self::fmt_recent(params)];
+            fields.extend(
+                client
+                    .get_bookmark_tags(name)
+                    .unwrap_or_default()
+                    .iter()
+                    .cloned(),
+            );
+            Some(Candidate {
+                entry: SearchEntry::Bookmark(name.clone()),
+                fields,
+                last_used: client.bookmark_last_used(name).map(str::to_string),
+            })
+        });
+        let recents = self.recents_list.iter().filter_map(|key| {
+            Some(Candidate {
+                entry: SearchEntry::Recent(key.clone()),
+                fields: vec![Self::fmt_recent(client.get_recent(key)?)],
+                last_used: Some(key.clone()),
+            })
+        });
+        search::search(query, bookmarks.chain(recents).collect())
+    }
+
+    /// Load the quick connect search result into the input fields
+    pub(super) fn load_search_entry(&mut self, entry: SearchEntry) {
+        match entry {
+            SearchEntry::Bookmark(name) => self.load_bookmark_by_name(name),
+            SearchEntry::Recent(key) => self.load_recent_by_key(&key),
         }
     }
 
@@ -124,21 +323,13 @@ impl AuthActivity {
             }
             self.bookmarks_list = bookmarks_list;
             self.recents_list = recents_list;
-            // Sort bookmark list
-            self.sort_bookmarks();
+            // Sort recents list; bookmarks are sorted by the tree
             self.sort_recents();
         }
     }
 
     // -- privates
 
-    /// Sort bookmarks in list
-    fn sort_bookmarks(&mut self) {
-        // Conver to lowercase when sorting
-        self.bookmarks_list
-            .sort_by(|a, b| a.to_lowercase().as_str().cmp(b.to_lowercase().as_str()));
-    }
-
     /// Sort recents in list
     fn sort_recents(&mut self) {
         // Reverse order
@@ -9,16 +9,22 @@ use tuirealm::props::{Alignment, BorderSides, BorderType, Borders, Color, InputT
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
 
 use super::{FormMsg, Msg, UiMsg};
+use crate::config::keybindings::{KeyAction, KeyBindings, KeyScope};
 
 // -- bookmark list
 
 #[derive(MockComponent)]
 pub struct BookmarksList {
     component: List,
+    keys: KeyBindings,
 }
 
 impl BookmarksList {
-    pub fn new(bookmarks: &[String], color: Color) -> Self {
+    pub fn new(bookmarks: &[String], filter: &[String], color: Color, keys: KeyBindings) -> Self {
+        let title = match filter {
+            [] => String::from("Bookmarks"),
+            filter => format!("Bookmarks (tags: {})", filter.join(", ")),
+        };
         Self {
             component: List::default()
                 .borders(Borders::default().color(color).modifiers(BorderType::Plain))
@@ -26,13 +32,14 @@ impl BookmarksList {
                 .rewind(true)
                 .scroll(true)
                 .step(4)
-                .title("Bookmarks", Alignment::Left)
+                .title(title, Alignment::Left)
                 .rows(
                     bookmarks
                         .iter()
                         .map(|x| vec![TextSpan::from(x.as_str())])
                         .collect(),
                 ),
+            keys,
         }
     }
 }
@@ -87,9 +94,15 @@ impl Component<Msg, NoUserEvent> for BookmarksList {
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
                 Some(Msg::Ui(UiMsg::BookmarksTabBlur))
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Delete, ..
-            }) => Some(Msg::Ui(UiMsg::ShowDeleteBookmarkPopup)),
+            Event::Keyboard(ev) => match self.keys.action(KeyScope::Auth, &ev) {
+                Some(KeyAction::AuthDeleteBookmark) => {
+                    Some(Msg::Ui(UiMsg::ShowDeleteBookmarkPopup))
+                }
+                Some(KeyAction::AuthFilterBookmarks) => {
+                    Some(Msg::Ui(UiMsg::ShowBookmarksFilterPopup))
+                }
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -100,10 +113,11 @@ impl Component<Msg, NoUserEvent> for BookmarksList {
 #[derive(MockComponent)]
 pub struct RecentsList {
     component: List,
+    keys: KeyBindings,
 }
 
 impl RecentsList {
-    pub fn new(bookmarks: &[String], color: Color) -> Self {
+    pub fn new(bookmarks: &[String], color: Color, keys: KeyBindings) -> Self {
         Self {
             component: List::default()
                 .borders(Borders::default().color(color).modifiers(BorderType::Plain))
@@ -118,6 +132,7 @@ impl RecentsList {
                         .map(|x| vec![TextSpan::from(x.as_str())])
                         .collect(),
                 ),
+            keys,
         }
     }
 }
@@ -172,9 +187,11 @@ impl Component<Msg, NoUserEvent> for RecentsList {
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
                 Some(Msg::Ui(UiMsg::BookmarksTabBlur))
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Delete, ..
-            }) => Some(Msg::Ui(UiMsg::ShowDeleteRecentPopup)),
+            Event::Keyboard(ev)
+                if self.keys.action(KeyScope::Auth, &ev) == Some(KeyAction::AuthDeleteBookmark) =>
+            {
+                Some(Msg::Ui(UiMsg::ShowDeleteRecentPopup))
+            }
             _ => None,
         }
     }
@@ -381,7 +398,7 @@ pub struct BookmarkName {
 }
 
 impl BookmarkName {
-    pub fn new(color: Color) -> Self {
+    pub fn new(name: &str, color: Color) -> Self {
         Self {
             component: Input::default()
                 .borders(
@@ -391,8 +408,9 @@ impl BookmarkName {
                         .modifiers(BorderType::Rounded),
                 )
                 .foreground(color)
-                .title("Bookmark name", Alignment::Left)
-                .input_type(InputType::Text),
+                .title("Bookmark name (e.g. prod/eu/db01)", Alignment::Left)
+                .input_type(InputType::Text)
+                .value(name),
         }
     }
 }
@@ -455,3 +473,446 @@ impl Component<Msg, NoUserEvent> for BookmarkName {
         }
     }
 }
+
+// -- new bookmark tags
+
+#[derive(MockComponent)]
+pub struct BookmarkTags {
+    component: Input,
+}
+
+impl BookmarkTags {
+    pub fn new(tags: &str, color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::Reset)
+                        .sides(BorderSides::TOP | BorderSides::LEFT | BorderSides::RIGHT)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .title("Tags (comma separated)", Alignment::Left)
+                .input_type(InputType::Text)
+                .value(tags),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for BookmarkTags {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseSaveBookmark))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                ..
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Form(FormMsg::SaveBookmark)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => Some(Msg::Ui(UiMsg::BookmarkTagsBlurDown)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                Some(Msg::Ui(UiMsg::BookmarkTagsBlurUp))
+            }
+            _ => None,
+        }
+    }
+}
+
+// -- bookmarks filter
+
+#[derive(MockComponent)]
+pub struct BookmarksFilterPopup {
+    component: Input,
+}
+
+impl BookmarksFilterPopup {
+    pub fn new(filter: &str, color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .title(
+                    "Filter bookmarks by tags (comma separated)",
+                    Alignment::Center,
+                )
+                .input_type(InputType::Text)
+                .value(filter),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for BookmarksFilterPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseBookmarksFilterPopup))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                ..
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.state() {
+                State::One(StateValue::String(filter)) => {
+                    Some(Msg::Form(FormMsg::FilterBookmarks(filter)))
+                }
+                _ => Some(Msg::None),
+            },
+            _ => None,
+        }
+    }
+}
+
+// -- import bookmarks
+
+#[derive(MockComponent)]
+pub struct ImportBookmarksFormat {
+    component: Radio,
+}
+
+impl ImportBookmarksFormat {
+    /// Choices must follow the order of `ImportFormat::ALL`
+    pub fn new(color: Color) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::Reset)
+                        .sides(BorderSides::TOP | BorderSides::LEFT | BorderSides::RIGHT)
+                        .modifiers(BorderType::Rounded),
+                )
+                .choices(&["FileZilla", "WinSCP", "SSH config", "rclone", "termscp"])
+                .value(0)
+                .rewind(true)
+                .foreground(color)
+                .title("Import bookmarks from", Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ImportBookmarksFormat {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseImportBookmarks))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Form(FormMsg::ImportBookmarks)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => Some(Msg::Ui(UiMsg::ImportBookmarksFormatBlur)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ImportBookmarksPath {
+    component: Input,
+}
+
+impl ImportBookmarksPath {
+    pub fn new(color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::Reset)
+                        .sides(BorderSides::BOTTOM | BorderSides::LEFT | BorderSides::RIGHT)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .title("File (empty for the default location)", Alignment::Left)
+                .input_type(InputType::Text),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ImportBookmarksPath {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseImportBookmarks))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                ..
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Form(FormMsg::ImportBookmarks)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                Some(Msg::Ui(UiMsg::ImportBookmarksPathBlur))
+            }
+            _ => None,
+        }
+    }
+}
+
+// -- quick connect
+
+#[derive(MockComponent)]
+pub struct QuickConnectInput {
+    component: Input,
+}
+
+impl QuickConnectInput {
+    pub fn new(color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .title("Quick connect", Alignment::Center)
+                .input_type(InputType::Text),
+        }
+    }
+
+    /// Get the search message for the current input
+    fn search(&self) -> Option<Msg> {
+        match self.state() {
+            State::One(StateValue::String(query)) => {
+                Some(Msg::Ui(UiMsg::QuickConnectSearch(query)))
+            }
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for QuickConnectInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseQuickConnect))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                self.search()
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                self.search()
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) => {
+                self.perform(Cmd::Type(ch));
+                self.search()
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => Some(Msg::Ui(UiMsg::QuickConnectNext)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                Some(Msg::Ui(UiMsg::QuickConnectPrevious))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Form(FormMsg::QuickConnect)),
+            _ => None,
+        }
+    }
+}
+
+/// Results of the quick connect search; driven by [`QuickConnectInput`]
+#[derive(MockComponent)]
+pub struct QuickConnectResults {
+    component: List,
+}
+
+impl QuickConnectResults {
+    pub fn new(results: &[String], color: Color) -> Self {
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .highlighted_color(color)
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .title(format!("{} results", results.len()), Alignment::Left)
+                .rows(
+                    results
+                        .iter()
+                        .map(|x| vec![TextSpan::from(x.as_str())])
+                        .collect(),
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for QuickConnectResults {
+    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -702,8 +702,8 @@ fn handle_input_ev(
             code: Key::Enter, ..
         }) => Some(Msg::Form(FormMsg::Connect)),
         Event::Keyboard(KeyEvent {
-            // NOTE: escaped control sequence
-            code: Key::Char('c') | Key::Char('h') | Key::Char('r') | Key::Char('s'),
+            // NOTE: escaped control sequence (may be bound to actions)
+            code: Key::Char(_),
             modifiers: KeyModifiers::CONTROL,
         }) => Some(Msg::None),
         Event::Keyboard(KeyEvent {
@@ -3,6 +3,7 @@
 //! auth activity components
 
 use super::{FileTransferProtocol, FormMsg, Msg, UiMsg};
+use crate::config::keybindings::{KeyAction, KeyBindings, KeyScope};
 
 mod bookmarks;
 mod form;
@@ -10,8 +11,9 @@ mod popup;
 mod text;
 
 pub use bookmarks::{
-    BookmarkName, BookmarkSavePassword, BookmarksList, DeleteBookmarkPopup, DeleteRecentPopup,
-    RecentsList,
+    BookmarkName, BookmarkSavePassword, BookmarkTags, BookmarksFilterPopup, BookmarksList,
+    DeleteBookmarkPopup, DeleteRecentPopup, ImportBookmarksFormat, ImportBookmarksPath,
+    QuickConnectInput, QuickConnectResults, RecentsList,
 };
 #[cfg(unix)]
 pub use form::InputSmbWorkgroup;
@@ -27,14 +29,24 @@ pub use popup::{
 };
 pub use text::{HelpFooter, NewVersionDisclaimer, Subtitle, Title};
 use tui_realm_stdlib::Phantom;
-use tuirealm::event::{Event, Key, KeyEvent, KeyModifiers, NoUserEvent};
+use tuirealm::event::{Event, Key, KeyEvent, NoUserEvent};
 use tuirealm::{Component, MockComponent};
 
 // -- global listener
 
-#[derive(Default, MockComponent)]
+#[derive(MockComponent)]
 pub struct GlobalListener {
     component: Phantom,
+    keys: KeyBindings,
+}
+
+impl GlobalListener {
+    pub fn new(keys: KeyBindings) -> Self {
+        Self {
+            component: Phantom::default(),
+            keys,
+        }
+    }
 }
 
 impl Component<Msg, NoUserEvent> for GlobalListener {
@@ -44,26 +56,17 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
                 code: Key::Esc | Key::Function(10),
                 ..
             }) => Some(Msg::Ui(UiMsg::ShowQuitPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Form(FormMsg::EnterSetup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('h'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Ui(UiMsg::ShowKeybindingsPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Function(1),
-                ..
-            }) => Some(Msg::Ui(UiMsg::ShowKeybindingsPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('r'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Ui(UiMsg::ShowReleaseNotes)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('s'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Ui(UiMsg::ShowSaveBookmarkPopup)),
+            Event::Keyboard(ev) => match self.keys.action(KeyScope::Auth, &ev) {
+                Some(KeyAction::AuthSetup) => Some(Msg::Form(FormMsg::EnterSetup)),
+                Some(KeyAction::AuthHelp) => Some(Msg::Ui(UiMsg::ShowKeybindingsPopup)),
+                Some(KeyAction::AuthReleaseNotes) => Some(Msg::Ui(UiMsg::ShowReleaseNotes)),
+                Some(KeyAction::AuthSaveBookmark) => Some(Msg::Ui(UiMsg::ShowSaveBookmarkPopup)),
+                Some(KeyAction::AuthImportBookmarks) => {
+                    Some(Msg::Ui(UiMsg::ShowImportBookmarksPopup))
+                }
+                Some(KeyAction::AuthQuickConnect) => Some(Msg::Ui(UiMsg::ShowQuickConnect)),
+                _ => None,
+            },
             Event::WindowResize(_, _) => Some(Msg::Ui(UiMsg::WindowResized)),
             _ => None,
         }
@@ -7,8 +7,10 @@ use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{Alignment, BorderType, Borders, Color, TableBuilder, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
+use unicode_width::UnicodeWidthStr;
 
 use super::{FormMsg, Msg, UiMsg};
+use crate::config::keybindings::{KeyBindings, KeyScope};
 
 // -- error popup
 
@@ -360,7 +362,24 @@ pub struct Keybindings {
 }
 
 impl Keybindings {
-    pub fn new(color: Color) -> Self {
+    pub fn new(color: Color, keys: &KeyBindings) -> Self {
+        let mut rows = TableBuilder::default();
+        let mut add_row = |key: &str, description: &str| {
+            rows.add_col(TextSpan::new(key).bold().fg(color))
+                .add_col(TextSpan::from(format!(
+                    "{}{description}",
+                    " ".repeat(16_usize.saturating_sub(key.width()).max(2))
+                )))
+                .add_row();
+        };
+        add_row("<ESC>", "Quit termscp");
+        add_row("<TAB>", "Switch from form and bookmarks");
+        add_row("<RIGHT/LEFT>", "Switch bookmark tab");
+        add_row("<UP/DOWN>", "Move up/down in current tab");
+        add_row("<ENTER>", "Connect/Load bookmark/Expand group");
+        for (action, _) in keys.iter(KeyScope::Auth) {
+            add_row(&keys.label(action), action.description());
+        }
         Self {
             component: List::default()
                 .borders(
@@ -372,33 +391,7 @@ impl Keybindings {
                 .title("Keybindings", Alignment::Center)
                 .scroll(true)
                 .step(4)
-                .rows(
-                    TableBuilder::default()
-                        .add_col(TextSpan::new("<ESC>").bold().fg(color))
-                        .add_col(TextSpan::from("           Quit termscp"))
-                        .add_row()
-                        .add_col(TextSpan::new("<TAB>").bold().fg(color))
-                        .add_col(TextSpan::from("           Switch from form and bookmarks"))
-                        .add_row()
-                        .add_col(TextSpan::new("<RIGHT/LEFT>").bold().fg(color))
-                        .add_col(TextSpan::from("    Switch bookmark tab"))
-                        .add_row()
-                        .add_col(TextSpan::new("<UP/DOWN>").bold().fg(color))
-                        .add_col(TextSpan::from("       Move up/down in current tab"))
-                        .add_row()
-                        .add_col(TextSpan::new("<ENTER>").bold().fg(color))
-                        .add_col(TextSpan::from("         Connect/Load bookmark"))
-                        .add_row()
-                        .add_col(TextSpan::new("<DEL|E>").bold().fg(color))
-                        .add_col(TextSpan::from("         Delete selected bookmark"))
-                        .add_row()
-                        .add_col(TextSpan::new("<CTRL+C>").bold().fg(color))
-                        .add_col(TextSpan::from("        Enter setup"))
-                        .add_row()
-                        .add_col(TextSpan::new("<CTRL+S>").bold().fg(color))
-                        .add_col(TextSpan::from("        Save bookmark"))
-                        .build(),
-                ),
+                .rows(rows.build()),
         }
     }
 }
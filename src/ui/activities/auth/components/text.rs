@@ -7,6 +7,7 @@ use tuirealm::props::{Color, TextModifiers, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
+use crate::config::keybindings::{KeyAction, KeyBindings};
 
 // -- Title
 
@@ -89,12 +90,16 @@ pub struct HelpFooter {
 }
 
 impl HelpFooter {
-    pub fn new(key_color: Color) -> Self {
+    pub fn new(key_color: Color, keys: &KeyBindings) -> Self {
         Self {
             component: Span::default().spans(&[
-                TextSpan::from("<F1|CTRL+H>").bold().fg(key_color),
+                TextSpan::from(keys.label(KeyAction::AuthHelp))
+                    .bold()
+                    .fg(key_color),
                 TextSpan::from(" Help "),
-                TextSpan::from("<CTRL+C>").bold().fg(key_color),
+                TextSpan::from(keys.label(KeyAction::AuthSetup))
+                    .bold()
+                    .fg(key_color),
                 TextSpan::from(" Enter setup "),
                 TextSpan::from("<UP/DOWN>").bold().fg(key_color),
                 TextSpan::from(" Change field "),
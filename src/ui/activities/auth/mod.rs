@@ -6,6 +6,9 @@
 mod bookmarks;
 mod components;
 mod misc;
+mod mouse;
+mod search;
+mod tree;
 mod update;
 mod view;
 
@@ -22,6 +25,9 @@ use crate::config::themes::Theme;
 use crate::filetransfer::{FileTransferParams, FileTransferProtocol};
 use crate::system::bookmarks_client::BookmarksClient;
 use crate::system::config_client::ConfigClient;
+use crate::ui::mouse::{MouseAreas, MouseInput};
+use search::SearchEntry;
+use tree::{BookmarksTree, TreeRow};
 
 // radio
 const RADIO_PROTOCOL_SFTP: usize = 0;
@@ -38,12 +44,16 @@ pub enum Id {
     Address,
     BookmarkName,
     BookmarkSavePassword,
+    BookmarkTags,
+    BookmarksFilterPopup,
     BookmarksList,
     DeleteBookmarkPopup,
     DeleteRecentPopup,
     ErrorPopup,
     GlobalListener,
     HelpFooter,
+    ImportBookmarksFormat,
+    ImportBookmarksPath,
     InfoPopup,
     InstallUpdatePopup,
     Keybindings,
@@ -53,6 +63,8 @@ pub enum Id {
     Password,
     Port,
     Protocol,
+    QuickConnectInput,
+    QuickConnectResults,
     QuitPopup,
     RecentsList,
     RemoteDirectory,
@@ -89,10 +101,13 @@ pub enum FormMsg {
     DeleteBookmark,
     DeleteRecent,
     EnterSetup,
+    FilterBookmarks(String),
+    ImportBookmarks,
     InstallUpdate,
     LoadBookmark(usize),
     LoadRecent(usize),
     ProtocolChanged(FileTransferProtocol),
+    QuickConnect,
     Quit,
     SaveBookmark,
 }
@@ -103,14 +118,21 @@ pub enum UiMsg {
     AddressBlurUp,
     BookmarksListBlur,
     BookmarksTabBlur,
+    BookmarkTagsBlurDown,
+    BookmarkTagsBlurUp,
+    CloseBookmarksFilterPopup,
     CloseDeleteBookmark,
     CloseDeleteRecent,
     CloseErrorPopup,
+    CloseImportBookmarks,
     CloseInfoPopup,
     CloseInstallUpdatePopup,
     CloseKeybindingsPopup,
+    CloseQuickConnect,
     CloseQuitPopup,
     CloseSaveBookmark,
+    ImportBookmarksFormatBlur,
+    ImportBookmarksPathBlur,
     LocalDirectoryBlurDown,
     LocalDirectoryBlurUp,
     ParamsFormBlur,
@@ -120,6 +142,9 @@ pub enum UiMsg {
     PortBlurUp,
     ProtocolBlurDown,
     ProtocolBlurUp,
+    QuickConnectNext,
+    QuickConnectPrevious,
+    QuickConnectSearch(String),
     RececentsListBlur,
     RemoteDirectoryBlurDown,
     RemoteDirectoryBlurUp,
@@ -149,9 +174,12 @@ pub enum UiMsg {
     SmbWorkgroupUp,
     BookmarkNameBlur,
     SaveBookmarkPasswordBlur,
+    ShowBookmarksFilterPopup,
     ShowDeleteBookmarkPopup,
     ShowDeleteRecentPopup,
+    ShowImportBookmarksPopup,
     ShowKeybindingsPopup,
+    ShowQuickConnect,
     ShowQuitPopup,
     ShowReleaseNotes,
     ShowSaveBookmarkPopup,
@@ -180,6 +208,14 @@ pub struct AuthActivity {
     app: Application<Id, Msg, NoUserEvent>,
     /// List of bookmarks
     bookmarks_list: Vec<String>,
+    /// Groups and tags filter of the bookmarks
+    bookmarks_tree: BookmarksTree,
+    /// Rows of the bookmarks list, as displayed
+    bookmarks_rows: Vec<TreeRow>,
+    /// Name of the last loaded bookmark; used to fill the save bookmark dialog
+    loaded_bookmark: Option<String>,
+    /// Results of the quick connect search, as displayed
+    quick_connect_results: Vec<SearchEntry>,
     /// List of recent hosts
     recents_list: Vec<String>,
     /// Exit reason
@@ -189,23 +225,34 @@ pub struct AuthActivity {
     /// Protocol
     protocol: FileTransferProtocol,
     context: Option<Context>,
+    /// Mouse events input
+    mouse: MouseInput,
+    /// Areas where components have been drawn, used to dispatch mouse events
+    mouse_areas: MouseAreas<Id>,
 }
 
 impl AuthActivity {
     /// Instantiates a new AuthActivity
     pub fn new(ticks: Duration) -> AuthActivity {
+        let mouse = MouseInput::default();
         AuthActivity {
             app: Application::init(
                 EventListenerCfg::default()
-                    .default_input_listener(ticks)
+                    .port(Box::new(mouse.listener(ticks)), ticks)
                     .poll_timeout(ticks),
             ),
             context: None,
             bookmarks_list: Vec::new(),
+            bookmarks_tree: BookmarksTree::default(),
+            bookmarks_rows: Vec::new(),
+            loaded_bookmark: None,
+            quick_connect_results: Vec::new(),
             exit_reason: None,
             recents_list: Vec::new(),
             redraw: true,
             protocol: FileTransferProtocol::Sftp,
+            mouse,
+            mouse_areas: MouseAreas::default(),
         }
     }
 
@@ -268,6 +315,10 @@ impl Activity for AuthActivity {
         if let Err(err) = self.context_mut().terminal().enable_raw_mode() {
             error!("Failed to enter raw mode: {}", err);
         }
+        let mouse_support = self.config().get_mouse_support();
+        if let Err(err) = self.context_mut().set_mouse_capture(mouse_support) {
+            error!("Failed to set mouse capture: {}", err);
+        }
         // If check for updates is enabled, check for updates
         self.check_for_updates();
         // Initialize view
@@ -306,6 +357,7 @@ impl Activity for AuthActivity {
                 self.mount_error(format!("Application error: {err}"));
             }
         }
+        self.poll_mouse();
         // View
         if self.redraw {
             self.view();
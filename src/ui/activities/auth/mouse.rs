@@ -0,0 +1,157 @@
+//! ## AuthActivity
+//!
+//! `auth_activity` is the module which implements the authentication activity
+
+use tuirealm::event::Key;
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use tuirealm::tui::layout::Rect;
+use tuirealm::{State, StateValue};
+
+use super::{AuthActivity, Id};
+use crate::ui::mouse::{self, MouseEvent, MouseEventKind};
+
+/// Choices of the yes/no radio popups
+const YES_NO: [&str; 2] = ["Yes", "No"];
+
+impl AuthActivity {
+    /// Handle mouse events received since the last tick
+    pub(super) fn poll_mouse(&mut self) {
+        let events = self.mouse.events();
+        if events.is_empty() || !self.config().get_mouse_support() {
+            return;
+        }
+        for ev in events.iter() {
+            self.on_mouse(ev);
+        }
+        self.redraw = true;
+    }
+
+    fn on_mouse(&mut self, ev: &MouseEvent) {
+        let (id, area) = match self.mouse_areas.at(ev) {
+            Some(target) => target,
+            None => return,
+        };
+        let popup = self.app.focus().map(Self::is_popup).unwrap_or_default();
+        match id {
+            Id::BookmarksList | Id::RecentsList if !popup => {
+                assert!(self.app.active(&id).is_ok());
+                if self.scroll(ev) || !self.select_list_entry(&id, area, ev.row) {
+                    return;
+                }
+                // Double click loads the bookmark
+                if ev.kind == MouseEventKind::DoubleClick {
+                    self.mouse.inject(Key::Enter);
+                }
+            }
+            // Results are driven by the quick connect input, which keeps the focus
+            Id::QuickConnectResults => {
+                if self.scroll(ev) || !self.select_list_entry(&id, area, ev.row) {
+                    return;
+                }
+                if ev.kind == MouseEventKind::DoubleClick {
+                    self.mouse.inject(Key::Enter);
+                }
+            }
+            Id::Keybindings | Id::NewVersionChangelog => {
+                assert!(self.app.active(&id).is_ok());
+                self.scroll(ev);
+            }
+            Id::BookmarkSavePassword
+            | Id::DeleteBookmarkPopup
+            | Id::DeleteRecentPopup
+            | Id::InstallUpdatePopup
+            | Id::QuitPopup => self.on_mouse_radio(id, area, ev),
+            Id::BookmarkName
+            | Id::BookmarkTags
+            | Id::BookmarksFilterPopup
+            | Id::ImportBookmarksFormat
+            | Id::ImportBookmarksPath
+            | Id::QuickConnectInput => {
+                assert!(self.app.active(&id).is_ok())
+            }
+            Id::HelpFooter | Id::NewVersionDisclaimer | Id::Subtitle | Id::Title => {}
+            // Form fields
+            id if !popup && !Self::is_popup(&id) => assert!(self.app.active(&id).is_ok()),
+            _ => {}
+        }
+    }
+
+    /// Click on a choice selects and submits it
+    fn on_mouse_radio(&mut self, id: Id, area: Rect, ev: &MouseEvent) {
+        if ev.kind != MouseEventKind::Click || ev.row != area.y + 1 {
+            return;
+        }
+        if let Some(choice) = mouse::radio_choice_at(area, &YES_NO, ev.column) {
+            assert!(self.app.active(&id).is_ok());
+            assert!(self
+                .app
+                .attr(
+                    &id,
+                    Attribute::Value,
+                    AttrValue::Payload(PropPayload::One(PropValue::Usize(choice)))
+                )
+                .is_ok());
+            self.mouse.inject(Key::Enter);
+        }
+    }
+
+    /// Scroll the active component if `ev` is a scroll event. Returns whether the event has been handled
+    fn scroll(&mut self, ev: &MouseEvent) -> bool {
+        match ev.kind {
+            MouseEventKind::ScrollDown => self.mouse.inject(Key::Down),
+            MouseEventKind::ScrollUp => self.mouse.inject(Key::Up),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Move the cursor of list `id` to the entry at `row`. Returns whether there's an entry at `row`
+    fn select_list_entry(&mut self, id: &Id, area: Rect, row: u16) -> bool {
+        let len = match self.app.query(id, Attribute::Content) {
+            Ok(Some(AttrValue::Table(rows))) => rows.len(),
+            _ => 0,
+        };
+        let selected = match self.app.state(id) {
+            Ok(State::One(StateValue::Usize(i))) => i,
+            _ => 0,
+        };
+        match mouse::list_index_at(area, 0, selected, len, row) {
+            Some(index) => {
+                assert!(self
+                    .app
+                    .attr(
+                        id,
+                        Attribute::Value,
+                        AttrValue::Payload(PropPayload::One(PropValue::Usize(index)))
+                    )
+                    .is_ok());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_popup(id: &Id) -> bool {
+        matches!(
+            id,
+            Id::BookmarkName
+                | Id::BookmarkSavePassword
+                | Id::BookmarkTags
+                | Id::BookmarksFilterPopup
+                | Id::DeleteBookmarkPopup
+                | Id::DeleteRecentPopup
+                | Id::ErrorPopup
+                | Id::ImportBookmarksFormat
+                | Id::ImportBookmarksPath
+                | Id::InfoPopup
+                | Id::InstallUpdatePopup
+                | Id::Keybindings
+                | Id::NewVersionChangelog
+                | Id::QuickConnectInput
+                | Id::QuickConnectResults
+                | Id::QuitPopup
+                | Id::WaitPopup
+                | Id::WindowSizeError
+        )
+    }
+}
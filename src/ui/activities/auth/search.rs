@@ -0,0 +1,138 @@
+//! ## Search
+//!
+//! `search` is the module which ranks bookmarks and recent connections for the quick connect search
+
+use std::cmp::Ordering;
+
+use crate::utils::string::fuzzy_score;
+
+/// A bookmark or recent connection found by the quick connect search
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SearchEntry {
+    /// Bookmark, with its name
+    Bookmark(String),
+    /// Recent connection, with its key
+    Recent(String),
+}
+
+/// A bookmark or recent connection which can be found by the search
+#[derive(Debug)]
+pub struct Candidate {
+    pub entry: SearchEntry,
+    /// Texts matched against the query (e.g. name, address, username and tags)
+    pub fields: Vec<String>,
+    /// Time of the last connection, as a recent key; recent keys sort chronologically
+    pub last_used: Option<String>,
+}
+
+/// Get the candidates matching `query`, best match first.
+/// Matches with the same score are sorted by last use, most recent first
+pub fn search(query: &str, candidates: Vec<Candidate>) -> Vec<SearchEntry> {
+    let query = query.trim();
+    let mut matches: Vec<(i64, Candidate)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            candidate
+                .fields
+                .iter()
+                .filter_map(|x| fuzzy_score(query, x))
+                .max()
+                .map(|score| (score, candidate))
+        })
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        b_score.cmp(a_score).then_with(|| cmp_last_used(a, b))
+    });
+    matches.into_iter().map(|(_, x)| x.entry).collect()
+}
+
+/// Most recently used first; never used last
+fn cmp_last_used(a: &Candidate, b: &Candidate) -> Ordering {
+    match (&a.last_used, &b.last_used) {
+        (Some(a), Some(b)) => b.cmp(a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn candidates() -> Vec<Candidate> {
+        vec![
+            Candidate {
+                entry: SearchEntry::Bookmark(String::from("prod/eu/db01")),
+                fields: vec![
+                    String::from("prod/eu/db01"),
+                    String::from("sftp://root@10.0.1.1:22"),
+                    String::from("db"),
+                ],
+                last_used: Some(String::from("ISO20231010T100000")),
+            },
+            Candidate {
+                entry: SearchEntry::Bookmark(String::from("raspberry")),
+                fields: vec![
+                    String::from("raspberry"),
+                    String::from("sftp://pi@192.168.1.31:22"),
+                ],
+                last_used: None,
+            },
+            Candidate {
+                entry: SearchEntry::Recent(String::from("ISO20231011T100000")),
+                fields: vec![String::from("scp://root@10.0.1.2:22")],
+                last_used: Some(String::from("ISO20231011T100000")),
+            },
+        ]
+    }
+
+    #[test]
+    fn should_search_by_name_address_username_and_tags() {
+        assert_eq!(
+            search("rasp", candidates()),
+            vec![SearchEntry::Bookmark(String::from("raspberry"))]
+        );
+        assert_eq!(
+            search("192.168", candidates()),
+            vec![SearchEntry::Bookmark(String::from("raspberry"))]
+        );
+        assert_eq!(
+            search("db", candidates()),
+            vec![SearchEntry::Bookmark(String::from("prod/eu/db01"))]
+        );
+        assert_eq!(
+            search("10.0.1.2", candidates())[0],
+            SearchEntry::Recent(String::from("ISO20231011T100000"))
+        );
+        assert!(search("nas", candidates()).is_empty());
+    }
+
+    #[test]
+    fn should_rank_by_score() {
+        let results = search("pi", candidates());
+        assert_eq!(results[0], SearchEntry::Bookmark(String::from("raspberry")));
+    }
+
+    #[test]
+    fn should_rank_by_last_used() {
+        assert_eq!(
+            search("", candidates()),
+            vec![
+                SearchEntry::Recent(String::from("ISO20231011T100000")),
+                SearchEntry::Bookmark(String::from("prod/eu/db01")),
+                SearchEntry::Bookmark(String::from("raspberry")),
+            ]
+        );
+        assert_eq!(
+            search("root", candidates()),
+            vec![
+                SearchEntry::Recent(String::from("ISO20231011T100000")),
+                SearchEntry::Bookmark(String::from("prod/eu/db01")),
+            ]
+        );
+    }
+}
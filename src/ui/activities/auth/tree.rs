@@ -0,0 +1,317 @@
+//! ## Tree
+//!
+//! `tree` is the module which arranges the bookmarks in a tree of groups.
+//! The group of a bookmark is given by its name: `prod/eu/db01` is the bookmark `db01` in the group `prod/eu`
+
+use std::collections::HashSet;
+
+/// Separator of the groups in the bookmark name
+pub const GROUP_SEPARATOR: char = '/';
+
+/// Separator of the tags, when typed by the user
+pub const TAG_SEPARATOR: char = ',';
+
+/// An entry of the bookmarks tree
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TreeEntry {
+    /// Bookmark group, with the path of the group (e.g. `prod/eu`) and the amount of bookmarks it contains
+    Group {
+        path: String,
+        expanded: bool,
+        bookmarks: usize,
+    },
+    /// Bookmark, with the bookmark name (e.g. `prod/eu/db01`)
+    Bookmark(String),
+}
+
+/// A row of the bookmarks tree, as displayed in the bookmarks list
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TreeRow {
+    pub entry: TreeEntry,
+    /// Nesting level of the row
+    pub depth: usize,
+    /// Last segment of the name
+    pub label: String,
+}
+
+/// Collapsed state and tags filter of the bookmarks tree
+#[derive(Debug, Default)]
+pub struct BookmarksTree {
+    /// Paths of the expanded groups; groups are collapsed by default
+    expanded: HashSet<String>,
+    /// Tags a bookmark must have to be displayed (lowercase)
+    filter: Vec<String>,
+}
+
+#[derive(Default)]
+struct Node {
+    groups: Vec<(String, Node)>,
+    bookmarks: Vec<(String, String)>,
+}
+
+impl Node {
+    fn insert(&mut self, segments: &[&str], name: &str) {
+        match segments {
+            [] => {}
+            [label] => self.bookmarks.push((label.to_string(), name.to_string())),
+            [group, segments @ ..] => {
+                let index = match self.groups.iter().position(|(x, _)| x == group) {
+                    Some(index) => index,
+                    None => {
+                        self.groups.push((group.to_string(), Node::default()));
+                        self.groups.len() - 1
+                    }
+                };
+                self.groups[index].1.insert(segments, name);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bookmarks.len() + self.groups.iter().map(|(_, x)| x.len()).sum::<usize>()
+    }
+}
+
+impl BookmarksTree {
+    /// Get the tags filter
+    pub fn filter(&self) -> &[String] {
+        &self.filter
+    }
+
+    /// Set the tags filter from the user input (e.g. `prod, db`). An empty input disables the filter
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = parse_tags(filter)
+            .into_iter()
+            .map(|x| x.to_lowercase())
+            .collect();
+    }
+
+    /// Collapse group at `path` if expanded, expand it otherwise
+    pub fn toggle(&mut self, path: &str) {
+        if !self.expanded.remove(path) {
+            self.expanded.insert(path.to_string());
+        }
+    }
+
+    /// Expand all the groups containing the bookmark `name`
+    pub fn reveal(&mut self, name: &str) {
+        let segments = split_name(name);
+        for i in 1..segments.len() {
+            self.expanded
+                .insert(segments[..i].join(&GROUP_SEPARATOR.to_string()));
+        }
+    }
+
+    /// Returns whether a bookmark with `tags` passes the tags filter
+    pub fn matches(&self, tags: &[String]) -> bool {
+        self.filter
+            .iter()
+            .all(|x| tags.iter().any(|tag| tag.to_lowercase() == *x))
+    }
+
+    /// Get the rows to display for `bookmarks`, given as name and tags.
+    /// Groups come before bookmarks and both are sorted alphabetically, ignoring case.
+    /// While filtering, groups are always expanded.
+    pub fn rows<'a, I>(&self, bookmarks: I) -> Vec<TreeRow>
+    where
+        I: Iterator<Item = (&'a str, &'a [String])>,
+    {
+        let mut root = Node::default();
+        for (name, _) in bookmarks.filter(|(_, tags)| self.matches(tags)) {
+            match split_name(name) {
+                segments if segments.is_empty() => root.insert(&[name], name),
+                segments => root.insert(&segments, name),
+            }
+        }
+        let mut rows = Vec::with_capacity(root.len());
+        self.flatten(root, "", 0, &mut rows);
+        rows
+    }
+
+    fn flatten(&self, mut node: Node, path: &str, depth: usize, rows: &mut Vec<TreeRow>) {
+        node.groups.sort_by_key(|(x, _)| x.to_lowercase());
+        node.bookmarks.sort_by_key(|(x, _)| x.to_lowercase());
+        for (label, group) in node.groups {
+            let path = match path {
+                "" => label.clone(),
+                parent => format!("{parent}{GROUP_SEPARATOR}{label}"),
+            };
+            let expanded = !self.filter.is_empty() || self.expanded.contains(&path);
+            rows.push(TreeRow {
+                entry: TreeEntry::Group {
+                    path: path.clone(),
+                    expanded,
+                    bookmarks: group.len(),
+                },
+                depth,
+                label,
+            });
+            if expanded {
+                self.flatten(group, &path, depth + 1, rows);
+            }
+        }
+        for (label, name) in node.bookmarks {
+            rows.push(TreeRow {
+                entry: TreeEntry::Bookmark(name),
+                depth,
+                label,
+            });
+        }
+    }
+}
+
+/// Normalize the bookmark name typed by the user, trimming the groups and removing the empty ones
+/// (e.g. ` prod//eu / db01` becomes `prod/eu/db01`)
+pub fn normalize_name(name: &str) -> String {
+    split_name(name).join(&GROUP_SEPARATOR.to_string())
+}
+
+/// Parse the tags typed by the user (e.g. `prod, db`)
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    tags.split(TAG_SEPARATOR)
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn split_name(name: &str) -> Vec<&str> {
+    name.split(GROUP_SEPARATOR)
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn bookmarks() -> Vec<(String, Vec<String>)> {
+        vec![
+            (String::from("raspberry"), vec![String::from("home")]),
+            (
+                String::from("prod/eu/db01"),
+                vec![String::from("db"), String::from("EU")],
+            ),
+            (String::from("prod/eu/web01"), vec![String::from("web")]),
+            (String::from("prod/us/db01"), vec![String::from("db")]),
+            (String::from("Backup"), vec![]),
+        ]
+    }
+
+    fn rows(tree: &BookmarksTree, bookmarks: &[(String, Vec<String>)]) -> Vec<(usize, String)> {
+        tree.rows(bookmarks.iter().map(|(x, t)| (x.as_str(), t.as_slice())))
+            .into_iter()
+            .map(|x| (x.depth, x.label))
+            .collect()
+    }
+
+    #[test]
+    fn should_collapse_groups_by_default() {
+        let tree = BookmarksTree::default();
+        let bookmarks = bookmarks();
+        let rows = tree.rows(bookmarks.iter().map(|(x, t)| (x.as_str(), t.as_slice())));
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            TreeRow {
+                entry: TreeEntry::Group {
+                    path: String::from("prod"),
+                    expanded: false,
+                    bookmarks: 3
+                },
+                depth: 0,
+                label: String::from("prod")
+            }
+        );
+        assert_eq!(rows[1].entry, TreeEntry::Bookmark(String::from("Backup")));
+        assert_eq!(
+            rows[2].entry,
+            TreeEntry::Bookmark(String::from("raspberry"))
+        );
+    }
+
+    #[test]
+    fn should_expand_and_collapse_groups() {
+        let mut tree = BookmarksTree::default();
+        let bookmarks = bookmarks();
+        tree.toggle("prod");
+        tree.toggle("prod/eu");
+        assert_eq!(
+            rows(&tree, &bookmarks),
+            vec![
+                (0, String::from("prod")),
+                (1, String::from("eu")),
+                (2, String::from("db01")),
+                (2, String::from("web01")),
+                (1, String::from("us")),
+                (0, String::from("Backup")),
+                (0, String::from("raspberry")),
+            ]
+        );
+        tree.toggle("prod");
+        assert_eq!(rows(&tree, &bookmarks).len(), 3);
+    }
+
+    #[test]
+    fn should_reveal_bookmark() {
+        let mut tree = BookmarksTree::default();
+        let bookmarks = bookmarks();
+        tree.reveal("prod/us/db01");
+        assert_eq!(
+            rows(&tree, &bookmarks),
+            vec![
+                (0, String::from("prod")),
+                (1, String::from("eu")),
+                (1, String::from("us")),
+                (2, String::from("db01")),
+                (0, String::from("Backup")),
+                (0, String::from("raspberry")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_filter_by_tags() {
+        let mut tree = BookmarksTree::default();
+        let bookmarks = bookmarks();
+        tree.set_filter("db");
+        assert_eq!(tree.filter(), &[String::from("db")]);
+        assert_eq!(
+            rows(&tree, &bookmarks),
+            vec![
+                (0, String::from("prod")),
+                (1, String::from("eu")),
+                (2, String::from("db01")),
+                (1, String::from("us")),
+                (2, String::from("db01")),
+            ]
+        );
+        tree.set_filter("db, eu");
+        assert_eq!(rows(&tree, &bookmarks).len(), 3);
+        tree.set_filter("cloud");
+        assert!(rows(&tree, &bookmarks).is_empty());
+        tree.set_filter(" ");
+        assert!(tree.filter().is_empty());
+        assert_eq!(rows(&tree, &bookmarks).len(), 3);
+    }
+
+    #[test]
+    fn should_normalize_name() {
+        assert_eq!(normalize_name(" prod//eu / db01"), "prod/eu/db01");
+        assert_eq!(normalize_name("raspberry"), "raspberry");
+        assert_eq!(normalize_name("/"), "");
+    }
+
+    #[test]
+    fn should_parse_tags() {
+        assert_eq!(
+            parse_tags("prod, db ,,eu"),
+            vec![String::from("prod"), String::from("db"), String::from("eu")]
+        );
+        assert!(parse_tags(" ").is_empty());
+    }
+}
@@ -26,8 +26,30 @@ impl AuthActivity {
                         // mount error
                         self.mount_error(err);
                     }
-                    Ok(params) => {
+                    Ok(mut params) => {
+                        // Resolve the password of the loaded bookmark from its secret backend
+                        if let Some(secret) = self
+                            .loaded_bookmark_secret()
+                            .filter(|_| params.password_missing())
+                        {
+                            match self.resolve_secret(&secret) {
+                                Ok(password) => params.set_default_secret(password),
+                                Err(err) => {
+                                    self.mount_error(err);
+                                    return None;
+                                }
+                            }
+                        }
                         self.save_recent();
+                        // Override configuration with the settings of the loaded bookmark for the session
+                        let settings = self.loaded_bookmark_settings();
+                        self.context_mut()
+                            .config_mut()
+                            .set_session_settings(settings);
+                        let hooks = self.loaded_bookmark_hooks();
+                        self.context_mut().set_bookmark_hooks(hooks);
+                        let name = self.loaded_bookmark.clone();
+                        self.context_mut().set_bookmark_name(name);
                         // Set file transfer params to context
                         self.context_mut().set_ftparams(params);
                         // Set exit reason
@@ -55,12 +77,26 @@ impl AuthActivity {
                     self.view_recent_connections();
                 }
             }
+            FormMsg::FilterBookmarks(filter) => {
+                self.umount_bookmarks_filter();
+                self.filter_bookmarks(&filter);
+                assert!(self.app.active(&Id::BookmarksList).is_ok());
+            }
+            FormMsg::ImportBookmarks => {
+                let (format, path) = self.get_import_bookmarks();
+                self.umount_import_bookmarks();
+                self.import_bookmarks(format, path);
+                self.view_bookmarks();
+            }
             FormMsg::EnterSetup => {
                 self.exit_reason = Some(ExitReason::EnterSetup);
             }
             FormMsg::InstallUpdate => {
                 self.install_update();
             }
+            FormMsg::LoadBookmark(i) if self.is_bookmark_group(i) => {
+                self.toggle_bookmark_group(i);
+            }
             FormMsg::LoadBookmark(i) => {
                 self.load_bookmark(i);
                 // Give focus to input password (or to protocol if not generic)
@@ -95,16 +131,27 @@ impl AuthActivity {
                     self.mount_port(Self::get_default_port_for_protocol(protocol));
                 }
             }
+            FormMsg::QuickConnect => {
+                let entry = match self.app.state(&Id::QuickConnectResults) {
+                    Ok(State::One(StateValue::Usize(idx))) => {
+                        self.quick_connect_results.get(idx).cloned()
+                    }
+                    _ => None,
+                };
+                if let Some(entry) = entry {
+                    self.umount_quick_connect();
+                    self.load_search_entry(entry);
+                    return Some(Msg::Form(FormMsg::Connect));
+                }
+            }
             FormMsg::Quit => {
                 self.exit_reason = Some(ExitReason::Quit);
             }
             FormMsg::SaveBookmark => {
                 // get bookmark name
-                let (name, save_password) = self.get_new_bookmark();
+                let (name, tags, save_password) = self.get_new_bookmark();
                 // Save bookmark
-                if !name.is_empty() {
-                    self.save_bookmark(name, save_password);
-                }
+                self.save_bookmark(name, tags, save_password);
                 // Umount popup
                 self.umount_bookmark_save_dialog();
                 // Reload bookmarks
@@ -131,11 +178,21 @@ impl AuthActivity {
                 assert!(self.app.active(&Id::RecentsList).is_ok());
             }
             UiMsg::BookmarkNameBlur => {
+                assert!(self.app.active(&Id::BookmarkTags).is_ok());
+            }
+            UiMsg::BookmarkTagsBlurDown => {
                 assert!(self.app.active(&Id::BookmarkSavePassword).is_ok());
             }
+            UiMsg::BookmarkTagsBlurUp => {
+                assert!(self.app.active(&Id::BookmarkName).is_ok());
+            }
             UiMsg::BookmarksTabBlur => {
                 assert!(self.app.active(&Id::Protocol).is_ok());
             }
+            UiMsg::CloseBookmarksFilterPopup => {
+                self.umount_bookmarks_filter();
+                assert!(self.app.active(&Id::BookmarksList).is_ok());
+            }
             UiMsg::CloseDeleteBookmark => {
                 assert!(self.app.umount(&Id::DeleteBookmarkPopup).is_ok());
             }
@@ -145,6 +202,9 @@ impl AuthActivity {
             UiMsg::CloseErrorPopup => {
                 self.umount_error();
             }
+            UiMsg::CloseImportBookmarks => {
+                self.umount_import_bookmarks();
+            }
             UiMsg::CloseInfoPopup => {
                 self.umount_info();
             }
@@ -155,11 +215,19 @@ impl AuthActivity {
             UiMsg::CloseKeybindingsPopup => {
                 self.umount_help();
             }
+            UiMsg::CloseQuickConnect => self.umount_quick_connect(),
             UiMsg::CloseQuitPopup => self.umount_quit(),
             UiMsg::CloseSaveBookmark => {
                 assert!(self.app.umount(&Id::BookmarkName).is_ok());
+                assert!(self.app.umount(&Id::BookmarkTags).is_ok());
                 assert!(self.app.umount(&Id::BookmarkSavePassword).is_ok());
             }
+            UiMsg::ImportBookmarksFormatBlur => {
+                assert!(self.app.active(&Id::ImportBookmarksPath).is_ok());
+            }
+            UiMsg::ImportBookmarksPathBlur => {
+                assert!(self.app.active(&Id::ImportBookmarksFormat).is_ok());
+            }
             UiMsg::LocalDirectoryBlurDown => {
                 assert!(self.app.active(&Id::Protocol).is_ok());
             }
@@ -214,6 +282,9 @@ impl AuthActivity {
             UiMsg::ProtocolBlurUp => {
                 assert!(self.app.active(&Id::LocalDirectory).is_ok());
             }
+            UiMsg::QuickConnectNext => self.move_quick_connect_cursor(1),
+            UiMsg::QuickConnectPrevious => self.move_quick_connect_cursor(-1),
+            UiMsg::QuickConnectSearch(query) => self.view_quick_connect_results(&query),
             UiMsg::RececentsListBlur => {
                 assert!(self.app.active(&Id::BookmarksList).is_ok());
             }
@@ -308,17 +379,31 @@ impl AuthActivity {
                 assert!(self.app.active(&Id::Password).is_ok());
             }
             UiMsg::SaveBookmarkPasswordBlur => {
-                assert!(self.app.active(&Id::BookmarkName).is_ok());
+                assert!(self.app.active(&Id::BookmarkTags).is_ok());
+            }
+            UiMsg::ShowBookmarksFilterPopup => {
+                self.mount_bookmarks_filter();
             }
             UiMsg::ShowDeleteBookmarkPopup => {
-                self.mount_bookmark_del_dialog();
+                // Groups can't be deleted
+                if let Ok(State::One(StateValue::Usize(idx))) = self.app.state(&Id::BookmarksList) {
+                    if self.bookmark_at(idx).is_some() {
+                        self.mount_bookmark_del_dialog();
+                    }
+                }
             }
             UiMsg::ShowDeleteRecentPopup => {
                 self.mount_recent_del_dialog();
             }
+            UiMsg::ShowImportBookmarksPopup => {
+                self.mount_import_bookmarks();
+            }
             UiMsg::ShowKeybindingsPopup => {
                 self.mount_keybindings();
             }
+            UiMsg::ShowQuickConnect => {
+                self.mount_quick_connect();
+            }
             UiMsg::ShowQuitPopup => {
                 self.mount_quit();
             }
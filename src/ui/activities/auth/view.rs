@@ -6,16 +6,20 @@
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use tuirealm::props::Color;
-use tuirealm::tui::layout::{Constraint, Direction, Layout};
+use tuirealm::props::{AttrValue, Attribute, Color, PropPayload, PropValue};
+use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use tuirealm::tui::widgets::Clear;
-use tuirealm::{State, StateValue, Sub, SubClause, SubEventClause};
+use tuirealm::{Frame, State, StateValue, Sub, SubClause, SubEventClause};
 
+use super::search::SearchEntry;
+use super::tree::{self, TreeEntry, TAG_SEPARATOR};
 use super::{components, AuthActivity, Context, FileTransferProtocol, Id, InputMask};
+use crate::config::keybindings::KeyAction;
 use crate::filetransfer::params::{
     AwsS3Params, GenericProtocolParams, ProtocolParams, SmbParams, WebDAVProtocolParams,
 };
 use crate::filetransfer::FileTransferParams;
+use crate::system::bookmarks_import::ImportFormat;
 use crate::utils::ui::{Popup, Size};
 
 impl AuthActivity {
@@ -37,7 +41,10 @@ impl AuthActivity {
             .app
             .mount(
                 Id::HelpFooter,
-                Box::new(components::HelpFooter::new(key_color)),
+                Box::new(components::HelpFooter::new(
+                    key_color,
+                    &self.context().config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
@@ -95,6 +102,7 @@ impl AuthActivity {
     /// Display view on canvas
     pub(super) fn view(&mut self) {
         self.redraw = false;
+        self.mouse_areas.clear();
         let mut ctx: Context = self.context.take().unwrap();
         let _ = ctx.terminal().raw_mut().draw(|f| {
             // Check window size
@@ -112,7 +120,7 @@ impl AuthActivity {
                 )
                 .split(f.size());
             // Footer
-            self.app.view(&Id::HelpFooter, f, body[1]);
+            self.view_component(&Id::HelpFooter, f, body[1]);
             let auth_form_len = 7 + self.input_mask_size();
             let main_chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -218,80 +226,80 @@ impl AuthActivity {
                 .split(main_chunks[1]);
             // Render
             // Auth chunks
-            self.app.view(&Id::Title, f, auth_chunks[0]);
-            self.app.view(&Id::Subtitle, f, auth_chunks[1]);
-            self.app.view(&Id::NewVersionDisclaimer, f, auth_chunks[2]);
-            self.app.view(&Id::Protocol, f, auth_chunks[3]);
+            self.view_component(&Id::Title, f, auth_chunks[0]);
+            self.view_component(&Id::Subtitle, f, auth_chunks[1]);
+            self.view_component(&Id::NewVersionDisclaimer, f, auth_chunks[2]);
+            self.view_component(&Id::Protocol, f, auth_chunks[3]);
             // Render input mask
             match self.input_mask() {
                 InputMask::AwsS3 => {
                     let view_ids = self.get_s3_view();
-                    self.app.view(&view_ids[0], f, input_mask[0]);
-                    self.app.view(&view_ids[1], f, input_mask[1]);
-                    self.app.view(&view_ids[2], f, input_mask[2]);
-                    self.app.view(&view_ids[3], f, input_mask[3]);
+                    self.view_component(&view_ids[0], f, input_mask[0]);
+                    self.view_component(&view_ids[1], f, input_mask[1]);
+                    self.view_component(&view_ids[2], f, input_mask[2]);
+                    self.view_component(&view_ids[3], f, input_mask[3]);
                 }
                 InputMask::Generic => {
                     let view_ids = self.get_generic_params_view();
-                    self.app.view(&view_ids[0], f, input_mask[0]);
-                    self.app.view(&view_ids[1], f, input_mask[1]);
-                    self.app.view(&view_ids[2], f, input_mask[2]);
-                    self.app.view(&view_ids[3], f, input_mask[3]);
+                    self.view_component(&view_ids[0], f, input_mask[0]);
+                    self.view_component(&view_ids[1], f, input_mask[1]);
+                    self.view_component(&view_ids[2], f, input_mask[2]);
+                    self.view_component(&view_ids[3], f, input_mask[3]);
                 }
                 InputMask::Smb => {
                     let view_ids = self.get_smb_view();
-                    self.app.view(&view_ids[0], f, input_mask[0]);
-                    self.app.view(&view_ids[1], f, input_mask[1]);
-                    self.app.view(&view_ids[2], f, input_mask[2]);
-                    self.app.view(&view_ids[3], f, input_mask[3]);
+                    self.view_component(&view_ids[0], f, input_mask[0]);
+                    self.view_component(&view_ids[1], f, input_mask[1]);
+                    self.view_component(&view_ids[2], f, input_mask[2]);
+                    self.view_component(&view_ids[3], f, input_mask[3]);
                 }
                 InputMask::WebDAV => {
                     let view_ids = self.get_webdav_view();
-                    self.app.view(&view_ids[0], f, input_mask[0]);
-                    self.app.view(&view_ids[1], f, input_mask[1]);
-                    self.app.view(&view_ids[2], f, input_mask[2]);
-                    self.app.view(&view_ids[3], f, input_mask[3]);
+                    self.view_component(&view_ids[0], f, input_mask[0]);
+                    self.view_component(&view_ids[1], f, input_mask[1]);
+                    self.view_component(&view_ids[2], f, input_mask[2]);
+                    self.view_component(&view_ids[3], f, input_mask[3]);
                 }
             }
             // Bookmark chunks
-            self.app.view(&Id::BookmarksList, f, bookmark_chunks[0]);
-            self.app.view(&Id::RecentsList, f, bookmark_chunks[1]);
+            self.view_component(&Id::BookmarksList, f, bookmark_chunks[0]);
+            self.view_component(&Id::RecentsList, f, bookmark_chunks[1]);
             // Popups
             if self.app.mounted(&Id::ErrorPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::ErrorPopup, f, popup);
+                self.view_component(&Id::ErrorPopup, f, popup);
             } else if self.app.mounted(&Id::InfoPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::InfoPopup, f, popup);
+                self.view_component(&Id::InfoPopup, f, popup);
             } else if self.app.mounted(&Id::WaitPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::WaitPopup, f, popup);
+                self.view_component(&Id::WaitPopup, f, popup);
             } else if self.app.mounted(&Id::WindowSizeError) {
                 let popup = Popup(Size::Percentage(80), Size::Percentage(20)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::WindowSizeError, f, popup);
+                self.view_component(&Id::WindowSizeError, f, popup);
             } else if self.app.mounted(&Id::QuitPopup) {
                 // make popup
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
-                self.app.view(&Id::QuitPopup, f, popup);
+                self.view_component(&Id::QuitPopup, f, popup);
             } else if self.app.mounted(&Id::DeleteBookmarkPopup) {
                 // make popup
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
-                self.app.view(&Id::DeleteBookmarkPopup, f, popup);
+                self.view_component(&Id::DeleteBookmarkPopup, f, popup);
             } else if self.app.mounted(&Id::DeleteRecentPopup) {
                 // make popup
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
-                self.app.view(&Id::DeleteRecentPopup, f, popup);
+                self.view_component(&Id::DeleteRecentPopup, f, popup);
             } else if self.app.mounted(&Id::NewVersionChangelog) {
                 // make popup
                 let popup = Popup(Size::Percentage(90), Size::Percentage(85)).draw_in(f.size());
@@ -306,56 +314,140 @@ impl AuthActivity {
                         .as_ref(),
                     )
                     .split(popup);
-                self.app.view(&Id::NewVersionChangelog, f, popup_chunks[0]);
-                self.app.view(&Id::InstallUpdatePopup, f, popup_chunks[1]);
+                self.view_component(&Id::NewVersionChangelog, f, popup_chunks[0]);
+                self.view_component(&Id::InstallUpdatePopup, f, popup_chunks[1]);
             } else if self.app.mounted(&Id::Keybindings) {
                 // make popup
                 let popup = Popup(Size::Percentage(50), Size::Percentage(70)).draw_in(f.size());
                 f.render_widget(Clear, popup);
-                self.app.view(&Id::Keybindings, f, popup);
+                self.view_component(&Id::Keybindings, f, popup);
             } else if self.app.mounted(&Id::BookmarkSavePassword) {
                 // make popup
-                let popup = Popup(Size::Percentage(20), Size::Percentage(20)).draw_in(f.size());
+                let popup = Popup(Size::Percentage(30), Size::Unit(8)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 let popup_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints(
                         [
                             Constraint::Length(3), // Input form
+                            Constraint::Length(3), // Tags
                             Constraint::Length(2), // Yes/No
                         ]
                         .as_ref(),
                     )
                     .split(popup);
-                self.app.view(&Id::BookmarkName, f, popup_chunks[0]);
-                self.app.view(&Id::BookmarkSavePassword, f, popup_chunks[1]);
+                self.view_component(&Id::BookmarkName, f, popup_chunks[0]);
+                self.view_component(&Id::BookmarkTags, f, popup_chunks[1]);
+                self.view_component(&Id::BookmarkSavePassword, f, popup_chunks[2]);
+            } else if self.app.mounted(&Id::QuickConnectInput) {
+                let popup = Popup(Size::Percentage(60), Size::Percentage(60)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                let popup_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Length(3), // Search
+                            Constraint::Min(3),    // Results
+                        ]
+                        .as_ref(),
+                    )
+                    .split(popup);
+                self.view_component(&Id::QuickConnectInput, f, popup_chunks[0]);
+                self.view_component(&Id::QuickConnectResults, f, popup_chunks[1]);
+            } else if self.app.mounted(&Id::BookmarksFilterPopup) {
+                let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                self.view_component(&Id::BookmarksFilterPopup, f, popup);
+            } else if self.app.mounted(&Id::ImportBookmarksFormat) {
+                let popup = Popup(Size::Percentage(40), Size::Unit(5)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                let popup_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Length(2), // Format
+                            Constraint::Length(3), // Path
+                        ]
+                        .as_ref(),
+                    )
+                    .split(popup);
+                self.view_component(&Id::ImportBookmarksFormat, f, popup_chunks[0]);
+                self.view_component(&Id::ImportBookmarksPath, f, popup_chunks[1]);
             }
         });
         self.context = Some(ctx);
     }
 
+    /// Render component and register its area for mouse events
+    fn view_component(&mut self, id: &Id, f: &mut Frame, area: Rect) {
+        self.app.view(id, f, area);
+        self.mouse_areas.insert(id, area);
+    }
+
     // -- partials
 
     /// Make text span from bookmarks
     pub(super) fn view_bookmarks(&mut self) {
-        let bookmarks: Vec<String> = self
-            .bookmarks_list
+        let client = self.bookmarks_client().unwrap();
+        let rows = self.bookmarks_tree.rows(
+            self.bookmarks_list
+                .iter()
+                .map(|x| (x.as_str(), client.get_bookmark_tags(x).unwrap_or_default())),
+        );
+        let bookmarks: Vec<String> = rows
             .iter()
-            .map(|x| {
-                Self::fmt_bookmark(x, self.bookmarks_client().unwrap().get_bookmark(x).unwrap())
+            .map(|row| {
+                let indent = "  ".repeat(row.depth);
+                match &row.entry {
+                    TreeEntry::Group {
+                        expanded,
+                        bookmarks,
+                        ..
+                    } => format!(
+                        "{indent}{} {}/ ({bookmarks})",
+                        if *expanded { '▾' } else { '▸' },
+                        row.label
+                    ),
+                    TreeEntry::Bookmark(name) => format!(
+                        "{indent}{}",
+                        Self::fmt_bookmark(
+                            &row.label,
+                            client.get_bookmark(name).unwrap(),
+                            client.get_bookmark_tags(name).unwrap_or_default()
+                        )
+                    ),
+                }
             })
             .collect();
+        self.bookmarks_rows = rows;
         let bookmarks_color = self.theme().auth_bookmarks;
         assert!(self
             .app
             .remount(
                 Id::BookmarksList,
-                Box::new(components::BookmarksList::new(&bookmarks, bookmarks_color)),
+                Box::new(components::BookmarksList::new(
+                    &bookmarks,
+                    self.bookmarks_tree.filter(),
+                    bookmarks_color,
+                    self.context().config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
     }
 
+    /// Move the cursor of the bookmarks list to the row at index
+    pub(super) fn select_bookmark_row(&mut self, idx: usize) {
+        assert!(self
+            .app
+            .attr(
+                &Id::BookmarksList,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(idx)))
+            )
+            .is_ok());
+    }
+
     /// View recent connections
     pub(super) fn view_recent_connections(&mut self) {
         let bookmarks: Vec<String> = self
@@ -368,7 +460,11 @@ impl AuthActivity {
             .app
             .remount(
                 Id::RecentsList,
-                Box::new(components::RecentsList::new(&bookmarks, recents_color)),
+                Box::new(components::RecentsList::new(
+                    &bookmarks,
+                    recents_color,
+                    self.context().config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
@@ -510,15 +606,30 @@ impl AuthActivity {
         let _ = self.app.umount(&Id::DeleteRecentPopup);
     }
 
-    /// Mount bookmark save dialog
+    /// Mount bookmark save dialog.
+    /// Name and tags are filled with the ones of the last loaded bookmark
     pub(super) fn mount_bookmark_save_dialog(&mut self) {
         let save_color = self.theme().misc_save_dialog;
         let warn_color = self.theme().misc_warn_dialog;
+        let name = self.loaded_bookmark.clone().unwrap_or_default();
+        let tags = self
+            .bookmarks_client()
+            .and_then(|cli| cli.get_bookmark_tags(&name))
+            .map(|x| x.join(&format!("{TAG_SEPARATOR} ")))
+            .unwrap_or_default();
         assert!(self
             .app
             .remount(
                 Id::BookmarkName,
-                Box::new(components::BookmarkName::new(save_color)),
+                Box::new(components::BookmarkName::new(&name, save_color)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::BookmarkTags,
+                Box::new(components::BookmarkTags::new(&tags, save_color)),
                 vec![]
             )
             .is_ok());
@@ -537,9 +648,136 @@ impl AuthActivity {
     /// Umount bookmark save dialog
     pub(super) fn umount_bookmark_save_dialog(&mut self) {
         let _ = self.app.umount(&Id::BookmarkName);
+        let _ = self.app.umount(&Id::BookmarkTags);
         let _ = self.app.umount(&Id::BookmarkSavePassword);
     }
 
+    /// Mount quick connect search, showing all the bookmarks and recents
+    pub(super) fn mount_quick_connect(&mut self) {
+        let color = self.theme().auth_bookmarks;
+        assert!(self
+            .app
+            .remount(
+                Id::QuickConnectInput,
+                Box::new(components::QuickConnectInput::new(color)),
+                vec![]
+            )
+            .is_ok());
+        self.view_quick_connect_results("");
+        assert!(self.app.active(&Id::QuickConnectInput).is_ok());
+    }
+
+    /// Search `query` and display the results of the quick connect search
+    pub(super) fn view_quick_connect_results(&mut self, query: &str) {
+        // NOTE: without bookmarks client there are no results
+        let results = self.search_hosts(query);
+        let rows: Vec<String> = match self.bookmarks_client() {
+            Some(client) => results
+                .iter()
+                .map(|entry| match entry {
+                    SearchEntry::Bookmark(name) => Self::fmt_bookmark(
+                        name,
+                        client.get_bookmark(name).unwrap(),
+                        client.get_bookmark_tags(name).unwrap_or_default(),
+                    ),
+                    SearchEntry::Recent(key) => {
+                        format!(
+                            "{} (recent)",
+                            Self::fmt_recent(client.get_recent(key).unwrap())
+                        )
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        self.quick_connect_results = results;
+        let color = self.theme().auth_bookmarks;
+        assert!(self
+            .app
+            .remount(
+                Id::QuickConnectResults,
+                Box::new(components::QuickConnectResults::new(&rows, color)),
+                vec![]
+            )
+            .is_ok());
+    }
+
+    /// Move the cursor of the quick connect results by `offset`
+    pub(super) fn move_quick_connect_cursor(&mut self, offset: isize) {
+        let idx = match self.app.state(&Id::QuickConnectResults) {
+            Ok(State::One(StateValue::Usize(idx))) => idx,
+            _ => 0,
+        };
+        assert!(self
+            .app
+            .attr(
+                &Id::QuickConnectResults,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                    idx.saturating_add_signed(offset)
+                )))
+            )
+            .is_ok());
+    }
+
+    /// Umount quick connect search
+    pub(super) fn umount_quick_connect(&mut self) {
+        let _ = self.app.umount(&Id::QuickConnectInput);
+        let _ = self.app.umount(&Id::QuickConnectResults);
+        self.quick_connect_results.clear();
+    }
+
+    /// Mount bookmarks filter popup
+    pub(super) fn mount_bookmarks_filter(&mut self) {
+        let filter_color = self.theme().auth_bookmarks;
+        let filter = self
+            .bookmarks_tree
+            .filter()
+            .join(&format!("{TAG_SEPARATOR} "));
+        assert!(self
+            .app
+            .remount(
+                Id::BookmarksFilterPopup,
+                Box::new(components::BookmarksFilterPopup::new(&filter, filter_color)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::BookmarksFilterPopup).is_ok());
+    }
+
+    /// Umount bookmarks filter popup
+    pub(super) fn umount_bookmarks_filter(&mut self) {
+        let _ = self.app.umount(&Id::BookmarksFilterPopup);
+    }
+
+    /// Mount import bookmarks dialog
+    pub(super) fn mount_import_bookmarks(&mut self) {
+        let color = self.theme().auth_bookmarks;
+        assert!(self
+            .app
+            .remount(
+                Id::ImportBookmarksFormat,
+                Box::new(components::ImportBookmarksFormat::new(color)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ImportBookmarksPath,
+                Box::new(components::ImportBookmarksPath::new(color)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::ImportBookmarksFormat).is_ok());
+    }
+
+    /// Umount import bookmarks dialog
+    pub(super) fn umount_import_bookmarks(&mut self) {
+        let _ = self.app.umount(&Id::ImportBookmarksFormat);
+        let _ = self.app.umount(&Id::ImportBookmarksPath);
+    }
+
     /// Mount keybindings
     pub(super) fn mount_keybindings(&mut self) {
         let key_color = self.theme().misc_keys;
@@ -547,7 +785,10 @@ impl AuthActivity {
             .app
             .remount(
                 Id::Keybindings,
-                Box::new(components::Keybindings::new(key_color)),
+                Box::new(components::Keybindings::new(
+                    key_color,
+                    &self.context().config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
@@ -1040,20 +1281,36 @@ impl AuthActivity {
         }
     }
 
-    /// Get new bookmark params
-    pub(super) fn get_new_bookmark(&self) -> (String, bool) {
+    /// Get new bookmark params: name, tags and whether to save password
+    pub(super) fn get_new_bookmark(&self) -> (String, Vec<String>, bool) {
         let name = match self.app.state(&Id::BookmarkName) {
             Ok(State::One(StateValue::String(name))) => name,
             _ => String::default(),
         };
-        if matches!(
+        let tags = match self.app.state(&Id::BookmarkTags) {
+            Ok(State::One(StateValue::String(tags))) => tree::parse_tags(&tags),
+            _ => Vec::new(),
+        };
+        let save_password = matches!(
             self.app.state(&Id::BookmarkSavePassword),
             Ok(State::One(StateValue::Usize(0)))
-        ) {
-            (name, true)
-        } else {
-            (name, false)
-        }
+        );
+        (name, tags, save_password)
+    }
+
+    /// Get the format and the file to import the bookmarks from; `None` if the file is empty
+    pub(super) fn get_import_bookmarks(&self) -> (ImportFormat, Option<PathBuf>) {
+        let format = match self.app.state(&Id::ImportBookmarksFormat) {
+            Ok(State::One(StateValue::Usize(idx))) => ImportFormat::ALL[idx],
+            _ => ImportFormat::ALL[0],
+        };
+        let path = match self.app.state(&Id::ImportBookmarksPath) {
+            Ok(State::One(StateValue::String(path))) if !path.trim().is_empty() => {
+                Some(PathBuf::from(path.trim()))
+            }
+            _ => None,
+        };
+        (format, path)
     }
 
     // -- len
@@ -1071,13 +1328,16 @@ impl AuthActivity {
     // -- fmt
 
     /// Format bookmark to display on ui
-    fn fmt_bookmark(name: &str, b: FileTransferParams) -> String {
+    fn fmt_bookmark(name: &str, b: FileTransferParams, tags: &[String]) -> String {
         let addr: String = Self::fmt_recent(b);
-        format!("{name} ({addr})")
+        match tags {
+            [] => format!("{name} ({addr})"),
+            tags => format!("{name} ({addr}) [{}]", tags.join(", ")),
+        }
     }
 
     /// Format recent connection to display on ui
-    fn fmt_recent(b: FileTransferParams) -> String {
+    pub(super) fn fmt_recent(b: FileTransferParams) -> String {
         let protocol: String = b.protocol.to_string().to_lowercase();
         match b.params {
             ProtocolParams::AwsS3(s3) => {
@@ -1254,63 +1514,45 @@ impl AuthActivity {
 
     fn init_global_listener(&mut self) {
         use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+        let keys = self.context().config().get_keybindings();
+        let mut subs = vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Esc,
+                    modifiers: KeyModifiers::NONE,
+                }),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Function(10),
+                    modifiers: KeyModifiers::NONE,
+                }),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(SubEventClause::WindowResize, SubClause::Always),
+        ];
+        for action in [
+            KeyAction::AuthHelp,
+            KeyAction::AuthImportBookmarks,
+            KeyAction::AuthQuickConnect,
+            KeyAction::AuthReleaseNotes,
+            KeyAction::AuthSaveBookmark,
+            KeyAction::AuthSetup,
+        ] {
+            for key in keys.keys(action) {
+                subs.push(Sub::new(
+                    SubEventClause::Keyboard(key.key_event()),
+                    Self::no_popup_mounted_clause(),
+                ));
+            }
+        }
         assert!(self
             .app
             .mount(
                 Id::GlobalListener,
-                Box::<components::GlobalListener>::default(),
-                vec![
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Esc,
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Function(10),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('c'),
-                            modifiers: KeyModifiers::CONTROL,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('h'),
-                            modifiers: KeyModifiers::CONTROL,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Function(1),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('r'),
-                            modifiers: KeyModifiers::CONTROL,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('s'),
-                            modifiers: KeyModifiers::CONTROL,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(SubEventClause::WindowResize, SubClause::Always)
-                ]
+                Box::new(components::GlobalListener::new(keys)),
+                subs
             )
             .is_ok());
     }
@@ -1345,9 +1587,24 @@ impl AuthActivity {
                                     Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
                                         Id::BookmarkSavePassword,
                                     )))),
-                                    Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
-                                        Id::WaitPopup,
-                                    )))),
+                                    Box::new(SubClause::And(
+                                        Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+                                            Id::BookmarksFilterPopup,
+                                        )))),
+                                        Box::new(SubClause::And(
+                                            Box::new(SubClause::Not(Box::new(
+                                                SubClause::IsMounted(Id::QuickConnectInput),
+                                            ))),
+                                            Box::new(SubClause::And(
+                                                Box::new(SubClause::Not(Box::new(
+                                                    SubClause::IsMounted(Id::ImportBookmarksFormat),
+                                                ))),
+                                                Box::new(SubClause::Not(Box::new(
+                                                    SubClause::IsMounted(Id::WaitPopup),
+                                                ))),
+                                            )),
+                                        )),
+                                    )),
                                 )),
                             )),
                         )),
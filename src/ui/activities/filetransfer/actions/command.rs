@@ -0,0 +1,129 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+// locals
+use std::str::FromStr;
+
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use wildmatch::WildMatch;
+
+use super::super::components::FILE_LIST_SELECTION;
+use super::super::lib::command::Command;
+use super::{FileExplorerTab, FileTransferActivity, Id, Msg, TransferMsg, UiMsg};
+
+/// Maximum amount of commands kept in the command line history
+const COMMAND_HISTORY_SIZE: usize = 64;
+
+impl FileTransferActivity {
+    /// Run the command submitted in the command line.
+    /// Returns the message the command is dispatched to
+    pub(in crate::ui::activities::filetransfer) fn action_submit_command_line(
+        &mut self,
+        input: String,
+    ) -> Option<Msg> {
+        self.umount_command_line();
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        self.push_command_history(input);
+        let command = match Command::from_str(input) {
+            Ok(command) => command,
+            Err(err) => {
+                self.mount_error(format!("Invalid command: {err}"));
+                return None;
+            }
+        };
+        if matches!(
+            self.browser.tab(),
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote
+        ) && !command.supported_in_find()
+        {
+            self.mount_error(format!(r#""{input}" can't be run on search results"#));
+            return None;
+        }
+        let msg = match command {
+            Command::Cd(dir) => Msg::Transfer(TransferMsg::GoTo(dir)),
+            Command::CdPrevious => Msg::Transfer(TransferMsg::GoToPreviousDirectory),
+            Command::CdUp => Msg::Transfer(TransferMsg::GoToParentDirectory),
+            Command::Chmod(mode) => Msg::Transfer(TransferMsg::Chmod(mode)),
+            Command::Copy(dest) => Msg::Transfer(TransferMsg::CopyFileTo(dest)),
+            Command::Delete => Msg::Ui(UiMsg::ShowDeletePopup),
+            Command::Disconnect => Msg::Ui(UiMsg::Disconnect),
+            Command::Edit => Msg::Transfer(TransferMsg::OpenTextFile),
+            Command::Exec(cmd) => Msg::Transfer(TransferMsg::ExecuteCmd(cmd)),
+            Command::Find(search) => Msg::Transfer(TransferMsg::SearchFile(search)),
+            Command::Get(pattern) => {
+                return self.command_transfer(FileExplorerTab::Remote, pattern)
+            }
+            Command::Help => Msg::Ui(UiMsg::ShowKeybindingsPopup),
+            Command::Mkdir(dir) => Msg::Transfer(TransferMsg::Mkdir(dir)),
+            Command::NewFile(name) => Msg::Transfer(TransferMsg::NewFile(name)),
+            Command::Open => Msg::Transfer(TransferMsg::OpenFile),
+            Command::Put(pattern) => return self.command_transfer(FileExplorerTab::Local, pattern),
+            Command::Quit => Msg::Ui(UiMsg::Quit),
+            Command::Reload => Msg::Transfer(TransferMsg::ReloadDir),
+            Command::Rename(dest) => Msg::Transfer(TransferMsg::RenameFile(dest)),
+            Command::ReverseSorting => Msg::Ui(UiMsg::ToggleReverseSorting),
+            Command::SaveAs(dest) => Msg::Transfer(TransferMsg::SaveFileAs(dest)),
+            Command::Shell => Msg::Transfer(TransferMsg::OpenRemoteShell),
+            Command::Sort(sorting) => Msg::Ui(UiMsg::ChangeFileSorting(sorting)),
+            Command::Symlink(name) => Msg::Transfer(TransferMsg::CreateSymlink(name)),
+            Command::ToggleHiddenFiles => Msg::Ui(UiMsg::ToggleHiddenFiles),
+            Command::ToggleSyncBrowsing => Msg::Ui(UiMsg::ToggleSyncBrowsing),
+            Command::Undo => Msg::Transfer(TransferMsg::Undo),
+        };
+        Some(msg)
+    }
+
+    /// Transfer the files of the explorer in `tab`: the files matching `pattern`, if any,
+    /// otherwise the selected ones
+    fn command_transfer(&mut self, tab: FileExplorerTab, pattern: Option<String>) -> Option<Msg> {
+        if self.browser.found().is_some() {
+            self.mount_error("Close the search results before transferring files");
+            return None;
+        }
+        let id = match tab {
+            FileExplorerTab::Local => Id::ExplorerLocal,
+            _ => Id::ExplorerRemote,
+        };
+        if let Some(pattern) = pattern {
+            let filter = WildMatch::new(&pattern);
+            let explorer = match tab {
+                FileExplorerTab::Local => self.local(),
+                _ => self.remote(),
+            };
+            let entries: Vec<PropValue> = explorer
+                .iter_files()
+                .enumerate()
+                .filter(|(_, file)| filter.matches(&file.name()))
+                .map(|(i, _)| PropValue::Usize(i))
+                .collect();
+            if entries.is_empty() {
+                self.mount_info(format!(r#"Could not find any file matching "{pattern}""#));
+                return None;
+            }
+            assert!(self
+                .app
+                .attr(
+                    &id,
+                    Attribute::Custom(FILE_LIST_SELECTION),
+                    AttrValue::Payload(PropPayload::Vec(entries))
+                )
+                .is_ok());
+        }
+        assert!(self.app.active(&id).is_ok());
+        self.browser.change_tab(tab);
+        Some(Msg::Transfer(TransferMsg::TransferFile))
+    }
+
+    fn push_command_history(&mut self, input: &str) {
+        if self.command_history.last().map(String::as_str) != Some(input) {
+            self.command_history.push(input.to_string());
+        }
+        if self.command_history.len() > COMMAND_HISTORY_SIZE {
+            self.command_history.remove(0);
+        }
+    }
+}
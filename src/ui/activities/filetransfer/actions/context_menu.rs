@@ -0,0 +1,64 @@
+//! # context menu actions
+//!
+//! actions associated to the context menu of the selected entries
+
+use super::{FileExplorerTab, FileTransferActivity, Msg, SelectedFile, TransferMsg, UiMsg};
+use crate::filetransfer::FileTransferProtocol;
+use crate::ui::activities::filetransfer::lib::context_menu::{
+    self, ContextMenuEntry, ContextMenuTarget,
+};
+
+impl FileTransferActivity {
+    /// Show the actions which can be performed on the entries selected in the current explorer
+    pub(crate) fn action_show_context_menu(&mut self) {
+        let (remote, found, selection) = match self.browser.tab() {
+            FileExplorerTab::Local => (false, false, self.get_local_selected_entries()),
+            FileExplorerTab::Remote => (true, false, self.get_remote_selected_entries()),
+            FileExplorerTab::FindLocal => (false, true, self.get_found_selected_entries()),
+            FileExplorerTab::FindRemote => (true, true, self.get_found_selected_entries()),
+        };
+        // permissions and symlinks are supported only by SSH on remote
+        let ssh = matches!(
+            self.context().ft_params().map(|x| x.protocol),
+            Some(FileTransferProtocol::Scp | FileTransferProtocol::Sftp)
+        );
+        let (selected, dir, title) = match &selection {
+            SelectedFile::One(file) => (1, file.is_dir(), file.name()),
+            SelectedFile::Many(files) => (
+                files.len(),
+                false,
+                format!("{} files selected", files.len()),
+            ),
+            SelectedFile::None => (0, false, String::new()),
+        };
+        let target = ContextMenuTarget {
+            remote,
+            found,
+            selected,
+            dir,
+            mode: selection.unix_pex().is_some(),
+            chmod: if remote { ssh } else { cfg!(unix) },
+            symlink: if remote { ssh } else { cfg!(unix) },
+            watch: remote || self.fswatcher.is_some(),
+        };
+        let entries = context_menu::entries(&target);
+        if !entries.is_empty() {
+            self.mount_context_menu(title.as_str(), &entries, remote);
+        }
+    }
+}
+
+/// Get the message dispatched when `entry` is chosen from the context menu
+pub(crate) fn context_menu_entry_msg(entry: ContextMenuEntry) -> Msg {
+    match entry {
+        ContextMenuEntry::Chmod => Msg::Ui(UiMsg::ShowChmodPopup),
+        ContextMenuEntry::Copy => Msg::Ui(UiMsg::ShowCopyPopup),
+        ContextMenuEntry::Delete => Msg::Ui(UiMsg::ShowDeletePopup),
+        ContextMenuEntry::FileInfo => Msg::Ui(UiMsg::ShowFileInfoPopup),
+        ContextMenuEntry::OpenWith => Msg::Ui(UiMsg::ShowOpenWithPopup),
+        ContextMenuEntry::Rename => Msg::Ui(UiMsg::ShowRenamePopup),
+        ContextMenuEntry::Symlink => Msg::Ui(UiMsg::ShowSymlinkPopup),
+        ContextMenuEntry::Transfer => Msg::Transfer(TransferMsg::TransferFile),
+        ContextMenuEntry::Watch => Msg::Ui(UiMsg::ShowWatcherPopup),
+    }
+}
@@ -0,0 +1,80 @@
+//! # custom actions
+//!
+//! actions associated to the commands defined by the user in the configuration
+
+use remotefs::File;
+
+use super::exec::local_quote;
+use super::{FileExplorerTab, FileTransferActivity, LogLevel};
+use crate::config::custom_actions::CustomAction;
+use crate::ui::activities::filetransfer::lib::exec::ExecOutput;
+use crate::utils::string::shell_quote;
+
+impl FileTransferActivity {
+    /// Show the custom actions which can be executed on the files selected in the current explorer
+    pub(crate) fn action_show_custom_actions(&mut self) {
+        let actions = self.config().get_custom_actions();
+        let (remote, files) = self.custom_action_files();
+        let files: Vec<&File> = files.iter().collect();
+        let available: Vec<(usize, &CustomAction)> = actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| action.applies_to(remote, &files))
+            .collect();
+        match (actions.is_empty(), available.is_empty()) {
+            (true, _) => self.mount_info("No custom action is defined in the configuration"),
+            (false, true) => self.mount_info("No custom action applies to the selected files"),
+            (false, false) => self.mount_custom_actions(&available),
+        }
+    }
+
+    /// Get the name of the custom action at `idx`, if it must be confirmed before running it
+    pub(crate) fn custom_action_to_confirm(&self, idx: usize) -> Option<String> {
+        self.config()
+            .get_custom_actions()
+            .into_iter()
+            .nth(idx)
+            .filter(|action| action.confirm)
+            .map(|action| action.name)
+    }
+
+    /// Run the custom action at `idx` on the files selected in the current explorer.
+    /// Returns the output of the executed commands
+    pub(crate) fn action_run_custom_action(&mut self, idx: usize) -> Vec<ExecOutput> {
+        let action = match self.config().get_custom_actions().into_iter().nth(idx) {
+            Some(action) => action,
+            None => return Vec::new(),
+        };
+        let (remote, files) = self.custom_action_files();
+        let files: Vec<&File> = files.iter().collect();
+        if !action.applies_to(remote, &files) {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!(
+                    "Could not run \"{}\": the action doesn't apply to the selected files",
+                    action.name
+                ),
+            );
+            return Vec::new();
+        }
+        let commands = match remote {
+            true => action.commands(&files, shell_quote),
+            false => action.commands(&files, local_quote),
+        };
+        self.log(
+            LogLevel::Info,
+            format!("Running custom action \"{}\"", action.name),
+        );
+        self.exec_commands(commands, remote)
+    }
+
+    /// Get whether the current explorer is on the remote host and its selected files
+    fn custom_action_files(&self) -> (bool, Vec<File>) {
+        match self.browser.tab() {
+            FileExplorerTab::Local => (false, self.get_local_selected_entries().get_files()),
+            FileExplorerTab::Remote => (true, self.get_remote_selected_entries().get_files()),
+            FileExplorerTab::FindLocal => (false, self.get_found_selected_entries().get_files()),
+            FileExplorerTab::FindRemote => (true, self.get_found_selected_entries().get_files()),
+        }
+    }
+}
@@ -3,46 +3,71 @@
 //! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
 
 // locals
+use std::path::PathBuf;
+
+use chrono::Local;
+use remotefs::fs::UnixPex;
 use remotefs::File;
 
+use super::super::lib::undo::{remote_trash_path, UndoOp, REMOTE_TRASH_DIR};
 use super::{FileTransferActivity, LogLevel, SelectedFile};
+use crate::host::{HostErrorType, Localhost};
 
 impl FileTransferActivity {
     pub(crate) fn action_local_delete(&mut self) {
-        match self.get_local_selected_entries() {
+        let ops: Vec<UndoOp> = match self.get_local_selected_entries() {
             SelectedFile::One(entry) => {
                 // Delete file
-                self.local_remove_file(&entry);
+                self.local_remove_file(&entry).into_iter().collect()
             }
             SelectedFile::Many(entries) => {
                 // Iter files
-                for entry in entries.iter() {
-                    // Delete file
-                    self.local_remove_file(entry);
-                }
+                entries
+                    .iter()
+                    .filter_map(|entry| self.local_remove_file(entry))
+                    .collect()
             }
-            SelectedFile::None => {}
-        }
+            SelectedFile::None => Vec::new(),
+        };
+        self.undo.push(ops);
     }
 
     pub(crate) fn action_remote_delete(&mut self) {
-        match self.get_remote_selected_entries() {
+        let ops: Vec<UndoOp> = match self.get_remote_selected_entries() {
             SelectedFile::One(entry) => {
                 // Delete file
-                self.remote_remove_file(&entry);
+                self.remote_remove_file(&entry).into_iter().collect()
             }
             SelectedFile::Many(entries) => {
                 // Iter files
-                for entry in entries.iter() {
-                    // Delete file
-                    self.remote_remove_file(entry);
-                }
+                entries
+                    .iter()
+                    .filter_map(|entry| self.remote_remove_file(entry))
+                    .collect()
             }
-            SelectedFile::None => {}
+            SelectedFile::None => Vec::new(),
+        };
+        self.undo.push(ops);
+    }
+
+    /// Delete `entry` from localhost, moving it to the trash if enabled.
+    /// Returns how to restore the entry, if it can be restored
+    pub(crate) fn local_remove_file(&mut self, entry: &File) -> Option<UndoOp> {
+        if self.config().get_local_trash() {
+            return self.local_trash_file(entry);
+        }
+        self.local_remove_file_permanently(entry);
+        None
+    }
+
+    /// Delete permanently the local files which couldn't be moved to the trash
+    pub(crate) fn action_local_delete_untrashed(&mut self) {
+        for entry in std::mem::take(&mut self.untrashed).iter() {
+            self.local_remove_file_permanently(entry);
         }
     }
 
-    pub(crate) fn local_remove_file(&mut self, entry: &File) {
+    fn local_remove_file_permanently(&mut self, entry: &File) {
         match self.host.remove(entry) {
             Ok(_) => {
                 // Log
@@ -64,7 +89,17 @@ impl FileTransferActivity {
         }
     }
 
-    pub(crate) fn remote_remove_file(&mut self, entry: &File) {
+    /// Delete `entry` from the remote host, moving it to the remote trash if enabled.
+    /// Returns how to restore the entry, if it can be restored
+    pub(crate) fn remote_remove_file(&mut self, entry: &File) -> Option<UndoOp> {
+        if self.config().get_remote_trash() {
+            return self.remote_trash_file(entry);
+        }
+        self.remote_remove_file_permanently(entry);
+        None
+    }
+
+    fn remote_remove_file_permanently(&mut self, entry: &File) {
         match self.client.remove_dir_all(entry.path()) {
             Ok(_) => {
                 self.log(
@@ -84,4 +119,129 @@ impl FileTransferActivity {
             }
         }
     }
+
+    fn local_trash_file(&mut self, entry: &File) -> Option<UndoOp> {
+        match self.host.trash(entry) {
+            Ok(_) if Localhost::can_restore_from_trash() => {
+                self.log(
+                    LogLevel::Info,
+                    format!(
+                        "Moved \"{}\" to trash; it can be restored with undo",
+                        entry.path().display()
+                    ),
+                );
+                Some(UndoOp::LocalTrash {
+                    path: entry.path().to_path_buf(),
+                })
+            }
+            Ok(_) => {
+                self.log(
+                    LogLevel::Info,
+                    format!("Moved \"{}\" to trash", entry.path().display()),
+                );
+                None
+            }
+            Err(err) if matches!(err.error, HostErrorType::TrashFailed) => {
+                // i.e. there's no trash on the volume of the file: the user is asked whether to delete it permanently
+                self.log(
+                    LogLevel::Warn,
+                    format!(
+                        "Could not move file \"{}\" to trash: {}",
+                        entry.path().display(),
+                        err
+                    ),
+                );
+                self.untrashed.push(entry.clone());
+                None
+            }
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Could not move file \"{}\" to trash: {}",
+                        entry.path().display(),
+                        err
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    fn remote_trash_file(&mut self, entry: &File) -> Option<UndoOp> {
+        let trash_dir = match self.remote_trash_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Could not move file \"{}\" to trash: {}",
+                        entry.path().display(),
+                        err
+                    ),
+                );
+                return None;
+            }
+        };
+        // Entries deleted from the trash are gone for good
+        if entry.path().starts_with(trash_dir.as_path()) {
+            self.remote_remove_file_permanently(entry);
+            return None;
+        }
+        let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let mut attempt = 0;
+        let trashed = loop {
+            let path = remote_trash_path(trash_dir.as_path(), &entry.name(), &stamp, attempt);
+            if !self.client.exists(path.as_path()).unwrap_or(false) {
+                break path;
+            }
+            attempt += 1;
+        };
+        match self.client.as_mut().mov(entry.path(), trashed.as_path()) {
+            Ok(_) => {
+                self.log(
+                    LogLevel::Info,
+                    format!(
+                        "Moved \"{}\" to \"{}\"; it can be restored with undo",
+                        entry.path().display(),
+                        trashed.display()
+                    ),
+                );
+                Some(UndoOp::RemoteTrash {
+                    path: entry.path().to_path_buf(),
+                    trashed,
+                })
+            }
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Could not move file \"{}\" to trash: {}",
+                        entry.path().display(),
+                        err
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    /// Get the path of the remote trash, in the remote home directory. The directory is created if it doesn't exist
+    fn remote_trash_dir(&mut self) -> Result<PathBuf, String> {
+        let trash_dir = self
+            .remote_home
+            .as_ref()
+            .map(|home| home.join(REMOTE_TRASH_DIR))
+            .ok_or_else(|| String::from("the remote home directory is unknown"))?;
+        match self.client.exists(trash_dir.as_path()) {
+            Ok(true) => Ok(trash_dir),
+            Ok(false) => self
+                .client
+                .as_mut()
+                .create_dir(trash_dir.as_path(), UnixPex::from(0o700))
+                .map(|_| trash_dir)
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
 }
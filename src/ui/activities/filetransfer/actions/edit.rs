@@ -106,6 +106,11 @@ impl FileTransferActivity {
             if let Err(err) = ctx.terminal().enter_alternate_screen() {
                 error!("Could not enter alternate screen: {}", err);
             }
+            // Entering alternate screen enables mouse capture
+            let mouse_support = ctx.config().get_mouse_support();
+            if let Err(err) = ctx.set_mouse_capture(mouse_support) {
+                error!("Failed to set mouse capture: {}", err);
+            }
             // Re-enable raw mode
             if let Err(err) = ctx.terminal().enable_raw_mode() {
                 error!("Failed to enter raw mode: {}", err);
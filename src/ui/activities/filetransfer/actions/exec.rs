@@ -4,40 +4,119 @@
 
 // locals
 use super::{FileTransferActivity, LogLevel};
+use crate::ui::activities::filetransfer::lib::exec::{self, ExecOutput};
+use crate::utils::string::shell_quote;
+
+/// Maximum amount of commands kept in the exec history
+const EXEC_HISTORY_SIZE: usize = 64;
 
 impl FileTransferActivity {
-    pub(crate) fn action_local_exec(&mut self, input: String) {
-        match self.host.exec(input.as_str()) {
-            Ok(output) => {
-                // Reload files
-                self.log(LogLevel::Info, format!("\"{input}\": {output}"));
-            }
-            Err(err) => {
-                // Report err
-                self.log_and_alert(
-                    LogLevel::Error,
-                    format!("Could not execute command \"{input}\": {err}"),
-                );
-            }
+    /// Execute `input` on localhost, once for each selected file if it contains `{}`.
+    /// Returns the output of the executed commands
+    pub(crate) fn action_local_exec(&mut self, input: String) -> Vec<ExecOutput> {
+        self.push_exec_history(&input);
+        let files: Vec<String> = match exec::has_file_placeholder(&input) {
+            true => self
+                .get_local_selected_entries()
+                .get_files()
+                .iter()
+                .map(|x| local_quote(&x.path().to_string_lossy()))
+                .collect(),
+            false => Vec::new(),
+        };
+        let commands = match self.expand_exec(&input, &files) {
+            Some(commands) => commands,
+            None => return Vec::new(),
+        };
+        self.exec_commands(commands, false)
+    }
+
+    /// Execute `input` on the remote host, once for each selected file if it contains `{}`.
+    /// Returns the output of the executed commands
+    pub(crate) fn action_remote_exec(&mut self, input: String) -> Vec<ExecOutput> {
+        self.push_exec_history(&input);
+        let files: Vec<String> = match exec::has_file_placeholder(&input) {
+            true => self
+                .get_remote_selected_entries()
+                .get_files()
+                .iter()
+                .map(|x| shell_quote(&x.path().to_string_lossy()))
+                .collect(),
+            false => Vec::new(),
+        };
+        let commands = match self.expand_exec(&input, &files) {
+            Some(commands) => commands,
+            None => return Vec::new(),
+        };
+        self.exec_commands(commands, true)
+    }
+
+    /// Get the commands to execute for `input`, replacing `{}` with each of the `files`.
+    /// Reports an error if `{}` is used but no file is selected
+    fn expand_exec(&mut self, input: &str, files: &[String]) -> Option<Vec<String>> {
+        let commands = exec::expand_file_placeholder(input, files);
+        if commands.is_none() {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not execute command \"{input}\": {{}} requires a selected file"),
+            );
         }
+        commands
     }
 
-    pub(crate) fn action_remote_exec(&mut self, input: String) {
-        match self.client.as_mut().exec(input.as_str()) {
-            Ok((rc, output)) => {
-                // Reload files
-                self.log(
-                    LogLevel::Info,
-                    format!("\"{input}\" (exitcode: {rc}): {output}"),
-                );
-            }
-            Err(err) => {
-                // Report err
-                self.log_and_alert(
-                    LogLevel::Error,
-                    format!("Could not execute command \"{input}\": {err}"),
-                );
+    /// Execute `commands` on the remote host or on localhost, logging their exit code.
+    /// If no command could be executed, the error is reported
+    pub(crate) fn exec_commands(&mut self, commands: Vec<String>, remote: bool) -> Vec<ExecOutput> {
+        let mut outputs = Vec::with_capacity(commands.len());
+        let mut error = None;
+        for cmd in commands.into_iter() {
+            let result = match remote {
+                true => self
+                    .client
+                    .exec_output(cmd.as_str())
+                    .map_err(|e| e.to_string()),
+                false => self
+                    .host
+                    .exec_shell(cmd.as_str())
+                    .map_err(|e| e.to_string()),
+            };
+            match result {
+                Ok((rc, stdout, stderr)) => {
+                    self.log(LogLevel::Info, format!("\"{cmd}\" (exitcode: {rc})"));
+                    outputs.push(ExecOutput::new(cmd, rc, stdout, stderr));
+                }
+                Err(err) => {
+                    let err = format!("Could not execute command \"{cmd}\": {err}");
+                    self.log(LogLevel::Error, err.clone());
+                    error = Some(err);
+                }
             }
         }
+        // Report err, if no output can be shown
+        if let (true, Some(err)) = (outputs.is_empty(), error) {
+            self.mount_error(err);
+        }
+        outputs
     }
+
+    fn push_exec_history(&mut self, input: &str) {
+        if self.exec_history.last().map(String::as_str) != Some(input) {
+            self.exec_history.push(input.to_string());
+        }
+        if self.exec_history.len() > EXEC_HISTORY_SIZE {
+            self.exec_history.remove(0);
+        }
+    }
+}
+
+/// Quote `arg` to be used as an argument of a local shell command
+#[cfg(unix)]
+pub(super) fn local_quote(arg: &str) -> String {
+    shell_quote(arg)
+}
+
+/// Quote `arg` to be used as an argument of a local shell command
+#[cfg(windows)]
+pub(super) fn local_quote(arg: &str) -> String {
+    format!("\"{arg}\"")
 }
@@ -0,0 +1,143 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+// locals
+use std::io::{Read, Seek, SeekFrom};
+
+use remotefs::RemoteErrorType;
+
+use super::{File, FileTransferActivity, LogLevel};
+use crate::filetransfer::FileTransferProtocol;
+use crate::utils::media::{
+    is_image_name, is_media_name, ImagePreview, MediaInfo, IMAGE_PREVIEW_MAX_SIZE,
+    MEDIA_HEADER_SIZE,
+};
+
+/// Image preview width in columns
+const PREVIEW_WIDTH: u32 = 48;
+/// Image preview height in rows
+const PREVIEW_HEIGHT: u32 = 16;
+
+impl FileTransferActivity {
+    /// Show file info popup for a local file, reading its media properties
+    pub(crate) fn action_local_file_info(&mut self, file: &File) {
+        let (media, preview) = match file.is_file() {
+            true => match self.host.open_file_read(file.path()) {
+                Ok(mut reader) => Self::read_media(&mut reader, file.metadata().size),
+                Err(err) => {
+                    self.log(
+                        LogLevel::Warn,
+                        format!("Could not read {}: {}", file.path().display(), err),
+                    );
+                    (None, None)
+                }
+            },
+            false => (None, None),
+        };
+        self.mount_file_info(file, media.as_ref(), preview.as_ref());
+    }
+
+    /// Show file info popup for a remote file, reading its media properties if its name is the one of a media file.
+    /// Only the file header is fetched if the protocol supports streams; otherwise images are
+    /// entirely downloaded, as long as they're not bigger than `IMAGE_PREVIEW_MAX_SIZE`.
+    pub(crate) fn action_remote_file_info(&mut self, file: &File) {
+        let (media, preview) = match file.is_file() && is_media_name(&file.name()) {
+            true => {
+                self.mount_blocking_wait("Reading file…");
+                let media = self.read_remote_media(file);
+                self.umount_wait();
+                media
+            }
+            false => (None, None),
+        };
+        self.mount_file_info(file, media.as_ref(), preview.as_ref());
+    }
+
+    fn read_remote_media(&mut self, file: &File) -> (Option<MediaInfo>, Option<ImagePreview>) {
+        let size = file.metadata().size;
+        // FTP can't abort a transfer: the stream must be read to the end, so large files are skipped
+        let must_drain = matches!(
+            self.context().ft_params().map(|x| x.protocol),
+            Some(FileTransferProtocol::Ftp(_))
+        );
+        if must_drain && size > IMAGE_PREVIEW_MAX_SIZE {
+            return (None, None);
+        }
+        match self.client.open(file.path()) {
+            Ok(mut reader) => {
+                let media = Self::read_media(&mut reader, size);
+                if must_drain {
+                    if let Err(err) = std::io::copy(&mut reader, &mut std::io::sink()) {
+                        debug!("Could not drain remote stream: {}", err);
+                    }
+                }
+                if let Err(err) = self.client.on_read(reader) {
+                    debug!("Could not finalize remote stream: {}", err);
+                }
+                media
+            }
+            Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                // Never download an entire file, unless it's an image small enough to be previewed
+                if !is_image_name(&file.name()) || size > IMAGE_PREVIEW_MAX_SIZE {
+                    return (None, None);
+                }
+                let result = tempfile::tempfile().and_then(|tmp| Ok((tmp.try_clone()?, tmp)));
+                let (writer, mut reader) = match result {
+                    Ok(files) => files,
+                    Err(err) => {
+                        self.log(
+                            LogLevel::Error,
+                            format!("Could not create temporary file: {err}"),
+                        );
+                        return (None, None);
+                    }
+                };
+                if let Err(err) = self.client.open_file(file.path(), Box::new(writer)) {
+                    self.log(
+                        LogLevel::Warn,
+                        format!("Could not read {}: {}", file.path().display(), err),
+                    );
+                    return (None, None);
+                }
+                match reader.seek(SeekFrom::Start(0)) {
+                    Ok(_) => Self::read_media(&mut reader, size),
+                    Err(_) => (None, None),
+                }
+            }
+            Err(err) => {
+                self.log(
+                    LogLevel::Warn,
+                    format!("Could not read {}: {}", file.path().display(), err),
+                );
+                (None, None)
+            }
+        }
+    }
+
+    /// Read media properties from reader.
+    /// Only the header is read, unless the file is an image small enough to be previewed
+    fn read_media(reader: &mut dyn Read, size: u64) -> (Option<MediaInfo>, Option<ImagePreview>) {
+        let mut data: Vec<u8> = Vec::with_capacity(MEDIA_HEADER_SIZE);
+        if let Err(err) = reader.take(MEDIA_HEADER_SIZE as u64).read_to_end(&mut data) {
+            debug!("Could not read file header: {}", err);
+            return (None, None);
+        }
+        let media = match MediaInfo::from_header(&data, size) {
+            Some(media) => media,
+            None => return (None, None),
+        };
+        if !media.image || size > IMAGE_PREVIEW_MAX_SIZE {
+            return (Some(media), None);
+        }
+        if let Err(err) = reader
+            .take(IMAGE_PREVIEW_MAX_SIZE - data.len() as u64)
+            .read_to_end(&mut data)
+        {
+            debug!("Could not read image: {}", err);
+            return (Some(media), None);
+        }
+        let preview = ImagePreview::new(&data, PREVIEW_WIDTH, PREVIEW_HEIGHT);
+        (Some(media), preview)
+    }
+}
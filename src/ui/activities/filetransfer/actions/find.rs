@@ -6,6 +6,7 @@
 use std::path::PathBuf;
 
 use super::super::browser::FileExplorerTab;
+use super::super::lib::undo::UndoOp;
 use super::{File, FileTransferActivity, LogLevel, SelectedFile, TransferOpts, TransferPayload};
 
 impl FileTransferActivity {
@@ -174,30 +175,27 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_find_delete(&mut self) {
-        match self.get_found_selected_entries() {
+        let ops: Vec<UndoOp> = match self.get_found_selected_entries() {
             SelectedFile::One(entry) => {
                 // Delete file
-                self.remove_found_file(&entry);
+                self.remove_found_file(&entry).into_iter().collect()
             }
             SelectedFile::Many(entries) => {
                 // Iter files
-                for entry in entries.iter() {
-                    // Delete file
-                    self.remove_found_file(entry);
-                }
+                entries
+                    .iter()
+                    .filter_map(|entry| self.remove_found_file(entry))
+                    .collect()
             }
-            SelectedFile::None => {}
-        }
+            SelectedFile::None => Vec::new(),
+        };
+        self.undo.push(ops);
     }
 
-    fn remove_found_file(&mut self, entry: &File) {
+    fn remove_found_file(&mut self, entry: &File) -> Option<UndoOp> {
         match self.browser.tab() {
-            FileExplorerTab::FindLocal | FileExplorerTab::Local => {
-                self.local_remove_file(entry);
-            }
-            FileExplorerTab::FindRemote | FileExplorerTab::Remote => {
-                self.remote_remove_file(entry);
-            }
+            FileExplorerTab::FindLocal | FileExplorerTab::Local => self.local_remove_file(entry),
+            FileExplorerTab::FindRemote | FileExplorerTab::Remote => self.remote_remove_file(entry),
         }
     }
 
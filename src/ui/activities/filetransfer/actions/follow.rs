@@ -0,0 +1,181 @@
+//! # follow actions
+//!
+//! actions associated to following a remote file, as with `tail -f`
+
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use remotefs::RemoteErrorType;
+
+use super::{FileTransferActivity, LogLevel, SelectedFile};
+use crate::filetransfer::FileTransferProtocol;
+use crate::ui::activities::filetransfer::lib::follow::{self, Follower};
+
+/// Highlight patterns suggested when following a file
+const DEFAULT_HIGHLIGHT_PATTERNS: &str = "ERROR, WARN";
+/// Interval between the polls of the followed file
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+impl FileTransferActivity {
+    pub(crate) fn action_show_follow_highlight(&mut self) {
+        if let SelectedFile::One(file) = self.get_remote_selected_entries() {
+            if file.is_file() {
+                self.mount_follow_highlight(
+                    file.path().to_string_lossy().to_string().as_str(),
+                    DEFAULT_HIGHLIGHT_PATTERNS,
+                );
+            }
+        }
+    }
+
+    /// Follow the selected remote file, highlighting the lines containing the comma-separated `patterns`
+    pub(crate) fn action_follow(&mut self, patterns: &str) {
+        self.umount_follow_highlight();
+        let file = match self.get_remote_selected_entries() {
+            SelectedFile::One(file) if file.is_file() => file,
+            _ => return,
+        };
+        let highlight: Vec<String> = patterns
+            .split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.follower = Some(Follower::new(
+            file.path(),
+            file.metadata().size,
+            highlight,
+            FOLLOW_INTERVAL,
+        ));
+        self.log(
+            LogLevel::Info,
+            format!("following {}", file.path().display()),
+        );
+        self.poll_follower();
+        self.mount_follow();
+    }
+
+    pub(crate) fn action_toggle_follow_pause(&mut self) {
+        if let Some(follower) = self.follower.as_mut() {
+            follower.toggle_pause();
+            self.mount_follow();
+        }
+    }
+
+    pub(crate) fn action_close_follow(&mut self) {
+        self.umount_follow();
+        if let Some(follower) = self.follower.take() {
+            self.log(
+                LogLevel::Info,
+                format!("stopped following {}", follower.path().display()),
+            );
+        }
+    }
+
+    /// Read the bytes appended to the followed file, if it's time to
+    pub(crate) fn poll_follower(&mut self) {
+        let path = match self.follower.as_ref() {
+            Some(follower) if follower.due() => follower.path().to_path_buf(),
+            _ => return,
+        };
+        if !self.client.is_connected() {
+            return;
+        }
+        if let Some(follower) = self.follower.as_mut() {
+            follower.polled();
+        }
+        let size = match self.client.stat(path.as_path()) {
+            Ok(file) => file.metadata().size,
+            Err(err) => {
+                self.log(
+                    LogLevel::Error,
+                    format!("could not stat followed file {}: {}", path.display(), err),
+                );
+                return;
+            }
+        };
+        let (offset, len) = match self.follower.as_mut() {
+            Some(follower) => {
+                let len = follower.resize(size);
+                (follower.offset(), len)
+            }
+            None => return,
+        };
+        if len == 0 {
+            return;
+        }
+        match self.read_remote_from(path.as_path(), offset) {
+            Ok(data) => {
+                if let Some(follower) = self.follower.as_mut() {
+                    follower.append(&data);
+                }
+                self.mount_follow();
+            }
+            Err(err) => {
+                self.log(
+                    LogLevel::Error,
+                    format!("could not read followed file {}: {}", path.display(), err),
+                );
+            }
+        }
+    }
+
+    /// Read the remote file at `path` from `offset` to its end.
+    /// On SSH remotes `od` is executed, otherwise the file is read from the offset, or downloaded
+    /// again if the protocol doesn't support seeking
+    fn read_remote_from(&mut self, path: &Path, offset: u64) -> Result<Vec<u8>, String> {
+        let ssh = matches!(
+            self.context().ft_params().map(|x| x.protocol),
+            Some(FileTransferProtocol::Scp | FileTransferProtocol::Sftp)
+        );
+        if ssh {
+            let cmd = follow::tail_command(path, offset);
+            match self.client.exec(cmd.as_str()) {
+                Ok((rc, output)) => match (rc, follow::parse_tail_output(&output)) {
+                    (0, Some(data)) => return Ok(data),
+                    _ => self.log(
+                        LogLevel::Warn,
+                        format!("\"{cmd}\" (exitcode: {rc}): {output}; reading the file instead"),
+                    ),
+                },
+                Err(err) => {
+                    self.log(
+                        LogLevel::Warn,
+                        format!("could not execute \"{cmd}\": {err}; reading the file instead"),
+                    );
+                }
+            }
+        }
+        match self.client.open(path) {
+            Ok(mut stream) => {
+                let mut data = Vec::new();
+                let result = match stream.seekable() {
+                    true => stream.seek(SeekFrom::Start(offset)).map(|_| ()),
+                    false => io::copy(&mut (&mut stream).take(offset), &mut io::sink()).map(|_| ()),
+                }
+                .and_then(|_| stream.read_to_end(&mut data));
+                if let Err(err) = self.client.on_read(stream) {
+                    self.log(
+                        LogLevel::Warn,
+                        format!("Could not finalize remote stream: \"{err}\""),
+                    );
+                }
+                result.map(|_| data).map_err(|e| e.to_string())
+            }
+            Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                let mut tmp = tempfile::tempfile().map_err(|e| e.to_string())?;
+                let writer = tmp.try_clone().map_err(|e| e.to_string())?;
+                self.client
+                    .open_file(path, Box::new(writer))
+                    .map_err(|e| e.to_string())?;
+                let mut data = Vec::new();
+                tmp.seek(SeekFrom::Start(offset))
+                    .and_then(|_| tmp.read_to_end(&mut data))
+                    .map_err(|e| e.to_string())?;
+                Ok(data)
+            }
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
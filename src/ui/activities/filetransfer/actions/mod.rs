@@ -15,19 +15,26 @@ use super::{
 // actions
 pub(crate) mod change_dir;
 pub(crate) mod chmod;
+pub(crate) mod command;
+pub(crate) mod context_menu;
 pub(crate) mod copy;
+pub(crate) mod custom_actions;
 pub(crate) mod delete;
 pub(crate) mod edit;
 pub(crate) mod exec;
+pub(crate) mod file_info;
 pub(crate) mod find;
+pub(crate) mod follow;
 pub(crate) mod mkdir;
 pub(crate) mod newfile;
 pub(crate) mod open;
 mod pending;
 pub(crate) mod rename;
 pub(crate) mod save;
+pub(crate) mod shell;
 pub(crate) mod submit;
 pub(crate) mod symlink;
+pub(crate) mod undo;
 pub(crate) mod watcher;
 
 #[derive(Debug)]
@@ -41,6 +41,7 @@ impl FileTransferActivity {
                     error!("Application error: {}", err);
                 }
             }
+            self.poll_mouse();
             // Redraw
             if self.redraw {
                 self.view();
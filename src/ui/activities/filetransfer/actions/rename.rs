@@ -7,50 +7,66 @@ use std::path::{Path, PathBuf};
 
 use remotefs::RemoteErrorType;
 
+use super::super::lib::undo::UndoOp;
 use super::{File, FileTransferActivity, LogLevel, SelectedFile};
+use crate::utils::path;
 
 impl FileTransferActivity {
     pub(crate) fn action_local_rename(&mut self, input: String) {
-        match self.get_local_selected_entries() {
+        let ops: Vec<UndoOp> = match self.get_local_selected_entries() {
             SelectedFile::One(entry) => {
                 let dest_path: PathBuf = PathBuf::from(input);
-                self.local_rename_file(&entry, dest_path.as_path());
+                self.local_rename_file(&entry, dest_path.as_path())
+                    .into_iter()
+                    .collect()
             }
             SelectedFile::Many(entries) => {
                 // Try to copy each file to Input/{FILE_NAME}
                 let base_path: PathBuf = PathBuf::from(input);
                 // Iter files
-                for entry in entries.iter() {
-                    let mut dest_path: PathBuf = base_path.clone();
-                    dest_path.push(entry.name());
-                    self.local_rename_file(entry, dest_path.as_path());
-                }
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let mut dest_path: PathBuf = base_path.clone();
+                        dest_path.push(entry.name());
+                        self.local_rename_file(entry, dest_path.as_path())
+                    })
+                    .collect()
             }
-            SelectedFile::None => {}
-        }
+            SelectedFile::None => Vec::new(),
+        };
+        self.undo.push(ops);
     }
 
     pub(crate) fn action_remote_rename(&mut self, input: String) {
-        match self.get_remote_selected_entries() {
+        let ops: Vec<UndoOp> = match self.get_remote_selected_entries() {
             SelectedFile::One(entry) => {
                 let dest_path: PathBuf = PathBuf::from(input);
-                self.remote_rename_file(&entry, dest_path.as_path());
+                self.remote_rename_file(&entry, dest_path.as_path())
+                    .into_iter()
+                    .collect()
             }
             SelectedFile::Many(entries) => {
                 // Try to copy each file to Input/{FILE_NAME}
                 let base_path: PathBuf = PathBuf::from(input);
                 // Iter files
-                for entry in entries.iter() {
-                    let mut dest_path: PathBuf = base_path.clone();
-                    dest_path.push(entry.name());
-                    self.remote_rename_file(entry, dest_path.as_path());
-                }
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let mut dest_path: PathBuf = base_path.clone();
+                        dest_path.push(entry.name());
+                        self.remote_rename_file(entry, dest_path.as_path())
+                    })
+                    .collect()
             }
-            SelectedFile::None => {}
-        }
+            SelectedFile::None => Vec::new(),
+        };
+        self.undo.push(ops);
     }
 
-    fn local_rename_file(&mut self, entry: &File, dest: &Path) {
+    /// Move `entry` to `dest` on localhost.
+    /// Returns how to revert the move, if it succeeded
+    pub(crate) fn local_rename_file(&mut self, entry: &File, dest: &Path) -> Option<UndoOp> {
         match self.host.rename(entry, dest) {
             Ok(_) => {
                 self.log(
@@ -61,21 +77,31 @@ impl FileTransferActivity {
                         dest.display()
                     ),
                 );
+                Some(UndoOp::Move {
+                    remote: false,
+                    from: entry.path().to_path_buf(),
+                    to: path::absolutize(self.host.pwd().as_path(), dest),
+                })
+            }
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Could not move \"{}\" to \"{}\": {}",
+                        entry.path().display(),
+                        dest.display(),
+                        err
+                    ),
+                );
+                None
             }
-            Err(err) => self.log_and_alert(
-                LogLevel::Error,
-                format!(
-                    "Could not move \"{}\" to \"{}\": {}",
-                    entry.path().display(),
-                    dest.display(),
-                    err
-                ),
-            ),
         }
     }
 
-    pub(crate) fn remote_rename_file(&mut self, entry: &File, dest: &Path) {
-        match self.client.as_mut().mov(entry.path(), dest) {
+    /// Move `entry` to `dest` on the remote host.
+    /// Returns how to revert the move, if it succeeded
+    pub(crate) fn remote_rename_file(&mut self, entry: &File, dest: &Path) -> Option<UndoOp> {
+        let moved = match self.client.as_mut().mov(entry.path(), dest) {
             Ok(_) => {
                 self.log(
                     LogLevel::Info,
@@ -85,25 +111,35 @@ impl FileTransferActivity {
                         dest.display()
                     ),
                 );
+                true
             }
             Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
-                self.tricky_move(entry, dest);
+                self.tricky_move(entry, dest)
             }
-            Err(err) => self.log_and_alert(
-                LogLevel::Error,
-                format!(
-                    "Could not move \"{}\" to \"{}\": {}",
-                    entry.path().display(),
-                    dest.display(),
-                    err
-                ),
-            ),
-        }
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Could not move \"{}\" to \"{}\": {}",
+                        entry.path().display(),
+                        dest.display(),
+                        err
+                    ),
+                );
+                false
+            }
+        };
+        moved.then(|| UndoOp::Move {
+            remote: true,
+            from: entry.path().to_path_buf(),
+            to: path::absolutize(self.remote().wrkdir.as_path(), dest),
+        })
     }
 
     /// Tricky move will be used whenever copy command is not available on remote host.
-    /// It basically uses the tricky_copy function, then it just deletes the previous entry (`entry`)
-    fn tricky_move(&mut self, entry: &File, dest: &Path) {
+    /// It basically uses the tricky_copy function, then it just deletes the previous entry (`entry`).
+    /// Returns whether the entry has been moved
+    pub(super) fn tricky_move(&mut self, entry: &File, dest: &Path) -> bool {
         debug!(
             "Using tricky-move to move entry {} to {}",
             entry.path().display(),
@@ -113,26 +149,33 @@ impl FileTransferActivity {
             // Delete remote existing entry
             debug!("Tricky-copy worked; removing existing remote entry");
             match self.client.remove_dir_all(entry.path()) {
-                Ok(_) => self.log(
-                    LogLevel::Info,
-                    format!(
-                        "Moved \"{}\" to \"{}\"",
-                        entry.path().display(),
-                        dest.display()
-                    ),
-                ),
-                Err(err) => self.log_and_alert(
-                    LogLevel::Error,
-                    format!(
-                        "Copied \"{}\" to \"{}\"; but failed to remove src: {}",
-                        entry.path().display(),
-                        dest.display(),
-                        err
-                    ),
-                ),
+                Ok(_) => {
+                    self.log(
+                        LogLevel::Info,
+                        format!(
+                            "Moved \"{}\" to \"{}\"",
+                            entry.path().display(),
+                            dest.display()
+                        ),
+                    );
+                    true
+                }
+                Err(err) => {
+                    self.log_and_alert(
+                        LogLevel::Error,
+                        format!(
+                            "Copied \"{}\" to \"{}\"; but failed to remove src: {}",
+                            entry.path().display(),
+                            dest.display(),
+                            err
+                        ),
+                    );
+                    false
+                }
             }
         } else {
             error!("Tricky move aborted due to tricky-copy failure");
+            false
         }
     }
 }
@@ -0,0 +1,158 @@
+//! # shell actions
+//!
+//! actions associated to the interactive remote shell
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crossterm::event::{self, Event};
+use ssh2::{Channel, Session};
+
+use super::{FileTransferActivity, LogLevel};
+use crate::ui::activities::filetransfer::lib::shell;
+
+/// Time waited for user input when the remote shell has no output
+const INPUT_POLL_TIMEOUT: Duration = Duration::from_millis(10);
+
+impl FileTransferActivity {
+    /// Open an interactive shell on the remote host, in the working directory of the remote explorer.
+    /// The shell runs full-screen on the SSH session in use; the remote directory is reloaded once it exits
+    pub(crate) fn action_open_remote_shell(&mut self) {
+        let session = match self.client.ssh_session() {
+            Some(session) => session,
+            None => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    String::from("The remote shell is available only on SCP and SFTP connections"),
+                );
+                return;
+            }
+        };
+        let wrkdir = self.remote().wrkdir.clone();
+        // Leave the ui; raw mode is kept, so that keys are sent to the remote terminal as typed
+        if let Some(ctx) = self.context.as_mut() {
+            if let Err(err) = ctx.set_mouse_capture(false) {
+                error!("Failed to set mouse capture: {}", err);
+            }
+            if let Err(err) = ctx.terminal().leave_alternate_screen() {
+                error!("Could not leave alternate screen: {}", err);
+            }
+        }
+        // Lock ports
+        assert!(self.app.lock_ports().is_ok());
+        let result = run_shell(&session, wrkdir.as_path());
+        if let Some(ctx) = self.context.as_mut() {
+            // Enter alternate mode
+            if let Err(err) = ctx.terminal().enter_alternate_screen() {
+                error!("Could not enter alternate screen: {}", err);
+            }
+            // Entering alternate screen enables mouse capture
+            let mouse_support = ctx.config().get_mouse_support();
+            if let Err(err) = ctx.set_mouse_capture(mouse_support) {
+                error!("Failed to set mouse capture: {}", err);
+            }
+            // Clear screens
+            if let Err(err) = ctx.terminal().clear_screen() {
+                error!("Could not clear screen screen: {}", err);
+            }
+            // Unlock ports
+            assert!(self.app.unlock_ports().is_ok());
+        }
+        match result {
+            Ok(()) => self.log(
+                LogLevel::Info,
+                format!("remote shell in {} closed", wrkdir.display()),
+            ),
+            Err(err) => self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not run remote shell: {err}"),
+            ),
+        }
+        // the shell may have changed the directory content
+        self.reload_remote_dir();
+        self.update_remote_filelist();
+    }
+}
+
+/// Run the login shell of the user in `wrkdir` on a pty, until it exits
+fn run_shell(session: &Session, wrkdir: &Path) -> io::Result<()> {
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let term = std::env::var("TERM").unwrap_or_else(|_| String::from("xterm"));
+    let mut channel = session.channel_session()?;
+    channel.request_pty(term.as_str(), None, Some((cols as u32, rows as u32, 0, 0)))?;
+    channel.exec(shell::shell_command(wrkdir).as_str())?;
+    // the same session is used by the client, so blocking mode must be restored afterwards
+    session.set_blocking(false);
+    let result = pipe_shell(&mut channel);
+    session.set_blocking(true);
+    let _ = channel.close();
+    let _ = channel.wait_close();
+    result
+}
+
+/// Forward the output of `channel` to stdout and the user input to `channel`, until the channel is closed
+fn pipe_shell(channel: &mut Channel) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut buffer = [0; 8192];
+    loop {
+        let idle = match channel.read(&mut buffer) {
+            Ok(0) if channel.eof() => return Ok(()),
+            Ok(0) => true,
+            Ok(bytes) => {
+                stdout.write_all(&buffer[..bytes])?;
+                stdout.flush()?;
+                false
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => true,
+            Err(err) => return Err(err),
+        };
+        if channel.eof() {
+            return Ok(());
+        }
+        let timeout = match idle {
+            true => INPUT_POLL_TIMEOUT,
+            false => Duration::ZERO,
+        };
+        if !event::poll(timeout)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) => write_channel(channel, &shell::encode_key(key))?,
+            Event::Paste(text) => write_channel(channel, text.as_bytes())?,
+            Event::Resize(cols, rows) => {
+                retry(|| channel.request_pty_size(cols as u32, rows as u32, None, None))?
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Write all `data` to the non-blocking `channel`
+fn write_channel(channel: &mut Channel, mut data: &[u8]) -> io::Result<()> {
+    while !data.is_empty() {
+        match channel.write(data) {
+            Ok(bytes) => data = &data[bytes..],
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(1))
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// Retry `f` until it doesn't fail because the session would block
+fn retry<F>(mut f: F) -> io::Result<()>
+where
+    F: FnMut() -> Result<(), ssh2::Error>,
+{
+    loop {
+        match f().map_err(io::Error::from) {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(1))
+            }
+            result => return result,
+        }
+    }
+}
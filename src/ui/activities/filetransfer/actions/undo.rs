@@ -0,0 +1,84 @@
+//! # undo
+//!
+//! actions which revert the operations performed during the session
+
+use std::path::Path;
+
+use remotefs::RemoteErrorType;
+
+use super::super::lib::undo::UndoOp;
+use super::{FileTransferActivity, LogLevel};
+
+impl FileTransferActivity {
+    /// Revert the latest delete, rename or move performed during the session
+    pub(crate) fn action_undo(&mut self) {
+        let ops = match self.undo.pop() {
+            Some(ops) => ops,
+            None => {
+                self.mount_info("Nothing to undo");
+                return;
+            }
+        };
+        // Revert the changes in the opposite order they were made
+        for op in ops.into_iter().rev() {
+            match self.undo_op(&op) {
+                Ok(()) => self.log(LogLevel::Info, format!("Undo: {}", op.describe())),
+                Err(err) => self.log_and_alert(
+                    LogLevel::Error,
+                    format!("Could not {}: {}", op.describe(), err),
+                ),
+            }
+        }
+    }
+
+    fn undo_op(&mut self, op: &UndoOp) -> Result<(), String> {
+        match op {
+            UndoOp::LocalTrash { path } => self
+                .host
+                .restore_from_trash(path.as_path())
+                .map_err(|err| err.to_string()),
+            UndoOp::RemoteTrash { path, trashed } => {
+                self.remote_undo_move(trashed.as_path(), path.as_path())
+            }
+            UndoOp::Move {
+                remote: false,
+                from,
+                to,
+            } => {
+                if self.host.file_exists(from.as_path()) {
+                    return Err(format!("\"{}\" already exists", from.display()));
+                }
+                let entry = self
+                    .host
+                    .stat(to.as_path())
+                    .map_err(|err| err.to_string())?;
+                self.host
+                    .rename(&entry, from.as_path())
+                    .map_err(|err| err.to_string())
+            }
+            UndoOp::Move {
+                remote: true,
+                from,
+                to,
+            } => self.remote_undo_move(to.as_path(), from.as_path()),
+        }
+    }
+
+    /// Move the remote entry at `src` back to `dest`, which mustn't exist
+    fn remote_undo_move(&mut self, src: &Path, dest: &Path) -> Result<(), String> {
+        if self.client.exists(dest).map_err(|err| err.to_string())? {
+            return Err(format!("\"{}\" already exists", dest.display()));
+        }
+        match self.client.as_mut().mov(src, dest) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                let entry = self.client.stat(src).map_err(|err| err.to_string())?;
+                match self.tricky_move(&entry, dest) {
+                    true => Ok(()),
+                    false => Err(String::from("could not copy the entry")),
+                }
+            }
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
@@ -4,10 +4,37 @@
 
 use std::path::{Path, PathBuf};
 
-use super::{FileTransferActivity, LogLevel, Msg, SelectedFile, TransferMsg, UiMsg};
+use super::{
+    FileExplorerTab, FileTransferActivity, LogLevel, Msg, SelectedFile, TransferMsg, UiMsg,
+};
+use crate::config::bookmarks::WatchedPath;
+use crate::utils::path as path_utils;
+
+/// Ignore patterns suggested when watching a path
+const DEFAULT_IGNORE_PATTERNS: &str = "*.swp, *.swx, *~, .DS_Store";
+
+/// A path synchronized by the file watcher (local to remote) or by the remote watcher (remote to local)
+struct WatchedEntry {
+    local: PathBuf,
+    remote: PathBuf,
+    /// Whether changes are downloaded from the remote
+    download: bool,
+    ignore: Vec<String>,
+}
 
 impl FileTransferActivity {
     pub fn action_show_radio_watch(&mut self) {
+        // watch remote directory
+        if self.browser.tab() == FileExplorerTab::Remote {
+            if let Some((watched, remote, local)) = self.get_remote_watcher_dirs() {
+                self.mount_radio_watch(
+                    watched,
+                    remote.to_string_lossy().to_string().as_str(),
+                    local.to_string_lossy().to_string().as_str(),
+                );
+            }
+            return;
+        }
         // return if fswatcher is not working
         if self.fswatcher.is_none() {
             return;
@@ -23,42 +50,133 @@ impl FileTransferActivity {
     }
 
     pub fn action_show_watched_paths_list(&mut self) {
+        let watched_paths: Vec<(String, Vec<String>)> = self
+            .watched_entries()
+            .into_iter()
+            .map(|entry| {
+                let label = match entry.download {
+                    false => format!("{} → {}", entry.local.display(), entry.remote.display()),
+                    true => format!("{} ← {}", entry.local.display(), entry.remote.display()),
+                };
+                (label, entry.ignore)
+            })
+            .collect();
+        self.mount_watched_paths_list(watched_paths.as_slice());
+    }
+
+    pub fn action_show_sync_queue_list(&mut self) {
         // return if fswatcher is not working
         if self.fswatcher.is_none() {
             return;
         }
-        let watched_paths: Vec<PathBuf> = self
-            .map_on_fswatcher(|w| w.watched_paths().iter().map(|p| p.to_path_buf()).collect())
-            .unwrap_or_default();
-        self.mount_watched_paths_list(watched_paths.as_slice());
+        self.mount_sync_queue_list();
+    }
+
+    /// Pause or resume the sync of the changes to the watched paths
+    pub fn action_toggle_sync_pause(&mut self) {
+        if self.fswatcher.is_none() {
+            return;
+        }
+        match self.sync_queue.toggle_pause() {
+            true => self.log(LogLevel::Info, String::from("sync of watched paths paused")),
+            false => self.log(
+                LogLevel::Info,
+                String::from("sync of watched paths resumed"),
+            ),
+        }
+        self.refresh_sync_queue();
     }
 
     pub fn action_toggle_watch(&mut self) {
         // umount radio
         self.umount_radio_watcher();
+        if self.browser.tab() == FileExplorerTab::Remote {
+            match self.get_remote_watcher_dirs() {
+                Some((true, remote, _)) => self.unwatch_remote_path(&remote),
+                Some((false, remote, local)) => self.watch_remote_path(&remote, &local),
+                None => {}
+            }
+            return;
+        }
         // return if fswatcher is not working
         if self.fswatcher.is_none() {
             return;
         }
         match self.get_watcher_dirs() {
             Some((true, local, _)) => self.unwatch_path(&local),
-            Some((false, local, remote)) => self.watch_path(&local, &remote),
+            // ask for the patterns to ignore
+            Some((false, local, _)) => self.mount_watcher_ignore(
+                local.to_string_lossy().to_string().as_str(),
+                DEFAULT_IGNORE_PATTERNS,
+            ),
             None => {}
         }
     }
 
-    pub fn action_toggle_watch_for(&mut self, index: usize) {
-        // umount
-        self.umount_watched_paths_list();
-        // return if fswatcher is not working
+    /// Watch the selected path, ignoring the comma-separated `patterns`
+    pub fn action_watch(&mut self, patterns: &str) {
+        self.umount_watcher_ignore();
+        let patterns: Vec<String> = patterns
+            .split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some((false, local, remote)) = self.get_watcher_dirs() {
+            if self.watch_path(&local, &remote, &patterns) {
+                self.save_watched_path(WatchedPath {
+                    local,
+                    remote,
+                    ignore: patterns,
+                    reconcile: false,
+                });
+            }
+        }
+    }
+
+    /// Watch the paths saved with the bookmark in use.
+    /// Files changed while termscp was closed are uploaded for the paths which have `reconcile` set
+    pub fn restore_watched_paths(&mut self) {
         if self.fswatcher.is_none() {
             return;
         }
+        let watched_paths: Vec<WatchedPath> = match self.context().bookmark_name() {
+            Some(name) => self
+                .context()
+                .bookmarks_client()
+                .and_then(|cli| cli.get_bookmark_watched_paths(name))
+                .map(|x| x.to_vec())
+                .unwrap_or_default(),
+            None => return,
+        };
+        for watched in watched_paths.iter() {
+            if !watched.local.exists() {
+                self.log(
+                    LogLevel::Warn,
+                    format!(
+                        "could not restore watched path {}: no such file or directory",
+                        watched.local.display()
+                    ),
+                );
+                continue;
+            }
+            if self.watch_path(&watched.local, &watched.remote, &watched.ignore)
+                && watched.reconcile
+            {
+                self.reconcile_watched_path(&watched.local, &watched.remote);
+            }
+        }
+    }
+
+    pub fn action_toggle_watch_for(&mut self, index: usize) {
+        // umount
+        self.umount_watched_paths_list();
         // get path
-        if let Some(path) = self
-            .map_on_fswatcher(|w| w.watched_paths().get(index).map(|p| p.to_path_buf()))
-            .flatten()
-        {
+        if let Some(entry) = self.watched_entries().into_iter().nth(index) {
+            let path = match entry.download {
+                true => entry.remote,
+                false => entry.local,
+            };
             // ask whether to unwatch
             self.mount_radio_watch(true, path.to_string_lossy().to_string().as_str(), "");
             // wait for response
@@ -67,20 +185,40 @@ impl FileTransferActivity {
                 Msg::Transfer(TransferMsg::ToggleWatch),
             ]) {
                 // unwatch path
-                self.unwatch_path(&path);
+                match entry.download {
+                    true => self.unwatch_remote_path(&path),
+                    false => self.unwatch_path(&path),
+                }
             }
             self.umount_radio_watcher();
         }
         self.action_show_watched_paths_list();
     }
 
-    fn watch_path(&mut self, local: &Path, remote: &Path) {
+    /// Watch `local`, synching changes with `remote`. Returns whether the path is now watched
+    fn watch_path(&mut self, local: &Path, remote: &Path, ignore: &[String]) -> bool {
         debug!(
-            "tracking changes at {} to {}",
+            "tracking changes at {} to {} (ignoring {:?})",
             local.display(),
-            remote.display()
+            remote.display(),
+            ignore
         );
-        match self.map_on_fswatcher(|w| w.watch(local, remote)) {
+        // downloaded files would be uploaded back
+        if let Some(remote_watched) = self.remote_watcher.watched_paths().into_iter().find(|x| {
+            self.remote_watcher
+                .local_path(x)
+                .map(|x| path_utils::is_child_of(x, local))
+                .unwrap_or(false)
+        }) {
+            let err = format!(
+                "could not track changes to {}: changes to {} are downloaded there",
+                local.display(),
+                remote_watched.display()
+            );
+            self.log_and_alert(LogLevel::Error, err);
+            return false;
+        }
+        match self.map_on_fswatcher(|w| w.watch(local, remote, ignore)) {
             Some(Ok(())) => {
                 self.log(
                     LogLevel::Info,
@@ -90,14 +228,17 @@ impl FileTransferActivity {
                         remote.display()
                     ),
                 );
+                self.refresh_remote_status_bar();
+                true
             }
             Some(Err(err)) => {
                 self.log_and_alert(
                     LogLevel::Error,
                     format!("could not track changes to {}: {}", local.display(), err),
                 );
+                false
             }
-            None => {}
+            None => false,
         }
     }
 
@@ -109,6 +250,8 @@ impl FileTransferActivity {
                     LogLevel::Info,
                     format!("{} is no longer watched", path.display()),
                 );
+                self.forget_watched_path(&path);
+                self.refresh_remote_status_bar();
             }
             Some(Err(err)) => {
                 self.log_and_alert(LogLevel::Error, format!("could not unwatch path: {err}"));
@@ -117,6 +260,134 @@ impl FileTransferActivity {
         }
     }
 
+    /// Download new and changed files in the `remote` directory to the `local` directory
+    fn watch_remote_path(&mut self, remote: &Path, local: &Path) {
+        debug!(
+            "tracking changes at remote {} to {}",
+            remote.display(),
+            local.display()
+        );
+        // downloaded files would be uploaded back
+        if self.map_on_fswatcher(|w| w.watched(local)).unwrap_or(false) {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!(
+                    "could not track changes to {}: changes to {} are uploaded to the remote",
+                    remote.display(),
+                    local.display()
+                ),
+            );
+            return;
+        }
+        if !self.host.file_exists(local) {
+            if let Err(err) = self.host.mkdir_ex(local, true) {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!("could not create directory {}: {}", local.display(), err),
+                );
+                return;
+            }
+            self.reload_local_dir();
+            self.update_local_filelist();
+        }
+        match self.remote_watcher.watch(remote, local) {
+            Ok(()) => {
+                self.log(
+                    LogLevel::Info,
+                    format!(
+                        "changes to remote {} will now be synched with {}",
+                        remote.display(),
+                        local.display()
+                    ),
+                );
+            }
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!("could not track changes to {}: {}", remote.display(), err),
+                );
+            }
+        }
+    }
+
+    fn unwatch_remote_path(&mut self, remote: &Path) {
+        debug!("unwatching remote path at {}", remote.display());
+        match self.remote_watcher.unwatch(remote) {
+            Ok(path) => {
+                self.log(
+                    LogLevel::Info,
+                    format!("remote {} is no longer watched", path.display()),
+                );
+            }
+            Err(err) => {
+                self.log_and_alert(LogLevel::Error, format!("could not unwatch path: {err}"));
+            }
+        }
+    }
+
+    /// Get the paths synchronized by the file watcher, followed by the paths synchronized by the remote watcher
+    fn watched_entries(&mut self) -> Vec<WatchedEntry> {
+        let mut entries: Vec<WatchedEntry> = self
+            .map_on_fswatcher(|w| {
+                w.watched_paths()
+                    .iter()
+                    .map(|p| WatchedEntry {
+                        local: p.to_path_buf(),
+                        remote: w.remote_path(p).unwrap_or(p).to_path_buf(),
+                        download: false,
+                        ignore: w.ignore_patterns(p).unwrap_or_default().to_vec(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        for remote in self.remote_watcher.watched_paths() {
+            entries.push(WatchedEntry {
+                local: self
+                    .remote_watcher
+                    .local_path(remote)
+                    .unwrap_or(remote)
+                    .to_path_buf(),
+                remote: remote.to_path_buf(),
+                download: true,
+                ignore: Vec::new(),
+            });
+        }
+        entries
+    }
+
+    /// Save the watched path with the bookmark in use, if any
+    fn save_watched_path(&mut self, watched: WatchedPath) {
+        if let Some(name) = self.context().bookmark_name().map(str::to_string) {
+            if let Some(cli) = self.context_mut().bookmarks_client_mut() {
+                cli.add_bookmark_watched_path(&name, watched);
+            }
+            self.write_bookmarks();
+        }
+    }
+
+    /// Remove the watched path at `local` from the bookmark in use, if any
+    fn forget_watched_path(&mut self, local: &Path) {
+        if let Some(name) = self.context().bookmark_name().map(str::to_string) {
+            if let Some(cli) = self.context_mut().bookmarks_client_mut() {
+                cli.del_bookmark_watched_path(&name, local);
+            }
+            self.write_bookmarks();
+        }
+    }
+
+    fn write_bookmarks(&mut self) {
+        let result = self
+            .context()
+            .bookmarks_client()
+            .map(|cli| cli.write_bookmarks());
+        if let Some(Err(err)) = result {
+            self.log(
+                LogLevel::Error,
+                format!("could not save watched paths to bookmark: {err}"),
+            );
+        }
+    }
+
     fn get_watcher_dirs(&mut self) -> Option<(bool, PathBuf, PathBuf)> {
         if let SelectedFile::One(file) = self.get_local_selected_entries() {
             // check if entry is already watched
@@ -131,4 +402,18 @@ impl FileTransferActivity {
             None
         }
     }
+
+    /// Get whether the selected remote directory is watched, the remote directory and the local directory
+    /// where its files are downloaded
+    fn get_remote_watcher_dirs(&mut self) -> Option<(bool, PathBuf, PathBuf)> {
+        match self.get_remote_selected_entries() {
+            SelectedFile::One(file) if file.is_dir() => {
+                let watched = self.remote_watcher.watched(file.path());
+                let mut local = self.local().wrkdir.clone();
+                local.push(file.name().as_str());
+                Some((watched, file.path().to_path_buf(), local))
+            }
+            _ => None,
+        }
+    }
 }
@@ -10,14 +10,16 @@ use tuirealm::tui::widgets::{List as TuiList, ListItem, ListState};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, Props, State, StateValue};
 
 use super::{Msg, UiMsg};
+use crate::config::keybindings::{KeyAction, KeyBindings, KeyScope};
 
 pub struct Log {
     props: Props,
     states: OwnStates,
+    keys: KeyBindings,
 }
 
 impl Log {
-    pub fn new(lines: Table, fg: Color, bg: Color) -> Self {
+    pub fn new(lines: Table, fg: Color, bg: Color, keys: KeyBindings) -> Self {
         let mut props = Props::default();
         props.set(
             Attribute::Borders,
@@ -28,6 +30,7 @@ impl Log {
         Self {
             props,
             states: OwnStates::default(),
+            keys,
         }
     }
 }
@@ -192,9 +195,14 @@ impl Component<Msg, NoUserEvent> for Log {
             }
             // -- comp msg
             Event::Keyboard(KeyEvent {
-                code: Key::BackTab | Key::Tab | Key::Char('p'),
+                code: Key::BackTab | Key::Tab,
                 ..
             }) => Some(Msg::Ui(UiMsg::LogBackTabbed)),
+            Event::Keyboard(ev)
+                if self.keys.action(KeyScope::Explorer, &ev) == Some(KeyAction::LogPanel) =>
+            {
+                Some(Msg::Ui(UiMsg::LogBackTabbed))
+            }
             _ => None,
         }
     }
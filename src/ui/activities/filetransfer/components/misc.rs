@@ -7,6 +7,7 @@ use tuirealm::props::{Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
+use crate::config::keybindings::{KeyAction, KeyBindings};
 
 #[derive(MockComponent)]
 pub struct FooterBar {
@@ -14,32 +15,33 @@ pub struct FooterBar {
 }
 
 impl FooterBar {
-    pub fn new(key_color: Color) -> Self {
+    pub fn new(key_color: Color, keys: &KeyBindings) -> Self {
+        let key = |action| TextSpan::from(keys.label(action)).bold().fg(key_color);
         Self {
             component: Span::default().spans(&[
-                TextSpan::from("<F1|H>").bold().fg(key_color),
+                key(KeyAction::Help),
                 TextSpan::from(" Help "),
                 TextSpan::from("<TAB>").bold().fg(key_color),
                 TextSpan::from(" Change tab "),
-                TextSpan::from("<SPACE>").bold().fg(key_color),
+                key(KeyAction::Transfer),
                 TextSpan::from(" Transfer "),
                 TextSpan::from("<ENTER>").bold().fg(key_color),
                 TextSpan::from(" Enter dir "),
-                TextSpan::from("<F2|S>").bold().fg(key_color),
+                key(KeyAction::SaveAs),
                 TextSpan::from(" Save as "),
-                TextSpan::from("<F3|V>").bold().fg(key_color),
+                key(KeyAction::Open),
                 TextSpan::from(" View "),
-                TextSpan::from("<F4|O>").bold().fg(key_color),
+                key(KeyAction::OpenTextFile),
                 TextSpan::from(" Edit "),
-                TextSpan::from("<F5|C>").bold().fg(key_color),
+                key(KeyAction::Copy),
                 TextSpan::from(" Copy "),
-                TextSpan::from("<F6|R>").bold().fg(key_color),
+                key(KeyAction::Rename),
                 TextSpan::from(" Rename "),
-                TextSpan::from("<F7|D>").bold().fg(key_color),
+                key(KeyAction::Mkdir),
                 TextSpan::from(" Make dir "),
-                TextSpan::from("<F8|DEL>").bold().fg(key_color),
+                key(KeyAction::Delete),
                 TextSpan::from(" Delete "),
-                TextSpan::from("<F10|Q>").bold().fg(key_color),
+                key(KeyAction::Quit),
                 TextSpan::from(" Quit "),
             ]),
         }
@@ -3,10 +3,11 @@
 //! file transfer activity components
 
 use tui_realm_stdlib::Phantom;
-use tuirealm::event::{Event, Key, KeyEvent, KeyModifiers};
+use tuirealm::event::{Event, Key, KeyEvent};
 use tuirealm::{Component, MockComponent, NoUserEvent};
 
 use super::{Msg, PendingActionMsg, TransferMsg, UiMsg};
+use crate::config::keybindings::{KeyAction, KeyBindings, KeyScope};
 
 // -- export
 mod log;
@@ -16,19 +17,35 @@ mod transfer;
 
 pub use misc::FooterBar;
 pub use popups::{
-    ChmodPopup, CopyPopup, DeletePopup, DisconnectPopup, ErrorPopup, ExecPopup, FatalPopup,
-    FileInfoPopup, FindPopup, GoToPopup, KeybindingsPopup, MkdirPopup, NewfilePopup, OpenWithPopup,
+    ChmodPopup, CommandLinePopup, ContextMenuPopup, CopyPopup, CustomActionConfirmPopup,
+    CustomActionsPopup, DeletePopup, DisconnectPopup, ErrorPopup, ExecOutputPopup, ExecPopup,
+    FatalPopup, FileInfoPopup, FindPopup, FollowHighlightPopup, FollowPopup, GoToPopup,
+    KeybindingsPopup, MkdirPopup, NewfilePopup, OpenWithPopup, PermanentDeletePopup,
     ProgressBarFull, ProgressBarPartial, QuitPopup, RenamePopup, ReplacePopup,
     ReplacingFilesListPopup, SaveAsPopup, SortingPopup, StatusBarLocal, StatusBarRemote,
-    SymlinkPopup, SyncBrowsingMkdirPopup, WaitPopup, WatchedPathsList, WatcherPopup,
+    SymlinkPopup, SyncBrowsingMkdirPopup, SyncQueueList, WaitPopup, WatchedPathsList,
+    WatcherIgnorePopup, WatcherPopup,
+};
+pub use transfer::{
+    ExplorerFind, ExplorerLocal, ExplorerRemote, FILE_LIST_COLUMN_SPACING, FILE_LIST_COLUMN_WIDTHS,
+    FILE_LIST_HEADER, FILE_LIST_SELECTION,
 };
-pub use transfer::{ExplorerFind, ExplorerLocal, ExplorerRemote};
 
 pub use self::log::Log;
 
-#[derive(Default, MockComponent)]
+#[derive(MockComponent)]
 pub struct GlobalListener {
     component: Phantom,
+    keys: KeyBindings,
+}
+
+impl GlobalListener {
+    pub fn new(keys: KeyBindings) -> Self {
+        Self {
+            component: Phantom::default(),
+            keys,
+        }
+    }
 }
 
 impl Component<Msg, NoUserEvent> for GlobalListener {
@@ -37,14 +54,11 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Ui(UiMsg::ShowDisconnectPopup))
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('q') | Key::Function(10),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowQuitPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('h') | Key::Function(1),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowKeybindingsPopup)),
+            Event::Keyboard(ev) => match self.keys.action(KeyScope::Explorer, &ev) {
+                Some(KeyAction::Quit) => Some(Msg::Ui(UiMsg::ShowQuitPopup)),
+                Some(KeyAction::Help) => Some(Msg::Ui(UiMsg::ShowKeybindingsPopup)),
+                _ => None,
+            },
             Event::WindowResize(_, _) => Some(Msg::Ui(UiMsg::WindowResized)),
             _ => None,
         }
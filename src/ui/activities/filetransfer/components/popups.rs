@@ -10,28 +10,47 @@ use tui_realm_stdlib::{Input, List, Paragraph, ProgressBar, Radio, Span};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{
-    Alignment, BorderSides, BorderType, Borders, Color, InputType, Style, TableBuilder, TextSpan,
+    Alignment, AttrValue, Attribute, BorderSides, BorderType, Borders, Color, InputType, Style,
+    TableBuilder, TextSpan,
 };
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
 #[cfg(unix)]
+use unicode_width::UnicodeWidthStr;
 use users::{get_group_by_gid, get_user_by_uid};
 
+use super::super::lib::command;
+use super::super::lib::context_menu::ContextMenuEntry;
+use super::super::lib::exec::ExecOutput;
+use super::super::lib::follow::Follower;
 use super::super::Browser;
 use super::{Msg, PendingActionMsg, TransferMsg, UiMsg};
-use crate::explorer::FileSorting;
+use crate::config::custom_actions::CustomAction;
+use crate::config::keybindings::{KeyBinding, KeyBindings, KeyScope};
+use crate::explorer::{FileExplorer, FileSorting};
+use crate::system::watcher::{FsChange, SyncQueue};
 use crate::utils::fmt::fmt_time;
+use crate::utils::media::{ImagePreview, MediaInfo};
 
 mod chmod;
 
 pub use chmod::ChmodPopup;
 
 #[derive(MockComponent)]
-pub struct CopyPopup {
+pub struct CommandLinePopup {
     component: Input,
-}
-
-impl CopyPopup {
-    pub fn new(color: Color) -> Self {
+    /// Names of the files in the current explorer, used for completion
+    files: Vec<String>,
+    /// Previously submitted commands, oldest first
+    history: Vec<String>,
+    /// Index of the history entry currently displayed
+    history_index: Option<usize>,
+    /// Completions being cycled with `<TAB>`
+    completions: Vec<String>,
+    completion_index: usize,
+}
+
+impl CommandLinePopup {
+    pub fn new(color: Color, files: Vec<String>, history: Vec<String>) -> Self {
         Self {
             component: Input::default()
                 .borders(
@@ -42,15 +61,71 @@ impl CopyPopup {
                 .foreground(color)
                 .input_type(InputType::Text)
                 .placeholder(
-                    "destination",
+                    "cd /etc, put *.log, chmod 644, sort size, q…",
                     Style::default().fg(Color::Rgb(128, 128, 128)),
                 )
-                .title("Copy file(s) to…", Alignment::Center),
+                .title(":", Alignment::Left),
+            files,
+            history,
+            history_index: None,
+            completions: Vec::new(),
+            completion_index: 0,
+        }
+    }
+
+    fn input(&self) -> String {
+        match self.state() {
+            State::One(StateValue::String(input)) => input,
+            _ => String::new(),
+        }
+    }
+
+    fn set_input(&mut self, input: &str) {
+        self.attr(Attribute::Value, AttrValue::String(input.to_string()));
+    }
+
+    /// Complete the input; if the input is already a completion, cycle to the next one
+    fn complete(&mut self) {
+        let input = self.input();
+        if let Some(index) = self.completions.iter().position(|x| *x == input) {
+            self.completion_index = (index + 1) % self.completions.len();
+            let completion = self.completions[self.completion_index].clone();
+            self.set_input(&completion);
+            return;
+        }
+        self.completions = command::complete(&input, &self.files);
+        let prefix = command::common_prefix(&self.completions);
+        if prefix.len() > input.len() {
+            self.set_input(&prefix);
+        } else if let Some(completion) = self.completions.first().cloned() {
+            self.set_input(&completion);
         }
     }
+
+    /// Show the previous (`back`) or the next history entry
+    fn browse_history(&mut self, back: bool) {
+        self.history_index = browse_history(&self.history, self.history_index, back);
+        let input = self
+            .history_index
+            .and_then(|x| self.history.get(x))
+            .cloned()
+            .unwrap_or_default();
+        self.set_input(&input);
+    }
 }
 
-impl Component<Msg, NoUserEvent> for CopyPopup {
+/// Get the index of the previous (`back`) or the next entry of `history`, from the entry at `index`.
+/// `None` stands for the empty input after the last entry
+fn browse_history(history: &[String], index: Option<usize>, back: bool) -> Option<usize> {
+    match (index, back) {
+        (None, true) => history.len().checked_sub(1),
+        (None, false) => None,
+        (Some(index), true) => Some(index.saturating_sub(1)),
+        (Some(index), false) => Some(index + 1).filter(|x| *x < history.len()),
+    }
+}
+
+impl Component<Msg, NoUserEvent> for CommandLinePopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -75,12 +150,31 @@ impl Component<Msg, NoUserEvent> for CopyPopup {
                 self.perform(Cmd::GoTo(Position::End));
                 Some(Msg::None)
             }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.browse_history(true);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.browse_history(false);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                self.complete();
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
                 code: Key::Delete, ..
             }) => {
                 self.perform(Cmd::Cancel);
                 Some(Msg::None)
             }
+            // Like in vim, backspace on an empty command line closes it
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) if self.input().is_empty() => Some(Msg::Ui(UiMsg::CloseCommandLine)),
             Event::Keyboard(KeyEvent {
                 code: Key::Backspace,
                 ..
@@ -90,21 +184,16 @@ impl Component<Msg, NoUserEvent> for CopyPopup {
             }
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
-                ..
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
             }) => {
                 self.perform(Cmd::Type(ch));
                 Some(Msg::None)
             }
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
-            }) => match self.state() {
-                State::One(StateValue::String(i)) => {
-                    Some(Msg::Transfer(TransferMsg::CopyFileTo(i)))
-                }
-                _ => Some(Msg::None),
-            },
+            }) => Some(Msg::Ui(UiMsg::SubmitCommandLine(self.input()))),
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::Ui(UiMsg::CloseCopyPopup))
+                Some(Msg::Ui(UiMsg::CloseCommandLine))
             }
             _ => None,
         }
@@ -112,28 +201,117 @@ impl Component<Msg, NoUserEvent> for CopyPopup {
 }
 
 #[derive(MockComponent)]
-pub struct DeletePopup {
-    component: Radio,
+pub struct ContextMenuPopup {
+    component: List,
+    entries: Vec<ContextMenuEntry>,
 }
 
-impl DeletePopup {
+impl ContextMenuPopup {
+    /// Instantiate a new `ContextMenuPopup` listing `entries` with the keys which perform them
+    pub fn new(
+        title: &str,
+        entries: &[ContextMenuEntry],
+        remote: bool,
+        keys: &KeyBindings,
+        color: Color,
+        key_color: Color,
+    ) -> Self {
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .highlighted_color(color)
+                .highlighted_str("➤ ")
+                .title(title, Alignment::Center)
+                .rows(
+                    entries
+                        .iter()
+                        .map(|entry| {
+                            vec![
+                                TextSpan::from(entry.label(remote)),
+                                TextSpan::from(format!(" {}", keys.label(entry.key_action())))
+                                    .fg(key_color),
+                            ]
+                        })
+                        .collect(),
+                ),
+            entries: entries.to_vec(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ContextMenuPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseContextMenu))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::Usize(idx)) => self
+                    .entries
+                    .get(idx)
+                    .map(|entry| Msg::Ui(UiMsg::SelectContextMenuEntry(*entry))),
+                _ => Some(Msg::None),
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct CopyPopup {
+    component: Input,
+}
+
+impl CopyPopup {
     pub fn new(color: Color) -> Self {
         Self {
-            component: Radio::default()
+            component: Input::default()
                 .borders(
                     Borders::default()
                         .color(color)
                         .modifiers(BorderType::Rounded),
                 )
                 .foreground(color)
-                .choices(&["Yes", "No"])
-                .value(1)
-                .title("Delete file(s)?", Alignment::Center),
+                .input_type(InputType::Text)
+                .placeholder(
+                    "destination",
+                    Style::default().fg(Color::Rgb(128, 128, 128)),
+                )
+                .title("Copy file(s) to…", Alignment::Center),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for DeletePopup {
+impl Component<Msg, NoUserEvent> for CopyPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -148,28 +326,46 @@ impl Component<Msg, NoUserEvent> for DeletePopup {
                 self.perform(Cmd::Move(Direction::Right));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::Ui(UiMsg::CloseDeletePopup))
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
             }
             Event::Keyboard(KeyEvent {
-                code: Key::Char('y'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::DeleteFile)),
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
-                code: Key::Char('n'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::CloseDeletePopup)),
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
+                code: Key::Char(ch),
+                ..
             }) => {
-                if matches!(
-                    self.perform(Cmd::Submit),
-                    CmdResult::Submit(State::One(StateValue::Usize(0)))
-                ) {
-                    Some(Msg::Transfer(TransferMsg::DeleteFile))
-                } else {
-                    Some(Msg::Ui(UiMsg::CloseDeletePopup))
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.state() {
+                State::One(StateValue::String(i)) => {
+                    Some(Msg::Transfer(TransferMsg::CopyFileTo(i)))
                 }
+                _ => Some(Msg::None),
+            },
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseCopyPopup))
             }
             _ => None,
         }
@@ -177,12 +373,14 @@ impl Component<Msg, NoUserEvent> for DeletePopup {
 }
 
 #[derive(MockComponent)]
-pub struct DisconnectPopup {
+pub struct CustomActionConfirmPopup {
     component: Radio,
+    action: usize,
 }
 
-impl DisconnectPopup {
-    pub fn new(color: Color) -> Self {
+impl CustomActionConfirmPopup {
+    /// Instantiate a new `CustomActionConfirmPopup` for the custom action at index `action`
+    pub fn new(name: &str, action: usize, color: Color) -> Self {
         Self {
             component: Radio::default()
                 .borders(
@@ -192,12 +390,14 @@ impl DisconnectPopup {
                 )
                 .foreground(color)
                 .choices(&["Yes", "No"])
-                .title("Are you sure you want to disconnect?", Alignment::Center),
+                .value(1)
+                .title(format!("Run \"{name}\"?"), Alignment::Center),
+            action,
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for DisconnectPopup {
+impl Component<Msg, NoUserEvent> for CustomActionConfirmPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -213,16 +413,16 @@ impl Component<Msg, NoUserEvent> for DisconnectPopup {
                 Some(Msg::None)
             }
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::Ui(UiMsg::CloseDisconnectPopup))
+                Some(Msg::Ui(UiMsg::CloseCustomActionConfirmPopup))
             }
             Event::Keyboard(KeyEvent {
                 code: Key::Char('y'),
                 modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::Disconnect)),
+            }) => Some(Msg::Transfer(TransferMsg::RunCustomAction(self.action))),
             Event::Keyboard(KeyEvent {
                 code: Key::Char('n'),
                 modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::CloseDisconnectPopup)),
+            }) => Some(Msg::Ui(UiMsg::CloseCustomActionConfirmPopup)),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => {
@@ -230,9 +430,9 @@ impl Component<Msg, NoUserEvent> for DisconnectPopup {
                     self.perform(Cmd::Submit),
                     CmdResult::Submit(State::One(StateValue::Usize(0)))
                 ) {
-                    Some(Msg::Ui(UiMsg::Disconnect))
+                    Some(Msg::Transfer(TransferMsg::RunCustomAction(self.action)))
                 } else {
-                    Some(Msg::Ui(UiMsg::CloseDisconnectPopup))
+                    Some(Msg::Ui(UiMsg::CloseCustomActionConfirmPopup))
                 }
             }
             _ => None,
@@ -241,62 +441,124 @@ impl Component<Msg, NoUserEvent> for DisconnectPopup {
 }
 
 #[derive(MockComponent)]
-pub struct ErrorPopup {
-    component: Paragraph,
+pub struct CustomActionsPopup {
+    component: List,
+    /// Index of each listed action and the key which runs it
+    actions: Vec<(usize, Option<KeyBinding>)>,
 }
 
-impl ErrorPopup {
-    pub fn new<S: AsRef<str>>(text: S, color: Color) -> Self {
+impl CustomActionsPopup {
+    /// Instantiate a new `CustomActionsPopup` listing `actions`, with their index among the custom actions
+    pub fn new(actions: &[(usize, &CustomAction)], color: Color, key_color: Color) -> Self {
         Self {
-            component: Paragraph::default()
-                .alignment(Alignment::Center)
+            component: List::default()
                 .borders(
                     Borders::default()
                         .color(color)
                         .modifiers(BorderType::Rounded),
                 )
-                .foreground(color)
-                .text(&[TextSpan::from(text.as_ref())])
-                .wrap(true),
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .highlighted_color(color)
+                .highlighted_str("➤ ")
+                .title("Actions", Alignment::Center)
+                .rows(
+                    actions
+                        .iter()
+                        .map(|(_, action)| {
+                            let key = action
+                                .key
+                                .map(|x| format!("<{}> ", x.label()))
+                                .unwrap_or_default();
+                            vec![
+                                TextSpan::from(key).bold().fg(key_color),
+                                TextSpan::from(action.name.as_str()),
+                                TextSpan::from(format!(" ({})", action.command))
+                                    .fg(Color::Rgb(128, 128, 128)),
+                            ]
+                        })
+                        .collect(),
+                ),
+            actions: actions
+                .iter()
+                .map(|(idx, action)| (*idx, action.key))
+                .collect(),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for ErrorPopup {
+impl Component<Msg, NoUserEvent> for CustomActionsPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseCustomActionsPopup))
+            }
             Event::Keyboard(KeyEvent {
-                code: Key::Esc | Key::Enter,
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
                 ..
-            }) => Some(Msg::Ui(UiMsg::CloseErrorPopup)),
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::Usize(idx)) => self
+                    .actions
+                    .get(idx)
+                    .map(|(action, _)| Msg::Ui(UiMsg::SelectCustomAction(*action))),
+                _ => Some(Msg::None),
+            },
+            Event::Keyboard(ev) => self
+                .actions
+                .iter()
+                .find(|(_, key)| key.map(|x| x.matches(&ev)).unwrap_or(false))
+                .map(|(action, _)| Msg::Ui(UiMsg::SelectCustomAction(*action))),
             _ => None,
         }
     }
 }
 
 #[derive(MockComponent)]
-pub struct ExecPopup {
-    component: Input,
+pub struct DeletePopup {
+    component: Radio,
 }
 
-impl ExecPopup {
+impl DeletePopup {
     pub fn new(color: Color) -> Self {
         Self {
-            component: Input::default()
+            component: Radio::default()
                 .borders(
                     Borders::default()
                         .color(color)
                         .modifiers(BorderType::Rounded),
                 )
                 .foreground(color)
-                .input_type(InputType::Text)
-                .placeholder("ps a", Style::default().fg(Color::Rgb(128, 128, 128)))
-                .title("Execute command", Alignment::Center),
+                .choices(&["Yes", "No"])
+                .value(1)
+                .title("Delete file(s)?", Alignment::Center),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for ExecPopup {
+impl Component<Msg, NoUserEvent> for DeletePopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -311,14 +573,279 @@ impl Component<Msg, NoUserEvent> for ExecPopup {
                 self.perform(Cmd::Move(Direction::Right));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Home, ..
-            }) => {
-                self.perform(Cmd::GoTo(Position::Begin));
-                Some(Msg::None)
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseDeletePopup))
             }
-            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
-                self.perform(Cmd::GoTo(Position::End));
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Transfer(TransferMsg::DeleteFile)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Ui(UiMsg::CloseDeletePopup)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if matches!(
+                    self.perform(Cmd::Submit),
+                    CmdResult::Submit(State::One(StateValue::Usize(0)))
+                ) {
+                    Some(Msg::Transfer(TransferMsg::DeleteFile))
+                } else {
+                    Some(Msg::Ui(UiMsg::CloseDeletePopup))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PermanentDeletePopup {
+    component: Radio,
+}
+
+impl PermanentDeletePopup {
+    /// Instantiate a new `PermanentDeletePopup` for `files` files which couldn't be moved to the trash
+    pub fn new(files: usize, color: Color) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .choices(&["Yes", "No"])
+                .value(1)
+                .title(
+                    format!("Could not move {files} file(s) to trash. Delete permanently?"),
+                    Alignment::Center,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PermanentDeletePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::ClosePermanentDeletePopup))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Transfer(TransferMsg::DeleteFilePermanently)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Ui(UiMsg::ClosePermanentDeletePopup)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if matches!(
+                    self.perform(Cmd::Submit),
+                    CmdResult::Submit(State::One(StateValue::Usize(0)))
+                ) {
+                    Some(Msg::Transfer(TransferMsg::DeleteFilePermanently))
+                } else {
+                    Some(Msg::Ui(UiMsg::ClosePermanentDeletePopup))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct DisconnectPopup {
+    component: Radio,
+}
+
+impl DisconnectPopup {
+    pub fn new(color: Color) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .choices(&["Yes", "No"])
+                .title("Are you sure you want to disconnect?", Alignment::Center),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for DisconnectPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseDisconnectPopup))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Ui(UiMsg::Disconnect)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Ui(UiMsg::CloseDisconnectPopup)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if matches!(
+                    self.perform(Cmd::Submit),
+                    CmdResult::Submit(State::One(StateValue::Usize(0)))
+                ) {
+                    Some(Msg::Ui(UiMsg::Disconnect))
+                } else {
+                    Some(Msg::Ui(UiMsg::CloseDisconnectPopup))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ErrorPopup {
+    component: Paragraph,
+}
+
+impl ErrorPopup {
+    pub fn new<S: AsRef<str>>(text: S, color: Color) -> Self {
+        Self {
+            component: Paragraph::default()
+                .alignment(Alignment::Center)
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .text(&[TextSpan::from(text.as_ref())])
+                .wrap(true),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ErrorPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc | Key::Enter,
+                ..
+            }) => Some(Msg::Ui(UiMsg::CloseErrorPopup)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ExecPopup {
+    component: Input,
+    /// Previously executed commands, oldest first
+    history: Vec<String>,
+    /// Index of the history entry currently displayed
+    history_index: Option<usize>,
+}
+
+impl ExecPopup {
+    pub fn new(color: Color, history: Vec<String>) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .input_type(InputType::Text)
+                .placeholder(
+                    "ps a, gzip -k {}",
+                    Style::default().fg(Color::Rgb(128, 128, 128)),
+                )
+                .title(
+                    "Execute command ({} runs it for each selected file)",
+                    Alignment::Center,
+                ),
+            history,
+            history_index: None,
+        }
+    }
+
+    /// Show the previous (`back`) or the next history entry
+    fn browse_history(&mut self, back: bool) {
+        self.history_index = browse_history(&self.history, self.history_index, back);
+        let input = self
+            .history_index
+            .and_then(|x| self.history.get(x))
+            .cloned()
+            .unwrap_or_default();
+        self.attr(Attribute::Value, AttrValue::String(input));
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ExecPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.browse_history(true);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.browse_history(false);
                 Some(Msg::None)
             }
             Event::Keyboard(KeyEvent {
@@ -357,6 +884,97 @@ impl Component<Msg, NoUserEvent> for ExecPopup {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ExecOutputPopup {
+    component: List,
+}
+
+impl ExecOutputPopup {
+    /// Instantiate a new `ExecOutputPopup` displaying the exit code, the stdout and the stderr of the executed commands
+    pub fn new(outputs: &[ExecOutput], color: Color, error_color: Color) -> Self {
+        let mut rows: Vec<Vec<TextSpan>> = Vec::new();
+        for output in outputs.iter() {
+            if !rows.is_empty() {
+                rows.push(vec![TextSpan::from("")]);
+            }
+            let rc_color = match output.rc {
+                0 => color,
+                _ => error_color,
+            };
+            rows.push(vec![
+                TextSpan::from(format!("$ {}", output.command)).bold(),
+                TextSpan::from(format!(" (exitcode: {})", output.rc)).fg(rc_color),
+            ]);
+            rows.extend(output.stdout.lines().map(|x| vec![TextSpan::from(x)]));
+            rows.extend(
+                output
+                    .stderr
+                    .lines()
+                    .map(|x| vec![TextSpan::from(x).fg(error_color)]),
+            );
+        }
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .scroll(true)
+                .step(8)
+                .highlighted_color(color)
+                .highlighted_str("➤ ")
+                .title("Command output", Alignment::Center)
+                .rows(rows),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ExecOutputPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc | Key::Enter,
+                ..
+            }) => Some(Msg::Ui(UiMsg::CloseExecOutputPopup)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(MockComponent)]
 pub struct FatalPopup {
     component: Paragraph,
@@ -397,7 +1015,7 @@ pub struct FileInfoPopup {
 }
 
 impl FileInfoPopup {
-    pub fn new(file: &File) -> Self {
+    pub fn new(file: &File, media: Option<&MediaInfo>, preview: Option<&ImagePreview>) -> Self {
         let mut texts: TableBuilder = TableBuilder::default();
         // Abs path
         let real_path = file.metadata().symlink.as_deref();
@@ -474,10 +1092,67 @@ impl FileInfoPopup {
             .add_row()
             .add_col(TextSpan::from("Group: "))
             .add_col(TextSpan::new(group.as_str()).fg(Color::Blue));
+        // Media
+        if let Some(media) = media {
+            texts
+                .add_row()
+                .add_col(TextSpan::from("Format: "))
+                .add_col(TextSpan::new(media.format).fg(Color::LightMagenta));
+            if let Some((width, height)) = media.dimensions {
+                texts
+                    .add_row()
+                    .add_col(TextSpan::from("Dimensions: "))
+                    .add_col(TextSpan::new(format!("{width}x{height}")).fg(Color::LightCyan));
+            }
+            if let Some(duration) = media.duration {
+                let secs = duration.as_secs();
+                texts
+                    .add_row()
+                    .add_col(TextSpan::from("Duration: "))
+                    .add_col(
+                        TextSpan::new(format!(
+                            "{:02}:{:02}:{:02}",
+                            secs / 3600,
+                            (secs / 60) % 60,
+                            secs % 60
+                        ))
+                        .fg(Color::LightGreen),
+                    );
+            }
+            if let Some(codec) = media.codec.as_deref() {
+                texts
+                    .add_row()
+                    .add_col(TextSpan::from("Codec: "))
+                    .add_col(TextSpan::new(codec).fg(Color::LightYellow));
+            }
+            if let Some(sample_rate) = media.sample_rate {
+                texts
+                    .add_row()
+                    .add_col(TextSpan::from("Sample rate: "))
+                    .add_col(TextSpan::new(format!("{sample_rate} Hz")).fg(Color::LightBlue));
+            }
+            if let Some(channels) = media.channels {
+                texts
+                    .add_row()
+                    .add_col(TextSpan::from("Channels: "))
+                    .add_col(TextSpan::new(channels.to_string()).fg(Color::LightRed));
+            }
+        }
+        // Image preview
+        if let Some(preview) = preview {
+            texts.add_row();
+            for row in preview.rows() {
+                texts.add_row();
+                for (fg, bg) in row.iter() {
+                    texts.add_col(TextSpan::new(ImagePreview::CELL).fg(*fg).bg(*bg));
+                }
+            }
+        }
         Self {
             component: List::default()
                 .borders(Borders::default().modifiers(BorderType::Rounded))
-                .scroll(false)
+                .scroll(preview.is_some())
+                .step(4)
                 .title(file.name(), Alignment::Left)
                 .rows(texts.build()),
         }
@@ -487,6 +1162,29 @@ impl FileInfoPopup {
 impl Component<Msg, NoUserEvent> for FileInfoPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
                 code: Key::Esc | Key::Enter,
                 ..
@@ -672,7 +1370,32 @@ pub struct KeybindingsPopup {
 }
 
 impl KeybindingsPopup {
-    pub fn new(key_color: Color) -> Self {
+    pub fn new(key_color: Color, keys: &KeyBindings, vim_mode: bool) -> Self {
+        let mut rows = TableBuilder::default();
+        let mut add_row = |key: &str, description: &str| {
+            rows.add_col(TextSpan::new(key).bold().fg(key_color))
+                .add_col(TextSpan::from(format!(
+                    "{}{description}",
+                    " ".repeat(18_usize.saturating_sub(key.width()).max(2))
+                )))
+                .add_row();
+        };
+        add_row("<ESC>", "Disconnect");
+        add_row("<TAB|RIGHT|LEFT>", "Change explorer tab");
+        add_row("<UP/DOWN>", "Move up/down in list");
+        add_row("<ENTER>", "Enter directory");
+        add_row("<BACKTAB>", "Switch between explorer and log window");
+        if vim_mode {
+            add_row("<J/K>", "Move down/up in list");
+            add_row("<H>", "Go to parent directory");
+            add_row("<L>", "Enter directory");
+            add_row("<GG/SHIFT+G>", "Go to first/last file");
+            add_row("<COUNT>", "Repeat motion (5J) or go to file (10SHIFT+G)");
+        }
+        for (action, _) in keys.iter(KeyScope::Explorer) {
+            add_row(&keys.label(action), action.description());
+        }
+        add_row("<CTRL+C>", "Interrupt file transfer");
         Self {
             component: List::default()
                 .borders(Borders::default().modifiers(BorderType::Rounded))
@@ -681,128 +1404,7 @@ impl KeybindingsPopup {
                 .highlighted_str("? ")
                 .title("Keybindings", Alignment::Center)
                 .rewind(true)
-                .rows(
-                    TableBuilder::default()
-                        .add_col(TextSpan::new("<ESC>").bold().fg(key_color))
-                        .add_col(TextSpan::from("             Disconnect"))
-                        .add_row()
-                        .add_col(TextSpan::new("<BACKSPACE>").bold().fg(key_color))
-                        .add_col(TextSpan::from("       Go to previous directory"))
-                        .add_row()
-                        .add_col(TextSpan::new("<TAB|RIGHT|LEFT>").bold().fg(key_color))
-                        .add_col(TextSpan::from("  Change explorer tab"))
-                        .add_row()
-                        .add_col(TextSpan::new("<UP/DOWN>").bold().fg(key_color))
-                        .add_col(TextSpan::from("         Move up/down in list"))
-                        .add_row()
-                        .add_col(TextSpan::new("<ENTER>").bold().fg(key_color))
-                        .add_col(TextSpan::from("           Enter directory"))
-                        .add_row()
-                        .add_col(TextSpan::new("<SPACE>").bold().fg(key_color))
-                        .add_col(TextSpan::from("           Upload/Download file"))
-                        .add_row()
-                        .add_col(TextSpan::new("<BACKTAB>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "         Switch between explorer and log window",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<A>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Toggle hidden files"))
-                        .add_row()
-                        .add_col(TextSpan::new("<B>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Change file sorting mode"))
-                        .add_row()
-                        .add_col(TextSpan::new("<C|F5>").bold().fg(key_color))
-                        .add_col(TextSpan::from("            Copy"))
-                        .add_row()
-                        .add_col(TextSpan::new("<D|F7>").bold().fg(key_color))
-                        .add_col(TextSpan::from("            Make directory"))
-                        .add_row()
-                        .add_col(TextSpan::new("<F>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Search files"))
-                        .add_row()
-                        .add_col(TextSpan::new("<G>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Go to path"))
-                        .add_row()
-                        .add_col(TextSpan::new("<H|F1>").bold().fg(key_color))
-                        .add_col(TextSpan::from("            Show help"))
-                        .add_row()
-                        .add_col(TextSpan::new("<I>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "               Show info about selected file",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<K>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "               Create symlink pointing to the current selected entry",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<L>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Reload directory content"))
-                        .add_row()
-                        .add_col(TextSpan::new("<M>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Select file"))
-                        .add_row()
-                        .add_col(TextSpan::new("<N>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Create new file"))
-                        .add_row()
-                        .add_col(TextSpan::new("<O|F4>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "            Open text file with preferred editor",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<P>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Toggle log panel"))
-                        .add_row()
-                        .add_col(TextSpan::new("<Q|F10>").bold().fg(key_color))
-                        .add_col(TextSpan::from("           Quit termscp"))
-                        .add_row()
-                        .add_col(TextSpan::new("<R|F6>").bold().fg(key_color))
-                        .add_col(TextSpan::from("            Rename file"))
-                        .add_row()
-                        .add_col(TextSpan::new("<S|F2>").bold().fg(key_color))
-                        .add_col(TextSpan::from("            Save file as"))
-                        .add_row()
-                        .add_col(TextSpan::new("<T>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Watch/unwatch file changes"))
-                        .add_row()
-                        .add_col(TextSpan::new("<U>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Go to parent directory"))
-                        .add_row()
-                        .add_col(TextSpan::new("<V|F3>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "            Open file with default application for file type",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<W>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "               Open file with specified application",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<X>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Execute shell command"))
-                        .add_row()
-                        .add_col(TextSpan::new("<Y>").bold().fg(key_color))
-                        .add_col(TextSpan::from(
-                            "               Toggle synchronized browsing",
-                        ))
-                        .add_row()
-                        .add_col(TextSpan::new("<Z>").bold().fg(key_color))
-                        .add_col(TextSpan::from("               Change file permissions"))
-                        .add_row()
-                        .add_col(TextSpan::new("<DEL|F8|E>").bold().fg(key_color))
-                        .add_col(TextSpan::from("        Delete selected file"))
-                        .add_row()
-                        .add_col(TextSpan::new("<CTRL+A>").bold().fg(key_color))
-                        .add_col(TextSpan::from("          Select all files"))
-                        .add_row()
-                        .add_col(TextSpan::new("<CTRL+C>").bold().fg(key_color))
-                        .add_col(TextSpan::from("          Interrupt file transfer"))
-                        .add_row()
-                        .add_col(TextSpan::new("<CTRL+T>").bold().fg(key_color))
-                        .add_col(TextSpan::from("          Show watched paths"))
-                        .build(),
-                ),
+                .rows(rows.build()),
         }
     }
 }
@@ -1417,72 +2019,383 @@ impl ReplacingFilesListPopup {
                         .color(color)
                         .modifiers(BorderType::Rounded),
                 )
-                .scroll(true)
-                .step(4)
+                .scroll(true)
+                .step(4)
+                .highlighted_color(color)
+                .highlighted_str("➤ ")
+                .title(
+                    "The following files are going to be replaced",
+                    Alignment::Center,
+                )
+                .rows(files.iter().map(|x| vec![TextSpan::from(x)]).collect()),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ReplacingFilesListPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::PendingAction(PendingActionMsg::CloseReplacePopups))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                Some(Msg::Ui(UiMsg::ReplacePopupTabbed))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct SaveAsPopup {
+    component: Input,
+}
+
+impl SaveAsPopup {
+    pub fn new(color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .input_type(InputType::Text)
+                .placeholder(
+                    "/foo/bar/buzz.txt",
+                    Style::default().fg(Color::Rgb(128, 128, 128)),
+                )
+                .title("Save as…", Alignment::Center),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for SaveAsPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                ..
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.state() {
+                State::One(StateValue::String(i)) => {
+                    Some(Msg::Transfer(TransferMsg::SaveFileAs(i)))
+                }
+                _ => Some(Msg::None),
+            },
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseSaveAsPopup))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// File sortings, in the order they're displayed in the `SortingPopup`
+const FILE_SORTINGS: [FileSorting; 11] = [
+    FileSorting::Name,
+    FileSorting::ModifyTime,
+    FileSorting::CreationTime,
+    FileSorting::AccessTime,
+    FileSorting::Size,
+    FileSorting::Extension,
+    FileSorting::Type,
+    FileSorting::Owner,
+    FileSorting::Group,
+    FileSorting::Permissions,
+    FileSorting::Path,
+];
+
+#[derive(MockComponent)]
+pub struct SortingPopup {
+    component: List,
+}
+
+impl SortingPopup {
+    /// Instantiate a new `SortingPopup` for `explorer`, with the cursor at `line`.
+    /// The popup lists all the file sortings, followed by the sorting options
+    pub fn new(explorer: &FileExplorer, line: usize, color: Color) -> Self {
+        let current = explorer.get_file_sorting();
+        let options = [
+            (explorer.reverse_sorting(), "Reverse order"),
+            (explorer.natural_sorting(), "Natural order (file2 < file10)"),
+            (explorer.case_sensitive_sorting(), "Case sensitive"),
+        ];
+        let rows = FILE_SORTINGS
+            .iter()
+            .map(|x| match *x == current {
+                true => format!("● {}", file_sorting_label(*x, false)),
+                false => format!("○ {}", file_sorting_label(*x, false)),
+            })
+            .chain(options.iter().map(|(enabled, label)| match enabled {
+                true => format!("[x] {label}"),
+                false => format!("[ ] {label}"),
+            }))
+            .map(|x| vec![TextSpan::from(x)])
+            .collect();
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
                 .highlighted_color(color)
                 .highlighted_str("➤ ")
-                .title(
-                    "The following files are going to be replaced",
-                    Alignment::Center,
-                )
-                .rows(files.iter().map(|x| vec![TextSpan::from(x)]).collect()),
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .title("Sort files by…", Alignment::Center)
+                .rows(rows)
+                .selected_line(line),
+        }
+    }
+
+    /// Get the line of `sorting` in the popup
+    pub fn line_of(sorting: FileSorting) -> usize {
+        FILE_SORTINGS
+            .iter()
+            .position(|x| *x == sorting)
+            .unwrap_or_default()
+    }
+
+    /// Get the index of the sorting option at cursor; `None` if the cursor is on a file sorting
+    fn option_at_cursor(&self) -> Option<usize> {
+        match self.state() {
+            State::One(StateValue::Usize(line)) => line.checked_sub(FILE_SORTINGS.len()),
+            _ => None,
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for ReplacingFilesListPopup {
+impl Component<Msg, NoUserEvent> for SortingPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::PendingAction(PendingActionMsg::CloseReplacePopups))
-            }
-            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
-                Some(Msg::Ui(UiMsg::ReplacePopupTabbed))
-            }
+        let result = match ev {
             Event::Keyboard(KeyEvent {
                 code: Key::Down, ..
-            }) => {
-                self.perform(Cmd::Move(Direction::Down));
-                Some(Msg::None)
-            }
+            }) => self.perform(Cmd::Move(Direction::Down)),
             Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
-                self.perform(Cmd::Move(Direction::Up));
-                Some(Msg::None)
+                self.perform(Cmd::Move(Direction::Up))
             }
             Event::Keyboard(KeyEvent {
-                code: Key::PageDown,
+                code: code @ (Key::Enter | Key::Char(' ')),
                 ..
             }) => {
-                self.perform(Cmd::Scroll(Direction::Down));
-                Some(Msg::None)
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::PageUp, ..
-            }) => {
-                self.perform(Cmd::Scroll(Direction::Up));
-                Some(Msg::None)
+                return match (self.option_at_cursor(), code) {
+                    (Some(0), _) => Some(Msg::Ui(UiMsg::ToggleReverseSorting)),
+                    (Some(1), _) => Some(Msg::Ui(UiMsg::ToggleNaturalSorting)),
+                    (Some(_), _) => Some(Msg::Ui(UiMsg::ToggleCaseSensitiveSorting)),
+                    (None, Key::Enter) => Some(Msg::Ui(UiMsg::CloseFileSortingPopup)),
+                    (None, _) => match self.state() {
+                        State::One(StateValue::Usize(i)) => {
+                            Some(Msg::Ui(UiMsg::ChangeFileSorting(FILE_SORTINGS[i])))
+                        }
+                        _ => Some(Msg::None),
+                    },
+                };
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Home, ..
-            }) => {
-                self.perform(Cmd::GoTo(Position::Begin));
-                Some(Msg::None)
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Ui(UiMsg::CloseFileSortingPopup))
             }
-            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
-                self.perform(Cmd::GoTo(Position::End));
-                Some(Msg::None)
+            _ => return None,
+        };
+        match result {
+            CmdResult::Changed(State::One(StateValue::Usize(i))) if i < FILE_SORTINGS.len() => {
+                Some(Msg::Ui(UiMsg::ChangeFileSorting(FILE_SORTINGS[i])))
             }
-            _ => None,
+            _ => Some(Msg::None),
         }
     }
 }
 
 #[derive(MockComponent)]
-pub struct SaveAsPopup {
+pub struct StatusBarLocal {
+    component: Span,
+}
+
+impl StatusBarLocal {
+    pub fn new(browser: &Browser, sorting_color: Color, hidden_color: Color) -> Self {
+        let file_sorting = file_sorting_label(
+            browser.local().file_sorting,
+            browser.local().reverse_sorting(),
+        );
+        let hidden_files = hidden_files_label(browser.local().hidden_files_visible());
+        Self {
+            component: Span::default().spans(&[
+                TextSpan::new("File sorting: ").fg(sorting_color),
+                TextSpan::new(file_sorting).fg(sorting_color).reversed(),
+                TextSpan::new(" Hidden files: ").fg(hidden_color),
+                TextSpan::new(hidden_files).fg(hidden_color).reversed(),
+            ]),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for StatusBarLocal {
+    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+#[derive(MockComponent)]
+pub struct StatusBarRemote {
+    component: Span,
+}
+
+impl StatusBarRemote {
+    /// Instantiate a new `StatusBarRemote`; `sync_queue` is displayed if the file watcher is in use
+    pub fn new(
+        browser: &Browser,
+        sync_queue: Option<&SyncQueue>,
+        sorting_color: Color,
+        hidden_color: Color,
+        sync_color: Color,
+    ) -> Self {
+        let file_sorting = file_sorting_label(
+            browser.remote().file_sorting,
+            browser.remote().reverse_sorting(),
+        );
+        let hidden_files = hidden_files_label(browser.remote().hidden_files_visible());
+        let sync_browsing = match browser.sync_browsing {
+            true => "ON ",
+            false => "OFF",
+        };
+        let mut spans = vec![
+            TextSpan::new("File sorting: ").fg(sorting_color),
+            TextSpan::new(file_sorting).fg(sorting_color).reversed(),
+            TextSpan::new(" Hidden files: ").fg(hidden_color),
+            TextSpan::new(hidden_files).fg(hidden_color).reversed(),
+            TextSpan::new(" Sync browsing: ").fg(sync_color),
+            TextSpan::new(sync_browsing).fg(sync_color).reversed(),
+        ];
+        if let Some(queue) = sync_queue {
+            let pending = match queue.paused() {
+                true => format!("{} (paused)", queue.len()),
+                false => queue.len().to_string(),
+            };
+            spans.push(TextSpan::new(" Pending sync: ").fg(sync_color));
+            spans.push(TextSpan::new(pending).fg(sync_color).reversed());
+        }
+        Self {
+            component: Span::default().spans(&spans),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for StatusBarRemote {
+    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+fn file_sorting_label(sorting: FileSorting, reversed: bool) -> String {
+    let label = match sorting {
+        FileSorting::AccessTime => "By access time",
+        FileSorting::CreationTime => "By creation time",
+        FileSorting::Extension => "By extension",
+        FileSorting::Group => "By group",
+        FileSorting::ModifyTime => "By modify time",
+        FileSorting::Name => "By name",
+        FileSorting::Owner => "By owner",
+        FileSorting::Path => "By path",
+        FileSorting::Permissions => "By permissions",
+        FileSorting::Size => "By size",
+        FileSorting::Type => "By type",
+    };
+    match reversed {
+        true => format!("{label} (reversed)"),
+        false => label.to_string(),
+    }
+}
+
+fn hidden_files_label(visible: bool) -> &'static str {
+    match visible {
+        true => "Show",
+        false => "Hide",
+    }
+}
+
+#[derive(MockComponent)]
+pub struct SymlinkPopup {
     component: Input,
 }
 
-impl SaveAsPopup {
+impl SymlinkPopup {
     pub fn new(color: Color) -> Self {
         Self {
             component: Input::default()
@@ -1494,15 +2407,18 @@ impl SaveAsPopup {
                 .foreground(color)
                 .input_type(InputType::Text)
                 .placeholder(
-                    "/foo/bar/buzz.txt",
+                    "Symlink name",
                     Style::default().fg(Color::Rgb(128, 128, 128)),
                 )
-                .title("Save as…", Alignment::Center),
+                .title(
+                    "Create a symlink pointing to the selected entry",
+                    Alignment::Center,
+                ),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for SaveAsPopup {
+impl Component<Msg, NoUserEvent> for SymlinkPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -1551,12 +2467,12 @@ impl Component<Msg, NoUserEvent> for SaveAsPopup {
                 code: Key::Enter, ..
             }) => match self.state() {
                 State::One(StateValue::String(i)) => {
-                    Some(Msg::Transfer(TransferMsg::SaveFileAs(i)))
+                    Some(Msg::Transfer(TransferMsg::CreateSymlink(i)))
                 }
                 _ => Some(Msg::None),
             },
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::Ui(UiMsg::CloseSaveAsPopup))
+                Some(Msg::Ui(UiMsg::CloseSymlinkPopup))
             }
             _ => None,
         }
@@ -1564,12 +2480,12 @@ impl Component<Msg, NoUserEvent> for SaveAsPopup {
 }
 
 #[derive(MockComponent)]
-pub struct SortingPopup {
+pub struct SyncBrowsingMkdirPopup {
     component: Radio,
 }
 
-impl SortingPopup {
-    pub fn new(value: FileSorting, color: Color) -> Self {
+impl SyncBrowsingMkdirPopup {
+    pub fn new(color: Color, dir_name: &str) -> Self {
         Self {
             component: Radio::default()
                 .borders(
@@ -1578,133 +2494,185 @@ impl SortingPopup {
                         .modifiers(BorderType::Rounded),
                 )
                 .foreground(color)
-                .choices(&["Name", "Modify time", "Creation time", "Size"])
-                .title("Sort files by…", Alignment::Center)
-                .value(match value {
-                    FileSorting::CreationTime => 2,
-                    FileSorting::ModifyTime => 1,
-                    FileSorting::Name => 0,
-                    FileSorting::Size => 3,
-                }),
+                .choices(&["Yes", "No"])
+                .title(
+                    format!(
+                        r#"Sync browsing: directory "{dir_name}" doesn't exist. Do you want to create it?"#
+                    ),
+                    Alignment::Center,
+                ),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for SortingPopup {
+impl Component<Msg, NoUserEvent> for SyncBrowsingMkdirPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        let result = match ev {
+        match ev {
             Event::Keyboard(KeyEvent {
                 code: Key::Left, ..
-            }) => self.perform(Cmd::Move(Direction::Left)),
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
                 code: Key::Right, ..
-            }) => self.perform(Cmd::Move(Direction::Right)),
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => Some(Msg::PendingAction(
+                PendingActionMsg::CloseSyncBrowsingMkdirPopup,
+            )),
             Event::Keyboard(KeyEvent {
-                code: Key::Esc | Key::Enter,
-                ..
-            }) => return Some(Msg::Ui(UiMsg::CloseFileSortingPopup)),
-            _ => return None,
-        };
-        if let CmdResult::Changed(State::One(StateValue::Usize(i))) = result {
-            Some(Msg::Ui(UiMsg::ChangeFileSorting(match i {
-                0 => FileSorting::Name,
-                1 => FileSorting::ModifyTime,
-                2 => FileSorting::CreationTime,
-                3 => FileSorting::Size,
-                _ => FileSorting::Name,
-            })))
-        } else {
-            Some(Msg::None)
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::PendingAction(PendingActionMsg::MakePendingDirectory)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::PendingAction(
+                PendingActionMsg::CloseSyncBrowsingMkdirPopup,
+            )),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if matches!(
+                    self.perform(Cmd::Submit),
+                    CmdResult::Submit(State::One(StateValue::Usize(0)))
+                ) {
+                    Some(Msg::PendingAction(PendingActionMsg::MakePendingDirectory))
+                } else {
+                    Some(Msg::PendingAction(
+                        PendingActionMsg::CloseSyncBrowsingMkdirPopup,
+                    ))
+                }
+            }
+            _ => None,
         }
     }
 }
 
 #[derive(MockComponent)]
-pub struct StatusBarLocal {
-    component: Span,
+pub struct WaitPopup {
+    component: Paragraph,
 }
 
-impl StatusBarLocal {
-    pub fn new(browser: &Browser, sorting_color: Color, hidden_color: Color) -> Self {
-        let file_sorting = file_sorting_label(browser.local().file_sorting);
-        let hidden_files = hidden_files_label(browser.local().hidden_files_visible());
+impl WaitPopup {
+    pub fn new<S: AsRef<str>>(text: S, color: Color) -> Self {
         Self {
-            component: Span::default().spans(&[
-                TextSpan::new("File sorting: ").fg(sorting_color),
-                TextSpan::new(file_sorting).fg(sorting_color).reversed(),
-                TextSpan::new(" Hidden files: ").fg(hidden_color),
-                TextSpan::new(hidden_files).fg(hidden_color).reversed(),
-            ]),
+            component: Paragraph::default()
+                .alignment(Alignment::Center)
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .text(&[TextSpan::from(text.as_ref())])
+                .wrap(true),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for StatusBarLocal {
+impl Component<Msg, NoUserEvent> for WaitPopup {
     fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
         None
     }
 }
 
 #[derive(MockComponent)]
-pub struct StatusBarRemote {
-    component: Span,
+pub struct FollowPopup {
+    component: List,
 }
 
-impl StatusBarRemote {
-    pub fn new(
-        browser: &Browser,
-        sorting_color: Color,
-        hidden_color: Color,
-        sync_color: Color,
-    ) -> Self {
-        let file_sorting = file_sorting_label(browser.remote().file_sorting);
-        let hidden_files = hidden_files_label(browser.remote().hidden_files_visible());
-        let sync_browsing = match browser.sync_browsing {
-            true => "ON ",
-            false => "OFF",
+impl FollowPopup {
+    /// Instantiate a new `FollowPopup` displaying the lines read from the followed file, scrolled to the last one
+    pub fn new(follower: &Follower, color: Color, highlight_color: Color) -> Self {
+        let title = match follower.paused() {
+            true => format!("{} (paused)", follower.path().display()),
+            false => format!("{} (following)", follower.path().display()),
         };
+        let rows: Vec<Vec<TextSpan>> = follower
+            .lines()
+            .map(|line| match follower.is_highlighted(line) {
+                true => vec![TextSpan::from(line).fg(highlight_color).bold()],
+                false => vec![TextSpan::from(line)],
+            })
+            .collect();
+        let last = rows.len().saturating_sub(1);
         Self {
-            component: Span::default().spans(&[
-                TextSpan::new("File sorting: ").fg(sorting_color),
-                TextSpan::new(file_sorting).fg(sorting_color).reversed(),
-                TextSpan::new(" Hidden files: ").fg(hidden_color),
-                TextSpan::new(hidden_files).fg(hidden_color).reversed(),
-                TextSpan::new(" Sync browsing: ").fg(sync_color),
-                TextSpan::new(sync_browsing).fg(sync_color).reversed(),
-            ]),
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .scroll(true)
+                .step(8)
+                .highlighted_color(color)
+                .title(title, Alignment::Center)
+                .rows(rows)
+                .selected_line(last),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for StatusBarRemote {
-    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
-        None
-    }
-}
-
-fn file_sorting_label(sorting: FileSorting) -> &'static str {
-    match sorting {
-        FileSorting::CreationTime => "By creation time",
-        FileSorting::ModifyTime => "By modify time",
-        FileSorting::Name => "By name",
-        FileSorting::Size => "By size",
-    }
-}
-
-fn hidden_files_label(visible: bool) -> &'static str {
-    match visible {
-        true => "Show",
-        false => "Hide",
+impl Component<Msg, NoUserEvent> for FollowPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc | Key::Enter,
+                ..
+            }) => Some(Msg::Ui(UiMsg::CloseFollowPopup)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(' ') | Key::Char('p'),
+                ..
+            }) => Some(Msg::Ui(UiMsg::ToggleFollowPause)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            _ => None,
+        }
     }
 }
 
 #[derive(MockComponent)]
-pub struct SymlinkPopup {
+pub struct FollowHighlightPopup {
     component: Input,
 }
 
-impl SymlinkPopup {
-    pub fn new(color: Color) -> Self {
+impl FollowHighlightPopup {
+    pub fn new(file: &str, patterns: &str, color: Color) -> Self {
         Self {
             component: Input::default()
                 .borders(
@@ -1715,18 +2683,19 @@ impl SymlinkPopup {
                 .foreground(color)
                 .input_type(InputType::Text)
                 .placeholder(
-                    "Symlink name",
+                    "ERROR, WARN",
                     Style::default().fg(Color::Rgb(128, 128, 128)),
                 )
                 .title(
-                    "Create a symlink pointing to the selected entry",
+                    format!(r#"Follow "{file}", highlighting lines containing (comma separated)"#),
                     Alignment::Center,
-                ),
+                )
+                .value(patterns),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for SymlinkPopup {
+impl Component<Msg, NoUserEvent> for FollowHighlightPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
@@ -1774,13 +2743,11 @@ impl Component<Msg, NoUserEvent> for SymlinkPopup {
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => match self.state() {
-                State::One(StateValue::String(i)) => {
-                    Some(Msg::Transfer(TransferMsg::CreateSymlink(i)))
-                }
-                _ => Some(Msg::None),
+                State::One(StateValue::String(i)) => Some(Msg::Transfer(TransferMsg::Follow(i))),
+                _ => Some(Msg::Transfer(TransferMsg::Follow(String::new()))),
             },
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
-                Some(Msg::Ui(UiMsg::CloseSymlinkPopup))
+                Some(Msg::Ui(UiMsg::CloseFollowHighlightPopup))
             }
             _ => None,
         }
@@ -1788,113 +2755,118 @@ impl Component<Msg, NoUserEvent> for SymlinkPopup {
 }
 
 #[derive(MockComponent)]
-pub struct SyncBrowsingMkdirPopup {
-    component: Radio,
+pub struct SyncQueueList {
+    component: List,
 }
 
-impl SyncBrowsingMkdirPopup {
-    pub fn new(color: Color, dir_name: &str) -> Self {
+impl SyncQueueList {
+    /// Instantiate a new `SyncQueueList` from the changes waiting to be synched
+    pub fn new(queue: &SyncQueue, color: Color) -> Self {
+        let title = match queue.paused() {
+            true => "Changes waiting to be synched (paused)",
+            false => "Changes waiting to be synched",
+        };
         Self {
-            component: Radio::default()
+            component: List::default()
                 .borders(
                     Borders::default()
                         .color(color)
                         .modifiers(BorderType::Rounded),
                 )
-                .foreground(color)
-                .choices(&["Yes", "No"])
-                .title(
-                    format!(
-                        r#"Sync browsing: directory "{dir_name}" doesn't exist. Do you want to create it?"#
-                    ),
-                    Alignment::Center,
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .highlighted_color(color)
+                .highlighted_str("➤ ")
+                .title(title, Alignment::Center)
+                .rows(
+                    queue
+                        .iter()
+                        .map(|pending| {
+                            let label = match pending.change() {
+                                FsChange::Move(mov) => format!(
+                                    "rename {} to {}",
+                                    mov.source().display(),
+                                    mov.destination().display()
+                                ),
+                                FsChange::Remove(remove) => {
+                                    format!("remove {}", remove.path().display())
+                                }
+                                FsChange::Update(update) => {
+                                    format!("upload {}", update.remote().display())
+                                }
+                            };
+                            let mut row = vec![TextSpan::from(label)];
+                            if pending.attempts() > 0 {
+                                row.push(
+                                    TextSpan::from(format!(
+                                        " (failed {} times)",
+                                        pending.attempts()
+                                    ))
+                                    .fg(Color::Rgb(128, 128, 128)),
+                                );
+                            }
+                            row
+                        })
+                        .collect(),
                 ),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for SyncBrowsingMkdirPopup {
+impl Component<Msg, NoUserEvent> for SyncQueueList {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         match ev {
             Event::Keyboard(KeyEvent {
-                code: Key::Left, ..
+                code: Key::Esc | Key::Enter,
+                ..
+            }) => Some(Msg::Ui(UiMsg::CloseSyncQueueList)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
             }) => {
-                self.perform(Cmd::Move(Direction::Left));
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
                 Some(Msg::None)
             }
             Event::Keyboard(KeyEvent {
-                code: Key::Right, ..
+                code: Key::PageDown,
+                ..
             }) => {
-                self.perform(Cmd::Move(Direction::Right));
+                self.perform(Cmd::Scroll(Direction::Down));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => Some(Msg::PendingAction(
-                PendingActionMsg::CloseSyncBrowsingMkdirPopup,
-            )),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('y'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::PendingAction(PendingActionMsg::MakePendingDirectory)),
             Event::Keyboard(KeyEvent {
-                code: Key::Char('n'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::PendingAction(
-                PendingActionMsg::CloseSyncBrowsingMkdirPopup,
-            )),
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
             Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
+                code: Key::Home, ..
             }) => {
-                if matches!(
-                    self.perform(Cmd::Submit),
-                    CmdResult::Submit(State::One(StateValue::Usize(0)))
-                ) {
-                    Some(Msg::PendingAction(PendingActionMsg::MakePendingDirectory))
-                } else {
-                    Some(Msg::PendingAction(
-                        PendingActionMsg::CloseSyncBrowsingMkdirPopup,
-                    ))
-                }
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
             }
             _ => None,
         }
     }
 }
 
-#[derive(MockComponent)]
-pub struct WaitPopup {
-    component: Paragraph,
-}
-
-impl WaitPopup {
-    pub fn new<S: AsRef<str>>(text: S, color: Color) -> Self {
-        Self {
-            component: Paragraph::default()
-                .alignment(Alignment::Center)
-                .borders(
-                    Borders::default()
-                        .color(color)
-                        .modifiers(BorderType::Rounded),
-                )
-                .foreground(color)
-                .text(&[TextSpan::from(text.as_ref())])
-                .wrap(true),
-        }
-    }
-}
-
-impl Component<Msg, NoUserEvent> for WaitPopup {
-    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
-        None
-    }
-}
-
 #[derive(MockComponent)]
 pub struct WatchedPathsList {
     component: List,
 }
 
 impl WatchedPathsList {
-    pub fn new(paths: &[std::path::PathBuf], color: Color) -> Self {
+    /// Instantiate a new `WatchedPathsList` from the watched paths labels and their ignore patterns
+    pub fn new(paths: &[(String, Vec<String>)], color: Color) -> Self {
         Self {
             component: List::default()
                 .borders(
@@ -1914,7 +2886,16 @@ impl WatchedPathsList {
                 .rows(
                     paths
                         .iter()
-                        .map(|x| vec![TextSpan::from(x.to_string_lossy().to_string())])
+                        .map(|(path, ignore)| {
+                            let mut row = vec![TextSpan::from(path.as_str())];
+                            if !ignore.is_empty() {
+                                row.push(
+                                    TextSpan::from(format!(" (ignoring {})", ignore.join(", ")))
+                                        .fg(Color::Rgb(128, 128, 128)),
+                                );
+                            }
+                            row
+                        })
                         .collect(),
                 ),
         }
@@ -1975,16 +2956,104 @@ impl Component<Msg, NoUserEvent> for WatchedPathsList {
     }
 }
 
+#[derive(MockComponent)]
+pub struct WatcherIgnorePopup {
+    component: Input,
+}
+
+impl WatcherIgnorePopup {
+    pub fn new(local: &str, patterns: &str, color: Color) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(color)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(color)
+                .input_type(InputType::Text)
+                .placeholder(
+                    "*.swp, node_modules/",
+                    Style::default().fg(Color::Rgb(128, 128, 128)),
+                )
+                .title(
+                    format!(r#"Ignore changes in "{local}" matching (comma separated)"#),
+                    Alignment::Center,
+                )
+                .value(patterns),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for WatcherIgnorePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                ..
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.state() {
+                State::One(StateValue::String(i)) => Some(Msg::Transfer(TransferMsg::Watch(i))),
+                _ => Some(Msg::Transfer(TransferMsg::Watch(String::new()))),
+            },
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Ui(UiMsg::CloseWatcherIgnorePopup))
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(MockComponent)]
 pub struct WatcherPopup {
     component: Radio,
 }
 
 impl WatcherPopup {
-    pub fn new(watched: bool, local: &str, remote: &str, color: Color) -> Self {
+    pub fn new(watched: bool, source: &str, destination: &str, color: Color) -> Self {
         let text = match watched {
-            false => format!(r#"Synchronize changes from "{local}" to "{remote}"?"#),
-            true => format!(r#"Stop synchronizing changes at "{local}"?"#),
+            false => format!(r#"Synchronize changes from "{source}" to "{destination}"?"#),
+            true => format!(r#"Stop synchronizing changes at "{source}"?"#),
         };
         Self {
             component: Radio::default()
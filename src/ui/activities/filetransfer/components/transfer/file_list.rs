@@ -4,14 +4,31 @@
 
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{
-    Alignment, AttrValue, Attribute, Borders, Color, Style, Table, TextModifiers,
+    Alignment, AttrValue, Attribute, Borders, Color, PropPayload, PropValue, Style, Table,
+    TextModifiers, TextSpan,
 };
-use tuirealm::tui::layout::Corner;
+use tuirealm::tui::layout::{Constraint, Corner, Rect};
 use tuirealm::tui::text::{Line, Span};
-use tuirealm::tui::widgets::{List as TuiList, ListItem, ListState};
-use tuirealm::{MockComponent, Props, State, StateValue};
+use tuirealm::tui::widgets::{
+    Block, Cell, List as TuiList, ListItem, ListState, Row, Table as TuiTable, TableState,
+};
+use tuirealm::{Frame, MockComponent, Props, State, StateValue};
+use unicode_width::UnicodeWidthStr;
+
+use crate::utils::string::secure_substring;
 
 pub const FILE_LIST_CMD_SELECT_ALL: &str = "A";
+/// Table header. If set and not empty, the file list is rendered as a table, with a column for each
+/// header entry; otherwise each row is rendered as a line
+pub const FILE_LIST_HEADER: &str = "header";
+/// Widths of the table columns, as rendered in the last frame (query only)
+pub const FILE_LIST_COLUMN_WIDTHS: &str = "column-widths";
+/// Indexes of the selected files; replaces the current selection
+pub const FILE_LIST_SELECTION: &str = "selection";
+/// Spacing between table columns
+pub const FILE_LIST_COLUMN_SPACING: u16 = 1;
+/// Columns are never shrinked below this width
+const COLUMN_MIN_WIDTH: u16 = 3;
 
 /// OwnStates contains states for this component
 #[derive(Clone, Default)]
@@ -111,6 +128,15 @@ impl OwnStates {
         }
     }
 
+    /// Replace the selection with the provided entries
+    pub fn set_selection(&mut self, entries: &[usize]) {
+        let len = self.list_len();
+        self.selected.clear();
+        for entry in entries.iter().filter(|x| **x < len) {
+            self.select(*entry);
+        }
+    }
+
     /// Select provided index if not selected yet
     fn select(&mut self, entry: usize) {
         if !self.is_selected(entry) {
@@ -130,6 +156,7 @@ impl OwnStates {
 pub struct FileList {
     props: Props,
     states: OwnStates,
+    column_widths: Vec<u16>,
 }
 
 impl FileList {
@@ -165,26 +192,52 @@ impl FileList {
         self.attr(Attribute::Content, AttrValue::Table(rows));
         self
     }
-}
 
-impl MockComponent for FileList {
-    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
-        let title = self
+    /// Get table header; empty if the list is not rendered as a table
+    fn header(&self) -> Vec<String> {
+        match self
             .props
-            .get_or(
-                Attribute::Title,
-                AttrValue::Title((String::default(), Alignment::Left)),
-            )
-            .unwrap_title();
-        let borders = self
-            .props
-            .get_or(Attribute::Borders, AttrValue::Borders(Borders::default()))
-            .unwrap_borders();
-        let focus = self
-            .props
-            .get_or(Attribute::Focus, AttrValue::Flag(false))
-            .unwrap_flag();
-        let div = tui_realm_stdlib::utils::get_block(borders, Some(title), focus, None);
+            .get(Attribute::Custom(FILE_LIST_HEADER))
+            .map(|x| x.unwrap_payload())
+        {
+            Some(PropPayload::Vec(labels)) => labels
+                .into_iter()
+                .map(|x| match x {
+                    PropValue::Str(label) => label,
+                    _ => String::new(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get style for row at `num`
+    fn row_modifiers(&self, num: usize, modifiers: TextModifiers) -> TextModifiers {
+        match self.states.is_selected(num) {
+            true => {
+                modifiers
+                    | TextModifiers::REVERSED
+                    | TextModifiers::UNDERLINED
+                    | TextModifiers::ITALIC
+            }
+            false => modifiers,
+        }
+    }
+
+    /// Get highlight style for current row
+    fn highlight_style(&self, focus: bool) -> Option<Style> {
+        let modifiers = match focus {
+            true => TextModifiers::REVERSED,
+            false => TextModifiers::empty(),
+        };
+        self.props
+            .get(Attribute::HighlightedColor)
+            .map(|x| x.unwrap_color())
+            .map(|color| Style::default().fg(color).add_modifier(modifiers))
+    }
+
+    /// Render file list as a list; each row is a line
+    fn view_list(&self, frame: &mut Frame, area: Rect, div: Block, focus: bool) {
         // Make list entries
         let list_items: Vec<ListItem> = match self
             .props
@@ -198,16 +251,14 @@ impl MockComponent for FileList {
                     let columns: Vec<Span> = row
                         .iter()
                         .map(|col| {
-                            let (fg, bg, mut modifiers) =
+                            let (fg, bg, modifiers) =
                                 tui_realm_stdlib::utils::use_or_default_styles(&self.props, col);
-                            if self.states.is_selected(num) {
-                                modifiers |= TextModifiers::REVERSED
-                                    | TextModifiers::UNDERLINED
-                                    | TextModifiers::ITALIC;
-                            }
                             Span::styled(
                                 col.content.clone(),
-                                Style::default().add_modifier(modifiers).fg(fg).bg(bg),
+                                Style::default()
+                                    .add_modifier(self.row_modifiers(num, modifiers))
+                                    .fg(fg)
+                                    .bg(bg),
                             )
                         })
                         .collect();
@@ -216,30 +267,145 @@ impl MockComponent for FileList {
                 .collect(), // Make List item from TextSpan
             _ => Vec::new(),
         };
-        let highlighted_color = self
-            .props
-            .get(Attribute::HighlightedColor)
-            .map(|x| x.unwrap_color());
-        let modifiers = match focus {
-            true => TextModifiers::REVERSED,
-            false => TextModifiers::empty(),
-        };
         // Make list
         let mut list = TuiList::new(list_items)
             .block(div)
             .start_corner(Corner::TopLeft);
-        if let Some(highlighted_color) = highlighted_color {
-            list = list.highlight_style(
-                Style::default()
-                    .fg(highlighted_color)
-                    .add_modifier(modifiers),
-            );
+        if let Some(style) = self.highlight_style(focus) {
+            list = list.highlight_style(style);
         }
         let mut state: ListState = ListState::default();
         state.select(Some(self.states.list_index));
         frame.render_stateful_widget(list, area, &mut state);
     }
 
+    /// Render file list as a table; each row has a cell for each column in `header`.
+    /// Column widths are fit to the available space and cells exceeding the column width are elided
+    fn view_table(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        div: Block,
+        focus: bool,
+        header: Vec<String>,
+    ) {
+        let table: Table = self
+            .props
+            .get(Attribute::Content)
+            .map(|x| x.unwrap_table())
+            .unwrap_or_default();
+        let widths: Vec<u16> =
+            Self::column_widths(header.as_slice(), table.as_slice(), div.inner(area).width);
+        let rows: Vec<Row> = table
+            .iter()
+            .enumerate()
+            .map(|(num, row)| {
+                let cells: Vec<Cell> = row
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(col, width)| {
+                        let (fg, bg, modifiers) =
+                            tui_realm_stdlib::utils::use_or_default_styles(&self.props, col);
+                        Cell::from(Span::styled(
+                            Self::elide_cell(col.content.as_str(), *width as usize),
+                            Style::default()
+                                .add_modifier(self.row_modifiers(num, modifiers))
+                                .fg(fg)
+                                .bg(bg),
+                        ))
+                    })
+                    .collect();
+                Row::new(cells)
+            })
+            .collect();
+        let header = Row::new(
+            header
+                .iter()
+                .zip(widths.iter())
+                .map(|(label, width)| Cell::from(Self::elide_cell(label, *width as usize))),
+        )
+        .style(Style::default().add_modifier(TextModifiers::BOLD));
+        let constraints: Vec<Constraint> = widths.iter().map(|x| Constraint::Length(*x)).collect();
+        self.column_widths = widths;
+        let mut table = TuiTable::new(rows)
+            .block(div)
+            .header(header)
+            .column_spacing(FILE_LIST_COLUMN_SPACING)
+            .widths(constraints.as_slice());
+        if let Some(style) = self.highlight_style(focus) {
+            table = table.highlight_style(style);
+        }
+        let mut state: TableState = TableState::default();
+        state.select(Some(self.states.list_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    /// Calculate column widths to fit `available` width.
+    /// Each column is as wide as its widest cell; if columns don't fit, the widest columns are shrinked first
+    fn column_widths(header: &[String], table: &[Vec<TextSpan>], available: u16) -> Vec<u16> {
+        let mut widths: Vec<u16> = header.iter().map(|x| x.width() as u16).collect();
+        for row in table.iter() {
+            for (width, col) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(col.content.width() as u16);
+            }
+        }
+        let spacing: u16 = FILE_LIST_COLUMN_SPACING * (widths.len().saturating_sub(1) as u16);
+        let available: u16 = available.saturating_sub(spacing);
+        let mut total: u16 = widths.iter().sum();
+        while total > available {
+            // Shrink widest column
+            match widths
+                .iter_mut()
+                .filter(|x| **x > COLUMN_MIN_WIDTH)
+                .max_by_key(|x| **x)
+            {
+                Some(width) => {
+                    *width -= 1;
+                    total -= 1;
+                }
+                None => break,
+            }
+        }
+        widths
+    }
+
+    /// Elide cell content if wider than `width`
+    fn elide_cell(content: &str, width: usize) -> String {
+        match content.width() > width {
+            false => content.to_string(),
+            true => format!("{}…", secure_substring(content, 0, width.saturating_sub(1))),
+        }
+    }
+}
+
+impl MockComponent for FileList {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let title = self
+            .props
+            .get_or(
+                Attribute::Title,
+                AttrValue::Title((String::default(), Alignment::Left)),
+            )
+            .unwrap_title();
+        let borders = self
+            .props
+            .get_or(Attribute::Borders, AttrValue::Borders(Borders::default()))
+            .unwrap_borders();
+        let focus = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        let div = tui_realm_stdlib::utils::get_block(borders, Some(title), focus, None);
+        let header = self.header();
+        match header.is_empty() {
+            true => {
+                self.column_widths.clear();
+                self.view_list(frame, area, div, focus)
+            }
+            false => self.view_table(frame, area, div, focus, header),
+        }
+    }
+
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
         self.props.set(attr, value);
         if matches!(attr, Attribute::Content) {
@@ -250,11 +416,39 @@ impl MockComponent for FileList {
                 },
             );
             self.states.fix_list_index();
+        } else if matches!(attr, Attribute::Value) {
+            if let Some(index) = self
+                .props
+                .get(Attribute::Value)
+                .map(|x| x.unwrap_payload().unwrap_one().unwrap_usize())
+            {
+                self.states.list_index = index;
+                self.states.fix_list_index();
+            }
+        } else if matches!(attr, Attribute::Custom(FILE_LIST_SELECTION)) {
+            if let Some(AttrValue::Payload(PropPayload::Vec(entries))) = self.props.get(attr) {
+                let entries: Vec<usize> = entries.into_iter().map(|x| x.unwrap_usize()).collect();
+                self.states.set_selection(&entries);
+            }
         }
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            Attribute::Value => Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                self.states.list_index(),
+            )))),
+            Attribute::Custom(FILE_LIST_COLUMN_WIDTHS) => {
+                Some(AttrValue::Payload(PropPayload::Vec(
+                    self.column_widths
+                        .iter()
+                        .copied()
+                        .map(PropValue::U16)
+                        .collect(),
+                )))
+            }
+            attr => self.props.get(attr),
+        }
     }
 
     fn state(&self) -> State {
@@ -3,21 +3,37 @@
 //! file transfer components
 
 use super::{Msg, TransferMsg, UiMsg};
+use crate::config::keybindings::{KeyAction, KeyBindings, KeyScope};
 
 mod file_list;
+mod vim;
 use file_list::FileList;
+pub use file_list::{
+    FILE_LIST_COLUMN_SPACING, FILE_LIST_COLUMN_WIDTHS, FILE_LIST_HEADER, FILE_LIST_SELECTION,
+};
 use tuirealm::command::{Cmd, Direction, Position};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::event::{Key, KeyEvent};
 use tuirealm::props::{Alignment, Borders, Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
+use vim::VimNavigation;
 
 #[derive(MockComponent)]
 pub struct ExplorerFind {
     component: FileList,
+    keys: KeyBindings,
+    vim: Option<VimNavigation>,
 }
 
 impl ExplorerFind {
-    pub fn new<S: AsRef<str>>(title: S, files: &[&str], bg: Color, fg: Color, hg: Color) -> Self {
+    pub fn new<S: AsRef<str>>(
+        title: S,
+        files: &[&str],
+        bg: Color,
+        fg: Color,
+        hg: Color,
+        keys: KeyBindings,
+        vim_mode: bool,
+    ) -> Self {
         Self {
             component: FileList::default()
                 .background(bg)
@@ -26,6 +42,8 @@ impl ExplorerFind {
                 .highlighted_color(hg)
                 .title(title, Alignment::Left)
                 .rows(files.iter().map(|x| vec![TextSpan::from(x)]).collect()),
+            keys,
+            vim: vim_mode.then(VimNavigation::default),
         }
     }
 }
@@ -66,20 +84,6 @@ impl Component<Msg, NoUserEvent> for ExplorerFind {
                 self.perform(Cmd::GoTo(Position::End));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => {
-                let _ = self.perform(Cmd::Custom(file_list::FILE_LIST_CMD_SELECT_ALL));
-                Some(Msg::None)
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('m'),
-                modifiers: KeyModifiers::NONE,
-            }) => {
-                let _ = self.perform(Cmd::Toggle);
-                Some(Msg::None)
-            }
             // -- comp msg
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Ui(UiMsg::CloseFindExplorer))
@@ -91,46 +95,34 @@ impl Component<Msg, NoUserEvent> for ExplorerFind {
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => Some(Msg::Transfer(TransferMsg::EnterDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(' '),
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::TransferFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Backspace,
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::GoToPreviousDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ToggleHiddenFiles)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileSortingPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('e') | Key::Delete | Key::Function(8),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowDeletePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('i'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileInfoPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('s') | Key::Function(2),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowSaveAsPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('v') | Key::Function(3),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::OpenFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('w'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowOpenWithPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('z'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowChmodPopup)),
+            Event::Keyboard(ev) => match self
+                .vim
+                .as_mut()
+                .and_then(|vim| vim.on(&mut self.component, &ev))
+            {
+                Some(msg) => Some(msg),
+                None => match self.keys.action(KeyScope::Explorer, &ev) {
+                    Some(
+                        action @ (KeyAction::Chmod
+                        | KeyAction::CommandLine
+                        | KeyAction::Delete
+                        | KeyAction::FileInfo
+                        | KeyAction::GoToPreviousDirectory
+                        | KeyAction::Mark
+                        | KeyAction::Open
+                        | KeyAction::OpenWith
+                        | KeyAction::ReverseSorting
+                        | KeyAction::SaveAs
+                        | KeyAction::SelectAll
+                        | KeyAction::SortByNextColumn
+                        | KeyAction::Sorting
+                        | KeyAction::TableView
+                        | KeyAction::ToggleHiddenFiles
+                        | KeyAction::Transfer),
+                    ) => explorer_action(&mut self.component, action),
+                    _ => None,
+                },
+            },
             _ => None,
         }
     }
@@ -139,10 +131,20 @@ impl Component<Msg, NoUserEvent> for ExplorerFind {
 #[derive(MockComponent)]
 pub struct ExplorerLocal {
     component: FileList,
+    keys: KeyBindings,
+    vim: Option<VimNavigation>,
 }
 
 impl ExplorerLocal {
-    pub fn new<S: AsRef<str>>(title: S, files: &[&str], bg: Color, fg: Color, hg: Color) -> Self {
+    pub fn new<S: AsRef<str>>(
+        title: S,
+        files: &[&str],
+        bg: Color,
+        fg: Color,
+        hg: Color,
+        keys: KeyBindings,
+        vim_mode: bool,
+    ) -> Self {
         Self {
             component: FileList::default()
                 .background(bg)
@@ -151,6 +153,8 @@ impl ExplorerLocal {
                 .highlighted_color(hg)
                 .title(title, Alignment::Left)
                 .rows(files.iter().map(|x| vec![TextSpan::from(x)]).collect()),
+            keys,
+            vim: vim_mode.then(VimNavigation::default),
         }
     }
 }
@@ -191,20 +195,6 @@ impl Component<Msg, NoUserEvent> for ExplorerLocal {
                 self.perform(Cmd::GoTo(Position::End));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => {
-                let _ = self.perform(Cmd::Custom(file_list::FILE_LIST_CMD_SELECT_ALL));
-                Some(Msg::None)
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('m'),
-                modifiers: KeyModifiers::NONE,
-            }) => {
-                let _ = self.perform(Cmd::Toggle);
-                Some(Msg::None)
-            }
             // -- comp msg
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Ui(UiMsg::ShowDisconnectPopup))
@@ -213,109 +203,20 @@ impl Component<Msg, NoUserEvent> for ExplorerLocal {
                 code: Key::Right | Key::Tab | Key::BackTab,
                 ..
             }) => Some(Msg::Ui(UiMsg::ChangeTransferWindow)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Backspace,
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::GoToPreviousDirectory)),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => Some(Msg::Transfer(TransferMsg::EnterDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(' '),
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::TransferFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ToggleHiddenFiles)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileSortingPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c') | Key::Function(5),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowCopyPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('d') | Key::Function(7),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowMkdirPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('e') | Key::Delete | Key::Function(8),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowDeletePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('f'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFindPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('g'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowGotoPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('i'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileInfoPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowSymlinkPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('l'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::ReloadDir)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('n'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowNewFilePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('o') | Key::Function(4),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::OpenTextFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('p'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowLogPanel)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('r') | Key::Function(6),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowRenamePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('s') | Key::Function(2),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowSaveAsPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('t'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowWatcherPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('t'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Ui(UiMsg::ShowWatchedPathsList)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('u'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::GoToParentDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('x'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowExecPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('y'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ToggleSyncBrowsing)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('v') | Key::Function(3),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::OpenFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('w'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowOpenWithPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('z'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowChmodPopup)),
+            Event::Keyboard(ev) => match self
+                .vim
+                .as_mut()
+                .and_then(|vim| vim.on(&mut self.component, &ev))
+            {
+                Some(msg) => Some(msg),
+                None => match self.keys.action(KeyScope::Explorer, &ev) {
+                    Some(action) => explorer_action(&mut self.component, action),
+                    None => None,
+                },
+            },
             _ => None,
         }
     }
@@ -324,10 +225,20 @@ impl Component<Msg, NoUserEvent> for ExplorerLocal {
 #[derive(MockComponent)]
 pub struct ExplorerRemote {
     component: FileList,
+    keys: KeyBindings,
+    vim: Option<VimNavigation>,
 }
 
 impl ExplorerRemote {
-    pub fn new<S: AsRef<str>>(title: S, files: &[&str], bg: Color, fg: Color, hg: Color) -> Self {
+    pub fn new<S: AsRef<str>>(
+        title: S,
+        files: &[&str],
+        bg: Color,
+        fg: Color,
+        hg: Color,
+        keys: KeyBindings,
+        vim_mode: bool,
+    ) -> Self {
         Self {
             component: FileList::default()
                 .background(bg)
@@ -336,6 +247,8 @@ impl ExplorerRemote {
                 .highlighted_color(hg)
                 .title(title, Alignment::Left)
                 .rows(files.iter().map(|x| vec![TextSpan::from(x)]).collect()),
+            keys,
+            vim: vim_mode.then(VimNavigation::default),
         }
     }
 }
@@ -376,20 +289,6 @@ impl Component<Msg, NoUserEvent> for ExplorerRemote {
                 self.perform(Cmd::GoTo(Position::End));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => {
-                let _ = self.perform(Cmd::Custom(file_list::FILE_LIST_CMD_SELECT_ALL));
-                Some(Msg::None)
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('m'),
-                modifiers: KeyModifiers::NONE,
-            }) => {
-                let _ = self.perform(Cmd::Toggle);
-                Some(Msg::None)
-            }
             // -- comp msg
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Ui(UiMsg::ShowDisconnectPopup))
@@ -398,110 +297,81 @@ impl Component<Msg, NoUserEvent> for ExplorerRemote {
                 code: Key::Left | Key::Tab | Key::BackTab,
                 ..
             }) => Some(Msg::Ui(UiMsg::ChangeTransferWindow)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Backspace,
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::GoToPreviousDirectory)),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => Some(Msg::Transfer(TransferMsg::EnterDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(' '),
-                ..
-            }) => Some(Msg::Transfer(TransferMsg::TransferFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('a'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ToggleHiddenFiles)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileSortingPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c') | Key::Function(5),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowCopyPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('d') | Key::Function(7),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowMkdirPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('e') | Key::Delete | Key::Function(8),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowDeletePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('f'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFindPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('g'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowGotoPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('i'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowFileInfoPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowSymlinkPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('l'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::ReloadDir)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('n'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowNewFilePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('o') | Key::Function(4),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::OpenTextFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('p'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowLogPanel)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('r') | Key::Function(6),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowRenamePopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('s') | Key::Function(2),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowSaveAsPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('t'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowWatcherPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('t'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => Some(Msg::Ui(UiMsg::ShowWatchedPathsList)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('u'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::GoToParentDirectory)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('x'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowExecPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('y'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ToggleSyncBrowsing)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('v') | Key::Function(3),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Transfer(TransferMsg::OpenFile)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('w'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowOpenWithPopup)),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('z'),
-                modifiers: KeyModifiers::NONE,
-            }) => Some(Msg::Ui(UiMsg::ShowChmodPopup)),
+            Event::Keyboard(ev) => match self
+                .vim
+                .as_mut()
+                .and_then(|vim| vim.on(&mut self.component, &ev))
+            {
+                Some(msg) => Some(msg),
+                None => match self.keys.action(KeyScope::Explorer, &ev) {
+                    Some(action) => explorer_action(&mut self.component, action),
+                    None => None,
+                },
+            },
             _ => None,
         }
     }
 }
+
+/// Get the message for the explorer `action`; selection actions are performed on the file list
+fn explorer_action(component: &mut FileList, action: KeyAction) -> Option<Msg> {
+    match action {
+        KeyAction::SelectAll => {
+            let _ = component.perform(Cmd::Custom(file_list::FILE_LIST_CMD_SELECT_ALL));
+            Some(Msg::None)
+        }
+        KeyAction::Mark => {
+            let _ = component.perform(Cmd::Toggle);
+            Some(Msg::None)
+        }
+        KeyAction::Chmod => Some(Msg::Ui(UiMsg::ShowChmodPopup)),
+        KeyAction::CommandLine => Some(Msg::Ui(UiMsg::ShowCommandLine)),
+        KeyAction::ContextMenu => Some(Msg::Ui(UiMsg::ShowContextMenu)),
+        KeyAction::Copy => Some(Msg::Ui(UiMsg::ShowCopyPopup)),
+        KeyAction::CustomActions => Some(Msg::Ui(UiMsg::ShowCustomActionsPopup)),
+        KeyAction::Delete => Some(Msg::Ui(UiMsg::ShowDeletePopup)),
+        KeyAction::Exec => Some(Msg::Ui(UiMsg::ShowExecPopup)),
+        KeyAction::FileInfo => Some(Msg::Ui(UiMsg::ShowFileInfoPopup)),
+        KeyAction::Find => Some(Msg::Ui(UiMsg::ShowFindPopup)),
+        KeyAction::GoTo => Some(Msg::Ui(UiMsg::ShowGotoPopup)),
+        KeyAction::GoToParentDirectory => Some(Msg::Transfer(TransferMsg::GoToParentDirectory)),
+        KeyAction::GoToPreviousDirectory => Some(Msg::Transfer(TransferMsg::GoToPreviousDirectory)),
+        KeyAction::LogPanel => Some(Msg::Ui(UiMsg::ShowLogPanel)),
+        KeyAction::Mkdir => Some(Msg::Ui(UiMsg::ShowMkdirPopup)),
+        KeyAction::NewFile => Some(Msg::Ui(UiMsg::ShowNewFilePopup)),
+        KeyAction::Open => Some(Msg::Transfer(TransferMsg::OpenFile)),
+        KeyAction::OpenTextFile => Some(Msg::Transfer(TransferMsg::OpenTextFile)),
+        KeyAction::OpenWith => Some(Msg::Ui(UiMsg::ShowOpenWithPopup)),
+        KeyAction::PauseSync => Some(Msg::Ui(UiMsg::ToggleSyncPause)),
+        KeyAction::Reload => Some(Msg::Transfer(TransferMsg::ReloadDir)),
+        KeyAction::Rename => Some(Msg::Ui(UiMsg::ShowRenamePopup)),
+        KeyAction::ReverseSorting => Some(Msg::Ui(UiMsg::ToggleReverseSorting)),
+        KeyAction::SaveAs => Some(Msg::Ui(UiMsg::ShowSaveAsPopup)),
+        KeyAction::SortByNextColumn => Some(Msg::Ui(UiMsg::SortByNextColumn)),
+        KeyAction::Sorting => Some(Msg::Ui(UiMsg::ShowFileSortingPopup)),
+        KeyAction::Symlink => Some(Msg::Ui(UiMsg::ShowSymlinkPopup)),
+        KeyAction::SyncBrowsing => Some(Msg::Ui(UiMsg::ToggleSyncBrowsing)),
+        KeyAction::SyncQueue => Some(Msg::Ui(UiMsg::ShowSyncQueueList)),
+        KeyAction::Follow => Some(Msg::Ui(UiMsg::ShowFollowHighlightPopup)),
+        KeyAction::Shell => Some(Msg::Transfer(TransferMsg::OpenRemoteShell)),
+        KeyAction::TableView => Some(Msg::Ui(UiMsg::ToggleTableView)),
+        KeyAction::ToggleHiddenFiles => Some(Msg::Ui(UiMsg::ToggleHiddenFiles)),
+        KeyAction::Transfer => Some(Msg::Transfer(TransferMsg::TransferFile)),
+        KeyAction::Undo => Some(Msg::Transfer(TransferMsg::Undo)),
+        KeyAction::Watch => Some(Msg::Ui(UiMsg::ShowWatcherPopup)),
+        KeyAction::WatchedPaths => Some(Msg::Ui(UiMsg::ShowWatchedPathsList)),
+        // Handled by the global listener
+        KeyAction::Help | KeyAction::Quit => None,
+        KeyAction::AuthDeleteBookmark
+        | KeyAction::AuthFilterBookmarks
+        | KeyAction::AuthHelp
+        | KeyAction::AuthImportBookmarks
+        | KeyAction::AuthQuickConnect
+        | KeyAction::AuthReleaseNotes
+        | KeyAction::AuthSaveBookmark
+        | KeyAction::AuthSetup => None,
+    }
+}
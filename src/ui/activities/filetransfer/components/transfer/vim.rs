@@ -0,0 +1,120 @@
+//! ## Vim
+//!
+//! vim-like navigation for file lists
+
+use tuirealm::command::{Cmd, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use tuirealm::MockComponent;
+
+use super::{FileList, Msg, TransferMsg};
+
+/// Vim motions state: `hjkl`, `gg`, `G` and count prefixes (e.g. `5j`)
+#[derive(Default)]
+pub struct VimNavigation {
+    /// Count typed before the motion
+    count: Option<usize>,
+    /// Whether `g` has been pressed, waiting for the second `g`
+    pending_g: bool,
+}
+
+impl VimNavigation {
+    /// Handle `ev` as a vim motion on `list`.
+    /// Returns `None` if `ev` is not a vim motion; in this case the pending count is discarded
+    pub fn on(&mut self, list: &mut FileList, ev: &KeyEvent) -> Option<Msg> {
+        let ch = match ev {
+            KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            } => *ch,
+            _ => {
+                self.reset();
+                return None;
+            }
+        };
+        let pending_g = std::mem::take(&mut self.pending_g);
+        match ch {
+            // `0` is a motion in vim, so it's part of the count only if a count has been typed
+            '0'..='9' if ch != '0' || self.count.is_some() => {
+                let digit = ch.to_digit(10).unwrap_or_default() as usize;
+                self.count = Some(
+                    self.count
+                        .unwrap_or_default()
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+                Some(Msg::None)
+            }
+            'j' => {
+                let count = self.take_count();
+                Self::move_by(list, count as isize);
+                Some(Msg::None)
+            }
+            'k' => {
+                let count = self.take_count();
+                Self::move_by(list, -(count as isize));
+                Some(Msg::None)
+            }
+            'h' => {
+                self.reset();
+                Some(Msg::Transfer(TransferMsg::GoToParentDirectory))
+            }
+            'l' => {
+                self.reset();
+                Some(Msg::Transfer(TransferMsg::EnterDirectory))
+            }
+            'g' if pending_g => {
+                self.go_to(list, Position::Begin);
+                Some(Msg::None)
+            }
+            'g' => {
+                self.pending_g = true;
+                Some(Msg::None)
+            }
+            'G' => {
+                self.go_to(list, Position::End);
+                Some(Msg::None)
+            }
+            _ => {
+                self.reset();
+                None
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.count = None;
+        self.pending_g = false;
+    }
+
+    fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1).max(1)
+    }
+
+    /// Move to the entry at the typed count (1-based) or to `default` if no count has been typed
+    fn go_to(&mut self, list: &mut FileList, default: Position) {
+        match self.count.take() {
+            Some(line) => Self::select(list, line.saturating_sub(1)),
+            None => {
+                list.perform(Cmd::GoTo(default));
+            }
+        }
+    }
+
+    /// Move the cursor by `offset` entries, without rewinding
+    fn move_by(list: &mut FileList, offset: isize) {
+        let index = match list.query(Attribute::Value) {
+            Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(index)))) => index,
+            _ => 0,
+        };
+        Self::select(list, index.saturating_add_signed(offset));
+    }
+
+    /// Move the cursor to `index`; the index is fixed by the file list if out of range
+    fn select(list: &mut FileList, index: usize) {
+        list.attr(
+            Attribute::Value,
+            AttrValue::Payload(PropPayload::One(PropValue::Usize(index))),
+        );
+    }
+}
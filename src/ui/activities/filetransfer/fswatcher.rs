@@ -1,50 +1,201 @@
 use std::path::Path;
 
-use super::{FileTransferActivity, LogLevel, TransferPayload};
-use crate::system::watcher::FsChange;
+use remotefs::{File, RemoteErrorType};
+
+use super::{FileTransferActivity, Id, LogLevel, TransferPayload};
+use crate::system::watcher::{self, FsChange};
+
+/// Maximum amount of changes synched at each tick, so that the UI keeps responding while syncing many changes
+const SYNC_BATCH_SIZE: usize = 8;
 
 impl FileTransferActivity {
-    /// poll file watcher
+    /// poll file watcher, pushing the changes to the sync queue, then sync the changes which are ready
     pub(super) fn poll_watcher(&mut self) {
-        if self.fswatcher.is_none() {
+        let changes = match self.fswatcher.as_ref() {
+            Some(watcher) => watcher.poll_all(),
+            None => return,
+        };
+        let mut queue_changed = false;
+        for change in changes.into_iter() {
+            match change {
+                Ok(change) => {
+                    debug!("fs watcher reported a change: {:?}", change);
+                    self.sync_queue.push(change);
+                    queue_changed = true;
+                }
+                Err(err) => {
+                    self.log(
+                        LogLevel::Error,
+                        format!("error while polling file watcher: {err}"),
+                    );
+                }
+            }
+        }
+        for _ in 0..SYNC_BATCH_SIZE {
+            let pending = match self.sync_queue.pop() {
+                Some(pending) => pending,
+                None => break,
+            };
+            queue_changed = true;
+            if let Err(err) = self.sync_change(pending.change()) {
+                let attempts = pending.attempts() + 1;
+                if self.sync_queue.retry(pending) {
+                    self.log(
+                        LogLevel::Warn,
+                        format!("{err} (attempt {attempts}); sync will be retried"),
+                    );
+                } else {
+                    self.log(
+                        LogLevel::Error,
+                        format!("{err} (attempt {attempts}); giving up"),
+                    );
+                }
+                // the remote is probably unreachable; don't try the next changes now
+                break;
+            }
+        }
+        if queue_changed {
+            self.refresh_sync_queue();
+        }
+    }
+
+    /// poll the remote directories watched by the remote watcher, downloading new and changed files
+    pub(super) fn poll_remote_watcher(&mut self) {
+        if !self.connected || !self.client.is_connected() {
             return;
         }
-        let watcher = self.fswatcher.as_mut().unwrap();
-        match watcher.poll() {
-            Ok(None) => {}
-            Ok(Some(FsChange::Move(mov))) => {
+        for remote in self.remote_watcher.due_paths() {
+            let local = match self.remote_watcher.local_path(&remote) {
+                Some(local) => local.to_path_buf(),
+                None => continue,
+            };
+            let entries = match self.client.list_dir(&remote) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    self.log(
+                        LogLevel::Error,
+                        format!(
+                            "failed to list watched remote directory {}: {}",
+                            remote.display(),
+                            err
+                        ),
+                    );
+                    self.remote_watcher.postpone(&remote);
+                    continue;
+                }
+            };
+            let changed = self.remote_watcher.update(&remote, &entries);
+            for file in changed.iter() {
+                if let Err(err) = self.download_watched_file(file, &local) {
+                    self.log(LogLevel::Error, err);
+                    // download it again at next poll
+                    self.remote_watcher.invalidate(&remote, file.path());
+                }
+            }
+            if !changed.is_empty() && self.local().wrkdir == local {
+                self.reload_local_dir();
+                self.update_local_filelist();
+            }
+        }
+    }
+
+    /// Refresh the components displaying the sync queue
+    pub(super) fn refresh_sync_queue(&mut self) {
+        self.refresh_remote_status_bar();
+        if self.app.mounted(&Id::SyncQueueList) {
+            self.mount_sync_queue_list();
+        }
+        self.redraw = true;
+    }
+
+    /// Upload the files in the watched path `local` which changed since they were last synched with `remote`,
+    /// comparing their size and modification time
+    pub(super) fn reconcile_watched_path(&mut self, local: &Path, remote: &Path) {
+        debug!(
+            "reconciling watched path {} with {}",
+            local.display(),
+            remote.display()
+        );
+        let local_entry = match self.host.stat(local) {
+            Ok(e) => e,
+            Err(err) => {
+                self.log(
+                    LogLevel::Error,
+                    format!(
+                        "failed to reconcile {} (stat failed): {}",
+                        local.display(),
+                        err
+                    ),
+                );
+                return;
+            }
+        };
+        let ignored = self
+            .fswatcher
+            .as_ref()
+            .map(|w| w.is_ignored(local))
+            .unwrap_or(false);
+        if ignored || local_entry.is_symlink() {
+            return;
+        }
+        let remote_entry = match self.client.stat(remote) {
+            Ok(e) => e,
+            Err(_) => {
+                // doesn't exist on remote; upload it entirely
+                if let Err(err) = self.upload_watched_file(local, remote) {
+                    self.log(LogLevel::Error, err);
+                }
+                return;
+            }
+        };
+        if local_entry.is_dir() {
+            let entries = match self.host.scan_dir(local) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    self.log(
+                        LogLevel::Error,
+                        format!("failed to reconcile {}: {}", local.display(), err),
+                    );
+                    return;
+                }
+            };
+            for entry in entries.iter() {
+                self.reconcile_watched_path(entry.path(), &remote.join(entry.name()));
+            }
+        } else if watcher::is_outdated(local_entry.metadata(), remote_entry.metadata()) {
+            if let Err(err) = self.upload_watched_file(local, remote) {
+                self.log(LogLevel::Error, err);
+            }
+        }
+    }
+
+    /// Apply `change` to the remote file system
+    fn sync_change(&mut self, change: &FsChange) -> Result<(), String> {
+        match change {
+            FsChange::Move(mov) => {
                 debug!(
-                    "fs watcher reported a `Move` from {} to {}",
+                    "syncing `Move` from {} to {}",
                     mov.source().display(),
                     mov.destination().display()
                 );
-                self.move_watched_file(mov.source(), mov.destination());
+                self.move_watched_file(mov.source(), mov.destination())
             }
-            Ok(Some(FsChange::Remove(remove))) => {
-                debug!(
-                    "fs watcher reported a `Remove` of {}",
-                    remove.path().display()
-                );
-                self.remove_watched_file(remove.path());
+            FsChange::Remove(remove) => {
+                debug!("syncing `Remove` of {}", remove.path().display());
+                self.remove_watched_file(remove.path())
             }
-            Ok(Some(FsChange::Update(update))) => {
+            FsChange::Update(update) => {
                 debug!(
-                    "fs watcher reported an `Update` from {} to {}",
+                    "syncing `Update` from {} to {}",
                     update.local().display(),
                     update.remote().display()
                 );
-                self.upload_watched_file(update.local(), update.remote());
-            }
-            Err(err) => {
-                self.log(
-                    LogLevel::Error,
-                    format!("error while polling file watcher: {err}"),
-                );
+                self.upload_watched_file(update.local(), update.remote())
             }
         }
     }
 
-    fn move_watched_file(&mut self, source: &Path, destination: &Path) {
+    fn move_watched_file(&mut self, source: &Path, destination: &Path) -> Result<(), String> {
         // stat remote file
         trace!(
             "renaming watched file {} to {}",
@@ -52,57 +203,91 @@ impl FileTransferActivity {
             destination.display()
         );
         // stat fs entry
-        let origin = match self.client.stat(source) {
-            Ok(f) => f,
-            Err(err) => {
+        let origin = self.client.stat(source).map_err(|err| {
+            format!(
+                "failed to stat file to rename {}: {}",
+                source.display(),
+                err
+            )
+        })?;
+        match self.client.mov(origin.path(), destination) {
+            Ok(()) => {
                 self.log(
-                    LogLevel::Error,
+                    LogLevel::Info,
                     format!(
-                        "failed to stat file to rename {}: {}",
+                        "moved watched file {} to {}",
                         source.display(),
-                        err
+                        destination.display()
                     ),
                 );
-                return;
+                Ok(())
             }
-        };
-        // rename using action
-        self.remote_rename_file(&origin, destination)
+            // rename using action, which falls back to copy and remove
+            Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                self.remote_rename_file(&origin, destination);
+                Ok(())
+            }
+            Err(err) => Err(format!(
+                "failed to move watched file {} to {}: {}",
+                source.display(),
+                destination.display(),
+                err
+            )),
+        }
     }
 
-    fn remove_watched_file(&mut self, file: &Path) {
+    fn remove_watched_file(&mut self, file: &Path) -> Result<(), String> {
         match self.client.remove_dir_all(file) {
             Ok(()) => {
                 self.log(
                     LogLevel::Info,
                     format!("removed watched file at {}", file.display()),
                 );
+                Ok(())
             }
-            Err(err) => {
-                self.log(
-                    LogLevel::Error,
-                    format!("failed to remove watched file {}: {}", file.display(), err),
-                );
-            }
+            Err(err) => Err(format!(
+                "failed to remove watched file {}: {}",
+                file.display(),
+                err
+            )),
         }
     }
 
-    fn upload_watched_file(&mut self, local: &Path, remote: &Path) {
-        // stat local file
-        let entry = match self.host.stat(local) {
-            Ok(e) => e,
-            Err(err) => {
+    fn download_watched_file(&mut self, file: &File, local: &Path) -> Result<(), String> {
+        trace!(
+            "syncing remote file {} with local directory {}",
+            file.path().display(),
+            local.display()
+        );
+        match self.filetransfer_recv(TransferPayload::Any(file.clone()), local, None) {
+            Ok(()) => {
                 self.log(
-                    LogLevel::Error,
+                    LogLevel::Info,
                     format!(
-                        "failed to sync file {} with remote (stat failed): {}",
-                        remote.display(),
-                        err
+                        "synched watched remote file {} with {}",
+                        file.path().display(),
+                        local.join(file.name()).display()
                     ),
                 );
-                return;
+                Ok(())
             }
-        };
+            Err(err) => Err(format!(
+                "failed to sync watched remote file {}: {}",
+                file.path().display(),
+                err
+            )),
+        }
+    }
+
+    fn upload_watched_file(&mut self, local: &Path, remote: &Path) -> Result<(), String> {
+        // stat local file
+        let entry = self.host.stat(local).map_err(|err| {
+            format!(
+                "failed to sync file {} with remote (stat failed): {}",
+                remote.display(),
+                err
+            )
+        })?;
         // send
         trace!(
             "syncing local file {} with remote {}",
@@ -120,13 +305,13 @@ impl FileTransferActivity {
                         remote.display()
                     ),
                 );
+                Ok(())
             }
-            Err(err) => {
-                self.log(
-                    LogLevel::Error,
-                    format!("failed to sync watched file {}: {}", remote.display(), err),
-                );
-            }
+            Err(err) => Err(format!(
+                "failed to sync watched file {}: {}",
+                remote.display(),
+                err
+            )),
         }
     }
 }
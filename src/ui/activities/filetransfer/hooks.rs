@@ -0,0 +1,101 @@
+//! ## Hooks
+//!
+//! Run the hooks of the bookmark in use around the connection
+
+use std::fmt;
+
+use super::{FileTransferActivity, LogLevel};
+use crate::config::bookmarks::Hook;
+
+/// Describes when a hook runs
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum HookEvent {
+    OnConnect,
+    AfterConnect,
+    OnDisconnect,
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::OnConnect => "on_connect",
+            Self::AfterConnect => "after_connect",
+            Self::OnDisconnect => "on_disconnect",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FileTransferActivity {
+    /// Run the hooks of the bookmark in use for `event`, reporting their output to the log.
+    /// Returns error if a hook which should abort the connection failed
+    pub(super) fn run_hooks(&mut self, event: HookEvent) -> Result<(), String> {
+        let hooks: Vec<Hook> = match self.context().bookmark_hooks() {
+            None => return Ok(()),
+            Some(hooks) => match event {
+                HookEvent::OnConnect => hooks.on_connect.clone(),
+                HookEvent::AfterConnect => hooks.after_connect.clone(),
+                HookEvent::OnDisconnect => hooks.on_disconnect.clone(),
+            },
+        };
+        for hook in hooks.iter() {
+            if hook.remote && (event == HookEvent::OnConnect || !self.client.is_connected()) {
+                self.log(
+                    LogLevel::Warn,
+                    format!(
+                        "{event} hook \"{}\" skipped: remote hooks can't run while disconnected",
+                        hook.command
+                    ),
+                );
+                continue;
+            }
+            let result = match hook.remote {
+                true => self
+                    .client
+                    .as_mut()
+                    .exec(hook.command.as_str())
+                    .map_err(|e| e.to_string()),
+                // local hooks run through the shell, so quotes, pipes and redirections work as in the terminal
+                false => self
+                    .host
+                    .exec_shell(hook.command.as_str())
+                    .map(|(rc, stdout, stderr)| (rc, format!("{stdout}{stderr}")))
+                    .map_err(|e| e.to_string()),
+            };
+            let failed = match result {
+                Ok((0, output)) => {
+                    self.log(
+                        LogLevel::Info,
+                        format!("{event} hook \"{}\" (exitcode: 0): {output}", hook.command),
+                    );
+                    false
+                }
+                Ok((rc, output)) => {
+                    self.log(
+                        LogLevel::Error,
+                        format!(
+                            "{event} hook \"{}\" (exitcode: {rc}): {output}",
+                            hook.command
+                        ),
+                    );
+                    true
+                }
+                Err(err) => {
+                    self.log(
+                        LogLevel::Error,
+                        format!("Could not execute {event} hook \"{}\": {err}", hook.command),
+                    );
+                    true
+                }
+            };
+            // NOTE: the disconnection can't be aborted
+            if failed && hook.abort_on_failure && event != HookEvent::OnDisconnect {
+                return Err(format!(
+                    "Connection aborted: {event} hook \"{}\" failed",
+                    hook.command
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,9 +3,11 @@
 //! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
 
 use std::path::Path;
+use std::str::FromStr;
 
 use remotefs::File;
 
+use crate::config::params::FileSortingConfig;
 use crate::explorer::builder::FileExplorerBuilder;
 use crate::explorer::{FileExplorer, FileSorting, GroupDirs};
 use crate::system::config_client::ConfigClient;
@@ -33,6 +35,7 @@ pub struct Browser {
     found: Option<(FoundExplorerTab, FileExplorer)>, // File explorer for find result
     tab: FileExplorerTab,                            // Current selected tab
     pub sync_browsing: bool,
+    pub table_view: bool,
 }
 
 impl Browser {
@@ -44,6 +47,7 @@ impl Browser {
             found: None,
             tab: FileExplorerTab::Local,
             sync_browsing: false,
+            table_view: false,
         }
     }
 
@@ -100,26 +104,41 @@ impl Browser {
         self.sync_browsing = !self.sync_browsing;
     }
 
+    /// Switch between list view and table view for file explorers
+    pub fn toggle_table_view(&mut self) {
+        self.table_view = !self.table_view;
+    }
+
     /// Build a file explorer with local host setup
     pub fn build_local_explorer(cli: &ConfigClient) -> FileExplorer {
-        let mut builder = Self::build_explorer(cli);
-        builder.with_formatter(cli.get_local_file_fmt().as_deref());
+        let mut builder = Self::build_explorer(cli, &cli.get_local_file_sorting());
+        builder
+            .with_content_sniffing(true)
+            .with_formatter(cli.get_local_file_fmt().as_deref());
         builder.build()
     }
 
     /// Build a file explorer with remote host setup
     pub fn build_remote_explorer(cli: &ConfigClient) -> FileExplorer {
-        let mut builder = Self::build_explorer(cli);
+        let mut builder = Self::build_explorer(cli, &cli.get_remote_file_sorting());
         builder.with_formatter(cli.get_remote_file_fmt().as_deref());
         builder.build()
     }
 
     /// Build explorer reading configuration from `ConfigClient`
-    fn build_explorer(cli: &ConfigClient) -> FileExplorerBuilder {
+    fn build_explorer(cli: &ConfigClient, sorting: &FileSortingConfig) -> FileExplorerBuilder {
         let mut builder: FileExplorerBuilder = FileExplorerBuilder::new();
+        let file_sorting = sorting
+            .by
+            .as_deref()
+            .and_then(|x| FileSorting::from_str(x).ok())
+            .unwrap_or(FileSorting::Name);
         // Set common keys
         builder
-            .with_file_sorting(FileSorting::Name)
+            .with_file_sorting(file_sorting)
+            .with_reverse_sorting(sorting.reverse.unwrap_or(false))
+            .with_natural_sorting(sorting.natural.unwrap_or(false))
+            .with_case_sensitive_sorting(sorting.case_sensitive.unwrap_or(false))
             .with_stack_size(16)
             .with_group_dirs(cli.get_group_dirs())
             .with_hidden_files(cli.get_show_hidden_files());
@@ -0,0 +1,355 @@
+//! ## Command
+//!
+//! `command` is the module which parses the commands typed in the file transfer command line
+
+use std::str::FromStr;
+
+use remotefs::fs::UnixPex;
+use thiserror::Error;
+
+use crate::explorer::FileSorting;
+
+/// Names of the commands, used for completion
+const COMMANDS: [&str; 25] = [
+    "cd",
+    "chmod",
+    "cp",
+    "delete",
+    "disconnect",
+    "edit",
+    "exec",
+    "find",
+    "get",
+    "help",
+    "hidden",
+    "ln",
+    "mkdir",
+    "mv",
+    "open",
+    "put",
+    "quit",
+    "reload",
+    "rm",
+    "saveas",
+    "shell",
+    "sort",
+    "sync",
+    "touch",
+    "undo",
+];
+
+/// Describes an error while parsing a command
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum CommandError {
+    #[error("invalid argument for \"{0}\": {1}")]
+    InvalidArgument(String, String),
+    #[error("\"{0}\" requires an argument")]
+    MissingArgument(String),
+    #[error("\"{0}\" doesn't take any argument")]
+    UnexpectedArgument(String),
+    #[error("not a command: {0}")]
+    UnknownCommand(String),
+}
+
+/// A command typed in the command line
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    /// Change working directory
+    Cd(String),
+    /// Go to the previous working directory (`cd -`)
+    CdPrevious,
+    /// Go to the parent directory (`cd ..`)
+    CdUp,
+    Chmod(UnixPex),
+    Copy(String),
+    Delete,
+    Disconnect,
+    Edit,
+    Exec(String),
+    Find(String),
+    /// Download the files matching the pattern, or the selected files, from the remote host
+    Get(Option<String>),
+    Help,
+    Mkdir(String),
+    NewFile(String),
+    Open,
+    /// Upload the files matching the pattern, or the selected files, to the remote host
+    Put(Option<String>),
+    Quit,
+    Reload,
+    Rename(String),
+    ReverseSorting,
+    SaveAs(String),
+    /// Open an interactive shell on the remote host
+    Shell,
+    Sort(FileSorting),
+    Symlink(String),
+    ToggleHiddenFiles,
+    ToggleSyncBrowsing,
+    /// Revert the latest delete, rename or move
+    Undo,
+}
+
+impl Command {
+    /// Returns whether the command can be run while the find results are displayed
+    pub fn supported_in_find(&self) -> bool {
+        !matches!(
+            self,
+            Self::Cd(_)
+                | Self::CdPrevious
+                | Self::CdUp
+                | Self::Copy(_)
+                | Self::Exec(_)
+                | Self::Find(_)
+                | Self::Get(_)
+                | Self::Mkdir(_)
+                | Self::NewFile(_)
+                | Self::Put(_)
+                | Self::Rename(_)
+                | Self::Symlink(_)
+        )
+    }
+
+    /// Parse the sorting criteria of the `sort` command
+    fn parse_sorting(arg: &str) -> Result<FileSorting, CommandError> {
+        match arg {
+            "atime" => Ok(FileSorting::AccessTime),
+            "ctime" => Ok(FileSorting::CreationTime),
+            "ext" | "extension" => Ok(FileSorting::Extension),
+            "group" => Ok(FileSorting::Group),
+            "mtime" => Ok(FileSorting::ModifyTime),
+            "name" => Ok(FileSorting::Name),
+            "owner" => Ok(FileSorting::Owner),
+            "path" => Ok(FileSorting::Path),
+            "pex" | "permissions" => Ok(FileSorting::Permissions),
+            "size" => Ok(FileSorting::Size),
+            "type" => Ok(FileSorting::Type),
+            arg => FileSorting::from_str(arg).map_err(|_| {
+                CommandError::InvalidArgument(
+                    String::from("sort"),
+                    format!("unknown sorting \"{arg}\""),
+                )
+            }),
+        }
+    }
+
+    /// Parse the mode of the `chmod` command, in octal notation
+    fn parse_mode(arg: &str) -> Result<UnixPex, CommandError> {
+        match u32::from_str_radix(arg, 8) {
+            Ok(mode) if mode <= 0o7777 => Ok(UnixPex::from(mode)),
+            _ => Err(CommandError::InvalidArgument(
+                String::from("chmod"),
+                format!("\"{arg}\" is not an octal file mode"),
+            )),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_start_matches(':').trim_start();
+        // `!cmd` is a shorthand for `exec cmd`
+        if let Some(cmd) = s.strip_prefix('!') {
+            return match cmd.trim() {
+                "" => Err(CommandError::MissingArgument(String::from("!"))),
+                cmd => Ok(Self::Exec(cmd.to_string())),
+            };
+        }
+        let (name, arg) = match s.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, Some(arg.trim()).filter(|x| !x.is_empty())),
+            None => (s, None),
+        };
+        let required = |arg: Option<&str>| {
+            arg.map(str::to_string)
+                .ok_or_else(|| CommandError::MissingArgument(name.to_string()))
+        };
+        let no_arg = |cmd: Command| match arg {
+            Some(_) => Err(CommandError::UnexpectedArgument(name.to_string())),
+            None => Ok(cmd),
+        };
+        match name {
+            "cd" => match required(arg)?.as_str() {
+                "-" => Ok(Self::CdPrevious),
+                ".." => Ok(Self::CdUp),
+                dir => Ok(Self::Cd(dir.to_string())),
+            },
+            "chmod" => Self::parse_mode(&required(arg)?).map(Self::Chmod),
+            "cp" | "copy" => required(arg).map(Self::Copy),
+            "delete" | "rm" => no_arg(Self::Delete),
+            "disconnect" => no_arg(Self::Disconnect),
+            "e" | "edit" => no_arg(Self::Edit),
+            "exec" => required(arg).map(Self::Exec),
+            "find" => required(arg).map(Self::Find),
+            "get" => Ok(Self::Get(arg.map(str::to_string))),
+            "h" | "help" => no_arg(Self::Help),
+            "hidden" => no_arg(Self::ToggleHiddenFiles),
+            "ln" | "symlink" => required(arg).map(Self::Symlink),
+            "mkdir" => required(arg).map(Self::Mkdir),
+            "mv" | "rename" => required(arg).map(Self::Rename),
+            "open" => no_arg(Self::Open),
+            "put" => Ok(Self::Put(arg.map(str::to_string))),
+            "q" | "quit" => no_arg(Self::Quit),
+            "reload" => no_arg(Self::Reload),
+            "saveas" => required(arg).map(Self::SaveAs),
+            "sh" | "shell" => no_arg(Self::Shell),
+            "sort" => match required(arg)?.as_str() {
+                "reverse" => Ok(Self::ReverseSorting),
+                sorting => Self::parse_sorting(sorting).map(Self::Sort),
+            },
+            "sync" => no_arg(Self::ToggleSyncBrowsing),
+            "touch" => required(arg).map(Self::NewFile),
+            "undo" => no_arg(Self::Undo),
+            name => Err(CommandError::UnknownCommand(name.to_string())),
+        }
+    }
+}
+
+/// Get the completions for the command line `input`.
+/// The command name is completed with the known commands, its argument with `files`.
+/// Returns the completed inputs, sorted
+pub fn complete<S: AsRef<str>>(input: &str, files: &[S]) -> Vec<String> {
+    let input = input.trim_start();
+    let mut completions: Vec<String> = match input.split_once(' ') {
+        None => COMMANDS
+            .iter()
+            .filter(|x| x.starts_with(input))
+            .map(|x| x.to_string())
+            .collect(),
+        Some((name, arg)) => files
+            .iter()
+            .map(|x| x.as_ref())
+            .filter(|x| x.starts_with(arg))
+            .map(|x| format!("{name} {x}"))
+            .collect(),
+    };
+    completions.sort();
+    completions.dedup();
+    completions
+}
+
+/// Get the longest common prefix of `inputs`
+pub fn common_prefix<S: AsRef<str>>(inputs: &[S]) -> String {
+    let mut inputs = inputs.iter().map(|x| x.as_ref());
+    let first = match inputs.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+    let len = inputs.fold(first.len(), |len, input| {
+        first
+            .char_indices()
+            .zip(input.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+    });
+    first[..len].to_string()
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_commands() {
+        assert_eq!(
+            Command::from_str("cd /etc").unwrap(),
+            Command::Cd(String::from("/etc"))
+        );
+        assert_eq!(
+            Command::from_str(":cd  my dir ").unwrap(),
+            Command::Cd(String::from("my dir"))
+        );
+        assert_eq!(Command::from_str("cd -").unwrap(), Command::CdPrevious);
+        assert_eq!(Command::from_str("cd ..").unwrap(), Command::CdUp);
+        assert_eq!(
+            Command::from_str("put *.log").unwrap(),
+            Command::Put(Some(String::from("*.log")))
+        );
+        assert_eq!(Command::from_str("get").unwrap(), Command::Get(None));
+        assert_eq!(
+            Command::from_str("chmod 644").unwrap(),
+            Command::Chmod(UnixPex::from(0o644))
+        );
+        assert_eq!(
+            Command::from_str("sort size").unwrap(),
+            Command::Sort(FileSorting::Size)
+        );
+        assert_eq!(
+            Command::from_str("sort by_mtime").unwrap(),
+            Command::Sort(FileSorting::ModifyTime)
+        );
+        assert_eq!(
+            Command::from_str("sort reverse").unwrap(),
+            Command::ReverseSorting
+        );
+        assert_eq!(Command::from_str("q").unwrap(), Command::Quit);
+        assert_eq!(Command::from_str("sh").unwrap(), Command::Shell);
+        assert_eq!(
+            Command::from_str("!ls -l").unwrap(),
+            Command::Exec(String::from("ls -l"))
+        );
+        assert_eq!(
+            Command::from_str("touch README.md").unwrap(),
+            Command::NewFile(String::from("README.md"))
+        );
+        assert_eq!(Command::from_str("undo").unwrap(), Command::Undo);
+    }
+
+    #[test]
+    fn should_not_parse_bad_commands() {
+        assert_eq!(
+            Command::from_str("fly").unwrap_err(),
+            CommandError::UnknownCommand(String::from("fly"))
+        );
+        assert_eq!(
+            Command::from_str("cd").unwrap_err(),
+            CommandError::MissingArgument(String::from("cd"))
+        );
+        assert_eq!(
+            Command::from_str("q now").unwrap_err(),
+            CommandError::UnexpectedArgument(String::from("q"))
+        );
+        assert!(Command::from_str("chmod 999").is_err());
+        assert!(Command::from_str("chmod rwx").is_err());
+        assert!(Command::from_str("sort color").is_err());
+        assert!(Command::from_str("!").is_err());
+        assert!(Command::from_str("").is_err());
+    }
+
+    #[test]
+    fn should_tell_whether_command_is_supported_in_find() {
+        assert!(Command::Delete.supported_in_find());
+        assert!(Command::Sort(FileSorting::Name).supported_in_find());
+        assert!(!Command::Cd(String::from("/tmp")).supported_in_find());
+        assert!(!Command::Put(None).supported_in_find());
+    }
+
+    #[test]
+    fn should_complete_command_line() {
+        assert_eq!(
+            complete::<&str>("s", &[]),
+            vec!["saveas", "shell", "sort", "sync"]
+        );
+        assert_eq!(complete::<&str>("mkd", &[]), vec!["mkdir"]);
+        assert!(complete::<&str>("fly", &[]).is_empty());
+        let files = ["README.md", "src", "scripts", "Cargo.toml"];
+        assert_eq!(complete("cd s", &files), vec!["cd scripts", "cd src"]);
+        assert_eq!(complete("put R", &files), vec!["put README.md"]);
+        assert_eq!(complete("cd ", &files).len(), 4);
+    }
+
+    #[test]
+    fn should_get_common_prefix() {
+        assert_eq!(common_prefix(&["cd scripts", "cd src"]), "cd s");
+        assert_eq!(common_prefix(&["sort"]), "sort");
+        assert_eq!(common_prefix(&["àbc", "àbd"]), "àb");
+        assert_eq!(common_prefix(&["abc", "xyz"]), "");
+        assert_eq!(common_prefix::<&str>(&[]), "");
+    }
+}
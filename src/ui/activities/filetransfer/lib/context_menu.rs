@@ -0,0 +1,205 @@
+//! ## ContextMenu
+//!
+//! `context_menu` is the module which tells which actions can be performed on the selected entries
+
+use crate::config::keybindings::KeyAction;
+
+/// An action listed in the context menu
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContextMenuEntry {
+    Chmod,
+    Copy,
+    Delete,
+    FileInfo,
+    OpenWith,
+    Rename,
+    Symlink,
+    Transfer,
+    Watch,
+}
+
+impl ContextMenuEntry {
+    /// All the entries, in the order they're displayed
+    pub const ALL: [ContextMenuEntry; 9] = [
+        Self::Transfer,
+        Self::Rename,
+        Self::Copy,
+        Self::Delete,
+        Self::OpenWith,
+        Self::FileInfo,
+        Self::Chmod,
+        Self::Symlink,
+        Self::Watch,
+    ];
+
+    /// Label displayed in the context menu
+    pub fn label(&self, remote: bool) -> &'static str {
+        match self {
+            Self::Chmod => "Change permissions",
+            Self::Copy => "Copy",
+            Self::Delete => "Delete",
+            Self::FileInfo => "File info",
+            Self::OpenWith => "Open with",
+            Self::Rename => "Rename",
+            Self::Symlink => "Create symlink",
+            Self::Transfer if remote => "Download",
+            Self::Transfer => "Upload",
+            Self::Watch => "Watch/unwatch",
+        }
+    }
+
+    /// Key action which performs the same action from the explorer
+    pub fn key_action(&self) -> KeyAction {
+        match self {
+            Self::Chmod => KeyAction::Chmod,
+            Self::Copy => KeyAction::Copy,
+            Self::Delete => KeyAction::Delete,
+            Self::FileInfo => KeyAction::FileInfo,
+            Self::OpenWith => KeyAction::OpenWith,
+            Self::Rename => KeyAction::Rename,
+            Self::Symlink => KeyAction::Symlink,
+            Self::Transfer => KeyAction::Transfer,
+            Self::Watch => KeyAction::Watch,
+        }
+    }
+
+    /// Returns whether the action can be performed on `target`
+    pub fn applies_to(&self, target: &ContextMenuTarget) -> bool {
+        if target.selected == 0 {
+            return false;
+        }
+        let one = target.selected == 1;
+        match self {
+            Self::Chmod => target.mode && target.chmod,
+            Self::Copy | Self::Rename => !target.found,
+            Self::Delete | Self::OpenWith | Self::Transfer => true,
+            Self::FileInfo => one,
+            Self::Symlink => !target.found && one && target.symlink,
+            Self::Watch if target.remote => !target.found && one && target.dir && target.watch,
+            Self::Watch => !target.found && one && target.watch,
+        }
+    }
+}
+
+/// Describes the entries selected in the current explorer and the features of their host
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ContextMenuTarget {
+    /// The entries are on the remote host
+    pub remote: bool,
+    /// The entries are selected in the find result explorer
+    pub found: bool,
+    /// Amount of selected entries
+    pub selected: usize,
+    /// The selected entry is a directory
+    pub dir: bool,
+    /// The selected entries have permissions
+    pub mode: bool,
+    /// The host supports changing permissions
+    pub chmod: bool,
+    /// The host supports symlinks
+    pub symlink: bool,
+    /// The host can watch paths
+    pub watch: bool,
+}
+
+/// Get the context menu entries which can be performed on `target`
+pub fn entries(target: &ContextMenuTarget) -> Vec<ContextMenuEntry> {
+    ContextMenuEntry::ALL
+        .into_iter()
+        .filter(|x| x.applies_to(target))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_list_entries_for_local_file() {
+        let target = ContextMenuTarget {
+            selected: 1,
+            mode: true,
+            chmod: true,
+            symlink: true,
+            watch: true,
+            ..Default::default()
+        };
+        assert_eq!(entries(&target), ContextMenuEntry::ALL.to_vec());
+        assert_eq!(ContextMenuEntry::Transfer.label(false), "Upload");
+    }
+
+    #[test]
+    fn should_filter_entries_by_remote_capabilities() {
+        // e.g. S3: no permissions and no symlinks
+        let target = ContextMenuTarget {
+            remote: true,
+            selected: 1,
+            watch: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            entries(&target),
+            vec![
+                ContextMenuEntry::Transfer,
+                ContextMenuEntry::Rename,
+                ContextMenuEntry::Copy,
+                ContextMenuEntry::Delete,
+                ContextMenuEntry::OpenWith,
+                ContextMenuEntry::FileInfo,
+            ]
+        );
+        assert_eq!(ContextMenuEntry::Transfer.label(true), "Download");
+        // remote directories can be watched
+        let target = ContextMenuTarget {
+            dir: true,
+            ..target
+        };
+        assert!(entries(&target).contains(&ContextMenuEntry::Watch));
+    }
+
+    #[test]
+    fn should_filter_entries_by_selection() {
+        let target = ContextMenuTarget {
+            selected: 3,
+            mode: true,
+            chmod: true,
+            symlink: true,
+            watch: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            entries(&target),
+            vec![
+                ContextMenuEntry::Transfer,
+                ContextMenuEntry::Rename,
+                ContextMenuEntry::Copy,
+                ContextMenuEntry::Delete,
+                ContextMenuEntry::OpenWith,
+                ContextMenuEntry::Chmod,
+            ]
+        );
+        let found = ContextMenuTarget {
+            found: true,
+            selected: 1,
+            ..target
+        };
+        assert_eq!(
+            entries(&found),
+            vec![
+                ContextMenuEntry::Transfer,
+                ContextMenuEntry::Delete,
+                ContextMenuEntry::OpenWith,
+                ContextMenuEntry::FileInfo,
+                ContextMenuEntry::Chmod,
+            ]
+        );
+        assert!(entries(&ContextMenuTarget {
+            selected: 0,
+            ..target
+        })
+        .is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! ## Exec
+//!
+//! `exec` is the module which prepares the commands executed from the file explorer and keeps their output
+
+/// Placeholder replaced with each selected file
+const FILE_PLACEHOLDER: &str = "{}";
+/// Escape for a literal `{}` (e.g. for `find -exec`)
+const ESCAPED_PLACEHOLDER: &str = "{{}}";
+
+/// Output of an executed command
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExecOutput {
+    pub command: String,
+    pub rc: u32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ExecOutput {
+    pub fn new(command: impl ToString, rc: u32, stdout: String, stderr: String) -> Self {
+        Self {
+            command: command.to_string(),
+            rc,
+            stdout,
+            stderr,
+        }
+    }
+}
+
+/// Returns whether `cmd` must be executed for each selected file
+pub fn has_file_placeholder(cmd: &str) -> bool {
+    replace_file_placeholder(cmd, "").1
+}
+
+/// Get the commands to execute for `cmd`: if it contains the `{}` placeholder, a command is returned for
+/// each of the `files` (already quoted), with the placeholder replaced with the file; otherwise `cmd` itself.
+/// In both cases `{{}}` is replaced with a literal `{}`.
+/// Returns `None` if `cmd` contains the placeholder but there are no files
+pub fn expand_file_placeholder(cmd: &str, files: &[String]) -> Option<Vec<String>> {
+    let (unescaped, has_placeholder) = replace_file_placeholder(cmd, "");
+    if !has_placeholder {
+        return Some(vec![unescaped]);
+    }
+    if files.is_empty() {
+        return None;
+    }
+    Some(
+        files
+            .iter()
+            .map(|file| replace_file_placeholder(cmd, file).0)
+            .collect(),
+    )
+}
+
+/// Replace the `{}` placeholders in `cmd` with `file` and the escaped ones with a literal `{}`.
+/// Returns the command and whether it contains any placeholder
+fn replace_file_placeholder(cmd: &str, file: &str) -> (String, bool) {
+    let mut command = String::with_capacity(cmd.len());
+    let mut has_placeholder = false;
+    let mut rest = cmd;
+    while let Some(pos) = rest.find('{') {
+        command.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(tail) = rest.strip_prefix(ESCAPED_PLACEHOLDER) {
+            command.push_str(FILE_PLACEHOLDER);
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix(FILE_PLACEHOLDER) {
+            command.push_str(file);
+            has_placeholder = true;
+            rest = tail;
+        } else {
+            command.push('{');
+            rest = &rest[1..];
+        }
+    }
+    command.push_str(rest);
+    (command, has_placeholder)
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_expand_file_placeholder() {
+        let files = vec![String::from("'a.log'"), String::from("'my file.log'")];
+        assert_eq!(
+            expand_file_placeholder("gzip -k {}", &files).unwrap(),
+            vec!["gzip -k 'a.log'", "gzip -k 'my file.log'"]
+        );
+        assert_eq!(
+            expand_file_placeholder("cp {} {}.bak", std::slice::from_ref(&files[0])).unwrap(),
+            vec!["cp 'a.log' 'a.log'.bak"]
+        );
+    }
+
+    #[test]
+    fn should_not_expand_commands_without_placeholder() {
+        assert!(!has_file_placeholder("ps a"));
+        assert_eq!(expand_file_placeholder("ps a", &[]).unwrap(), vec!["ps a"]);
+    }
+
+    #[test]
+    fn should_keep_escaped_placeholder() {
+        let files = vec![String::from("'a.log'")];
+        assert!(!has_file_placeholder("find . -exec gzip {{}} \\;"));
+        assert_eq!(
+            expand_file_placeholder("find . -exec gzip {{}} \\;", &[]).unwrap(),
+            vec!["find . -exec gzip {} \\;"]
+        );
+        assert_eq!(
+            expand_file_placeholder("awk '{{}}{print}' {}", &files).unwrap(),
+            vec!["awk '{}{print}' 'a.log'"]
+        );
+    }
+
+    #[test]
+    fn should_not_expand_placeholder_without_files() {
+        assert!(has_file_placeholder("rm {}"));
+        assert!(expand_file_placeholder("rm {}", &[]).is_none());
+    }
+}
@@ -0,0 +1,334 @@
+//! ## Follow
+//!
+//! `follow` is the module which keeps the states of a remote file followed as with `tail -f`
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::utils::string::shell_quote;
+
+/// Maximum amount of lines kept in memory
+const MAX_LINES: usize = 1000;
+/// Amount of bytes read from the end of the file when following starts
+pub const TAIL_BYTES: u64 = 8192;
+
+/// Line added when the followed file has been truncated
+const TRUNCATED_LINE: &str = "--- file truncated ---";
+
+/// Marker printed before the output of the tail command, so that it's told apart from other output
+const TAIL_MARKER: char = '>';
+
+/// Get the shell command printing the content of the file at `path` from `offset`.
+/// The content is hex encoded by `od`, so that the output is valid UTF-8 even if a character is cut by `offset`.
+/// The output must be parsed with `parse_tail_output`
+pub fn tail_command(path: &Path, offset: u64) -> String {
+    format!(
+        "printf '{TAIL_MARKER}' && od -An -v -tx1 -j {offset} {}",
+        shell_quote(&path.to_string_lossy())
+    )
+}
+
+/// Get the content of the file from the output of the command built by `tail_command`
+pub fn parse_tail_output(output: &str) -> Option<Vec<u8>> {
+    output
+        .strip_prefix(TAIL_MARKER)?
+        .split_ascii_whitespace()
+        .map(|x| match x.len() {
+            2 => u8::from_str_radix(x, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The states of a followed file
+pub struct Follower {
+    path: PathBuf,
+    /// Offset of the next byte to read
+    offset: u64,
+    lines: VecDeque<String>,
+    /// Last line, not terminated by a new line yet
+    partial: String,
+    /// Trailing bytes of an UTF-8 sequence which hasn't been read completely yet
+    incomplete: Vec<u8>,
+    /// Whether the first bytes read may be the end of an UTF-8 sequence cut by the starting offset
+    cut: bool,
+    highlight: Vec<String>,
+    paused: bool,
+    interval: Duration,
+    last_poll: Option<Instant>,
+}
+
+impl Follower {
+    /// Instantiate a new `Follower` for `path`, which is `size` bytes long.
+    /// Lines containing any of the `highlight` patterns are highlighted
+    pub fn new(path: &Path, size: u64, highlight: Vec<String>, interval: Duration) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            offset: size.saturating_sub(TAIL_BYTES),
+            lines: VecDeque::new(),
+            partial: String::new(),
+            incomplete: Vec::new(),
+            cut: size > TAIL_BYTES,
+            highlight,
+            paused: false,
+            interval,
+            last_poll: None,
+        }
+    }
+
+    /// Get the followed path
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Get the offset of the next byte to read
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns whether the file should be polled
+    pub fn due(&self) -> bool {
+        !self.paused
+            && self
+                .last_poll
+                .map(|x| x.elapsed() >= self.interval)
+                .unwrap_or(true)
+    }
+
+    /// Mark the file as polled now
+    pub fn polled(&mut self) {
+        self.last_poll = Some(Instant::now());
+    }
+
+    /// Pause or resume following the file. Returns whether it is now paused
+    pub fn toggle_pause(&mut self) -> bool {
+        self.paused = !self.paused;
+        self.paused
+    }
+
+    /// Returns whether following the file is paused
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Update the states with the current `size` of the file.
+    /// If the file has been truncated (e.g. by log rotation) it is read again from the beginning.
+    /// Returns the amount of bytes which should be read from `offset`
+    pub fn resize(&mut self, size: u64) -> u64 {
+        if size < self.offset {
+            self.offset = 0;
+            self.incomplete.clear();
+            self.cut = false;
+            if !self.partial.is_empty() {
+                let line = std::mem::take(&mut self.partial);
+                self.push_line(line);
+            }
+            self.push_line(TRUNCATED_LINE.to_string());
+        }
+        size - self.offset
+    }
+
+    /// Append the bytes read at `offset`.
+    /// An UTF-8 sequence split across reads is kept until it's complete
+    pub fn append(&mut self, data: &[u8]) {
+        self.offset += data.len() as u64;
+        let mut bytes = std::mem::take(&mut self.incomplete);
+        bytes.extend_from_slice(data);
+        let mut bytes = bytes.as_slice();
+        if self.cut && !bytes.is_empty() {
+            // skip continuation bytes of the sequence cut by the starting offset
+            let start = bytes
+                .iter()
+                .position(|x| x & 0xc0 != 0x80)
+                .unwrap_or(bytes.len());
+            bytes = &bytes[start..];
+            self.cut = bytes.is_empty();
+        }
+        let data = self.decode(bytes);
+        let mut chunks = data.split('\n').peekable();
+        while let Some(chunk) = chunks.next() {
+            self.partial.push_str(chunk.trim_end_matches('\r'));
+            // the last chunk is not terminated yet
+            if chunks.peek().is_some() {
+                let line = std::mem::take(&mut self.partial);
+                self.push_line(line);
+            }
+        }
+    }
+
+    /// Decode `bytes` as UTF-8, keeping an incomplete trailing sequence for the next read.
+    /// Invalid sequences are replaced by U+FFFD
+    fn decode(&mut self, mut bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(bytes.len());
+        loop {
+            match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    return text;
+                }
+                Err(err) => {
+                    let (valid, rest) = bytes.split_at(err.valid_up_to());
+                    text.push_str(&String::from_utf8_lossy(valid));
+                    match err.error_len() {
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            bytes = &rest[len..];
+                        }
+                        None => {
+                            self.incomplete = rest.to_vec();
+                            return text;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Iterate over the lines read so far
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .map(String::as_str)
+            .chain(Some(self.partial.as_str()).filter(|x| !x.is_empty()))
+    }
+
+    /// Returns whether `line` contains any of the highlight patterns
+    pub fn is_highlighted(&self, line: &str) -> bool {
+        self.highlight.iter().any(|x| line.contains(x.as_str()))
+    }
+
+    fn push_line(&mut self, line: String) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn follower(size: u64) -> Follower {
+        Follower::new(
+            Path::new("/var/log/app.log"),
+            size,
+            vec![String::from("ERROR")],
+            Duration::from_secs(1),
+        )
+    }
+
+    #[test]
+    fn should_start_from_the_tail() {
+        assert_eq!(follower(100).offset(), 0);
+        assert_eq!(follower(TAIL_BYTES + 100).offset(), 100);
+        let follower = follower(0);
+        assert_eq!(follower.path(), Path::new("/var/log/app.log"));
+        assert!(follower.due());
+    }
+
+    #[test]
+    fn should_append_lines() {
+        let mut follower = follower(0);
+        assert_eq!(follower.resize(12), 12);
+        follower.append(b"hello\r\nwor");
+        assert_eq!(follower.offset(), 10);
+        assert_eq!(
+            follower.lines().collect::<Vec<&str>>(),
+            vec!["hello", "wor"]
+        );
+        follower.append(b"ld\nERROR: oops\n");
+        assert_eq!(
+            follower.lines().collect::<Vec<&str>>(),
+            vec!["hello", "world", "ERROR: oops"]
+        );
+        assert!(!follower.is_highlighted("world"));
+        assert!(follower.is_highlighted("ERROR: oops"));
+        assert_eq!(follower.resize(follower.offset()), 0);
+    }
+
+    #[test]
+    fn should_decode_utf8_split_across_reads() {
+        let mut follower = follower(0);
+        let data = "città\n€\n".as_bytes();
+        follower.append(&data[..5]);
+        assert_eq!(follower.lines().collect::<Vec<&str>>(), vec!["citt"]);
+        follower.append(&data[5..8]);
+        follower.append(&data[8..9]);
+        follower.append(&data[9..]);
+        assert_eq!(follower.offset(), data.len() as u64);
+        assert_eq!(follower.lines().collect::<Vec<&str>>(), vec!["città", "€"]);
+        follower.append(b"bad \xff byte\n");
+        assert_eq!(follower.lines().last(), Some("bad \u{fffd} byte"));
+    }
+
+    #[test]
+    fn should_skip_sequence_cut_by_starting_offset() {
+        let mut follower = follower(TAIL_BYTES + 2);
+        // the first two bytes are the end of `€`
+        follower.append(&"€".as_bytes()[1..]);
+        follower.append("ok\n".as_bytes());
+        assert_eq!(follower.lines().collect::<Vec<&str>>(), vec!["ok"]);
+    }
+
+    #[test]
+    fn should_read_truncated_file_from_beginning() {
+        let mut follower = follower(0);
+        follower.append(b"first\nsecond");
+        assert_eq!(follower.resize(4), 4);
+        assert_eq!(follower.offset(), 0);
+        follower.append(b"new\n");
+        assert_eq!(
+            follower.lines().collect::<Vec<&str>>(),
+            vec!["first", "second", TRUNCATED_LINE, "new"]
+        );
+    }
+
+    #[test]
+    fn should_keep_last_lines() {
+        let mut follower = follower(0);
+        for i in 0..MAX_LINES + 10 {
+            follower.append(format!("{i}\n").as_bytes());
+        }
+        assert_eq!(follower.lines().count(), MAX_LINES);
+        assert_eq!(follower.lines().next(), Some("10"));
+    }
+
+    #[test]
+    fn should_build_tail_command() {
+        assert_eq!(
+            tail_command(Path::new("/var/log/app.log"), 0),
+            "printf '>' && od -An -v -tx1 -j 0 '/var/log/app.log'"
+        );
+        assert_eq!(
+            tail_command(Path::new("/tmp/it's here.log"), 100),
+            r#"printf '>' && od -An -v -tx1 -j 100 '/tmp/it'\''s here.log'"#
+        );
+    }
+
+    #[test]
+    fn should_parse_tail_output() {
+        assert_eq!(
+            parse_tail_output("> 20 20 69 0a e2\n 82 ac\n"),
+            Some(b"  i\n\xe2\x82\xac".to_vec())
+        );
+        assert_eq!(parse_tail_output(">"), Some(vec![]));
+        assert_eq!(parse_tail_output("> 0g\n"), None);
+        assert_eq!(parse_tail_output("od: not found\n"), None);
+    }
+
+    #[test]
+    fn should_pause() {
+        let mut follower = follower(0);
+        follower.polled();
+        assert!(!follower.due());
+        assert!(follower.toggle_pause());
+        assert!(follower.paused());
+        assert!(!follower.toggle_pause());
+        assert!(!follower.paused());
+    }
+}
@@ -3,4 +3,10 @@
 //! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
 
 pub(crate) mod browser;
+pub(crate) mod command;
+pub(crate) mod context_menu;
+pub(crate) mod exec;
+pub(crate) mod follow;
+pub(crate) mod shell;
 pub(crate) mod transfer;
+pub(crate) mod undo;
@@ -0,0 +1,169 @@
+//! ## Shell
+//!
+//! `shell` is the module which translates the terminal events for the remote shell
+
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::utils::string::shell_quote;
+
+/// Get the command which starts the login shell of the user in `wrkdir`
+pub fn shell_command(wrkdir: &Path) -> String {
+    format!(
+        "cd {} && exec \"${{SHELL:-/bin/sh}}\" -l",
+        shell_quote(&wrkdir.to_string_lossy())
+    )
+}
+
+/// Encode `key` as the bytes a terminal would send for it
+pub fn encode_key(key: KeyEvent) -> Vec<u8> {
+    if key.kind == KeyEventKind::Release {
+        return Vec::new();
+    }
+    let mut bytes: Vec<u8> = match key.code {
+        KeyCode::Char(ch) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            match control_char(ch) {
+                Some(byte) => vec![byte],
+                None => ch.to_string().into_bytes(),
+            }
+        }
+        KeyCode::Char(ch) => ch.to_string().into_bytes(),
+        KeyCode::Enter => b"\r".to_vec(),
+        KeyCode::Tab => b"\t".to_vec(),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n) => match n {
+            1 => b"\x1bOP".to_vec(),
+            2 => b"\x1bOQ".to_vec(),
+            3 => b"\x1bOR".to_vec(),
+            4 => b"\x1bOS".to_vec(),
+            5 => b"\x1b[15~".to_vec(),
+            6 => b"\x1b[17~".to_vec(),
+            7 => b"\x1b[18~".to_vec(),
+            8 => b"\x1b[19~".to_vec(),
+            9 => b"\x1b[20~".to_vec(),
+            10 => b"\x1b[21~".to_vec(),
+            11 => b"\x1b[23~".to_vec(),
+            12 => b"\x1b[24~".to_vec(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    // alt is sent as an escape before the key
+    if key.modifiers.contains(KeyModifiers::ALT) && !bytes.is_empty() {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+/// Get the control character sent for `ctrl+ch`
+fn control_char(ch: char) -> Option<u8> {
+    match ch {
+        'a'..='z' => Some(ch as u8 - b'a' + 1),
+        'A'..='Z' => Some(ch as u8 - b'A' + 1),
+        ' ' | '@' | '2' => Some(0),
+        '[' | '3' => Some(0x1b),
+        '\\' | '4' => Some(0x1c),
+        ']' | '5' => Some(0x1d),
+        '^' | '6' => Some(0x1e),
+        '_' | '7' | '/' => Some(0x1f),
+        '8' | '?' => Some(0x7f),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn should_build_shell_command() {
+        assert_eq!(
+            shell_command(Path::new("/home/omar/my site")),
+            r#"cd '/home/omar/my site' && exec "${SHELL:-/bin/sh}" -l"#
+        );
+    }
+
+    #[test]
+    fn should_encode_chars() {
+        assert_eq!(
+            encode_key(key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            b"a".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            b"A".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Char('è'), KeyModifiers::NONE)),
+            "è".as_bytes().to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            vec![0x03]
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            vec![0x04]
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Char('b'), KeyModifiers::ALT)),
+            b"\x1bb".to_vec()
+        );
+    }
+
+    #[test]
+    fn should_encode_special_keys() {
+        assert_eq!(
+            encode_key(key(KeyCode::Enter, KeyModifiers::NONE)),
+            b"\r".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Backspace, KeyModifiers::NONE)),
+            vec![0x7f]
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Up, KeyModifiers::NONE)),
+            b"\x1b[A".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Delete, KeyModifiers::NONE)),
+            b"\x1b[3~".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::F(1), KeyModifiers::NONE)),
+            b"\x1bOP".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::F(12), KeyModifiers::NONE)),
+            b"\x1b[24~".to_vec()
+        );
+        assert!(encode_key(key(KeyCode::CapsLock, KeyModifiers::NONE)).is_empty());
+    }
+
+    #[test]
+    fn should_ignore_key_release() {
+        let mut event = key(KeyCode::Char('a'), KeyModifiers::NONE);
+        event.kind = KeyEventKind::Release;
+        assert!(encode_key(event).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+//! ## Undo
+//!
+//! `undo` is the module which keeps track of the operations which can be reverted during the session
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Name of the directory, in the remote home, where the deleted remote entries are moved
+pub const REMOTE_TRASH_DIR: &str = ".termscp-trash";
+
+/// Maximum amount of operations kept in the undo stack
+const UNDO_STACK_SIZE: usize = 64;
+
+/// A change made to a single entry, which can be reverted
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum UndoOp {
+    /// A local entry moved to the trash of the system
+    LocalTrash { path: PathBuf },
+    /// A remote entry moved to the remote trash directory
+    RemoteTrash { path: PathBuf, trashed: PathBuf },
+    /// An entry renamed or moved from `from` to `to`
+    Move {
+        remote: bool,
+        from: PathBuf,
+        to: PathBuf,
+    },
+}
+
+impl UndoOp {
+    /// Describe how the change is reverted
+    pub fn describe(&self) -> String {
+        match self {
+            Self::LocalTrash { path } => format!("restore \"{}\" from trash", path.display()),
+            Self::RemoteTrash { path, trashed } => format!(
+                "restore \"{}\" from \"{}\"",
+                path.display(),
+                trashed.display()
+            ),
+            Self::Move { from, to, .. } => {
+                format!("move \"{}\" back to \"{}\"", to.display(), from.display())
+            }
+        }
+    }
+}
+
+/// The operations performed during the session which can be reverted, latest last.
+/// An operation groups the changes made to all the entries it was performed on
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    operations: VecDeque<Vec<UndoOp>>,
+}
+
+impl UndoStack {
+    /// Push an operation to the stack; the oldest operation is discarded when the stack is full
+    pub fn push(&mut self, ops: Vec<UndoOp>) {
+        if ops.is_empty() {
+            return;
+        }
+        if self.operations.len() == UNDO_STACK_SIZE {
+            self.operations.pop_front();
+        }
+        self.operations.push_back(ops);
+    }
+
+    /// Pop the latest operation from the stack
+    pub fn pop(&mut self) -> Option<Vec<UndoOp>> {
+        self.operations.pop_back()
+    }
+}
+
+/// Get the path where `name` is moved into the remote trash `trash_dir`.
+/// `stamp` identifies the deletion, while `attempt` disambiguates entries with the same name
+pub fn remote_trash_path(trash_dir: &Path, name: &str, stamp: &str, attempt: usize) -> PathBuf {
+    match attempt {
+        0 => trash_dir.join(format!("{stamp}_{name}")),
+        n => trash_dir.join(format!("{stamp}_{n}_{name}")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_push_and_pop_operations() {
+        let mut stack = UndoStack::default();
+        // empty operations are ignored
+        stack.push(vec![]);
+        assert_eq!(stack.pop(), None);
+        let trash = vec![UndoOp::LocalTrash {
+            path: PathBuf::from("/home/omar/a.txt"),
+        }];
+        let mv = vec![
+            UndoOp::Move {
+                remote: true,
+                from: PathBuf::from("/srv/a.txt"),
+                to: PathBuf::from("/srv/old/a.txt"),
+            },
+            UndoOp::Move {
+                remote: true,
+                from: PathBuf::from("/srv/b.txt"),
+                to: PathBuf::from("/srv/old/b.txt"),
+            },
+        ];
+        stack.push(trash.clone());
+        stack.push(mv.clone());
+        assert_eq!(stack.pop(), Some(mv));
+        assert_eq!(stack.pop(), Some(trash));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn should_discard_oldest_operations() {
+        let mut stack = UndoStack::default();
+        for i in 0..=UNDO_STACK_SIZE {
+            stack.push(vec![UndoOp::LocalTrash {
+                path: PathBuf::from(format!("/tmp/{i}")),
+            }]);
+        }
+        let mut popped = 0;
+        while let Some(ops) = stack.pop() {
+            popped += 1;
+            assert_ne!(
+                ops,
+                vec![UndoOp::LocalTrash {
+                    path: PathBuf::from("/tmp/0")
+                }]
+            );
+        }
+        assert_eq!(popped, UNDO_STACK_SIZE);
+    }
+
+    #[test]
+    fn should_describe_operations() {
+        assert_eq!(
+            UndoOp::LocalTrash {
+                path: PathBuf::from("/home/omar/a.txt")
+            }
+            .describe()
+            .as_str(),
+            "restore \"/home/omar/a.txt\" from trash"
+        );
+        assert_eq!(
+            UndoOp::RemoteTrash {
+                path: PathBuf::from("/srv/a.txt"),
+                trashed: PathBuf::from("/home/omar/.termscp-trash/20261018-101010_a.txt"),
+            }
+            .describe()
+            .as_str(),
+            "restore \"/srv/a.txt\" from \"/home/omar/.termscp-trash/20261018-101010_a.txt\""
+        );
+        assert_eq!(
+            UndoOp::Move {
+                remote: false,
+                from: PathBuf::from("/tmp/a.txt"),
+                to: PathBuf::from("/tmp/b.txt"),
+            }
+            .describe()
+            .as_str(),
+            "move \"/tmp/b.txt\" back to \"/tmp/a.txt\""
+        );
+    }
+
+    #[test]
+    fn should_get_remote_trash_path() {
+        let trash_dir = Path::new("/home/omar/.termscp-trash");
+        assert_eq!(
+            remote_trash_path(trash_dir, "a.txt", "20261018-101010", 0),
+            PathBuf::from("/home/omar/.termscp-trash/20261018-101010_a.txt")
+        );
+        assert_eq!(
+            remote_trash_path(trash_dir, "a.txt", "20261018-101010", 2),
+            PathBuf::from("/home/omar/.termscp-trash/20261018-101010_2_a.txt")
+        );
+    }
+}
@@ -10,7 +10,10 @@ use tuirealm::props::{
 use tuirealm::{PollStrategy, Update};
 
 use super::browser::FileExplorerTab;
+use super::components::FILE_LIST_HEADER;
 use super::{ConfigClient, FileTransferActivity, Id, LogLevel, LogRecord, TransferPayload};
+use crate::config::params::FileSortingConfig;
+use crate::explorer::FileExplorer;
 use crate::filetransfer::ProtocolParams;
 use crate::system::environment;
 use crate::system::notifications::Notification;
@@ -233,12 +236,16 @@ impl FileTransferActivity {
             hostname,
             fmt_path_elide_ex(self.local().wrkdir.as_path(), width, hostname.len() + 3) // 3 because of '/…/'
         );
-        let files: Vec<Vec<TextSpan>> = self
-            .local()
-            .iter_files()
-            .map(|x| vec![TextSpan::from(self.local().fmt_file(x))])
-            .collect();
+        let (header, files) = Self::file_list_rows(self.local(), self.browser.table_view);
         // Update content and title
+        assert!(self
+            .app
+            .attr(
+                &Id::ExplorerLocal,
+                Attribute::Custom(FILE_LIST_HEADER),
+                header
+            )
+            .is_ok());
         assert!(self
             .app
             .attr(
@@ -277,12 +284,16 @@ impl FileTransferActivity {
                 hostname.len() + 3 // 3 because of '/…/'
             )
         );
-        let files: Vec<Vec<TextSpan>> = self
-            .remote()
-            .iter_files()
-            .map(|x| vec![TextSpan::from(self.remote().fmt_file(x))])
-            .collect();
+        let (header, files) = Self::file_list_rows(self.remote(), self.browser.table_view);
         // Update content and title
+        assert!(self
+            .app
+            .attr(
+                &Id::ExplorerRemote,
+                Attribute::Custom(FILE_LIST_HEADER),
+                header
+            )
+            .is_ok());
         assert!(self
             .app
             .attr(
@@ -301,6 +312,75 @@ impl FileTransferActivity {
             .is_ok());
     }
 
+    /// Persist file sorting of local and remote explorers to configuration.
+    /// The sorting overridden by the bookmark settings is kept for the session only
+    pub(super) fn save_file_sorting(&mut self) {
+        let local = Self::file_sorting_config(self.local());
+        let remote = Self::file_sorting_config(self.remote());
+        if let Err(err) = self
+            .context_mut()
+            .config_mut()
+            .save_file_sorting(local, remote)
+        {
+            self.log(
+                LogLevel::Error,
+                format!("Could not save file sorting: {err}"),
+            );
+        }
+    }
+
+    fn file_sorting_config(explorer: &FileExplorer) -> FileSortingConfig {
+        FileSortingConfig {
+            by: Some(explorer.get_file_sorting().to_string()),
+            reverse: Some(explorer.reverse_sorting()),
+            natural: Some(explorer.natural_sorting()),
+            case_sensitive: Some(explorer.case_sensitive_sorting()),
+        }
+    }
+
+    /// Make file list rows for `explorer`.
+    /// In table view, the header is returned too and each row has a cell for each column;
+    /// the column the files are sorted by is marked in the header
+    fn file_list_rows(
+        explorer: &FileExplorer,
+        table_view: bool,
+    ) -> (AttrValue, Vec<Vec<TextSpan>>) {
+        if !table_view {
+            let rows = explorer
+                .iter_files()
+                .map(|x| vec![TextSpan::from(explorer.fmt_file(x))])
+                .collect();
+            return (AttrValue::Payload(PropPayload::Vec(vec![])), rows);
+        }
+        let sorting = explorer.get_file_sorting();
+        let sorted_column = explorer
+            .columns()
+            .iter()
+            .position(|x| x.sorting() == Some(sorting));
+        let header: Vec<PropValue> = explorer
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match Some(i) == sorted_column {
+                true if explorer.reverse_sorting() => format!("{} ▼", column.label()),
+                true => format!("{} ▲", column.label()),
+                false => column.label().to_string(),
+            })
+            .map(PropValue::Str)
+            .collect();
+        let rows = explorer
+            .iter_files()
+            .map(|x| {
+                explorer
+                    .fmt_file_columns(x)
+                    .into_iter()
+                    .map(TextSpan::from)
+                    .collect()
+            })
+            .collect();
+        (AttrValue::Payload(PropPayload::Vec(header)), rows)
+    }
+
     /// Update log box
     pub(super) fn update_logbox(&mut self) {
         let mut table: TableBuilder = TableBuilder::default();
@@ -418,12 +498,15 @@ impl FileTransferActivity {
     }
 
     pub(super) fn update_find_list(&mut self) {
-        let files: Vec<Vec<TextSpan>> = self
-            .found()
-            .unwrap()
-            .iter_files()
-            .map(|x| vec![TextSpan::from(self.found().unwrap().fmt_file(x))])
-            .collect();
+        let (header, files) = Self::file_list_rows(self.found().unwrap(), self.browser.table_view);
+        assert!(self
+            .app
+            .attr(
+                &Id::ExplorerFind,
+                Attribute::Custom(FILE_LIST_HEADER),
+                header
+            )
+            .is_ok());
         assert!(self
             .app
             .attr(
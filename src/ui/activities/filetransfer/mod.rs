@@ -6,22 +6,28 @@
 mod actions;
 mod components;
 mod fswatcher;
+mod hooks;
 mod lib;
 mod misc;
+mod mouse;
 mod session;
 mod update;
 mod view;
 
 // locals
 use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::time::Duration;
 
 // Includes
 use chrono::{DateTime, Local};
 use lib::browser;
 use lib::browser::Browser;
+use lib::context_menu::ContextMenuEntry;
+use lib::follow::Follower;
 use lib::transfer::{TransferOpts, TransferStates};
-use remotefs::RemoteFs;
+use lib::undo::UndoStack;
+use remotefs::File;
 use session::TransferPayload;
 use tempfile::TempDir;
 use tuirealm::{Application, EventListenerCfg, NoUserEvent};
@@ -29,20 +35,26 @@ use tuirealm::{Application, EventListenerCfg, NoUserEvent};
 use super::{Activity, Context, ExitReason};
 use crate::config::themes::Theme;
 use crate::explorer::{FileExplorer, FileSorting};
-use crate::filetransfer::{Builder, FileTransferParams};
+use crate::filetransfer::{Builder, FileTransferParams, RemoteClient};
 use crate::host::Localhost;
 use crate::system::config_client::ConfigClient;
-use crate::system::watcher::FsWatcher;
+use crate::system::watcher::{FsWatcher, RemoteWatcher, SyncQueue};
+use crate::ui::mouse::{MouseAreas, MouseInput};
 
 // -- components
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 enum Id {
     ChmodPopup,
+    CommandLinePopup,
+    ContextMenuPopup,
     CopyPopup,
+    CustomActionConfirmPopup,
+    CustomActionsPopup,
     DeletePopup,
     DisconnectPopup,
     ErrorPopup,
+    ExecOutputPopup,
     ExecPopup,
     ExplorerFind,
     ExplorerLocal,
@@ -50,6 +62,8 @@ enum Id {
     FatalPopup,
     FileInfoPopup,
     FindPopup,
+    FollowHighlightPopup,
+    FollowPopup,
     FooterBar,
     GlobalListener,
     GotoPopup,
@@ -58,6 +72,7 @@ enum Id {
     MkdirPopup,
     NewfilePopup,
     OpenWithPopup,
+    PermanentDeletePopup,
     ProgressBarFull,
     ProgressBarPartial,
     QuitPopup,
@@ -70,8 +85,10 @@ enum Id {
     StatusBarRemote,
     SymlinkPopup,
     SyncBrowsingMkdirPopup,
+    SyncQueueList,
     WaitPopup,
     WatchedPathsList,
+    WatcherIgnorePopup,
     WatcherPopup,
 }
 
@@ -98,7 +115,11 @@ enum TransferMsg {
     CopyFileTo(String),
     CreateSymlink(String),
     DeleteFile,
+    /// Delete permanently the local files which couldn't be moved to the trash
+    DeleteFilePermanently,
     EnterDirectory,
+    /// Follow the selected file, highlighting the lines containing the comma-separated patterns
+    Follow(String),
     ExecuteCmd(String),
     GoTo(String),
     GoToParentDirectory,
@@ -107,14 +128,21 @@ enum TransferMsg {
     NewFile(String),
     OpenFile,
     OpenFileWith(String),
+    OpenRemoteShell,
     OpenTextFile,
     ReloadDir,
     RenameFile(String),
+    /// Run the custom action at the given index
+    RunCustomAction(usize),
     SaveFileAs(String),
     SearchFile(String),
     ToggleWatch,
     ToggleWatchFor(usize),
     TransferFile,
+    /// Revert the latest delete, rename or move
+    Undo,
+    /// Watch the selected path, ignoring the comma-separated patterns
+    Watch(String),
 }
 
 #[derive(Debug, PartialEq)]
@@ -122,39 +150,56 @@ enum UiMsg {
     ChangeFileSorting(FileSorting),
     ChangeTransferWindow,
     CloseChmodPopup,
+    CloseCommandLine,
+    CloseContextMenu,
     CloseCopyPopup,
+    CloseCustomActionConfirmPopup,
+    CloseCustomActionsPopup,
     CloseDeletePopup,
     CloseDisconnectPopup,
     CloseErrorPopup,
+    CloseExecOutputPopup,
     CloseExecPopup,
     CloseFatalPopup,
     CloseFileInfoPopup,
     CloseFileSortingPopup,
     CloseFindExplorer,
     CloseFindPopup,
+    CloseFollowHighlightPopup,
+    CloseFollowPopup,
     CloseGotoPopup,
     CloseKeybindingsPopup,
     CloseMkdirPopup,
     CloseNewFilePopup,
     CloseOpenWithPopup,
+    ClosePermanentDeletePopup,
     CloseQuitPopup,
     CloseRenamePopup,
     CloseSaveAsPopup,
     CloseSymlinkPopup,
+    CloseSyncQueueList,
     CloseWatchedPathsList,
+    CloseWatcherIgnorePopup,
     CloseWatcherPopup,
     Disconnect,
     LogBackTabbed,
     Quit,
     ReplacePopupTabbed,
+    SelectContextMenuEntry(ContextMenuEntry),
+    /// Run the custom action at the given index, asking for confirmation if required
+    SelectCustomAction(usize),
     ShowChmodPopup,
+    ShowCommandLine,
+    ShowContextMenu,
     ShowCopyPopup,
+    ShowCustomActionsPopup,
     ShowDeletePopup,
     ShowDisconnectPopup,
     ShowExecPopup,
     ShowFileInfoPopup,
     ShowFileSortingPopup,
     ShowFindPopup,
+    ShowFollowHighlightPopup,
     ShowGotoPopup,
     ShowKeybindingsPopup,
     ShowLogPanel,
@@ -165,10 +210,19 @@ enum UiMsg {
     ShowRenamePopup,
     ShowSaveAsPopup,
     ShowSymlinkPopup,
+    ShowSyncQueueList,
     ShowWatchedPathsList,
     ShowWatcherPopup,
+    SortByNextColumn,
+    SubmitCommandLine(String),
+    ToggleCaseSensitiveSorting,
+    ToggleFollowPause,
     ToggleHiddenFiles,
+    ToggleNaturalSorting,
+    ToggleReverseSorting,
     ToggleSyncBrowsing,
+    ToggleSyncPause,
+    ToggleTableView,
     WindowResized,
 }
 
@@ -210,7 +264,7 @@ pub struct FileTransferActivity {
     /// Localhost bridge
     host: Localhost,
     /// Remote host client
-    client: Box<dyn RemoteFs>,
+    client: Box<dyn RemoteClient>,
     /// Browser
     browser: Browser,
     /// Current log lines
@@ -220,8 +274,28 @@ pub struct FileTransferActivity {
     cache: Option<TempDir>,
     /// Fs watcher
     fswatcher: Option<FsWatcher>,
+    /// Changes reported by the fs watcher, waiting to be synched with the remote
+    sync_queue: SyncQueue,
+    /// Remote directories whose changes are downloaded
+    remote_watcher: RemoteWatcher,
+    /// Remote file followed as with `tail -f`
+    follower: Option<Follower>,
     /// connected once
     connected: bool,
+    /// Mouse events input
+    mouse: MouseInput,
+    /// Areas where components have been drawn, used to dispatch mouse events
+    mouse_areas: MouseAreas<Id>,
+    /// Commands submitted in the command line, oldest first
+    command_history: Vec<String>,
+    /// Commands executed with the exec popup, oldest first
+    exec_history: Vec<String>,
+    /// Operations performed during the session which can be reverted
+    undo: UndoStack,
+    /// Working directory of the remote host when connected
+    remote_home: Option<PathBuf>,
+    /// Local files which couldn't be moved to the trash, waiting for the user to confirm their permanent deletion
+    untrashed: Vec<File>,
 }
 
 impl FileTransferActivity {
@@ -229,13 +303,14 @@ impl FileTransferActivity {
     pub fn new(host: Localhost, params: &FileTransferParams, ticks: Duration) -> Self {
         // Get config client
         let config_client: ConfigClient = Self::init_config_client();
+        let mouse = MouseInput::default();
         Self {
             exit_reason: None,
             context: None,
             app: Application::init(
                 EventListenerCfg::default()
                     .poll_timeout(ticks)
-                    .default_input_listener(ticks),
+                    .port(Box::new(mouse.listener(ticks)), ticks),
             ),
             redraw: true,
             host,
@@ -254,7 +329,19 @@ impl FileTransferActivity {
                     None
                 }
             },
+            sync_queue: SyncQueue::new(Duration::from_secs(2)),
+            remote_watcher: RemoteWatcher::new(Duration::from_secs(
+                config_client.get_remote_watch_interval(),
+            )),
+            follower: None,
             connected: false,
+            mouse,
+            mouse_areas: MouseAreas::default(),
+            command_history: Vec::new(),
+            exec_history: Vec::new(),
+            undo: UndoStack::default(),
+            remote_home: None,
+            untrashed: Vec::new(),
         }
     }
 
@@ -350,6 +437,12 @@ impl Activity for FileTransferActivity {
         if let Err(err) = self.context_mut().terminal().enable_raw_mode() {
             error!("Failed to enter raw mode: {}", err);
         }
+        let mouse_support = self.config().get_mouse_support();
+        if let Err(err) = self.context_mut().set_mouse_capture(mouse_support) {
+            error!("Failed to set mouse capture: {}", err);
+        }
+        // Build explorers with the session settings of the bookmark in use
+        self.browser = Browser::new(self.config());
         // Get files at current pwd
         self.reload_local_dir();
         debug!("Read working directory");
@@ -387,8 +480,11 @@ impl Activity for FileTransferActivity {
             self.redraw = true;
         }
         self.tick();
+        self.poll_mouse();
         // poll
         self.poll_watcher();
+        self.poll_remote_watcher();
+        self.poll_follower();
         // View
         if self.redraw {
             self.view();
@@ -422,6 +518,10 @@ impl Activity for FileTransferActivity {
         if self.client.is_connected() {
             let _ = self.client.disconnect();
         }
+        // Session settings, hooks and bookmark apply only to this session
+        self.context_mut().config_mut().set_session_settings(None);
+        self.context_mut().set_bookmark_hooks(None);
+        self.context_mut().set_bookmark_name(None);
         self.context.take()
     }
 }
@@ -0,0 +1,239 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+use tuirealm::event::Key;
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use tuirealm::tui::layout::Rect;
+use tuirealm::{State, StateValue, Update};
+
+use super::browser::{FileExplorerTab, FoundExplorerTab};
+use super::components::{FILE_LIST_COLUMN_SPACING, FILE_LIST_COLUMN_WIDTHS};
+use super::{FileTransferActivity, Id, Msg, UiMsg};
+use crate::ui::mouse::{self, MouseEvent, MouseEventKind};
+
+/// Choices of the yes/no radio popups
+const YES_NO: [&str; 2] = ["Yes", "No"];
+
+impl FileTransferActivity {
+    /// Handle mouse events received since the last tick
+    pub(super) fn poll_mouse(&mut self) {
+        let events = self.mouse.events();
+        if events.is_empty() || !self.config().get_mouse_support() {
+            return;
+        }
+        for ev in events.iter() {
+            self.on_mouse(ev);
+        }
+        self.redraw = true;
+    }
+
+    fn on_mouse(&mut self, ev: &MouseEvent) {
+        let (id, area) = match self.mouse_areas.at(ev) {
+            Some(target) => target,
+            None => return,
+        };
+        // While a popup is open, explorers and log can't be focused
+        let popup = !matches!(
+            self.app.focus(),
+            Some(Id::ExplorerFind | Id::ExplorerLocal | Id::ExplorerRemote | Id::Log)
+        );
+        match id {
+            Id::ExplorerFind | Id::ExplorerLocal | Id::ExplorerRemote if !popup => {
+                self.on_mouse_explorer(id, area, ev)
+            }
+            Id::Log if !popup => {
+                assert!(self.app.active(&Id::Log).is_ok());
+                self.scroll(ev);
+            }
+            Id::ContextMenuPopup
+            | Id::CustomActionsPopup
+            | Id::ExecOutputPopup
+            | Id::FileInfoPopup
+            | Id::FollowPopup
+            | Id::KeybindingsPopup
+            | Id::ReplacingFilesListPopup
+            | Id::SortingPopup
+            | Id::SyncQueueList
+            | Id::WatchedPathsList => self.on_mouse_list(id, area, ev),
+            Id::CustomActionConfirmPopup
+            | Id::DeletePopup
+            | Id::DisconnectPopup
+            | Id::PermanentDeletePopup
+            | Id::QuitPopup
+            | Id::ReplacePopup
+            | Id::SyncBrowsingMkdirPopup
+            | Id::WatcherPopup => self.on_mouse_radio(id, area, ev),
+            _ => {}
+        }
+    }
+
+    /// Click selects the file under cursor (or sorts files by column, if on table header) and focuses
+    /// the explorer; double click enters the directory and right click shows the context menu
+    fn on_mouse_explorer(&mut self, id: Id, area: Rect, ev: &MouseEvent) {
+        self.focus_explorer(&id);
+        if self.scroll(ev) {
+            return;
+        }
+        let widths = self.explorer_column_widths(&id);
+        // Table header
+        if !widths.is_empty() && ev.row == area.y + 1 {
+            if ev.kind == MouseEventKind::Click {
+                self.sort_by_column_at(&id, area, &widths, ev.column);
+            }
+            return;
+        }
+        let header = u16::from(!widths.is_empty());
+        if !self.select_list_entry(&id, area, header, ev.row) {
+            return;
+        }
+        match ev.kind {
+            MouseEventKind::DoubleClick => self.mouse.inject(Key::Enter),
+            MouseEventKind::RightClick => self.action_show_context_menu(),
+            _ => {}
+        }
+    }
+
+    /// Click selects the entry under cursor; double click submits it
+    fn on_mouse_list(&mut self, id: Id, area: Rect, ev: &MouseEvent) {
+        assert!(self.app.active(&id).is_ok());
+        if self.scroll(ev) || !self.select_list_entry(&id, area, 0, ev.row) {
+            return;
+        }
+        match (&id, ev.kind) {
+            // Apply sorting or toggle option
+            (Id::SortingPopup, MouseEventKind::Click) => self.mouse.inject(Key::Char(' ')),
+            (
+                Id::ContextMenuPopup
+                | Id::CustomActionsPopup
+                | Id::SortingPopup
+                | Id::WatchedPathsList,
+                MouseEventKind::DoubleClick,
+            ) => self.mouse.inject(Key::Enter),
+            _ => {}
+        }
+    }
+
+    /// Click on a choice selects and submits it
+    fn on_mouse_radio(&mut self, id: Id, area: Rect, ev: &MouseEvent) {
+        if ev.kind != MouseEventKind::Click || ev.row != area.y + 1 {
+            return;
+        }
+        if let Some(choice) = mouse::radio_choice_at(area, &YES_NO, ev.column) {
+            assert!(self.app.active(&id).is_ok());
+            assert!(self
+                .app
+                .attr(
+                    &id,
+                    Attribute::Value,
+                    AttrValue::Payload(PropPayload::One(PropValue::Usize(choice)))
+                )
+                .is_ok());
+            self.mouse.inject(Key::Enter);
+        }
+    }
+
+    /// Scroll the active component if `ev` is a scroll event. Returns whether the event has been handled
+    fn scroll(&mut self, ev: &MouseEvent) -> bool {
+        match ev.kind {
+            MouseEventKind::ScrollDown => self.mouse.inject(Key::Down),
+            MouseEventKind::ScrollUp => self.mouse.inject(Key::Up),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Give focus to explorer `id`, switching transfer window if it's on the other side
+    fn focus_explorer(&mut self, id: &Id) {
+        let left = match id {
+            Id::ExplorerLocal => true,
+            Id::ExplorerRemote => false,
+            _ => matches!(self.browser.found_tab(), Some(FoundExplorerTab::Local)),
+        };
+        let current = matches!(
+            self.browser.tab(),
+            FileExplorerTab::Local | FileExplorerTab::FindLocal
+        );
+        match left == current {
+            true => assert!(self.app.active(id).is_ok()),
+            false => self.dispatch(Msg::Ui(UiMsg::ChangeTransferWindow)),
+        }
+    }
+
+    /// Get the widths of the explorer columns; empty if the explorer is not in table view
+    fn explorer_column_widths(&self, id: &Id) -> Vec<u16> {
+        match self
+            .app
+            .query(id, Attribute::Custom(FILE_LIST_COLUMN_WIDTHS))
+        {
+            Ok(Some(AttrValue::Payload(PropPayload::Vec(widths)))) => {
+                widths.into_iter().map(|x| x.unwrap_u16()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Sort files by the table column at `column`; if files are already sorted by it, the order is reversed
+    fn sort_by_column_at(&mut self, id: &Id, area: Rect, widths: &[u16], column: u16) {
+        let column = match mouse::table_column_at(area, widths, FILE_LIST_COLUMN_SPACING, column) {
+            Some(column) => column,
+            None => return,
+        };
+        let explorer = match id {
+            Id::ExplorerLocal => self.local(),
+            Id::ExplorerRemote => self.remote(),
+            _ => match self.found() {
+                Some(explorer) => explorer,
+                None => return,
+            },
+        };
+        let msg = match explorer.columns().get(column).and_then(|x| x.sorting()) {
+            Some(sorting) if sorting == explorer.get_file_sorting() => UiMsg::ToggleReverseSorting,
+            Some(sorting) => UiMsg::ChangeFileSorting(sorting),
+            None => return,
+        };
+        self.dispatch(Msg::Ui(msg));
+    }
+
+    /// Move the cursor of list `id` to the entry at `row`. Returns whether there's an entry at `row`
+    fn select_list_entry(&mut self, id: &Id, area: Rect, header: u16, row: u16) -> bool {
+        let len = match self.app.query(id, Attribute::Content) {
+            Ok(Some(AttrValue::Table(rows))) => rows.len(),
+            _ => 0,
+        };
+        // NOTE: file lists report selected files as state, so the cursor must be queried
+        let selected = match (self.app.query(id, Attribute::Value), self.app.state(id)) {
+            (Ok(Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(i))))), _)
+                if matches!(
+                    id,
+                    Id::ExplorerFind | Id::ExplorerLocal | Id::ExplorerRemote
+                ) =>
+            {
+                i
+            }
+            (_, Ok(State::One(StateValue::Usize(i)))) => i,
+            _ => 0,
+        };
+        match mouse::list_index_at(area, header, selected, len, row) {
+            Some(index) => {
+                assert!(self
+                    .app
+                    .attr(
+                        id,
+                        Attribute::Value,
+                        AttrValue::Payload(PropPayload::One(PropValue::Usize(index)))
+                    )
+                    .is_ok());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn dispatch(&mut self, msg: Msg) {
+        let mut msg = Some(msg);
+        while msg.is_some() {
+            msg = self.update(msg);
+        }
+    }
+}
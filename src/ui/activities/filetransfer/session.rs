@@ -14,6 +14,7 @@ use remotefs::fs::{File, Metadata, ReadStream, UnixPex, Welcome, WriteStream};
 use remotefs::{RemoteError, RemoteErrorType, RemoteResult};
 use thiserror::Error;
 
+use super::hooks::HookEvent;
 use super::{FileTransferActivity, LogLevel};
 use crate::host::HostError;
 use crate::utils::fmt::fmt_millis;
@@ -54,6 +55,12 @@ impl FileTransferActivity {
     pub(super) fn connect(&mut self) {
         let ft_params = self.context().ft_params().unwrap().clone();
         let entry_dir: Option<PathBuf> = ft_params.remote_path;
+        // Run hooks before connecting (e.g. bring up a vpn)
+        if let Err(err) = self.run_hooks(HookEvent::OnConnect) {
+            self.umount_wait();
+            self.mount_fatal(&err);
+            return;
+        }
         // Connect to remote
         match self.client.connect() {
             Ok(Welcome { banner, .. }) => {
@@ -69,6 +76,8 @@ impl FileTransferActivity {
                         ),
                     );
                 }
+                // Keep track of the home directory, where the remote trash is located
+                self.remote_home = self.client.pwd().ok();
                 // Try to change directory to entry directory
                 let mut remote_chdir: Option<PathBuf> = None;
                 if let Some(remote_path) = &entry_dir {
@@ -77,14 +86,26 @@ impl FileTransferActivity {
                 if let Some(remote_path) = remote_chdir {
                     self.remote_changedir(remote_path.as_path(), false);
                 }
+                // Run hooks after connecting; abort connection if a hook failed
+                if let Err(err) = self.run_hooks(HookEvent::AfterConnect) {
+                    let _ = self.run_hooks(HookEvent::OnDisconnect);
+                    let _ = self.client.disconnect();
+                    self.umount_wait();
+                    self.mount_fatal(&err);
+                    return;
+                }
                 // Set state to explorer
                 self.umount_wait();
                 self.reload_remote_dir();
                 // Update file lists
                 self.update_local_filelist();
                 self.update_remote_filelist();
+                // Restore the paths watched when connected to this bookmark
+                self.restore_watched_paths();
             }
             Err(err) => {
+                // Undo what the on_connect hooks did (e.g. bring down the vpn); a failure is just logged
+                let _ = self.run_hooks(HookEvent::OnDisconnect);
                 // Set popup fatal error
                 self.umount_wait();
                 self.mount_fatal(&err.to_string());
@@ -97,6 +118,8 @@ impl FileTransferActivity {
         let msg: String = format!("Disconnecting from {}…", self.get_remote_hostname());
         // Show popup disconnecting
         self.mount_wait(msg.as_str());
+        // Run hooks; a failure can't abort the disconnection
+        let _ = self.run_hooks(HookEvent::OnDisconnect);
         // Disconnect
         let _ = self.client.disconnect();
         // Quit
@@ -8,9 +8,10 @@ use remotefs::fs::File;
 use tuirealm::props::{AttrValue, Attribute};
 use tuirealm::{State, StateValue, Update};
 
-use super::actions::SelectedFile;
+use super::actions::{context_menu, SelectedFile};
 use super::browser::{FileExplorerTab, FoundExplorerTab};
 use super::{ExitReason, FileTransferActivity, Id, Msg, TransferMsg, TransferOpts, UiMsg};
+use crate::explorer::FileExplorer;
 
 impl Update<Msg> for FileTransferActivity {
     fn update(&mut self, msg: Option<Msg>) -> Option<Msg> {
@@ -109,6 +110,17 @@ impl FileTransferActivity {
                     FileExplorerTab::FindLocal => self.update_local_filelist(),
                     FileExplorerTab::FindRemote => self.update_remote_filelist(),
                 }
+                // Ask whether to delete the files which couldn't be moved to the trash
+                if !self.untrashed.is_empty() {
+                    self.mount_radio_permanent_delete(self.untrashed.len());
+                }
+            }
+            TransferMsg::DeleteFilePermanently => {
+                self.umount_radio_permanent_delete();
+                self.mount_blocking_wait("Removing file(s)…");
+                self.action_local_delete_untrashed();
+                self.umount_wait();
+                self.update_local_filelist();
             }
             TransferMsg::EnterDirectory if self.browser.tab() == FileExplorerTab::Local => {
                 if let SelectedFile::One(entry) = self.get_local_selected_entries() {
@@ -141,18 +153,22 @@ impl FileTransferActivity {
                 // Reload files
                 self.update_browser_file_list()
             }
+            TransferMsg::Follow(patterns) => self.action_follow(patterns.as_str()),
             TransferMsg::ExecuteCmd(cmd) => {
                 // Exex command
                 self.umount_exec();
                 self.mount_blocking_wait(format!("Executing '{cmd}'…").as_str());
-                match self.browser.tab() {
+                let outputs = match self.browser.tab() {
                     FileExplorerTab::Local => self.action_local_exec(cmd),
                     FileExplorerTab::Remote => self.action_remote_exec(cmd),
                     _ => panic!("Found tab doesn't support EXEC"),
-                }
+                };
                 self.umount_wait();
                 // Reload files
-                self.update_browser_file_list()
+                self.update_browser_file_list();
+                if !outputs.is_empty() {
+                    self.mount_exec_output(&outputs);
+                }
             }
             TransferMsg::GoTo(dir) => {
                 match self.browser.tab() {
@@ -254,7 +270,19 @@ impl FileTransferActivity {
                 }
                 self.update_browser_file_list()
             }
+            TransferMsg::OpenRemoteShell => self.action_open_remote_shell(),
             TransferMsg::ReloadDir => self.update_browser_file_list(),
+            TransferMsg::RunCustomAction(idx) => {
+                self.umount_radio_custom_action_confirm();
+                self.mount_blocking_wait("Running custom action…");
+                let outputs = self.action_run_custom_action(idx);
+                self.umount_wait();
+                // Reload files
+                self.update_browser_file_list();
+                if !outputs.is_empty() {
+                    self.mount_exec_output(&outputs);
+                }
+            }
             TransferMsg::RenameFile(dest) => {
                 self.umount_rename();
                 self.mount_blocking_wait("Moving file(s)…");
@@ -334,6 +362,7 @@ impl FileTransferActivity {
             }
             TransferMsg::ToggleWatch => self.action_toggle_watch(),
             TransferMsg::ToggleWatchFor(index) => self.action_toggle_watch_for(index),
+            TransferMsg::Watch(patterns) => self.action_watch(patterns.as_str()),
             TransferMsg::TransferFile => {
                 match self.browser.tab() {
                     FileExplorerTab::Local => self.action_local_send(),
@@ -344,6 +373,14 @@ impl FileTransferActivity {
                 }
                 self.update_browser_file_list_swapped();
             }
+            TransferMsg::Undo => {
+                self.mount_blocking_wait("Reverting changes…");
+                self.action_undo();
+                self.umount_wait();
+                // Reload files on both hosts
+                self.update_local_filelist();
+                self.update_remote_filelist();
+            }
         }
         // Force redraw
         self.redraw = true;
@@ -353,18 +390,10 @@ impl FileTransferActivity {
     fn update_ui(&mut self, msg: UiMsg) -> Option<Msg> {
         match msg {
             UiMsg::CloseChmodPopup => self.umount_chmod(),
+            UiMsg::CloseCommandLine => self.umount_command_line(),
+            UiMsg::CloseContextMenu => self.umount_context_menu(),
             UiMsg::ChangeFileSorting(sorting) => {
-                match self.browser.tab() {
-                    FileExplorerTab::Local | FileExplorerTab::FindLocal => {
-                        self.local_mut().sort_by(sorting);
-                        self.refresh_local_status_bar();
-                    }
-                    FileExplorerTab::Remote | FileExplorerTab::FindRemote => {
-                        self.remote_mut().sort_by(sorting);
-                        self.refresh_remote_status_bar()
-                    }
-                }
-                self.update_browser_file_list();
+                self.change_file_sorting(|explorer| explorer.sort_by(sorting))
             }
             UiMsg::ChangeTransferWindow => {
                 let new_tab = match self.browser.tab() {
@@ -390,9 +419,16 @@ impl FileTransferActivity {
                 self.browser.change_tab(new_tab);
             }
             UiMsg::CloseCopyPopup => self.umount_copy(),
+            UiMsg::CloseCustomActionConfirmPopup => self.umount_radio_custom_action_confirm(),
+            UiMsg::CloseCustomActionsPopup => self.umount_custom_actions(),
             UiMsg::CloseDeletePopup => self.umount_radio_delete(),
+            UiMsg::ClosePermanentDeletePopup => {
+                self.umount_radio_permanent_delete();
+                self.untrashed.clear();
+            }
             UiMsg::CloseDisconnectPopup => self.umount_disconnect(),
             UiMsg::CloseErrorPopup => self.umount_error(),
+            UiMsg::CloseExecOutputPopup => self.umount_exec_output(),
             UiMsg::CloseExecPopup => self.umount_exec(),
             UiMsg::CloseFatalPopup => {
                 self.umount_fatal();
@@ -405,6 +441,8 @@ impl FileTransferActivity {
                 self.umount_find();
             }
             UiMsg::CloseFindPopup => self.umount_find_input(),
+            UiMsg::CloseFollowHighlightPopup => self.umount_follow_highlight(),
+            UiMsg::CloseFollowPopup => self.action_close_follow(),
             UiMsg::CloseGotoPopup => self.umount_goto(),
             UiMsg::CloseKeybindingsPopup => self.umount_help(),
             UiMsg::CloseMkdirPopup => self.umount_mkdir(),
@@ -414,7 +452,9 @@ impl FileTransferActivity {
             UiMsg::CloseRenamePopup => self.umount_rename(),
             UiMsg::CloseSaveAsPopup => self.umount_saveas(),
             UiMsg::CloseSymlinkPopup => self.umount_symlink(),
+            UiMsg::CloseSyncQueueList => self.umount_sync_queue_list(),
             UiMsg::CloseWatchedPathsList => self.umount_watched_paths_list(),
+            UiMsg::CloseWatcherIgnorePopup => self.umount_watcher_ignore(),
             UiMsg::CloseWatcherPopup => self.umount_radio_watcher(),
             UiMsg::Disconnect => {
                 self.disconnect();
@@ -439,6 +479,17 @@ impl FileTransferActivity {
                     assert!(self.app.active(&Id::ReplacePopup).is_ok());
                 }
             }
+            UiMsg::SelectContextMenuEntry(entry) => {
+                self.umount_context_menu();
+                return Some(context_menu::context_menu_entry_msg(entry));
+            }
+            UiMsg::SelectCustomAction(idx) => {
+                self.umount_custom_actions();
+                match self.custom_action_to_confirm(idx) {
+                    Some(name) => self.mount_radio_custom_action_confirm(&name, idx),
+                    None => return Some(Msg::Transfer(TransferMsg::RunCustomAction(idx))),
+                }
+            }
             UiMsg::ShowChmodPopup => {
                 let selected_file = match self.browser.tab() {
                     #[cfg(unix)]
@@ -465,27 +516,34 @@ impl FileTransferActivity {
                     );
                 }
             }
+            UiMsg::ShowCommandLine => self.mount_command_line(),
+            UiMsg::ShowContextMenu => self.action_show_context_menu(),
             UiMsg::ShowCopyPopup => self.mount_copy(),
+            UiMsg::ShowCustomActionsPopup => self.action_show_custom_actions(),
             UiMsg::ShowDeletePopup => self.mount_radio_delete(),
             UiMsg::ShowDisconnectPopup => self.mount_disconnect(),
             UiMsg::ShowExecPopup => self.mount_exec(),
             UiMsg::ShowFileInfoPopup if self.browser.tab() == FileExplorerTab::Local => {
                 if let SelectedFile::One(file) = self.get_local_selected_entries() {
-                    self.mount_file_info(&file);
+                    self.action_local_file_info(&file);
                 }
             }
             UiMsg::ShowFileInfoPopup if self.browser.tab() == FileExplorerTab::Remote => {
                 if let SelectedFile::One(file) = self.get_remote_selected_entries() {
-                    self.mount_file_info(&file);
+                    self.action_remote_file_info(&file);
                 }
             }
             UiMsg::ShowFileInfoPopup => {
                 if let SelectedFile::One(file) = self.get_found_selected_entries() {
-                    self.mount_file_info(&file);
+                    match self.browser.found_tab() {
+                        Some(FoundExplorerTab::Remote) => self.action_remote_file_info(&file),
+                        _ => self.action_local_file_info(&file),
+                    }
                 }
             }
             UiMsg::ShowFileSortingPopup => self.mount_file_sorting(),
             UiMsg::ShowFindPopup => self.mount_find_input(),
+            UiMsg::ShowFollowHighlightPopup => self.action_show_follow_highlight(),
             UiMsg::ShowGotoPopup => self.mount_goto(),
             UiMsg::ShowKeybindingsPopup => self.mount_help(),
             UiMsg::ShowMkdirPopup => self.mount_mkdir(),
@@ -508,6 +566,7 @@ impl FileTransferActivity {
                     );
                 }
             }
+            UiMsg::ShowSyncQueueList => self.action_show_sync_queue_list(),
             UiMsg::ShowWatchedPathsList => self.action_show_watched_paths_list(),
             UiMsg::ShowWatcherPopup => self.action_show_radio_watch(),
             UiMsg::ToggleHiddenFiles => match self.browser.tab() {
@@ -522,14 +581,69 @@ impl FileTransferActivity {
                     self.update_browser_file_list();
                 }
             },
+            UiMsg::SubmitCommandLine(input) => return self.action_submit_command_line(input),
+            UiMsg::SortByNextColumn => {
+                self.change_file_sorting(|explorer| explorer.sort_by_next_column())
+            }
+            UiMsg::ToggleCaseSensitiveSorting => {
+                self.change_file_sorting(|explorer| explorer.toggle_case_sensitive_sorting())
+            }
+            UiMsg::ToggleFollowPause => self.action_toggle_follow_pause(),
+            UiMsg::ToggleNaturalSorting => {
+                self.change_file_sorting(|explorer| explorer.toggle_natural_sorting())
+            }
+            UiMsg::ToggleReverseSorting => {
+                self.change_file_sorting(|explorer| explorer.toggle_reverse_sorting())
+            }
             UiMsg::ToggleSyncBrowsing => {
                 self.browser.toggle_sync_browsing();
                 self.refresh_remote_status_bar();
             }
+            UiMsg::ToggleSyncPause => self.action_toggle_sync_pause(),
+            UiMsg::ToggleTableView => {
+                self.browser.toggle_table_view();
+                self.update_local_filelist();
+                self.update_remote_filelist();
+                if self.found().is_some() {
+                    self.update_find_list();
+                }
+            }
             UiMsg::WindowResized => {
                 self.redraw = true;
             }
         }
         None
     }
+
+    /// Change file sorting of the explorer in the current tab calling `change`.
+    /// Sorting is persisted to configuration, unless the explorer is the find result
+    fn change_file_sorting<F>(&mut self, change: F)
+    where
+        F: FnOnce(&mut FileExplorer),
+    {
+        match self.browser.tab() {
+            FileExplorerTab::Local => {
+                change(self.local_mut());
+                self.refresh_local_status_bar();
+                self.update_local_filelist();
+                self.save_file_sorting();
+            }
+            FileExplorerTab::Remote => {
+                change(self.remote_mut());
+                self.refresh_remote_status_bar();
+                self.update_remote_filelist();
+                self.save_file_sorting();
+            }
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => {
+                if let Some(explorer) = self.found_mut() {
+                    change(explorer);
+                }
+                self.update_find_list();
+            }
+        }
+        // Refresh sorting popup
+        if self.app.mounted(&Id::SortingPopup) {
+            self.mount_file_sorting();
+        }
+    }
 }
@@ -6,14 +6,18 @@
 // Ext
 use remotefs::fs::{File, UnixPex};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::tui::layout::{Constraint, Direction, Layout};
+use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use tuirealm::tui::widgets::Clear;
-use tuirealm::{Sub, SubClause, SubEventClause};
+use tuirealm::{Frame, State, StateValue, Sub, SubClause, SubEventClause};
 use unicode_width::UnicodeWidthStr;
 
 use super::browser::{FileExplorerTab, FoundExplorerTab};
+use super::lib::context_menu::ContextMenuEntry;
+use super::lib::exec::ExecOutput;
 use super::{components, Context, FileTransferActivity, Id};
-use crate::explorer::FileSorting;
+use crate::config::custom_actions::CustomAction;
+use crate::config::keybindings::KeyAction;
+use crate::utils::media::{ImagePreview, MediaInfo};
 use crate::utils::ui::{Popup, Size};
 
 impl FileTransferActivity {
@@ -35,7 +39,10 @@ impl FileTransferActivity {
             .app
             .mount(
                 Id::FooterBar,
-                Box::new(components::FooterBar::new(key_color)),
+                Box::new(components::FooterBar::new(
+                    key_color,
+                    &self.config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
@@ -48,7 +55,9 @@ impl FileTransferActivity {
                     &[],
                     local_explorer_background,
                     local_explorer_foreground,
-                    local_explorer_highlighted
+                    local_explorer_highlighted,
+                    self.config().get_keybindings(),
+                    self.config().get_vim_mode()
                 )),
                 vec![]
             )
@@ -62,7 +71,9 @@ impl FileTransferActivity {
                     &[],
                     remote_explorer_background,
                     remote_explorer_foreground,
-                    remote_explorer_highlighted
+                    remote_explorer_highlighted,
+                    self.config().get_keybindings(),
+                    self.config().get_vim_mode()
                 )),
                 vec![]
             )
@@ -71,7 +82,12 @@ impl FileTransferActivity {
             .app
             .mount(
                 Id::Log,
-                Box::new(components::Log::new(vec![], log_panel, log_background)),
+                Box::new(components::Log::new(
+                    vec![],
+                    log_panel,
+                    log_background,
+                    self.config().get_keybindings()
+                )),
                 vec![]
             )
             .is_ok());
@@ -92,6 +108,7 @@ impl FileTransferActivity {
     /// View gui
     pub(super) fn view(&mut self) {
         self.redraw = false;
+        self.mouse_areas.clear();
         let mut context: Context = self.context.take().unwrap();
         let _ = context.terminal.raw_mut().draw(|f| {
             // Prepare chunks
@@ -133,25 +150,25 @@ impl FileTransferActivity {
                 .horizontal_margin(1)
                 .split(bottom_chunks[0]);
             // Draw footer
-            self.app.view(&Id::FooterBar, f, body[1]);
+            self.view_component(&Id::FooterBar, f, body[1]);
             // Draw explorers
             // @! Local explorer (Find or default)
             if matches!(self.browser.found_tab(), Some(FoundExplorerTab::Local)) {
-                self.app.view(&Id::ExplorerFind, f, tabs_chunks[0]);
+                self.view_component(&Id::ExplorerFind, f, tabs_chunks[0]);
             } else {
-                self.app.view(&Id::ExplorerLocal, f, tabs_chunks[0]);
+                self.view_component(&Id::ExplorerLocal, f, tabs_chunks[0]);
             }
             // @! Remote explorer (Find or default)
             if matches!(self.browser.found_tab(), Some(FoundExplorerTab::Remote)) {
-                self.app.view(&Id::ExplorerFind, f, tabs_chunks[1]);
+                self.view_component(&Id::ExplorerFind, f, tabs_chunks[1]);
             } else {
-                self.app.view(&Id::ExplorerRemote, f, tabs_chunks[1]);
+                self.view_component(&Id::ExplorerRemote, f, tabs_chunks[1]);
             }
             // Draw log box
-            self.app.view(&Id::Log, f, bottom_chunks[1]);
+            self.view_component(&Id::Log, f, bottom_chunks[1]);
             // Draw status bar
-            self.app.view(&Id::StatusBarLocal, f, status_bar_chunks[0]);
-            self.app.view(&Id::StatusBarRemote, f, status_bar_chunks[1]);
+            self.view_component(&Id::StatusBarLocal, f, status_bar_chunks[0]);
+            self.view_component(&Id::StatusBarRemote, f, status_bar_chunks[1]);
             // @! Draw popups
             if self.app.mounted(&Id::FatalPopup) {
                 let popup = Popup(
@@ -161,67 +178,82 @@ impl FileTransferActivity {
                 .draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::FatalPopup, f, popup);
+                self.view_component(&Id::FatalPopup, f, popup);
             } else if self.app.mounted(&Id::CopyPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::CopyPopup, f, popup);
+                self.view_component(&Id::CopyPopup, f, popup);
             } else if self.app.mounted(&Id::ChmodPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(12)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::ChmodPopup, f, popup);
+                self.view_component(&Id::ChmodPopup, f, popup);
             } else if self.app.mounted(&Id::FindPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::FindPopup, f, popup);
+                self.view_component(&Id::FindPopup, f, popup);
             } else if self.app.mounted(&Id::GotoPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::GotoPopup, f, popup);
+                self.view_component(&Id::GotoPopup, f, popup);
             } else if self.app.mounted(&Id::MkdirPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::MkdirPopup, f, popup);
+                self.view_component(&Id::MkdirPopup, f, popup);
             } else if self.app.mounted(&Id::NewfilePopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::NewfilePopup, f, popup);
+                self.view_component(&Id::NewfilePopup, f, popup);
             } else if self.app.mounted(&Id::OpenWithPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::OpenWithPopup, f, popup);
+                self.view_component(&Id::OpenWithPopup, f, popup);
             } else if self.app.mounted(&Id::RenamePopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::RenamePopup, f, popup);
+                self.view_component(&Id::RenamePopup, f, popup);
             } else if self.app.mounted(&Id::SaveAsPopup) {
                 let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::SaveAsPopup, f, popup);
+                self.view_component(&Id::SaveAsPopup, f, popup);
             } else if self.app.mounted(&Id::SymlinkPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::SymlinkPopup, f, popup);
+                self.view_component(&Id::SymlinkPopup, f, popup);
+            } else if self.app.mounted(&Id::CommandLinePopup) {
+                // Like in vim, the command line is at the bottom of the screen
+                let popup = Rect {
+                    y: body[0].bottom().saturating_sub(3),
+                    height: 3.min(body[0].height),
+                    ..body[0]
+                };
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::CommandLinePopup, f, popup);
             } else if self.app.mounted(&Id::ExecPopup) {
-                let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
+                let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::ExecPopup, f, popup);
+                self.view_component(&Id::ExecPopup, f, popup);
+            } else if self.app.mounted(&Id::ExecOutputPopup) {
+                let popup = Popup(Size::Percentage(80), Size::Percentage(70)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::ExecOutputPopup, f, popup);
             } else if self.app.mounted(&Id::FileInfoPopup) {
-                let popup = Popup(Size::Percentage(50), Size::Percentage(50)).draw_in(f.size());
+                let popup = Popup(Size::Percentage(50), Size::Percentage(70)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::FileInfoPopup, f, popup);
+                self.view_component(&Id::FileInfoPopup, f, popup);
             } else if self.app.mounted(&Id::ProgressBarPartial) {
                 let popup = Popup(Size::Percentage(50), Size::Percentage(20)).draw_in(f.size());
                 f.render_widget(Clear, popup);
@@ -236,13 +268,33 @@ impl FileTransferActivity {
                         .as_ref(),
                     )
                     .split(popup);
-                self.app.view(&Id::ProgressBarFull, f, popup_chunks[0]);
-                self.app.view(&Id::ProgressBarPartial, f, popup_chunks[1]);
+                self.view_component(&Id::ProgressBarFull, f, popup_chunks[0]);
+                self.view_component(&Id::ProgressBarPartial, f, popup_chunks[1]);
+            } else if self.app.mounted(&Id::ContextMenuPopup) {
+                let popup = Popup(Size::Percentage(30), Size::Percentage(40)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::ContextMenuPopup, f, popup);
+            } else if self.app.mounted(&Id::CustomActionsPopup) {
+                let popup = Popup(Size::Percentage(60), Size::Percentage(50)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::CustomActionsPopup, f, popup);
+            } else if self.app.mounted(&Id::CustomActionConfirmPopup) {
+                let popup = Popup(Size::Percentage(40), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::CustomActionConfirmPopup, f, popup);
             } else if self.app.mounted(&Id::DeletePopup) {
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::DeletePopup, f, popup);
+                self.view_component(&Id::DeletePopup, f, popup);
+            } else if self.app.mounted(&Id::PermanentDeletePopup) {
+                let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::PermanentDeletePopup, f, popup);
             } else if self.app.mounted(&Id::ReplacePopup) {
                 // NOTE: handle extended / normal modes
                 if self.is_radio_replace_extended() {
@@ -258,40 +310,59 @@ impl FileTransferActivity {
                             .as_ref(),
                         )
                         .split(popup);
-                    self.app
-                        .view(&Id::ReplacingFilesListPopup, f, popup_chunks[0]);
-                    self.app.view(&Id::ReplacePopup, f, popup_chunks[1]);
+                    self.view_component(&Id::ReplacingFilesListPopup, f, popup_chunks[0]);
+                    self.view_component(&Id::ReplacePopup, f, popup_chunks[1]);
                 } else {
                     let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                     f.render_widget(Clear, popup);
                     // make popup
-                    self.app.view(&Id::ReplacePopup, f, popup);
+                    self.view_component(&Id::ReplacePopup, f, popup);
                 }
             } else if self.app.mounted(&Id::DisconnectPopup) {
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::DisconnectPopup, f, popup);
+                self.view_component(&Id::DisconnectPopup, f, popup);
             } else if self.app.mounted(&Id::QuitPopup) {
                 let popup = Popup(Size::Percentage(30), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::QuitPopup, f, popup);
+                self.view_component(&Id::QuitPopup, f, popup);
             } else if self.app.mounted(&Id::WatchedPathsList) {
                 let popup = Popup(Size::Percentage(60), Size::Percentage(50)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::WatchedPathsList, f, popup);
+                self.view_component(&Id::WatchedPathsList, f, popup);
+            } else if self.app.mounted(&Id::FollowPopup) {
+                let popup = Popup(Size::Percentage(90), Size::Percentage(80)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::FollowPopup, f, popup);
+            } else if self.app.mounted(&Id::FollowHighlightPopup) {
+                let popup = Popup(Size::Percentage(60), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::FollowHighlightPopup, f, popup);
+            } else if self.app.mounted(&Id::SyncQueueList) {
+                let popup = Popup(Size::Percentage(60), Size::Percentage(50)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::SyncQueueList, f, popup);
+            } else if self.app.mounted(&Id::WatcherIgnorePopup) {
+                let popup = Popup(Size::Percentage(60), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                // make popup
+                self.view_component(&Id::WatcherIgnorePopup, f, popup);
             } else if self.app.mounted(&Id::WatcherPopup) {
                 let popup = Popup(Size::Percentage(60), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::WatcherPopup, f, popup);
+                self.view_component(&Id::WatcherPopup, f, popup);
             } else if self.app.mounted(&Id::SortingPopup) {
-                let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
+                let popup = Popup(Size::Percentage(30), Size::Unit(16)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::SortingPopup, f, popup);
+                self.view_component(&Id::SortingPopup, f, popup);
             } else if self.app.mounted(&Id::ErrorPopup) {
                 let popup = Popup(
                     Size::Percentage(50),
@@ -300,28 +371,34 @@ impl FileTransferActivity {
                 .draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::ErrorPopup, f, popup);
+                self.view_component(&Id::ErrorPopup, f, popup);
             } else if self.app.mounted(&Id::WaitPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::WaitPopup, f, popup);
+                self.view_component(&Id::WaitPopup, f, popup);
             } else if self.app.mounted(&Id::SyncBrowsingMkdirPopup) {
                 let popup = Popup(Size::Percentage(60), Size::Unit(3)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::SyncBrowsingMkdirPopup, f, popup);
+                self.view_component(&Id::SyncBrowsingMkdirPopup, f, popup);
             } else if self.app.mounted(&Id::KeybindingsPopup) {
                 let popup = Popup(Size::Percentage(50), Size::Percentage(80)).draw_in(f.size());
                 f.render_widget(Clear, popup);
                 // make popup
-                self.app.view(&Id::KeybindingsPopup, f, popup);
+                self.view_component(&Id::KeybindingsPopup, f, popup);
             }
         });
         // Re-give context
         self.context = Some(context);
     }
 
+    /// Render component and register its area for mouse events
+    fn view_component(&mut self, id: &Id, f: &mut Frame, area: Rect) {
+        self.app.view(id, f, area);
+        self.mouse_areas.insert(id, area);
+    }
+
     // -- partials
 
     /// Mount info box
@@ -459,6 +536,35 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::ChmodPopup);
     }
 
+    pub(super) fn mount_command_line(&mut self) {
+        let input_color = self.theme().misc_input_dialog;
+        let files: Vec<String> = match self.browser.tab() {
+            FileExplorerTab::Local => self.local().iter_files().map(|x| x.name()).collect(),
+            FileExplorerTab::Remote => self.remote().iter_files().map(|x| x.name()).collect(),
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => self
+                .found()
+                .map(|x| x.iter_files().map(|x| x.name()).collect())
+                .unwrap_or_default(),
+        };
+        assert!(self
+            .app
+            .remount(
+                Id::CommandLinePopup,
+                Box::new(components::CommandLinePopup::new(
+                    input_color,
+                    files,
+                    self.command_history.clone()
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::CommandLinePopup).is_ok());
+    }
+
+    pub(super) fn umount_command_line(&mut self) {
+        let _ = self.app.umount(&Id::CommandLinePopup);
+    }
+
     pub(super) fn mount_copy(&mut self) {
         let input_color = self.theme().misc_input_dialog;
         assert!(self
@@ -482,7 +588,10 @@ impl FileTransferActivity {
             .app
             .remount(
                 Id::ExecPopup,
-                Box::new(components::ExecPopup::new(input_color)),
+                Box::new(components::ExecPopup::new(
+                    input_color,
+                    self.exec_history.clone()
+                )),
                 vec![],
             )
             .is_ok());
@@ -493,6 +602,28 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::ExecPopup);
     }
 
+    pub(super) fn mount_exec_output(&mut self, outputs: &[ExecOutput]) {
+        let info_color = self.theme().misc_info_dialog;
+        let error_color = self.theme().misc_error_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::ExecOutputPopup,
+                Box::new(components::ExecOutputPopup::new(
+                    outputs,
+                    info_color,
+                    error_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::ExecOutputPopup).is_ok());
+    }
+
+    pub(super) fn umount_exec_output(&mut self) {
+        let _ = self.app.umount(&Id::ExecOutputPopup);
+    }
+
     pub(super) fn mount_find(&mut self, search: &str) {
         // Get color
         let (bg, fg, hg) = match self.browser.tab() {
@@ -517,7 +648,9 @@ impl FileTransferActivity {
                     &[],
                     bg,
                     fg,
-                    hg
+                    hg,
+                    self.config().get_keybindings(),
+                    self.config().get_vim_mode()
                 )),
                 vec![],
             )
@@ -688,18 +821,22 @@ impl FileTransferActivity {
 
     pub(super) fn mount_file_sorting(&mut self) {
         let sorting_color = self.theme().transfer_status_sorting;
-        let sorting: FileSorting = match self.browser.tab() {
-            FileExplorerTab::Local => self.local().get_file_sorting(),
-            FileExplorerTab::Remote => self.remote().get_file_sorting(),
+        // Keep cursor position if popup is already mounted
+        let line: Option<usize> = match self.app.state(&Id::SortingPopup) {
+            Ok(State::One(StateValue::Usize(line))) => Some(line),
+            _ => None,
+        };
+        let explorer = match self.browser.tab() {
+            FileExplorerTab::Local => self.local(),
+            FileExplorerTab::Remote => self.remote(),
             _ => return,
         };
+        let line: usize =
+            line.unwrap_or_else(|| components::SortingPopup::line_of(explorer.get_file_sorting()));
+        let popup = components::SortingPopup::new(explorer, line, sorting_color);
         assert!(self
             .app
-            .remount(
-                Id::SortingPopup,
-                Box::new(components::SortingPopup::new(sorting, sorting_color)),
-                vec![],
-            )
+            .remount(Id::SortingPopup, Box::new(popup), vec![])
             .is_ok());
         assert!(self.app.active(&Id::SortingPopup).is_ok());
     }
@@ -708,6 +845,71 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::SortingPopup);
     }
 
+    pub(super) fn mount_context_menu(
+        &mut self,
+        title: &str,
+        entries: &[ContextMenuEntry],
+        remote: bool,
+    ) {
+        let info_color = self.theme().misc_info_dialog;
+        let key_color = self.theme().misc_keys;
+        let keys = self.config().get_keybindings();
+        assert!(self
+            .app
+            .remount(
+                Id::ContextMenuPopup,
+                Box::new(components::ContextMenuPopup::new(
+                    title, entries, remote, &keys, info_color, key_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::ContextMenuPopup).is_ok());
+    }
+
+    pub(super) fn umount_context_menu(&mut self) {
+        let _ = self.app.umount(&Id::ContextMenuPopup);
+    }
+
+    pub(super) fn mount_custom_actions(&mut self, actions: &[(usize, &CustomAction)]) {
+        let info_color = self.theme().misc_info_dialog;
+        let key_color = self.theme().misc_keys;
+        assert!(self
+            .app
+            .remount(
+                Id::CustomActionsPopup,
+                Box::new(components::CustomActionsPopup::new(
+                    actions, info_color, key_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::CustomActionsPopup).is_ok());
+    }
+
+    pub(super) fn umount_custom_actions(&mut self) {
+        let _ = self.app.umount(&Id::CustomActionsPopup);
+    }
+
+    pub(super) fn mount_radio_custom_action_confirm(&mut self, name: &str, action: usize) {
+        let warn_color = self.theme().misc_warn_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::CustomActionConfirmPopup,
+                Box::new(components::CustomActionConfirmPopup::new(
+                    name, action, warn_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::CustomActionConfirmPopup).is_ok());
+    }
+
+    pub(super) fn umount_radio_custom_action_confirm(&mut self) {
+        let _ = self.app.umount(&Id::CustomActionConfirmPopup);
+    }
+
     pub(super) fn mount_radio_delete(&mut self) {
         let warn_color = self.theme().misc_warn_dialog;
         assert!(self
@@ -725,14 +927,34 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::DeletePopup);
     }
 
-    pub(super) fn mount_radio_watch(&mut self, watch: bool, local: &str, remote: &str) {
+    pub(super) fn mount_radio_permanent_delete(&mut self, files: usize) {
+        let warn_color = self.theme().misc_warn_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::PermanentDeletePopup,
+                Box::new(components::PermanentDeletePopup::new(files, warn_color)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PermanentDeletePopup).is_ok());
+    }
+
+    pub(super) fn umount_radio_permanent_delete(&mut self) {
+        let _ = self.app.umount(&Id::PermanentDeletePopup);
+    }
+
+    pub(super) fn mount_radio_watch(&mut self, watch: bool, source: &str, destination: &str) {
         let info_color = self.theme().misc_info_dialog;
         assert!(self
             .app
             .remount(
                 Id::WatcherPopup,
                 Box::new(components::WatcherPopup::new(
-                    watch, local, remote, info_color
+                    watch,
+                    source,
+                    destination,
+                    info_color
                 )),
                 vec![],
             )
@@ -744,7 +966,28 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::WatcherPopup);
     }
 
-    pub(super) fn mount_watched_paths_list(&mut self, paths: &[std::path::PathBuf]) {
+    pub(super) fn mount_watcher_ignore(&mut self, local: &str, patterns: &str) {
+        let input_color = self.theme().misc_input_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::WatcherIgnorePopup,
+                Box::new(components::WatcherIgnorePopup::new(
+                    local,
+                    patterns,
+                    input_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::WatcherIgnorePopup).is_ok());
+    }
+
+    pub(super) fn umount_watcher_ignore(&mut self) {
+        let _ = self.app.umount(&Id::WatcherIgnorePopup);
+    }
+
+    pub(super) fn mount_watched_paths_list(&mut self, paths: &[(String, Vec<String>)]) {
         let info_color = self.theme().misc_info_dialog;
         assert!(self
             .app
@@ -761,6 +1004,69 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::WatchedPathsList);
     }
 
+    pub(super) fn mount_follow_highlight(&mut self, file: &str, patterns: &str) {
+        let input_color = self.theme().misc_input_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::FollowHighlightPopup,
+                Box::new(components::FollowHighlightPopup::new(
+                    file,
+                    patterns,
+                    input_color
+                )),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::FollowHighlightPopup).is_ok());
+    }
+
+    pub(super) fn umount_follow_highlight(&mut self) {
+        let _ = self.app.umount(&Id::FollowHighlightPopup);
+    }
+
+    /// Mount the popup displaying the followed file, or refresh it if already mounted
+    pub(super) fn mount_follow(&mut self) {
+        let info_color = self.theme().misc_info_dialog;
+        let highlight_color = self.theme().misc_warn_dialog;
+        if let Some(follower) = self.follower.as_ref() {
+            assert!(self
+                .app
+                .remount(
+                    Id::FollowPopup,
+                    Box::new(components::FollowPopup::new(
+                        follower,
+                        info_color,
+                        highlight_color
+                    )),
+                    vec![],
+                )
+                .is_ok());
+            assert!(self.app.active(&Id::FollowPopup).is_ok());
+        }
+    }
+
+    pub(super) fn umount_follow(&mut self) {
+        let _ = self.app.umount(&Id::FollowPopup);
+    }
+
+    pub(super) fn mount_sync_queue_list(&mut self) {
+        let info_color = self.theme().misc_info_dialog;
+        assert!(self
+            .app
+            .remount(
+                Id::SyncQueueList,
+                Box::new(components::SyncQueueList::new(&self.sync_queue, info_color)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::SyncQueueList).is_ok());
+    }
+
+    pub(super) fn umount_sync_queue_list(&mut self) {
+        let _ = self.app.umount(&Id::SyncQueueList);
+    }
+
     pub(super) fn mount_radio_replace(&mut self, file_name: &str) {
         let warn_color = self.theme().misc_warn_dialog;
         assert!(self
@@ -805,12 +1111,17 @@ impl FileTransferActivity {
         let _ = self.app.umount(&Id::ReplacingFilesListPopup); // NOTE: replace anyway
     }
 
-    pub(super) fn mount_file_info(&mut self, file: &File) {
+    pub(super) fn mount_file_info(
+        &mut self,
+        file: &File,
+        media: Option<&MediaInfo>,
+        preview: Option<&ImagePreview>,
+    ) {
         assert!(self
             .app
             .remount(
                 Id::FileInfoPopup,
-                Box::new(components::FileInfoPopup::new(file)),
+                Box::new(components::FileInfoPopup::new(file, media, preview)),
                 vec![],
             )
             .is_ok());
@@ -842,12 +1153,20 @@ impl FileTransferActivity {
         let sorting_color = self.theme().transfer_status_sorting;
         let hidden_color = self.theme().transfer_status_hidden;
         let sync_color = self.theme().transfer_status_sync_browsing;
+        // Show the sync queue only when the watcher is in use
+        let sync_queue = match self.fswatcher.as_ref() {
+            Some(w) if !w.watched_paths().is_empty() || !self.sync_queue.is_empty() => {
+                Some(&self.sync_queue)
+            }
+            _ => None,
+        };
         assert!(self
             .app
             .remount(
                 Id::StatusBarRemote,
                 Box::new(components::StatusBarRemote::new(
                     &self.browser,
+                    sync_queue,
                     sorting_color,
                     hidden_color,
                     sync_color
@@ -898,7 +1217,11 @@ impl FileTransferActivity {
             .app
             .remount(
                 Id::KeybindingsPopup,
-                Box::new(components::KeybindingsPopup::new(key_color)),
+                Box::new(components::KeybindingsPopup::new(
+                    key_color,
+                    &self.config().get_keybindings(),
+                    self.config().get_vim_mode()
+                )),
                 vec![],
             )
             .is_ok());
@@ -943,49 +1266,33 @@ impl FileTransferActivity {
     // -- global listener
 
     fn mount_global_listener(&mut self) {
+        let keys = self.config().get_keybindings();
+        let mut subs = vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Esc,
+                    modifiers: KeyModifiers::NONE,
+                }),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(SubEventClause::WindowResize, SubClause::Always),
+        ];
+        for key in keys
+            .keys(KeyAction::Help)
+            .iter()
+            .chain(keys.keys(KeyAction::Quit))
+        {
+            subs.push(Sub::new(
+                SubEventClause::Keyboard(key.key_event()),
+                Self::no_popup_mounted_clause(),
+            ));
+        }
         assert!(self
             .app
             .mount(
                 Id::GlobalListener,
-                Box::<components::GlobalListener>::default(),
-                vec![
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Esc,
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('h'),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Function(1),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Function(10),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(
-                        SubEventClause::Keyboard(KeyEvent {
-                            code: Key::Char('q'),
-                            modifiers: KeyModifiers::NONE,
-                        }),
-                        Self::no_popup_mounted_clause(),
-                    ),
-                    Sub::new(SubEventClause::WindowResize, SubClause::Always)
-                ]
+                Box::new(components::GlobalListener::new(keys)),
+                subs
             )
             .is_ok());
     }
@@ -1096,9 +1403,59 @@ impl FileTransferActivity {
                                                                                                                 Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
                                                                                                                     Id::ChmodPopup,
                                                                                                                 )))),
-                                                                                                                Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
-                                                                                                                    Id::WaitPopup,
-                                                                                                                )))),
+                                                                                                                Box::new(SubClause::And(
+                                                                                                                    Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+                                                                                                                        Id::CommandLinePopup,
+                                                                                                                    )))),
+                                                                                                                    Box::new(SubClause::And(
+                                                                                                                        Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+                                                                                                                            Id::WaitPopup,
+                                                                                                                        )))),
+                                                                                                                        Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::WatcherIgnorePopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::SyncQueueList,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::FollowHighlightPopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::FollowPopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::ExecOutputPopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::CustomActionsPopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::CustomActionConfirmPopup,
+)))),
+Box::new(SubClause::And(
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::ContextMenuPopup,
+)))),
+Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+Id::PermanentDeletePopup,
+)))),
+)),
+)),
+)),
+)),
+)),
+)),
+)),
+)),
+                                                                                                                    )),
+                                                                                                                )),
                                                                                                             )),
                                                                                                         )),
                                                                                                     )),
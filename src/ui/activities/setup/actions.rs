@@ -9,8 +9,9 @@ use std::env;
 use tuirealm::tui::style::Color;
 use tuirealm::{State, StateValue};
 
-use super::{Id, IdSsh, IdTheme, SetupActivity, ViewLayout};
+use super::{Id, IdKeyBindings, IdSsh, IdTheme, SetupActivity, ViewLayout};
 // Ext
+use crate::config::keybindings::{parse_key_bindings, KeyAction};
 use crate::config::themes::Theme;
 
 impl SetupActivity {
@@ -91,6 +92,52 @@ impl SetupActivity {
         }
     }
 
+    /// Reset key bindings list after reverting configuration changes
+    pub(super) fn action_reset_keybindings(&mut self) -> Result<(), String> {
+        self.reset_config_changes()?;
+        self.reload_keybindings();
+        Ok(())
+    }
+
+    /// Get the action selected in the key bindings list
+    pub(super) fn selected_key_action(&self) -> Option<KeyAction> {
+        match self
+            .app
+            .state(&Id::KeyBindings(IdKeyBindings::KeyBindingsList))
+        {
+            Ok(State::One(StateValue::Usize(idx))) => KeyAction::ALL.get(idx).copied(),
+            _ => None,
+        }
+    }
+
+    /// Bind the keys typed in the key binding input to the selected action
+    pub(super) fn action_save_keybinding(&mut self) -> Result<(), String> {
+        let action = match self.selected_key_action() {
+            Some(action) => action,
+            None => return Ok(()),
+        };
+        let keys = match self
+            .app
+            .state(&Id::KeyBindings(IdKeyBindings::KeyBindingInput))
+        {
+            Ok(State::One(StateValue::String(keys))) => keys,
+            _ => String::new(),
+        };
+        let keys = parse_key_bindings(&keys).map_err(|e| format!("Invalid key binding: {e}"))?;
+        self.config_mut().set_keybinding(action, &keys);
+        self.set_config_changed(true);
+        Ok(())
+    }
+
+    /// Restore the default keys of the selected action
+    pub(super) fn action_reset_keybinding(&mut self) {
+        if let Some(action) = self.selected_key_action() {
+            self.config_mut()
+                .set_keybinding(action, &action.default_bindings());
+            self.set_config_changed(true);
+        }
+    }
+
     /// delete of a ssh key
     pub(super) fn action_delete_ssh_key(&mut self) {
         // Get key
@@ -177,6 +224,10 @@ impl SetupActivity {
             if let Err(err) = ctx.terminal().enter_alternate_screen() {
                 error!("Could not enter alternate screen: {}", err);
             }
+            // Entering alternate screen enables mouse capture
+            if let Err(err) = ctx.set_mouse_capture(false) {
+                error!("Failed to disable mouse capture: {}", err);
+            }
             // Re-enable raw mode
             if let Err(err) = ctx.terminal().enable_raw_mode() {
                 error!("Failed to enter raw mode: {}", err);
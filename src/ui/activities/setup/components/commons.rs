@@ -88,12 +88,18 @@ impl Header {
                         .color(Color::Yellow)
                         .sides(BorderSides::BOTTOM),
                 )
-                .choices(&["Configuration parameters", "SSH Keys", "Theme"])
+                .choices(&[
+                    "Configuration parameters",
+                    "SSH Keys",
+                    "Theme",
+                    "Key bindings",
+                ])
                 .foreground(Color::Yellow)
                 .value(match layout {
                     ViewLayout::SetupForm => 0,
                     ViewLayout::SshKeys => 1,
                     ViewLayout::Theme => 2,
+                    ViewLayout::KeyBindings => 3,
                 }),
         }
     }
@@ -136,7 +142,9 @@ impl Default for Keybindings {
                         .add_col(TextSpan::from("         Select / Dismiss popup"))
                         .add_row()
                         .add_col(TextSpan::new("<DEL|E>").bold().fg(Color::Cyan))
-                        .add_col(TextSpan::from("         Delete SSH key"))
+                        .add_col(TextSpan::from(
+                            "         Delete SSH key / Reset key binding",
+                        ))
                         .add_row()
                         .add_col(TextSpan::new("<CTRL+N>").bold().fg(Color::Cyan))
                         .add_col(TextSpan::from("        New SSH key"))
@@ -235,6 +235,145 @@ impl Component<Msg, NoUserEvent> for PromptOnFileReplace {
     }
 }
 
+#[derive(MockComponent)]
+pub struct MouseSupport {
+    component: Radio,
+}
+
+impl MouseSupport {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightBlue)
+                        .modifiers(BorderType::Rounded),
+                )
+                .choices(&["Yes", "No"])
+                .foreground(Color::LightBlue)
+                .rewind(true)
+                .title("Enable mouse support?", Alignment::Left)
+                .value(usize::from(!enabled)),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for MouseSupport {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        handle_radio_ev(
+            self,
+            ev,
+            Msg::Config(ConfigMsg::MouseSupportBlurDown),
+            Msg::Config(ConfigMsg::MouseSupportBlurUp),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct VimMode {
+    component: Radio,
+}
+
+impl VimMode {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightMagenta)
+                        .modifiers(BorderType::Rounded),
+                )
+                .choices(&["Yes", "No"])
+                .foreground(Color::LightMagenta)
+                .rewind(true)
+                .title("Enable vim navigation?", Alignment::Left)
+                .value(usize::from(!enabled)),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for VimMode {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        handle_radio_ev(
+            self,
+            ev,
+            Msg::Config(ConfigMsg::VimModeBlurDown),
+            Msg::Config(ConfigMsg::VimModeBlurUp),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct LocalTrash {
+    component: Radio,
+}
+
+impl LocalTrash {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightYellow)
+                        .modifiers(BorderType::Rounded),
+                )
+                .choices(&["Yes", "No"])
+                .foreground(Color::LightYellow)
+                .rewind(true)
+                .title("Move deleted local files to trash?", Alignment::Left)
+                .value(usize::from(!enabled)),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for LocalTrash {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        handle_radio_ev(
+            self,
+            ev,
+            Msg::Config(ConfigMsg::LocalTrashBlurDown),
+            Msg::Config(ConfigMsg::LocalTrashBlurUp),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct RemoteTrash {
+    component: Radio,
+}
+
+impl RemoteTrash {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightCyan)
+                        .modifiers(BorderType::Rounded),
+                )
+                .choices(&["Yes", "No"])
+                .foreground(Color::LightCyan)
+                .rewind(true)
+                .title(
+                    "Move deleted remote files to ~/.termscp-trash?",
+                    Alignment::Left,
+                )
+                .value(usize::from(!enabled)),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for RemoteTrash {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        handle_radio_ev(
+            self,
+            ev,
+            Msg::Config(ConfigMsg::RemoteTrashBlurDown),
+            Msg::Config(ConfigMsg::RemoteTrashBlurUp),
+        )
+    }
+}
+
 #[derive(MockComponent)]
 pub struct LocalFileFmt {
     component: Input,
@@ -351,6 +490,48 @@ impl Component<Msg, NoUserEvent> for RemoteFileFmt {
     }
 }
 
+#[derive(MockComponent)]
+pub struct RemoteWatchInterval {
+    component: Input,
+}
+
+impl RemoteWatchInterval {
+    pub fn new(value: &str) -> Self {
+        // -- validators
+        fn validate(secs: &str) -> bool {
+            secs.parse::<u64>().map(|x| x > 0).unwrap_or(false)
+        }
+        fn char_valid(_input: &str, incoming: char) -> bool {
+            incoming.is_ascii_digit()
+        }
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightMagenta)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(Color::LightMagenta)
+                .invalid_style(Style::default().fg(Color::Red))
+                .input_type(InputType::Custom(validate, char_valid))
+                .placeholder("10", Style::default().fg(Color::Rgb(128, 128, 128)))
+                .title("Remote watcher: poll interval (seconds)", Alignment::Left)
+                .value(value),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for RemoteWatchInterval {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        handle_input_ev(
+            self,
+            ev,
+            Msg::Config(ConfigMsg::RemoteWatchIntervalBlurDown),
+            Msg::Config(ConfigMsg::RemoteWatchIntervalBlurUp),
+        )
+    }
+}
+
 #[derive(MockComponent)]
 pub struct SshConfig {
     component: Input,
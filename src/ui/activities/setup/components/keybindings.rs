@@ -0,0 +1,196 @@
+//! ## KeyBindings
+//!
+//! key bindings tab components
+
+use tui_realm_stdlib::{Input, List};
+use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, InputType, Style, TextSpan};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
+
+use super::{KeyBindingsMsg, Msg};
+use crate::config::keybindings::{KeyAction, KeyBinding, KeyBindings, KeyScope};
+
+#[derive(MockComponent)]
+pub struct KeyBindingsList {
+    component: List,
+}
+
+impl KeyBindingsList {
+    pub fn new(keys: &KeyBindings) -> Self {
+        let rows = KeyAction::ALL
+            .iter()
+            .map(|action| {
+                let scope = match action.scope() {
+                    KeyScope::Auth => "Auth",
+                    KeyScope::Explorer => "Explorer",
+                };
+                vec![
+                    TextSpan::from(format!("{:<26}", action.name())),
+                    TextSpan::from(format!("{:<20}", fmt_keys(keys.keys(*action))))
+                        .bold()
+                        .fg(Color::Cyan),
+                    TextSpan::from(format!("{scope}: {}", action.description())),
+                ]
+            })
+            .collect();
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightGreen)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(Color::LightGreen)
+                .highlighted_color(Color::LightGreen)
+                .rewind(true)
+                .rows(rows)
+                .step(8)
+                .scroll(true)
+                .title("Key bindings", Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KeyBindingsList {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.state() {
+                State::One(StateValue::Usize(choice)) => {
+                    Some(Msg::KeyBindings(KeyBindingsMsg::EditKeyBinding(choice)))
+                }
+                _ => Some(Msg::None),
+            },
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete | Key::Char('e'),
+                ..
+            }) => Some(Msg::KeyBindings(KeyBindingsMsg::ResetKeyBinding)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KeyBindingInput {
+    component: Input,
+}
+
+impl KeyBindingInput {
+    pub fn new(action: KeyAction, keys: &[KeyBinding]) -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightGreen)
+                        .modifiers(BorderType::Rounded),
+                )
+                .foreground(Color::LightGreen)
+                .input_type(InputType::Text)
+                .placeholder("ctrl+d, f8", Style::default().fg(Color::Rgb(128, 128, 128)))
+                .title(
+                    format!("Keys for \"{}\" (comma separated)", action.name()),
+                    Alignment::Center,
+                )
+                .value(fmt_keys(keys)),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KeyBindingInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => {
+                self.perform(Cmd::Cancel);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) => {
+                self.perform(Cmd::Type(ch));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::KeyBindings(KeyBindingsMsg::SaveKeyBinding)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::KeyBindings(KeyBindingsMsg::CloseKeyBindingInput))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Format keys as they're written in the configuration
+fn fmt_keys(keys: &[KeyBinding]) -> String {
+    keys.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
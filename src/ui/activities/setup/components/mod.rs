@@ -2,18 +2,21 @@
 //!
 //! setup activity components
 
-use super::{CommonMsg, ConfigMsg, Msg, SshMsg, ThemeMsg, ViewLayout};
+use super::{CommonMsg, ConfigMsg, KeyBindingsMsg, Msg, SshMsg, ThemeMsg, ViewLayout};
 
 mod commons;
 mod config;
+mod keybindings;
 mod ssh;
 mod theme;
 
 pub(super) use commons::{ErrorPopup, Footer, Header, Keybindings, QuitPopup, SavePopup};
 pub(super) use config::{
-    CheckUpdates, DefaultProtocol, GroupDirs, HiddenFiles, LocalFileFmt, NotificationsEnabled,
-    NotificationsThreshold, PromptOnFileReplace, RemoteFileFmt, SshConfig, TextEditor,
+    CheckUpdates, DefaultProtocol, GroupDirs, HiddenFiles, LocalFileFmt, LocalTrash, MouseSupport,
+    NotificationsEnabled, NotificationsThreshold, PromptOnFileReplace, RemoteFileFmt, RemoteTrash,
+    RemoteWatchInterval, SshConfig, TextEditor, VimMode,
 };
+pub(super) use keybindings::{KeyBindingInput, KeyBindingsList};
 pub(super) use ssh::{DelSshKeyPopup, SshHost, SshKeys, SshUsername};
 pub(super) use theme::*;
 use tui_realm_stdlib::Phantom;
@@ -98,6 +98,10 @@ impl SetupActivity {
                 if let Err(err) = ctx.terminal().enter_alternate_screen() {
                     error!("Could not enter alternate screen: {}", err);
                 }
+                // Entering alternate screen enables mouse capture
+                if let Err(err) = ctx.set_mouse_capture(false) {
+                    error!("Failed to disable mouse capture: {}", err);
+                }
                 // Re-enable raw mode
                 if let Err(err) = ctx.terminal().enable_raw_mode() {
                     error!("Failed to enter raw mode: {}", err);
@@ -38,6 +38,7 @@ const RADIO_PROTOCOL_WEBDAV: usize = 6;
 enum Id {
     Common(IdCommon),
     Config(IdConfig),
+    KeyBindings(IdKeyBindings),
     Ssh(IdSsh),
     Theme(IdTheme),
 }
@@ -60,12 +61,23 @@ enum IdConfig {
     GroupDirs,
     HiddenFiles,
     LocalFileFmt,
+    LocalTrash,
+    MouseSupport,
     NotificationsEnabled,
     NotificationsThreshold,
     PromptOnFileReplace,
     RemoteFileFmt,
+    RemoteTrash,
+    RemoteWatchInterval,
     SshConfig,
     TextEditor,
+    VimMode,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+enum IdKeyBindings {
+    KeyBindingInput,
+    KeyBindingsList,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
@@ -115,6 +127,7 @@ pub enum IdTheme {
 pub enum Msg {
     Common(CommonMsg),
     Config(ConfigMsg),
+    KeyBindings(KeyBindingsMsg),
     Ssh(SshMsg),
     Theme(ThemeMsg),
     None,
@@ -150,6 +163,10 @@ pub enum ConfigMsg {
     HiddenFilesBlurUp,
     LocalFileFmtBlurDown,
     LocalFileFmtBlurUp,
+    LocalTrashBlurDown,
+    LocalTrashBlurUp,
+    MouseSupportBlurDown,
+    MouseSupportBlurUp,
     NotificationsEnabledBlurDown,
     NotificationsEnabledBlurUp,
     NotificationsThresholdBlurDown,
@@ -158,10 +175,24 @@ pub enum ConfigMsg {
     PromptOnFileReplaceBlurUp,
     RemoteFileFmtBlurDown,
     RemoteFileFmtBlurUp,
+    RemoteTrashBlurDown,
+    RemoteTrashBlurUp,
+    RemoteWatchIntervalBlurDown,
+    RemoteWatchIntervalBlurUp,
     SshConfigBlurDown,
     SshConfigBlurUp,
     TextEditorBlurDown,
     TextEditorBlurUp,
+    VimModeBlurDown,
+    VimModeBlurUp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyBindingsMsg {
+    CloseKeyBindingInput,
+    EditKeyBinding(usize),
+    ResetKeyBinding,
+    SaveKeyBinding,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -245,6 +276,7 @@ pub enum ViewLayout {
     SetupForm,
     SshKeys,
     Theme,
+    KeyBindings,
 }
 
 /// Setup activity states holder
@@ -334,6 +366,10 @@ impl Activity for SetupActivity {
         if let Err(err) = self.context_mut().terminal().enable_raw_mode() {
             error!("Failed to enter raw mode: {}", err);
         }
+        // Mouse is not supported here; let the terminal handle it
+        if let Err(err) = self.context_mut().set_mouse_capture(false) {
+            error!("Failed to disable mouse capture: {}", err);
+        }
         // Init view
         self.init(ViewLayout::SetupForm);
         // Verify error state from context
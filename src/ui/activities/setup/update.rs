@@ -8,9 +8,10 @@
 use tuirealm::Update;
 
 use super::{
-    CommonMsg, ConfigMsg, Id, IdConfig, IdSsh, IdTheme, Msg, SetupActivity, SshMsg, ThemeMsg,
-    ViewLayout,
+    CommonMsg, ConfigMsg, Id, IdConfig, IdSsh, IdTheme, KeyBindingsMsg, Msg, SetupActivity, SshMsg,
+    ThemeMsg, ViewLayout,
 };
+use crate::config::keybindings::KeyAction;
 
 impl Update<Msg> for SetupActivity {
     /// Update auth activity model based on msg
@@ -19,6 +20,7 @@ impl Update<Msg> for SetupActivity {
         match msg.unwrap_or(Msg::None) {
             Msg::Common(msg) => self.common_update(msg),
             Msg::Config(msg) => self.config_update(msg),
+            Msg::KeyBindings(msg) => self.keybindings_update(msg),
             Msg::Ssh(msg) => self.ssh_update(msg),
             Msg::Theme(msg) => self.theme_update(msg),
             Msg::None => None,
@@ -33,7 +35,8 @@ impl SetupActivity {
                 let new_layout = match self.layout {
                     ViewLayout::SetupForm => ViewLayout::SshKeys,
                     ViewLayout::SshKeys => ViewLayout::Theme,
-                    ViewLayout::Theme => ViewLayout::SetupForm,
+                    ViewLayout::Theme => ViewLayout::KeyBindings,
+                    ViewLayout::KeyBindings => ViewLayout::SetupForm,
                 };
                 if let Err(err) = self.action_change_tab(new_layout) {
                     self.mount_error(err.as_str());
@@ -61,6 +64,12 @@ impl SetupActivity {
                         self.mount_error(err);
                     }
                 }
+                ViewLayout::KeyBindings => {
+                    if let Err(err) = self.action_reset_keybindings() {
+                        error!("Failed to reset config: {}", err);
+                        self.mount_error(err);
+                    }
+                }
                 ViewLayout::SshKeys | ViewLayout::SetupForm => {
                     if let Err(err) = self.action_reset_config() {
                         error!("Failed to reset config: {}", err);
@@ -118,7 +127,10 @@ impl SetupActivity {
                 assert!(self.app.active(&Id::Config(IdConfig::TextEditor)).is_ok());
             }
             ConfigMsg::GroupDirsBlurDown => {
-                assert!(self.app.active(&Id::Config(IdConfig::LocalFileFmt)).is_ok());
+                assert!(self
+                    .app
+                    .active(&Id::Config(IdConfig::RemoteWatchInterval))
+                    .is_ok());
             }
             ConfigMsg::GroupDirsBlurUp => {
                 assert!(self
@@ -142,7 +154,16 @@ impl SetupActivity {
                     .is_ok());
             }
             ConfigMsg::LocalFileFmtBlurUp => {
-                assert!(self.app.active(&Id::Config(IdConfig::GroupDirs)).is_ok());
+                assert!(self.app.active(&Id::Config(IdConfig::LocalTrash)).is_ok());
+            }
+            ConfigMsg::LocalTrashBlurDown => {
+                assert!(self.app.active(&Id::Config(IdConfig::LocalFileFmt)).is_ok());
+            }
+            ConfigMsg::LocalTrashBlurUp => {
+                assert!(self
+                    .app
+                    .active(&Id::Config(IdConfig::RemoteWatchInterval))
+                    .is_ok());
             }
             ConfigMsg::NotificationsEnabledBlurDown => {
                 assert!(self
@@ -180,6 +201,18 @@ impl SetupActivity {
             ConfigMsg::RemoteFileFmtBlurUp => {
                 assert!(self.app.active(&Id::Config(IdConfig::LocalFileFmt)).is_ok());
             }
+            ConfigMsg::RemoteTrashBlurDown => {
+                assert!(self.app.active(&Id::Config(IdConfig::TextEditor)).is_ok());
+            }
+            ConfigMsg::RemoteTrashBlurUp => {
+                assert!(self.app.active(&Id::Config(IdConfig::VimMode)).is_ok());
+            }
+            ConfigMsg::RemoteWatchIntervalBlurDown => {
+                assert!(self.app.active(&Id::Config(IdConfig::LocalTrash)).is_ok());
+            }
+            ConfigMsg::RemoteWatchIntervalBlurUp => {
+                assert!(self.app.active(&Id::Config(IdConfig::GroupDirs)).is_ok());
+            }
             ConfigMsg::TextEditorBlurDown => {
                 assert!(self
                     .app
@@ -187,10 +220,22 @@ impl SetupActivity {
                     .is_ok());
             }
             ConfigMsg::TextEditorBlurUp => {
-                assert!(self.app.active(&Id::Config(IdConfig::SshConfig)).is_ok());
+                assert!(self.app.active(&Id::Config(IdConfig::RemoteTrash)).is_ok());
             }
             ConfigMsg::SshConfigBlurDown => {
-                assert!(self.app.active(&Id::Config(IdConfig::TextEditor)).is_ok());
+                assert!(self.app.active(&Id::Config(IdConfig::MouseSupport)).is_ok());
+            }
+            ConfigMsg::MouseSupportBlurDown => {
+                assert!(self.app.active(&Id::Config(IdConfig::VimMode)).is_ok());
+            }
+            ConfigMsg::MouseSupportBlurUp => {
+                assert!(self.app.active(&Id::Config(IdConfig::SshConfig)).is_ok());
+            }
+            ConfigMsg::VimModeBlurDown => {
+                assert!(self.app.active(&Id::Config(IdConfig::RemoteTrash)).is_ok());
+            }
+            ConfigMsg::VimModeBlurUp => {
+                assert!(self.app.active(&Id::Config(IdConfig::MouseSupport)).is_ok());
             }
             ConfigMsg::SshConfigBlurUp => {
                 assert!(self
@@ -205,6 +250,31 @@ impl SetupActivity {
         None
     }
 
+    fn keybindings_update(&mut self, msg: KeyBindingsMsg) -> Option<Msg> {
+        match msg {
+            KeyBindingsMsg::CloseKeyBindingInput => {
+                self.umount_keybinding_input();
+            }
+            KeyBindingsMsg::EditKeyBinding(i) => {
+                if let Some(action) = KeyAction::ALL.get(i) {
+                    self.mount_keybinding_input(*action);
+                }
+            }
+            KeyBindingsMsg::ResetKeyBinding => {
+                self.action_reset_keybinding();
+                self.reload_keybindings();
+            }
+            KeyBindingsMsg::SaveKeyBinding => match self.action_save_keybinding() {
+                Ok(_) => {
+                    self.umount_keybinding_input();
+                    self.reload_keybindings();
+                }
+                Err(err) => self.mount_error(err),
+            },
+        }
+        None
+    }
+
     fn ssh_update(&mut self, msg: SshMsg) -> Option<Msg> {
         match msg {
             SshMsg::CloseDelSshKeyPopup => {
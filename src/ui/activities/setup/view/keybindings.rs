@@ -0,0 +1,125 @@
+//! ## SetupActivity
+//!
+//! `setup_activity` is the module which implements the Setup activity, which is the activity to
+//! work on termscp configuration
+
+// Locals
+// Ext
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use tuirealm::tui::layout::{Constraint, Direction, Layout};
+use tuirealm::tui::widgets::Clear;
+
+use super::{components, Context, Id, IdCommon, IdKeyBindings, SetupActivity, ViewLayout};
+use crate::config::keybindings::KeyAction;
+use crate::utils::ui::{Popup, Size};
+
+impl SetupActivity {
+    // -- view
+
+    /// Initialize key bindings view
+    pub(super) fn init_keybindings(&mut self) {
+        // Init view (and mount commons)
+        self.new_app(ViewLayout::KeyBindings);
+        // Load key bindings
+        self.reload_keybindings();
+    }
+
+    pub(crate) fn view_keybindings(&mut self) {
+        let mut ctx: Context = self.context.take().unwrap();
+        let _ = ctx.terminal().raw_mut().draw(|f| {
+            // Prepare main chunks
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(3), // Current tab
+                        Constraint::Min(5),    // Main body
+                        Constraint::Length(1), // Help footer
+                    ]
+                    .as_ref(),
+                )
+                .split(f.size());
+            // Render common widget
+            self.app.view(&Id::Common(IdCommon::Header), f, chunks[0]);
+            self.app.view(&Id::Common(IdCommon::Footer), f, chunks[2]);
+            self.app.view(
+                &Id::KeyBindings(IdKeyBindings::KeyBindingsList),
+                f,
+                chunks[1],
+            );
+            // Popups
+            self.view_popups(f);
+            if self
+                .app
+                .mounted(&Id::KeyBindings(IdKeyBindings::KeyBindingInput))
+            {
+                let popup = Popup(Size::Percentage(50), Size::Unit(3)).draw_in(f.size());
+                f.render_widget(Clear, popup);
+                self.app
+                    .view(&Id::KeyBindings(IdKeyBindings::KeyBindingInput), f, popup);
+            }
+        });
+        // Put context back to context
+        self.context = Some(ctx);
+    }
+
+    // -- mount
+
+    /// Mount key binding input for `action`
+    pub(crate) fn mount_keybinding_input(&mut self, action: KeyAction) {
+        let keys = self.config().get_keybindings();
+        assert!(self
+            .app
+            .remount(
+                Id::KeyBindings(IdKeyBindings::KeyBindingInput),
+                Box::new(components::KeyBindingInput::new(action, keys.keys(action))),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .active(&Id::KeyBindings(IdKeyBindings::KeyBindingInput))
+            .is_ok());
+    }
+
+    /// Umount key binding input
+    pub(crate) fn umount_keybinding_input(&mut self) {
+        let _ = self
+            .app
+            .umount(&Id::KeyBindings(IdKeyBindings::KeyBindingInput));
+        assert!(self
+            .app
+            .active(&Id::KeyBindings(IdKeyBindings::KeyBindingsList))
+            .is_ok());
+    }
+
+    /// Reload key bindings list, keeping the selected entry
+    pub(crate) fn reload_keybindings(&mut self) {
+        let selected = self.selected_key_action();
+        assert!(self
+            .app
+            .remount(
+                Id::KeyBindings(IdKeyBindings::KeyBindingsList),
+                Box::new(components::KeyBindingsList::new(
+                    &self.config().get_keybindings()
+                )),
+                vec![]
+            )
+            .is_ok());
+        if let Some(index) = selected.and_then(|x| KeyAction::ALL.iter().position(|a| *a == x)) {
+            assert!(self
+                .app
+                .attr(
+                    &Id::KeyBindings(IdKeyBindings::KeyBindingsList),
+                    Attribute::Value,
+                    AttrValue::Payload(PropPayload::One(PropValue::Usize(index)))
+                )
+                .is_ok());
+        }
+        assert!(self
+            .app
+            .active(&Id::KeyBindings(IdKeyBindings::KeyBindingsList))
+            .is_ok());
+    }
+}
@@ -3,6 +3,7 @@
 //! `setup_activity` is the module which implements the Setup activity, which is the activity to
 //! work on termscp configuration
 
+pub mod keybindings;
 pub mod setup;
 pub mod ssh_keys;
 pub mod theme;
@@ -23,6 +24,7 @@ impl SetupActivity {
             ViewLayout::SetupForm => self.init_setup(),
             ViewLayout::SshKeys => self.init_ssh_keys(),
             ViewLayout::Theme => self.init_theme(),
+            ViewLayout::KeyBindings => self.init_keybindings(),
         }
     }
 
@@ -33,6 +35,7 @@ impl SetupActivity {
             ViewLayout::SetupForm => self.view_setup(),
             ViewLayout::SshKeys => self.view_ssh_keys(),
             ViewLayout::Theme => self.view_theme(),
+            ViewLayout::KeyBindings => self.view_keybindings(),
         }
     }
 
@@ -255,9 +258,14 @@ impl SetupActivity {
                             Box::new(SubClause::Not(Box::new(SubClause::IsMounted(Id::Ssh(
                                 IdSsh::DelSshKeyPopup,
                             ))))),
-                            Box::new(SubClause::Not(Box::new(SubClause::IsMounted(Id::Ssh(
-                                IdSsh::SshHost,
-                            ))))),
+                            Box::new(SubClause::And(
+                                Box::new(SubClause::Not(Box::new(SubClause::IsMounted(Id::Ssh(
+                                    IdSsh::SshHost,
+                                ))))),
+                                Box::new(SubClause::Not(Box::new(SubClause::IsMounted(
+                                    Id::KeyBindings(IdKeyBindings::KeyBindingInput),
+                                )))),
+                            )),
                         )),
                     )),
                 )),
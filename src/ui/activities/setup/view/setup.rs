@@ -69,6 +69,8 @@ impl SetupActivity {
                         Constraint::Length(3), // Updates tab
                         Constraint::Length(3), // Prompt file replace
                         Constraint::Length(3), // Group dirs
+                        Constraint::Length(3), // Remote watch interval
+                        Constraint::Length(3), // Local trash
                         Constraint::Length(1), // Prevent overflow
                     ]
                     .as_ref(),
@@ -95,6 +97,13 @@ impl SetupActivity {
             );
             self.app
                 .view(&Id::Config(IdConfig::GroupDirs), f, ui_cfg_chunks_col1[5]);
+            self.app.view(
+                &Id::Config(IdConfig::RemoteWatchInterval),
+                f,
+                ui_cfg_chunks_col1[6],
+            );
+            self.app
+                .view(&Id::Config(IdConfig::LocalTrash), f, ui_cfg_chunks_col1[7]);
             // Column 2
             let ui_cfg_chunks_col2 = Layout::default()
                 .direction(Direction::Vertical)
@@ -105,6 +114,9 @@ impl SetupActivity {
                         Constraint::Length(3), // Notifications enabled
                         Constraint::Length(3), // Notifications threshold
                         Constraint::Length(3), // Ssh config
+                        Constraint::Length(3), // Mouse support
+                        Constraint::Length(3), // Vim mode
+                        Constraint::Length(3), // Remote trash
                         Constraint::Length(1), // Prevent overflow
                     ]
                     .as_ref(),
@@ -132,6 +144,15 @@ impl SetupActivity {
             );
             self.app
                 .view(&Id::Config(IdConfig::SshConfig), f, ui_cfg_chunks_col2[4]);
+            self.app.view(
+                &Id::Config(IdConfig::MouseSupport),
+                f,
+                ui_cfg_chunks_col2[5],
+            );
+            self.app
+                .view(&Id::Config(IdConfig::VimMode), f, ui_cfg_chunks_col2[6]);
+            self.app
+                .view(&Id::Config(IdConfig::RemoteTrash), f, ui_cfg_chunks_col2[7]);
             // Popups
             self.view_popups(f);
         });
@@ -260,6 +281,57 @@ impl SetupActivity {
                 vec![]
             )
             .is_ok());
+        // Mouse support
+        assert!(self
+            .app
+            .remount(
+                Id::Config(IdConfig::MouseSupport),
+                Box::new(components::MouseSupport::new(
+                    self.config().get_mouse_support()
+                )),
+                vec![]
+            )
+            .is_ok());
+        // Remote watch interval
+        assert!(self
+            .app
+            .remount(
+                Id::Config(IdConfig::RemoteWatchInterval),
+                Box::new(components::RemoteWatchInterval::new(
+                    &self.config().get_remote_watch_interval().to_string()
+                )),
+                vec![]
+            )
+            .is_ok());
+        // Vim mode
+        assert!(self
+            .app
+            .remount(
+                Id::Config(IdConfig::VimMode),
+                Box::new(components::VimMode::new(self.config().get_vim_mode())),
+                vec![]
+            )
+            .is_ok());
+        // Local trash
+        assert!(self
+            .app
+            .remount(
+                Id::Config(IdConfig::LocalTrash),
+                Box::new(components::LocalTrash::new(self.config().get_local_trash())),
+                vec![]
+            )
+            .is_ok());
+        // Remote trash
+        assert!(self
+            .app
+            .remount(
+                Id::Config(IdConfig::RemoteTrash),
+                Box::new(components::RemoteTrash::new(
+                    self.config().get_remote_trash()
+                )),
+                vec![]
+            )
+            .is_ok());
     }
 
     /// Collect values from input and put them into the configuration
@@ -347,5 +419,32 @@ impl SetupActivity {
                 self.config_mut().set_ssh_config(Some(path));
             }
         }
+        if let Ok(State::One(StateValue::Usize(opt))) =
+            self.app.state(&Id::Config(IdConfig::MouseSupport))
+        {
+            self.config_mut().set_mouse_support(opt == 0);
+        }
+        if let Ok(State::One(StateValue::String(secs))) =
+            self.app.state(&Id::Config(IdConfig::RemoteWatchInterval))
+        {
+            if let Ok(secs) = secs.parse::<u64>() {
+                self.config_mut().set_remote_watch_interval(secs);
+            }
+        }
+        if let Ok(State::One(StateValue::Usize(opt))) =
+            self.app.state(&Id::Config(IdConfig::VimMode))
+        {
+            self.config_mut().set_vim_mode(opt == 0);
+        }
+        if let Ok(State::One(StateValue::Usize(opt))) =
+            self.app.state(&Id::Config(IdConfig::LocalTrash))
+        {
+            self.config_mut().set_local_trash(opt == 0);
+        }
+        if let Ok(State::One(StateValue::Usize(opt))) =
+            self.app.state(&Id::Config(IdConfig::RemoteTrash))
+        {
+            self.config_mut().set_remote_trash(opt == 0);
+        }
     }
 }
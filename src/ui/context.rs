@@ -3,9 +3,12 @@
 //! `Context` is the module which provides all the functionalities related to the UI data holder, called Context
 
 // Locals
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
 use tuirealm::terminal::TerminalBridge;
 
 use super::store::Store;
+use crate::config::bookmarks::BookmarkHooks;
 use crate::filetransfer::FileTransferParams;
 use crate::system::bookmarks_client::BookmarksClient;
 use crate::system::config_client::ConfigClient;
@@ -14,6 +17,8 @@ use crate::system::theme_provider::ThemeProvider;
 /// Context holds data structures shared by the activities
 pub struct Context {
     ft_params: Option<FileTransferParams>,
+    bookmark_hooks: Option<BookmarkHooks>,
+    bookmark_name: Option<String>,
     bookmarks_client: Option<BookmarksClient>,
     config_client: ConfigClient,
     pub(crate) store: Store,
@@ -34,6 +39,8 @@ impl Context {
             bookmarks_client,
             config_client,
             ft_params: None,
+            bookmark_hooks: None,
+            bookmark_name: None,
             store: Store::init(),
             terminal: TerminalBridge::new().expect("Could not initialize terminal"),
             theme_provider,
@@ -47,6 +54,16 @@ impl Context {
         self.ft_params.as_ref()
     }
 
+    /// Get the hooks of the bookmark in use
+    pub fn bookmark_hooks(&self) -> Option<&BookmarkHooks> {
+        self.bookmark_hooks.as_ref()
+    }
+
+    /// Get the name of the bookmark in use
+    pub fn bookmark_name(&self) -> Option<&str> {
+        self.bookmark_name.as_deref()
+    }
+
     pub fn bookmarks_client(&self) -> Option<&BookmarksClient> {
         self.bookmarks_client.as_ref()
     }
@@ -83,12 +100,32 @@ impl Context {
         &mut self.terminal
     }
 
+    /// Enable or disable mouse capture on terminal.
+    /// When mouse capture is disabled, mouse events are handled by the terminal emulator (e.g. text selection)
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> std::io::Result<()> {
+        let backend = self.terminal.raw_mut().backend_mut();
+        match enabled {
+            true => execute!(backend, EnableMouseCapture),
+            false => execute!(backend, DisableMouseCapture),
+        }
+    }
+
     // -- setter
 
     pub fn set_ftparams(&mut self, params: FileTransferParams) {
         self.ft_params = Some(params);
     }
 
+    /// Set the hooks of the bookmark in use, which run around the connection
+    pub fn set_bookmark_hooks(&mut self, hooks: Option<BookmarkHooks>) {
+        self.bookmark_hooks = hooks;
+    }
+
+    /// Set the name of the bookmark in use; `None` if the connection is not from a bookmark
+    pub fn set_bookmark_name(&mut self, name: Option<String>) {
+        self.bookmark_name = name;
+    }
+
     // -- error
 
     /// Set context error
@@ -107,6 +144,7 @@ impl Drop for Context {
         // Re-enable terminal stuff
         let _ = self.terminal.disable_raw_mode();
         let _ = self.terminal.leave_alternate_screen();
+        let _ = self.set_mouse_capture(false);
         let _ = self.terminal.clear_screen();
     }
 }
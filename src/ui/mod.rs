@@ -5,4 +5,5 @@
 // Modules
 pub mod activities;
 pub mod context;
+pub(crate) mod mouse;
 pub(crate) mod store;
@@ -0,0 +1,347 @@
+//! ## Mouse
+//!
+//! `mouse` is the module which provides mouse support to the activities.
+//! tui-realm discards mouse events, so activities use the `InputListener` provided here as input port;
+//! the listener forwards keyboard events to the application as usual, while mouse events are pushed
+//! to a queue, which the activity consumes after each tick.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self as xterm, MouseButton, MouseEventKind as XtermMouseEventKind};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::listener::{ListenerError, ListenerResult, Poll};
+use tuirealm::tui::layout::Rect;
+use tuirealm::{Event, NoUserEvent};
+use unicode_width::UnicodeWidthStr;
+
+/// Two clicks on the same cell within this interval make a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Describes a mouse event
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MouseEvent {
+    pub kind: MouseEventKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MouseEventKind {
+    Click,
+    /// Second click of a double click. The first one is reported as a `Click`
+    DoubleClick,
+    RightClick,
+    ScrollDown,
+    ScrollUp,
+}
+
+impl MouseEvent {
+    /// Returns whether the event happened inside `area`
+    pub fn inside(&self, area: Rect) -> bool {
+        self.column >= area.x
+            && self.column < area.x + area.width
+            && self.row >= area.y
+            && self.row < area.y + area.height
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    mouse: VecDeque<MouseEvent>,
+    injected: VecDeque<KeyEvent>,
+}
+
+/// Mouse input handle shared between the activity and its input listener
+#[derive(Clone, Default)]
+pub struct MouseInput {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl MouseInput {
+    /// Create the input listener to use as input port for the application
+    pub fn listener(&self, interval: Duration) -> InputListener {
+        InputListener {
+            queue: self.queue.clone(),
+            interval: interval / 2,
+            last_click: None,
+        }
+    }
+
+    /// Take the mouse events received since the last call
+    pub fn events(&self) -> Vec<MouseEvent> {
+        match self.queue.lock() {
+            Ok(mut queue) => queue.mouse.drain(..).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Send a key to the active component, as if it had been pressed by the user.
+    /// The key is returned by the input listener on its next poll
+    pub fn inject(&self, key: Key) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.injected.push_back(KeyEvent::from(key));
+        }
+    }
+}
+
+/// Crossterm input listener, which forwards mouse events to `MouseInput`
+pub struct InputListener {
+    queue: Arc<Mutex<Queue>>,
+    interval: Duration,
+    last_click: Option<(MouseEvent, Instant)>,
+}
+
+impl InputListener {
+    /// Convert crossterm mouse event; returns `None` for unsupported events
+    fn mouse_event(&mut self, ev: xterm::MouseEvent) -> Option<MouseEvent> {
+        let kind = match ev.kind {
+            XtermMouseEventKind::Down(MouseButton::Left) => MouseEventKind::Click,
+            XtermMouseEventKind::Down(MouseButton::Right) => MouseEventKind::RightClick,
+            XtermMouseEventKind::ScrollDown => MouseEventKind::ScrollDown,
+            XtermMouseEventKind::ScrollUp => MouseEventKind::ScrollUp,
+            _ => return None,
+        };
+        let mut ev = MouseEvent {
+            kind,
+            column: ev.column,
+            row: ev.row,
+        };
+        if kind == MouseEventKind::Click {
+            ev.kind = match self.last_click.take() {
+                Some((last, time)) if last == ev && time.elapsed() < DOUBLE_CLICK_INTERVAL => {
+                    MouseEventKind::DoubleClick
+                }
+                _ => {
+                    self.last_click = Some((ev, Instant::now()));
+                    MouseEventKind::Click
+                }
+            };
+        }
+        Some(ev)
+    }
+}
+
+impl Poll<NoUserEvent> for InputListener {
+    fn poll(&mut self) -> ListenerResult<Option<Event<NoUserEvent>>> {
+        if let Some(key) = self
+            .queue
+            .lock()
+            .ok()
+            .and_then(|mut queue| queue.injected.pop_front())
+        {
+            return Ok(Some(Event::Keyboard(key)));
+        }
+        match xterm::poll(self.interval) {
+            Ok(true) => match xterm::read().map_err(|_| ListenerError::PollFailed)? {
+                xterm::Event::Mouse(ev) => {
+                    if let Some(ev) = self.mouse_event(ev) {
+                        if let Ok(mut queue) = self.queue.lock() {
+                            queue.mouse.push_back(ev);
+                        }
+                    }
+                    Ok(None)
+                }
+                ev => Ok(Some(Event::from(ev))),
+            },
+            Ok(false) => Ok(None),
+            Err(_) => Err(ListenerError::PollFailed),
+        }
+    }
+}
+
+/// Areas where components have been drawn in the last frame
+pub struct MouseAreas<K> {
+    areas: Vec<(K, Rect)>,
+}
+
+impl<K> Default for MouseAreas<K> {
+    fn default() -> Self {
+        Self { areas: Vec::new() }
+    }
+}
+
+impl<K: Clone + Eq> MouseAreas<K> {
+    /// Forget all the areas; must be called before drawing a new frame
+    pub fn clear(&mut self) {
+        self.areas.clear();
+    }
+
+    /// Register the area where `id` has been drawn
+    pub fn insert(&mut self, id: &K, area: Rect) {
+        self.areas.retain(|(x, _)| x != id);
+        self.areas.push((id.clone(), area));
+    }
+
+    /// Get the topmost component at the position of `ev`
+    pub fn at(&self, ev: &MouseEvent) -> Option<(K, Rect)> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(_, area)| ev.inside(*area))
+            .cloned()
+    }
+}
+
+/// Get the index of the entry at `row` in a bordered list with `len` entries, drawn in `area`.
+/// `selected` is the index of the highlighted entry, which determines the scroll offset;
+/// `header` is the amount of lines drawn before the entries (e.g. table header)
+pub fn list_index_at(
+    area: Rect,
+    header: u16,
+    selected: usize,
+    len: usize,
+    row: u16,
+) -> Option<usize> {
+    let first_row = area.y + 1 + header;
+    let height = area.height.saturating_sub(2 + header) as usize;
+    if row < first_row || (row - first_row) as usize >= height {
+        return None;
+    }
+    let offset = selected.saturating_sub(height.saturating_sub(1));
+    let index = offset + (row - first_row) as usize;
+    (index < len).then_some(index)
+}
+
+/// Get the index of the choice at `column` in a bordered radio group drawn in `area`
+pub fn radio_choice_at(area: Rect, choices: &[&str], column: u16) -> Option<usize> {
+    // Choices are padded by a space and separated by a divider
+    let mut start = area.x + 1;
+    for (i, choice) in choices.iter().enumerate() {
+        let end = start + choice.width() as u16 + 2;
+        if (start..end).contains(&column) {
+            return Some(i);
+        }
+        start = end + 1;
+    }
+    None
+}
+
+/// Get the index of the table column at `column`, for a bordered table drawn in `area`
+pub fn table_column_at(area: Rect, widths: &[u16], spacing: u16, column: u16) -> Option<usize> {
+    let mut start = area.x + 1;
+    for (i, width) in widths.iter().enumerate() {
+        if (start..start + width + spacing).contains(&column) {
+            return Some(i);
+        }
+        start += width + spacing;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_detect_double_click() {
+        let mut listener = MouseInput::default().listener(Duration::from_millis(10));
+        let click = |column| xterm::MouseEvent {
+            kind: XtermMouseEventKind::Down(MouseButton::Left),
+            column,
+            row: 4,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(
+            listener.mouse_event(click(2)).unwrap().kind,
+            MouseEventKind::Click
+        );
+        assert_eq!(
+            listener.mouse_event(click(2)).unwrap().kind,
+            MouseEventKind::DoubleClick
+        );
+        // Third click starts a new double click
+        assert_eq!(
+            listener.mouse_event(click(2)).unwrap().kind,
+            MouseEventKind::Click
+        );
+        // Different cell
+        assert_eq!(
+            listener.mouse_event(click(3)).unwrap().kind,
+            MouseEventKind::Click
+        );
+        assert!(listener
+            .mouse_event(xterm::MouseEvent {
+                kind: XtermMouseEventKind::Moved,
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::NONE,
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn should_inject_keys() {
+        let input = MouseInput::default();
+        let mut listener = input.listener(Duration::from_millis(10));
+        input.inject(Key::Enter);
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::Keyboard(KeyEvent::from(Key::Enter)))
+        );
+        assert!(input.events().is_empty());
+    }
+
+    #[test]
+    fn should_get_topmost_area() {
+        let mut areas = MouseAreas::default();
+        areas.insert(&"explorer", Rect::new(0, 0, 40, 20));
+        areas.insert(&"popup", Rect::new(10, 5, 20, 3));
+        let ev = |column, row| MouseEvent {
+            kind: MouseEventKind::Click,
+            column,
+            row,
+        };
+        assert_eq!(areas.at(&ev(12, 6)).unwrap().0, "popup");
+        assert_eq!(areas.at(&ev(2, 6)).unwrap().0, "explorer");
+        assert!(areas.at(&ev(50, 6)).is_none());
+        areas.clear();
+        assert!(areas.at(&ev(2, 6)).is_none());
+    }
+
+    #[test]
+    fn should_get_list_index_at_row() {
+        let area = Rect::new(0, 2, 20, 7); // 5 rows
+        assert_eq!(list_index_at(area, 0, 0, 10, 3), Some(0));
+        assert_eq!(list_index_at(area, 0, 0, 10, 7), Some(4));
+        assert_eq!(list_index_at(area, 0, 0, 3, 7), None);
+        // Borders
+        assert_eq!(list_index_at(area, 0, 0, 10, 2), None);
+        assert_eq!(list_index_at(area, 0, 0, 10, 8), None);
+        // Scrolled
+        assert_eq!(list_index_at(area, 0, 7, 10, 3), Some(3));
+        assert_eq!(list_index_at(area, 0, 7, 10, 7), Some(7));
+        // Header
+        assert_eq!(list_index_at(area, 1, 0, 10, 3), None);
+        assert_eq!(list_index_at(area, 1, 0, 10, 4), Some(0));
+        assert_eq!(list_index_at(area, 1, 5, 10, 7), Some(5));
+    }
+
+    #[test]
+    fn should_get_radio_choice_at_column() {
+        let area = Rect::new(4, 0, 30, 3);
+        // "│ Yes | No"
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 4), None);
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 6), Some(0));
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 9), Some(0));
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 10), None);
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 12), Some(1));
+        assert_eq!(radio_choice_at(area, &["Yes", "No"], 16), None);
+    }
+
+    #[test]
+    fn should_get_table_column_at() {
+        let area = Rect::new(0, 0, 30, 10);
+        assert_eq!(table_column_at(area, &[4, 6], 1, 0), None);
+        assert_eq!(table_column_at(area, &[4, 6], 1, 1), Some(0));
+        assert_eq!(table_column_at(area, &[4, 6], 1, 5), Some(0));
+        assert_eq!(table_column_at(area, &[4, 6], 1, 6), Some(1));
+        assert_eq!(table_column_at(area, &[4, 6], 1, 12), Some(1));
+        assert_eq!(table_column_at(area, &[4, 6], 1, 13), None);
+    }
+}
@@ -3,7 +3,26 @@
 //! `crypto` is the module which provides utilities for crypting
 
 // Ext
+use argon2::Argon2;
+use hmac::{Hmac, Mac};
 use magic_crypt::MagicCryptTrait;
+use sha2::Sha256;
+use thiserror::Error;
+
+use super::random::random_alphanumeric_with_len;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Describes an error while decrypting an authenticated secret
+#[derive(Debug, Error)]
+pub enum CryptoError {
+    #[error("malformed secret")]
+    Malformed,
+    #[error("the secret could not be authenticated")]
+    Authentication,
+    #[error("could not decrypt secret: {0}")]
+    Decrypt(magic_crypt::MagicCryptError),
+}
 
 /// Crypt a string using AES128; output is returned as a BASE64 string
 pub fn aes128_b64_crypt(key: &str, input: &str) -> String {
@@ -17,6 +36,69 @@ pub fn aes128_b64_decrypt(key: &str, secret: &str) -> Result<String, magic_crypt
     crypter.decrypt_base64_to_string(secret)
 }
 
+/// Crypt a string using AES256 with a random IV, authenticated with HMAC-SHA256 (encrypt-then-MAC).
+/// `key` should be a derived key (see `derive_key`). Output is formatted as `<iv>$<BASE64 secret>$<hex mac>`
+pub fn aes256_hmac_crypt(key: &str, input: &str) -> String {
+    let iv = random_alphanumeric_with_len(32);
+    let crypter = new_magic_crypt!(subkey(key, "encryption"), 256, iv.as_str());
+    let secret = format!("{iv}${}", crypter.encrypt_str_to_base64(input));
+    let mac = mac(key, secret.as_str()).finalize().into_bytes();
+    format!("{secret}${}", to_hex(&mac))
+}
+
+/// Decrypt a string encrypted with `aes256_hmac_crypt`; returns error if the secret can't be authenticated with `key`
+pub fn aes256_hmac_decrypt(key: &str, secret: &str) -> Result<String, CryptoError> {
+    let (secret, tag) = secret.rsplit_once('$').ok_or(CryptoError::Malformed)?;
+    let (iv, data) = secret.split_once('$').ok_or(CryptoError::Malformed)?;
+    let tag = from_hex(tag).ok_or(CryptoError::Malformed)?;
+    mac(key, secret)
+        .verify_slice(&tag)
+        .map_err(|_| CryptoError::Authentication)?;
+    let crypter = new_magic_crypt!(subkey(key, "encryption"), 256, iv);
+    crypter
+        .decrypt_base64_to_string(data)
+        .map_err(CryptoError::Decrypt)
+}
+
+/// Derive an encryption key from `password` and `salt` using argon2id; the key is returned as an hex string
+pub fn derive_key(password: &str, salt: &str) -> Result<String, argon2::Error> {
+    let mut key = [0u8; 32];
+    Argon2::default().hash_password_into(password.as_bytes(), salt.as_bytes(), &mut key)?;
+    Ok(to_hex(&key))
+}
+
+/// Derive from `key` the subkey used for `purpose`, so the same key is never used by both the cipher and the mac
+fn subkey(key: &str, purpose: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(purpose.as_bytes());
+    to_hex(&mac.finalize().into_bytes())
+}
+
+/// Get the HMAC of `data`
+fn mac(key: &str, data: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(subkey(key, "authentication").as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(data.as_bytes());
+    mac
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    s.as_bytes()
+        .chunks(2)
+        .map(|x| {
+            std::str::from_utf8(x)
+                .ok()
+                .filter(|x| x.len() == 2)
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -38,4 +120,41 @@ mod tests {
             input
         );
     }
+
+    #[test]
+    fn should_crypt_and_authenticate() {
+        let key = derive_key("passphrase", "saltsaltsaltsalt").unwrap();
+        let secret = aes256_hmac_crypt(&key, "Hello world!");
+        // IV is random
+        assert_ne!(secret, aes256_hmac_crypt(&key, "Hello world!"));
+        assert_eq!(aes256_hmac_decrypt(&key, &secret).unwrap(), "Hello world!");
+        // Wrong key
+        let other_key = derive_key("drowssap", "saltsaltsaltsalt").unwrap();
+        assert!(matches!(
+            aes256_hmac_decrypt(&other_key, &secret),
+            Err(CryptoError::Authentication)
+        ));
+        // Tampered secret
+        let (iv, rest) = secret.split_once('$').unwrap();
+        let tampered = format!("{}{}${rest}", &iv[1..], &iv[..1]);
+        assert!(matches!(
+            aes256_hmac_decrypt(&key, &tampered),
+            Err(CryptoError::Authentication)
+        ));
+        assert!(matches!(
+            aes256_hmac_decrypt(&key, "z4Z6LpcpYqBW4+bkIok+5A=="),
+            Err(CryptoError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn should_derive_key() {
+        let key = derive_key("password", "saltsaltsaltsalt").unwrap();
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, derive_key("password", "saltsaltsaltsalt").unwrap());
+        assert_ne!(key, derive_key("password", "pepperpepperpepper").unwrap());
+        assert_ne!(key, derive_key("drowssap", "saltsaltsaltsalt").unwrap());
+        // Salt is too short
+        assert!(derive_key("password", "salt").is_err());
+    }
 }
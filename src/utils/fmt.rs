@@ -35,6 +35,29 @@ pub fn fmt_time(time: SystemTime, fmt: &str) -> String {
     format!("{}", datetime.format(fmt))
 }
 
+/// Format `time` relative to `now` in a human readable way (e.g. "3h ago", "in 2d")
+pub fn fmt_reltime(time: SystemTime, now: SystemTime) -> String {
+    let (elapsed, future) = match now.duration_since(time) {
+        Ok(elapsed) => (elapsed, false),
+        Err(err) => (err.duration(), true),
+    };
+    let secs = elapsed.as_secs();
+    let amount = match secs {
+        0..=9 => return String::from("now"),
+        10..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        86400..=604799 => format!("{}d", secs / 86400),
+        604800..=2629799 => format!("{}w", secs / 604800),
+        2629800..=31557599 => format!("{}mo", secs / 2629800),
+        _ => format!("{}y", secs / 31557600),
+    };
+    match future {
+        true => format!("in {amount}"),
+        false => format!("{amount} ago"),
+    }
+}
+
 /// Format duration as {secs}.{millis}
 pub fn fmt_millis(duration: Duration) -> String {
     let seconds: u128 = duration.as_millis() / 1000;
@@ -291,6 +314,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_fmt_reltime() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        assert_eq!(fmt_reltime(now, now).as_str(), "now");
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(42), now).as_str(),
+            "42s ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(300), now).as_str(),
+            "5m ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(3 * 3600 + 60), now).as_str(),
+            "3h ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(2 * 86400), now).as_str(),
+            "2d ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(3 * 604800), now).as_str(),
+            "3w ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(90 * 86400), now).as_str(),
+            "2mo ago"
+        );
+        assert_eq!(
+            fmt_reltime(now - Duration::from_secs(800 * 86400), now).as_str(),
+            "2y ago"
+        );
+        assert_eq!(
+            fmt_reltime(now + Duration::from_secs(7200), now).as_str(),
+            "in 2h"
+        );
+    }
+
     #[test]
     fn test_utils_fmt_millis() {
         assert_eq!(
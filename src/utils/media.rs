@@ -0,0 +1,633 @@
+//! ## Media
+//!
+//! `media` is the module which provides utilities to inspect image and media files,
+//! reading their properties from the file headers and building low-resolution previews
+
+use std::time::Duration;
+
+use tuirealm::props::Color;
+
+/// Amount of bytes which should be read from a file in order to get its media properties
+pub const MEDIA_HEADER_SIZE: usize = 65536;
+/// Maximum size of an image to be fully read in order to build a preview
+pub const IMAGE_PREVIEW_MAX_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Extensions of the image formats which can be inspected
+const IMAGE_EXTENSIONS: [&str; 6] = ["bmp", "gif", "jpeg", "jpg", "png", "webp"];
+/// Extensions of the audio and video formats which can be inspected
+const AV_EXTENSIONS: [&str; 8] = ["3gp", "flac", "m4a", "m4v", "mov", "mp3", "mp4", "wav"];
+
+/// Returns whether the file named `name` may be an image which can be inspected, judging by its extension
+pub fn is_image_name(name: &str) -> bool {
+    has_extension(name, &IMAGE_EXTENSIONS)
+}
+
+/// Returns whether the file named `name` may be an image or a media file which can be inspected,
+/// judging by its extension
+pub fn is_media_name(name: &str) -> bool {
+    is_image_name(name) || has_extension(name, &AV_EXTENSIONS)
+}
+
+fn has_extension(name: &str, extensions: &[&str]) -> bool {
+    name.rsplit_once('.')
+        .map(|(_, ext)| extensions.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Media properties read from the file header
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaInfo {
+    /// Media format (e.g. "PNG")
+    pub format: &'static str,
+    /// Whether the file is an image
+    pub image: bool,
+    /// Image or video dimensions (width x height)
+    pub dimensions: Option<(u32, u32)>,
+    /// Media duration
+    pub duration: Option<Duration>,
+    /// Codec(s) of the media streams
+    pub codec: Option<String>,
+    /// Audio sample rate in Hz
+    pub sample_rate: Option<u32>,
+    /// Audio channels
+    pub channels: Option<u16>,
+}
+
+impl MediaInfo {
+    /// Read media properties from the first bytes of a file.
+    /// `file_size` is the size of the entire file, used to estimate the duration of some formats.
+    /// Returns `None` if the format is not recognized
+    pub fn from_header(data: &[u8], file_size: u64) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::parse_png(data))
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(Self::parse_gif(data))
+        } else if data.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(Self::parse_jpeg(data))
+        } else if data.starts_with(b"BM") && data.len() >= 26 {
+            Some(Self::parse_bmp(data))
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+            Some(Self::parse_webp(data))
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+            Some(Self::parse_wav(data))
+        } else if data.starts_with(b"fLaC") {
+            Some(Self::parse_flac(data))
+        } else if data.get(4..8) == Some(b"ftyp") {
+            Some(Self::parse_mp4(data))
+        } else {
+            Self::parse_mp3(data, file_size)
+        }
+    }
+
+    fn image(format: &'static str) -> Self {
+        Self {
+            format,
+            image: true,
+            ..Default::default()
+        }
+    }
+
+    fn parse_png(data: &[u8]) -> Self {
+        let mut info = Self::image("PNG");
+        if data.get(12..16) == Some(b"IHDR") {
+            info.dimensions = be_u32(data, 16).zip(be_u32(data, 20));
+        }
+        info
+    }
+
+    fn parse_gif(data: &[u8]) -> Self {
+        let mut info = Self::image("GIF");
+        info.dimensions = le_u16(data, 6)
+            .zip(le_u16(data, 8))
+            .map(|(w, h)| (w as u32, h as u32));
+        info
+    }
+
+    fn parse_bmp(data: &[u8]) -> Self {
+        let mut info = Self::image("BMP");
+        info.dimensions = le_u32(data, 18)
+            .zip(le_u32(data, 22))
+            .map(|(w, h)| ((w as i32).unsigned_abs(), (h as i32).unsigned_abs()));
+        info
+    }
+
+    fn parse_jpeg(data: &[u8]) -> Self {
+        let mut info = Self::image("JPEG");
+        let mut offset = 2;
+        while offset + 4 <= data.len() {
+            if data[offset] != 0xff {
+                break;
+            }
+            let marker = data[offset + 1];
+            // Skip padding and standalone markers
+            if marker == 0xff {
+                offset += 1;
+                continue;
+            }
+            if marker == 0x01 || (0xd0..=0xd8).contains(&marker) {
+                offset += 2;
+                continue;
+            }
+            let len = match be_u16(data, offset + 2) {
+                Some(len) => len as usize,
+                None => break,
+            };
+            // Start of frame (excluding DHT, JPG and DAC)
+            if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+                info.dimensions = be_u16(data, offset + 7)
+                    .zip(be_u16(data, offset + 5))
+                    .map(|(w, h)| (w as u32, h as u32));
+                info.codec = Some(
+                    match marker {
+                        0xc0 | 0xc1 => "baseline",
+                        0xc2 | 0xc6 | 0xca | 0xce => "progressive",
+                        0xc3 | 0xc7 | 0xcb | 0xcf => "lossless",
+                        _ => "extended",
+                    }
+                    .to_string(),
+                );
+                break;
+            }
+            offset += 2 + len;
+        }
+        info
+    }
+
+    fn parse_webp(data: &[u8]) -> Self {
+        let mut info = Self::image("WebP");
+        match data.get(12..16) {
+            Some(b"VP8X") => {
+                info.dimensions = le_u24(data, 24)
+                    .zip(le_u24(data, 27))
+                    .map(|(w, h)| (w + 1, h + 1));
+            }
+            Some(b"VP8L") => {
+                info.codec = Some(String::from("VP8L"));
+                info.dimensions =
+                    le_u32(data, 21).map(|bits| ((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1));
+            }
+            Some(b"VP8 ") => {
+                info.codec = Some(String::from("VP8"));
+                info.dimensions = le_u16(data, 26)
+                    .zip(le_u16(data, 28))
+                    .map(|(w, h)| ((w & 0x3fff) as u32, (h & 0x3fff) as u32));
+            }
+            _ => {}
+        }
+        info
+    }
+
+    fn parse_wav(data: &[u8]) -> Self {
+        let mut info = Self {
+            format: "WAV",
+            ..Default::default()
+        };
+        let mut byte_rate: Option<u32> = None;
+        let mut offset = 12;
+        while let Some((id, len)) = data.get(offset..offset + 4).zip(le_u32(data, offset + 4)) {
+            match id {
+                b"fmt " => {
+                    info.codec = le_u16(data, offset + 8).map(|fmt| match fmt {
+                        0x0001 => String::from("PCM"),
+                        0x0003 => String::from("IEEE float"),
+                        0x0006 => String::from("A-law"),
+                        0x0007 => String::from("µ-law"),
+                        0xfffe => String::from("extensible"),
+                        fmt => format!("0x{fmt:04x}"),
+                    });
+                    info.channels = le_u16(data, offset + 10);
+                    info.sample_rate = le_u32(data, offset + 12);
+                    byte_rate = le_u32(data, offset + 16);
+                }
+                b"data" => {
+                    info.duration = byte_rate
+                        .filter(|rate| *rate > 0)
+                        .map(|rate| Duration::from_secs_f64(len as f64 / rate as f64));
+                    break;
+                }
+                _ => {}
+            }
+            // Chunks are word aligned
+            offset += 8 + len as usize + (len as usize % 2);
+        }
+        info
+    }
+
+    fn parse_flac(data: &[u8]) -> Self {
+        let mut info = Self {
+            format: "FLAC",
+            codec: Some(String::from("FLAC")),
+            ..Default::default()
+        };
+        // STREAMINFO is always the first metadata block
+        if let Some(streaminfo) = data.get(18..26) {
+            let bits = u64::from_be_bytes(streaminfo.try_into().unwrap());
+            let sample_rate = (bits >> 44) as u32;
+            let total_samples = bits & 0xf_ffff_ffff;
+            info.sample_rate = Some(sample_rate);
+            info.channels = Some(((bits >> 41) & 0x7) as u16 + 1);
+            if sample_rate > 0 && total_samples > 0 {
+                info.duration = Some(Duration::from_secs_f64(
+                    total_samples as f64 / sample_rate as f64,
+                ));
+            }
+        }
+        info
+    }
+
+    fn parse_mp4(data: &[u8]) -> Self {
+        let mut info = Self {
+            format: match data.get(8..12) {
+                Some(b"qt  ") => "QuickTime",
+                Some(b"M4A ") | Some(b"M4B ") => "M4A",
+                Some(b"heic") | Some(b"heix") | Some(b"mif1") => "HEIF",
+                Some(b"avif") => "AVIF",
+                _ => "MP4",
+            },
+            ..Default::default()
+        };
+        let mut codecs: Vec<String> = Vec::new();
+        for (kind, body) in Mp4Boxes::new(data) {
+            if kind != b"moov" {
+                continue;
+            }
+            for (kind, body) in Mp4Boxes::new(body) {
+                match kind {
+                    b"mvhd" => info.duration = Self::parse_mp4_mvhd(body),
+                    b"trak" => {
+                        if let Some((w, h)) = Self::parse_mp4_tkhd(body) {
+                            info.dimensions.get_or_insert((w, h));
+                        }
+                        if let Some(codec) = Self::parse_mp4_codec(body) {
+                            codecs.push(codec);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !codecs.is_empty() {
+            info.codec = Some(codecs.join(", "));
+        }
+        info
+    }
+
+    fn parse_mp4_mvhd(body: &[u8]) -> Option<Duration> {
+        let (timescale, duration) = match body.first()? {
+            1 => (be_u32(body, 20)?, be_u64(body, 24)?),
+            _ => (be_u32(body, 12)?, be_u32(body, 16)? as u64),
+        };
+        (timescale > 0).then(|| Duration::from_secs_f64(duration as f64 / timescale as f64))
+    }
+
+    fn parse_mp4_tkhd(trak: &[u8]) -> Option<(u32, u32)> {
+        let (_, tkhd) = Mp4Boxes::new(trak).find(|(kind, _)| *kind == b"tkhd")?;
+        let offset = match tkhd.first()? {
+            1 => 88,
+            _ => 76,
+        };
+        // Dimensions are 16.16 fixed point numbers
+        let (width, height) = (be_u32(tkhd, offset)? >> 16, be_u32(tkhd, offset + 4)? >> 16);
+        (width > 0 && height > 0).then_some((width, height))
+    }
+
+    fn parse_mp4_codec(trak: &[u8]) -> Option<String> {
+        let mut body = trak;
+        for path in [b"mdia", b"minf", b"stbl", b"stsd"] {
+            body = Mp4Boxes::new(body).find(|(kind, _)| *kind == path)?.1;
+        }
+        // version + flags, entries count, entry size
+        let format = body.get(12..16)?;
+        Some(String::from_utf8_lossy(format).trim().to_string())
+    }
+
+    fn parse_mp3(data: &[u8], file_size: u64) -> Option<Self> {
+        // Skip ID3v2 tag
+        let offset = match data.starts_with(b"ID3") {
+            true => {
+                let size = data
+                    .get(6..10)?
+                    .iter()
+                    .fold(0usize, |acc, b| (acc << 7) | (*b & 0x7f) as usize);
+                10 + size
+            }
+            false => 0,
+        };
+        let header = be_u32(data, offset)?;
+        if header >> 21 != 0x7ff {
+            return None;
+        }
+        let version = (header >> 19) & 0x3;
+        let layer = (header >> 17) & 0x3;
+        let bitrate_idx = ((header >> 12) & 0xf) as usize;
+        let sample_rate_idx = ((header >> 10) & 0x3) as usize;
+        if version == 1
+            || layer != 1
+            || bitrate_idx == 0
+            || bitrate_idx == 15
+            || sample_rate_idx == 3
+        {
+            // Only MPEG layer III is supported
+            return None;
+        }
+        const BITRATES_V1: [u32; 15] = [
+            0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+        ];
+        const BITRATES_V2: [u32; 15] =
+            [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+        const SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+        let (bitrate, sample_rate) = match version {
+            3 => (BITRATES_V1[bitrate_idx], SAMPLE_RATES[sample_rate_idx]),
+            2 => (BITRATES_V2[bitrate_idx], SAMPLE_RATES[sample_rate_idx] / 2),
+            _ => (BITRATES_V2[bitrate_idx], SAMPLE_RATES[sample_rate_idx] / 4),
+        };
+        // Estimate duration assuming a constant bitrate
+        let audio_size = file_size.saturating_sub(offset as u64);
+        Some(Self {
+            format: "MP3",
+            image: false,
+            dimensions: None,
+            duration: Some(Duration::from_secs_f64(
+                (audio_size * 8) as f64 / (bitrate * 1000) as f64,
+            )),
+            codec: Some(format!("MPEG layer III {bitrate} kbps")),
+            sample_rate: Some(sample_rate),
+            channels: Some(match (header >> 6) & 0x3 {
+                3 => 1,
+                _ => 2,
+            }),
+        })
+    }
+}
+
+/// Iterator over the boxes of an ISO base media file, yielding type and body
+struct Mp4Boxes<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Mp4Boxes<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+}
+
+impl<'a> Iterator for Mp4Boxes<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = be_u32(self.data, self.offset)? as usize;
+        let kind = self.data.get(self.offset + 4..self.offset + 8)?;
+        let (header_len, size) = match size {
+            // Box extends to the end of data
+            0 => (8, self.data.len() - self.offset),
+            // 64 bits size
+            1 => (16, be_u64(self.data, self.offset + 8)? as usize),
+            size => (8, size),
+        };
+        if size < header_len {
+            return None;
+        }
+        // Truncated boxes are yielded with the available data
+        let end = self.data.len().min(self.offset.saturating_add(size));
+        let body = self.data.get(self.offset + header_len..end)?;
+        self.offset = end;
+        Some((kind, body))
+    }
+}
+
+/// Low resolution image preview, drawn with half blocks.
+/// Each cell has the color of the upper pixel as foreground and the color of the lower pixel as background
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePreview {
+    rows: Vec<Vec<(Color, Color)>>,
+}
+
+impl ImagePreview {
+    /// Symbol used to draw each cell of the preview
+    pub const CELL: &'static str = "▀";
+
+    /// Decode image and build a preview which fits in `width` columns and `height` rows,
+    /// keeping the aspect ratio.
+    /// Returns `None` if the image couldn't be decoded
+    pub fn new(data: &[u8], width: u32, height: u32) -> Option<Self> {
+        let image = image::load_from_memory(data).ok()?;
+        let thumbnail = image.thumbnail(width, height * 2).to_rgba8();
+        let color = |x: u32, y: u32| -> Color {
+            if y >= thumbnail.height() {
+                return Color::Reset;
+            }
+            let [r, g, b, a] = thumbnail.get_pixel(x, y).0;
+            match a {
+                0..=127 => Color::Reset,
+                _ => Color::Rgb(r, g, b),
+            }
+        };
+        let rows = (0..thumbnail.height())
+            .step_by(2)
+            .map(|y| {
+                (0..thumbnail.width())
+                    .map(|x| (color(x, y), color(x, y + 1)))
+                    .collect()
+            })
+            .collect();
+        Some(Self { rows })
+    }
+
+    /// Get preview rows as (foreground, background) for each cell
+    pub fn rows(&self) -> &[Vec<(Color, Color)>] {
+        &self.rows
+    }
+}
+
+// -- bytes helpers
+
+fn le_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+fn le_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn be_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_read_png_header() {
+        let info = MediaInfo::from_header(&make_png(3, 5), 0).unwrap();
+        assert_eq!(info.format, "PNG");
+        assert!(info.image);
+        assert_eq!(info.dimensions, Some((3, 5)));
+    }
+
+    #[test]
+    fn should_read_gif_header() {
+        let mut data = b"GIF89a".to_vec();
+        data.extend_from_slice(&[0x40, 0x01, 0xf0, 0x00]);
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "GIF");
+        assert_eq!(info.dimensions, Some((320, 240)));
+    }
+
+    #[test]
+    fn should_read_jpeg_header() {
+        let data: Vec<u8> = vec![
+            0xff, 0xd8, // SOI
+            0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, // APP0
+            0xff, 0xc2, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80, 0x03, // SOF2
+        ];
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "JPEG");
+        assert_eq!(info.dimensions, Some((640, 480)));
+        assert_eq!(info.codec.as_deref(), Some("progressive"));
+    }
+
+    #[test]
+    fn should_read_webp_header() {
+        let mut data = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+        data.extend_from_slice(&[0x7f, 0x02, 0x00, 0xdf, 0x01, 0x00]);
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "WebP");
+        assert_eq!(info.dimensions, Some((640, 480)));
+    }
+
+    #[test]
+    fn should_read_wav_header() {
+        let mut data = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0".to_vec();
+        data.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        data.extend_from_slice(&2u16.to_le_bytes()); // channels
+        data.extend_from_slice(&44100u32.to_le_bytes()); // sample rate
+        data.extend_from_slice(&176400u32.to_le_bytes()); // byte rate
+        data.extend_from_slice(&[0x04, 0x00, 0x10, 0x00]);
+        data.extend_from_slice(b"data");
+        data.extend_from_slice(&352800u32.to_le_bytes());
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "WAV");
+        assert!(!info.image);
+        assert_eq!(info.codec.as_deref(), Some("PCM"));
+        assert_eq!(info.channels, Some(2));
+        assert_eq!(info.sample_rate, Some(44100));
+        assert_eq!(info.duration, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn should_read_flac_header() {
+        let mut data = b"fLaC\x00\x00\x00\x22".to_vec();
+        data.extend_from_slice(&[0; 10]);
+        // 48000 Hz, 2 channels, 16 bits, 144000 samples
+        let bits: u64 = (48000 << 44) | (1 << 41) | (15 << 36) | 144000;
+        data.extend_from_slice(&bits.to_be_bytes());
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "FLAC");
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(info.channels, Some(2));
+        assert_eq!(info.duration, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn should_read_mp3_header() {
+        // MPEG1 layer III, 128kbps, 44100Hz, joint stereo
+        let data: Vec<u8> = vec![0xff, 0xfb, 0x90, 0x44, 0x00];
+        let info = MediaInfo::from_header(&data, 160000).unwrap();
+        assert_eq!(info.format, "MP3");
+        assert_eq!(info.sample_rate, Some(44100));
+        assert_eq!(info.channels, Some(2));
+        assert_eq!(info.duration, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn should_read_mp4_header() {
+        let mut mvhd = vec![0u8; 100];
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&5000u32.to_be_bytes());
+        let mut tkhd = vec![0u8; 84];
+        tkhd[76..80].copy_from_slice(&(1920u32 << 16).to_be_bytes());
+        tkhd[80..84].copy_from_slice(&(1080u32 << 16).to_be_bytes());
+        let mut stsd = vec![0u8; 16];
+        stsd[12..16].copy_from_slice(b"avc1");
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+        let mdia = mp4_box(b"mdia", &mp4_box(b"minf", &stbl));
+        let trak = mp4_box(b"trak", &[mp4_box(b"tkhd", &tkhd), mdia].concat());
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &mvhd), trak].concat());
+        let data = [mp4_box(b"ftyp", b"isom\0\0\0\0"), moov].concat();
+        let info = MediaInfo::from_header(&data, 0).unwrap();
+        assert_eq!(info.format, "MP4");
+        assert_eq!(info.dimensions, Some((1920, 1080)));
+        assert_eq!(info.duration, Some(Duration::from_secs(5)));
+        assert_eq!(info.codec.as_deref(), Some("avc1"));
+    }
+
+    #[test]
+    fn should_not_read_unknown_header() {
+        assert!(MediaInfo::from_header(b"hello world", 11).is_none());
+        assert!(MediaInfo::from_header(&[], 0).is_none());
+    }
+
+    #[test]
+    fn should_tell_media_names() {
+        assert!(is_image_name("photo.JPG"));
+        assert!(!is_image_name("song.mp3"));
+        assert!(is_media_name("song.mp3"));
+        assert!(is_media_name("photo.png"));
+        assert!(!is_media_name("README.md"));
+        assert!(!is_media_name("png"));
+    }
+
+    #[test]
+    fn should_make_image_preview() {
+        let preview = ImagePreview::new(&make_png(4, 8), 2, 2).unwrap();
+        assert_eq!(preview.rows().len(), 2);
+        assert_eq!(preview.rows()[0].len(), 2);
+        assert_eq!(
+            preview.rows()[0][0],
+            (Color::Rgb(255, 0, 0), Color::Rgb(255, 0, 0))
+        );
+        assert!(ImagePreview::new(b"not an image", 2, 2).is_none());
+    }
+
+    fn make_png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbImage::from_pixel(width, height, image::Rgb([255, 0, 0]));
+        let mut data = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut data, image::ImageFormat::Png).unwrap();
+        data.into_inner()
+    }
+
+    fn mp4_box(kind: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(body);
+        data
+    }
+}
@@ -6,6 +6,7 @@
 pub mod crypto;
 pub mod file;
 pub mod fmt;
+pub mod media;
 pub mod parser;
 pub mod path;
 pub mod random;
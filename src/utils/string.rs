@@ -8,6 +8,54 @@ pub fn secure_substring(string: &str, start: usize, end: usize) -> String {
     string.chars().take(end).skip(start).collect()
 }
 
+/// Fuzzy match `pattern` against `text`, ignoring case.
+/// Returns `None` if the characters of `pattern` don't appear in order in `text`,
+/// otherwise a score which is higher the better the match is:
+/// consecutive characters, characters at the beginning of words and matches at the beginning of `text` are preferred.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let first = match pattern.first() {
+        Some(first) => *first,
+        None => return Some(0),
+    };
+    // Try each occurrence of the first character as the start of the match and keep the best one
+    text.iter()
+        .enumerate()
+        .filter(|(_, ch)| **ch == first)
+        .filter_map(|(start, _)| fuzzy_score_from(&pattern, &text, start))
+        .max()
+}
+
+fn fuzzy_score_from(pattern: &[char], text: &[char], start: usize) -> Option<i64> {
+    let mut score: i64 = 0;
+    let mut last: Option<usize> = None;
+    let mut pos = start;
+    for ch in pattern {
+        let index = pos + text[pos..].iter().position(|x| x == ch)?;
+        score += 1;
+        match last {
+            Some(last) if last + 1 == index => score += 6,
+            Some(last) => score -= 2 * (index - last - 1).min(5) as i64,
+            None => score -= index.min(10) as i64,
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 6;
+        }
+        last = Some(index);
+        pos = index + 1;
+    }
+    if pattern.len() == text.len() {
+        score += 20;
+    }
+    Some(score)
+}
+
+/// Quote `s` to be used as a single argument of a POSIX shell command
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r#"'\''"#))
+}
+
 #[cfg(test)]
 mod test {
 
@@ -18,4 +66,28 @@ mod test {
         assert_eq!(secure_substring("christian", 2, 5).as_str(), "ris");
         assert_eq!(secure_substring("россия", 3, 5).as_str(), "си");
     }
+
+    #[test]
+    fn should_quote_shell_argument() {
+        assert_eq!(shell_quote("/var/log/app.log"), "'/var/log/app.log'");
+        assert_eq!(shell_quote("it's here"), r#"'it'\''s here'"#);
+    }
+
+    #[test]
+    fn should_get_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "raspberry"), Some(0));
+        assert!(fuzzy_score("rpi", "raspberry-pi").is_some());
+        assert!(fuzzy_score("RPI", "raspberry-pi").is_some());
+        assert!(fuzzy_score("ipr", "raspberry-pi").is_none());
+        assert!(fuzzy_score("db01", "db0").is_none());
+        // exact match is better than prefix, which is better than substring
+        assert!(fuzzy_score("db01", "db01").unwrap() > fuzzy_score("db01", "db01-eu").unwrap());
+        assert!(fuzzy_score("db", "db01").unwrap() > fuzzy_score("db", "prod-db01").unwrap());
+        // consecutive characters are better than scattered ones
+        assert!(fuzzy_score("prod", "prod-eu").unwrap() > fuzzy_score("prod", "p-r-o-d").unwrap());
+        // word starts are preferred
+        assert!(
+            fuzzy_score("eu", "prod/eu/db01").unwrap() > fuzzy_score("eu", "prod/neuron").unwrap()
+        );
+    }
 }